@@ -0,0 +1,56 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use dtdrafts_core::*;
+use std::hint::black_box;
+
+const DRAFTS: u64 = 10_000;
+
+// Drafts of roughly 1,000 words, a few of which mention the query
+fn drafts() -> Vec<Article> {
+    let paragraph = "Ownership and borrowing let the compiler check memory safety without a garbage collector. ";
+    (1..=DRAFTS)
+        .map(|id| {
+            let mut body = paragraph.repeat(70);
+            if id % 100 == 0 {
+                body.push_str("Async closures landed, so here is how to use them.");
+            }
+            Article {
+                id,
+                title: format!("Draft number {id}"),
+                description: None,
+                body_markdown: Some(body),
+                url: format!("https://dev.to/bench/draft-{id}"),
+                canonical_url: None,
+                url_with_preview: None,
+                published: false,
+                created_at: timestamp::parse("2024-01-01T00:00:00Z"),
+                updated_at: None,
+                tags: Some(vec!["rust".to_string(), "performance".to_string()]),
+                slug: format!("draft-{id}"),
+                user: ArticleUser { username: "bench".to_string() },
+                series: None,
+                organization: None,
+                cover_image: None,
+                social_image: None,
+                reading_time_minutes: None,
+                comments_count: 0,
+                public_reactions_count: 0,
+                extra: Default::default(),
+            }
+        })
+        .collect()
+}
+
+fn search(c: &mut Criterion) {
+    let articles = drafts();
+    let filter = ArticleFilter::drafts();
+    c.bench_function("search 10k drafts", |b| {
+        b.iter(|| search_articles_with(black_box(&articles), black_box("async closures"), &filter))
+    });
+    c.bench_function("search 10k drafts with a tag filter", |b| {
+        let filter = ArticleFilter::drafts().tag("go");
+        b.iter(|| search_articles_with(black_box(&articles), black_box("async closures"), &filter))
+    });
+}
+
+criterion_group!(benches, search);
+criterion_main!(benches);
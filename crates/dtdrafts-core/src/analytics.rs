@@ -0,0 +1,97 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Engagement counters of a published article, as returned by
+/// `/articles/me/published`. dev.to's API only exposes lifetime totals.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ArticleAnalytics {
+    pub id: u64,
+    pub title: String,
+    pub url: String,
+    #[serde(default)]
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub page_views_count: u64,
+    #[serde(default)]
+    pub public_reactions_count: u64,
+    #[serde(default)]
+    pub comments_count: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalyticsSortKey {
+    Views,
+    Reactions,
+    Comments,
+    Published,
+    Title,
+}
+
+impl FromStr for AnalyticsSortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "views" => Ok(AnalyticsSortKey::Views),
+            "reactions" => Ok(AnalyticsSortKey::Reactions),
+            "comments" => Ok(AnalyticsSortKey::Comments),
+            "published" => Ok(AnalyticsSortKey::Published),
+            "title" => Ok(AnalyticsSortKey::Title),
+            _ => Err(format!(
+                "unknown sort key '{s}' (expected views, reactions, comments, published or title)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for AnalyticsSortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AnalyticsSortKey::Views => "views",
+            AnalyticsSortKey::Reactions => "reactions",
+            AnalyticsSortKey::Comments => "comments",
+            AnalyticsSortKey::Published => "published",
+            AnalyticsSortKey::Title => "title",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Counters and the publish date sort highest/newest first; titles A to Z.
+pub fn sort_analytics(rows: &mut [ArticleAnalytics], key: AnalyticsSortKey) {
+    match key {
+        AnalyticsSortKey::Views => rows.sort_by_key(|row| Reverse(row.page_views_count)),
+        AnalyticsSortKey::Reactions => rows.sort_by_key(|row| Reverse(row.public_reactions_count)),
+        AnalyticsSortKey::Comments => rows.sort_by_key(|row| Reverse(row.comments_count)),
+        AnalyticsSortKey::Published => rows.sort_by(|a, b| b.published_at.cmp(&a.published_at)),
+        AnalyticsSortKey::Title => rows.sort_by_cached_key(|row| row.title.to_lowercase()),
+    }
+}
+
+pub fn to_csv(rows: &[ArticleAnalytics]) -> String {
+    let mut out = String::from("id,title,url,published_at,page_views,reactions,comments\n");
+    for row in rows {
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            row.id,
+            csv_field(&row.title),
+            csv_field(&row.url),
+            row.published_at.as_deref().unwrap_or_default(),
+            row.page_views_count,
+            row.public_reactions_count,
+            row.comments_count
+        ));
+    }
+    out
+}
+
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{search_articles, Article};
+
+/// What local integrations (the HTTP API) see of a draft in listings: its
+/// metadata without the body.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DraftSummary {
+    pub id: u64,
+    pub title: String,
+    pub slug: String,
+    pub url: String,
+    pub edit_url: String,
+    pub published: bool,
+    pub tags: Vec<String>,
+    pub series: Option<String>,
+    #[serde(default, with = "crate::timestamp")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::timestamp")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<&Article> for DraftSummary {
+    fn from(article: &Article) -> Self {
+        Self {
+            id: article.id,
+            title: article.title.clone(),
+            slug: article.slug.clone(),
+            url: article.url.clone(),
+            edit_url: article.edit_url(),
+            published: article.published,
+            tags: article.tags.clone().unwrap_or_default(),
+            series: article.series(),
+            created_at: article.created_at,
+            updated_at: article.updated_at,
+        }
+    }
+}
+
+pub fn summaries(articles: &[&Article]) -> Vec<DraftSummary> {
+    articles.iter().map(|article| DraftSummary::from(*article)).collect()
+}
+
+/// Drafts matching `query`, as `search` would list them.
+pub fn search_summaries(articles: &[Article], query: &str) -> Vec<DraftSummary> {
+    summaries(&search_articles(articles, query))
+}
@@ -0,0 +1,133 @@
+use std::io::{Read, Write};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+
+use crate::client::ArticleInput;
+use crate::frontmatter::{document_to_input, draft_document, has_embedded_front_matter, parse_document};
+use crate::Article;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+pub const MANIFEST_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BackupManifest {
+    pub version: u32,
+    pub created_at: String,
+    pub drafts: Vec<BackupEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BackupEntry {
+    pub id: u64,
+    pub title: String,
+    /// Path of the markdown document inside the archive
+    pub file: String,
+    pub tags: Vec<String>,
+    #[serde(default, with = "crate::timestamp")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::timestamp")]
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Whether the front matter came from the body (see `draft_document`)
+    pub embedded_front_matter: bool,
+}
+
+/// A draft read back from an archive.
+#[derive(Debug, Clone)]
+pub struct RestoredDraft {
+    pub entry: BackupEntry,
+    pub document: String,
+}
+
+impl RestoredDraft {
+    /// The create request for this draft. Restored drafts are always
+    /// unpublished, whatever their front matter says.
+    pub fn to_input(&self) -> ArticleInput {
+        if !self.entry.embedded_front_matter {
+            return ArticleInput { published: Some(false), ..document_to_input(&self.document, false) };
+        }
+        let (front_matter, body) = parse_document(&self.document);
+        let mut front_matter = front_matter.unwrap_or_default();
+        front_matter.set("published", "false");
+        ArticleInput {
+            published: Some(false),
+            ..document_to_input(&format!("{}{body}", front_matter.render()), true)
+        }
+    }
+}
+
+/// Writes a gzipped tarball with one `drafts/<id>-<slug>.md` document per
+/// article (front matter + markdown) and a `manifest.json` describing them.
+pub fn write_backup<W: Write>(articles: &[&Article], writer: W) -> Result<BackupManifest> {
+    let mut archive = tar::Builder::new(GzEncoder::new(writer, Compression::default()));
+    let mut drafts = Vec::with_capacity(articles.len());
+    for article in articles {
+        let file = format!("drafts/{}-{}.md", article.id, article.slug);
+        append_file(&mut archive, &file, draft_document(article).as_bytes())?;
+        drafts.push(BackupEntry {
+            id: article.id,
+            title: article.title.clone(),
+            file,
+            tags: article.tags.clone().unwrap_or_default(),
+            created_at: article.created_at,
+            updated_at: article.updated_at,
+            embedded_front_matter: has_embedded_front_matter(article),
+        });
+    }
+    let manifest = BackupManifest { version: MANIFEST_VERSION, created_at: Utc::now().to_rfc3339(), drafts };
+    append_file(&mut archive, MANIFEST_FILE, &serde_json::to_vec_pretty(&manifest)?)?;
+    archive.into_inner()?.finish()?.flush()?;
+    Ok(manifest)
+}
+
+fn append_file<W: Write>(archive: &mut tar::Builder<W>, path: &str, contents: &[u8]) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    archive
+        .append_data(&mut header, path, contents)
+        .with_context(|| format!("Failed to add {path} to the archive"))
+}
+
+/// Reads an archive written by [`write_backup`], in manifest order.
+pub fn read_backup<R: Read>(reader: R) -> Result<(BackupManifest, Vec<RestoredDraft>)> {
+    let mut archive = tar::Archive::new(GzDecoder::new(reader));
+    let mut manifest: Option<BackupManifest> = None;
+    let mut files = std::collections::HashMap::new();
+    for entry in archive.entries().context("Not a tar.gz archive")? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let mut contents = String::new();
+        entry
+            .read_to_string(&mut contents)
+            .with_context(|| format!("Failed to read {path} from the archive"))?;
+        if path == MANIFEST_FILE {
+            manifest = Some(serde_json::from_str(&contents).context("Invalid manifest.json")?);
+        } else {
+            files.insert(path, contents);
+        }
+    }
+    let manifest = manifest.context("Archive has no manifest.json")?;
+    anyhow::ensure!(
+        manifest.version <= MANIFEST_VERSION,
+        "Backup format version {} is newer than this dtdrafts supports ({MANIFEST_VERSION})",
+        manifest.version
+    );
+    let drafts = manifest
+        .drafts
+        .iter()
+        .map(|entry| {
+            let document = files
+                .remove(&entry.file)
+                .with_context(|| format!("{} is listed in the manifest but missing", entry.file))?;
+            Ok(RestoredDraft { entry: entry.clone(), document })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok((manifest, drafts))
+}
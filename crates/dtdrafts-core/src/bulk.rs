@@ -0,0 +1,136 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::client::ArticleInput;
+use crate::export::ExportFormat;
+use crate::frontmatter::{series_input, tags_input};
+use crate::lint::{PublishMetadata, MAX_TAGS};
+use crate::Article;
+
+/// A change `bulk` applies to every selected draft, written as
+/// `add-tag:NAME`, `remove-tag:NAME`, `rename-tag:FROM:TO`, `set-series:NAME`,
+/// `remove-series`, `delete` or `export:FORMAT`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkAction {
+    AddTag(String),
+    RemoveTag(String),
+    /// Replaces `from` with `to`, or just drops `from` when `to` is already there
+    RenameTag { from: String, to: String },
+    SetSeries(String),
+    RemoveSeries,
+    Delete,
+    Export(ExportFormat),
+}
+
+impl FromStr for BulkAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, argument) = match s.split_once(':') {
+            Some((name, argument)) => (name, Some(argument.trim()).filter(|argument| !argument.is_empty())),
+            None => (s, None),
+        };
+        let required = |what: &str| {
+            argument.map(str::to_string).ok_or_else(|| format!("'{name}' needs {what}, e.g. {name}:{what}"))
+        };
+        match name.to_lowercase().as_str() {
+            "add-tag" => Ok(BulkAction::AddTag(normalize_tag(&required("TAG")?))),
+            "remove-tag" => Ok(BulkAction::RemoveTag(normalize_tag(&required("TAG")?))),
+            "rename-tag" => match required("FROM:TO")?.split_once(':') {
+                Some((from, to)) if !from.trim().is_empty() && !to.trim().is_empty() => {
+                    Ok(BulkAction::rename_tag(from, to))
+                }
+                _ => Err(format!("'{name}' needs FROM:TO, e.g. {name}:beginners:beginner")),
+            },
+            "set-series" => Ok(BulkAction::SetSeries(required("SERIES")?)),
+            "remove-series" => Ok(BulkAction::RemoveSeries),
+            "delete" => Ok(BulkAction::Delete),
+            "export" => Ok(BulkAction::Export(required("FORMAT")?.parse()?)),
+            _ => Err(format!(
+                "unknown action '{s}' (expected add-tag:TAG, remove-tag:TAG, rename-tag:FROM:TO, set-series:SERIES, \
+                 remove-series, delete or export:FORMAT)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for BulkAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BulkAction::AddTag(tag) => write!(f, "add-tag:{tag}"),
+            BulkAction::RemoveTag(tag) => write!(f, "remove-tag:{tag}"),
+            BulkAction::RenameTag { from, to } => write!(f, "rename-tag:{from}:{to}"),
+            BulkAction::SetSeries(series) => write!(f, "set-series:{series}"),
+            BulkAction::RemoveSeries => f.write_str("remove-series"),
+            BulkAction::Delete => f.write_str("delete"),
+            BulkAction::Export(format) => write!(f, "export:{format}"),
+        }
+    }
+}
+
+/// What a [`BulkAction`] does to one article.
+#[derive(Debug, Clone)]
+pub enum BulkChange {
+    /// Send this update to dev.to; the string describes it.
+    Update(ArticleInput, String),
+    Delete,
+    Export,
+    /// Nothing to do, for the given reason.
+    Skip(String),
+}
+
+// dev.to tags are lowercase and written without the `#`
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+impl BulkAction {
+    pub fn rename_tag(from: &str, to: &str) -> Self {
+        BulkAction::RenameTag { from: normalize_tag(from), to: normalize_tag(to) }
+    }
+
+    /// Plans the action for `article`, which needs its body so embedded front
+    /// matter is updated along with the fields.
+    pub fn plan(&self, article: &Article) -> BulkChange {
+        let tags = PublishMetadata::of(article).tags;
+        let has_tag = |tag: &str| tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag));
+        match self {
+            BulkAction::AddTag(tag) if has_tag(tag) => BulkChange::Skip(format!("already tagged #{tag}")),
+            BulkAction::AddTag(_) if tags.len() >= MAX_TAGS => {
+                BulkChange::Skip(format!("already has {MAX_TAGS} tags"))
+            }
+            BulkAction::AddTag(tag) => {
+                let mut tags = tags.clone();
+                tags.push(tag.clone());
+                BulkChange::Update(tags_input(article, &tags), format!("tags → {}", tags.join(", ")))
+            }
+            BulkAction::RemoveTag(tag) if !has_tag(tag) => BulkChange::Skip(format!("not tagged #{tag}")),
+            BulkAction::RemoveTag(tag) => {
+                let tags: Vec<String> =
+                    tags.iter().filter(|existing| !existing.eq_ignore_ascii_case(tag)).cloned().collect();
+                BulkChange::Update(tags_input(article, &tags), format!("tags → {}", tags.join(", ")))
+            }
+            BulkAction::RenameTag { from, .. } if !has_tag(from) => BulkChange::Skip(format!("not tagged #{from}")),
+            BulkAction::RenameTag { from, to } => {
+                let mut renamed: Vec<String> = Vec::new();
+                for tag in &tags {
+                    let tag = if tag.eq_ignore_ascii_case(from) { to } else { tag };
+                    if !renamed.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+                        renamed.push(tag.clone());
+                    }
+                }
+                BulkChange::Update(tags_input(article, &renamed), format!("tags → {}", renamed.join(", ")))
+            }
+            BulkAction::SetSeries(series) if article.series().as_deref() == Some(series) => {
+                BulkChange::Skip(format!("already in {series}"))
+            }
+            BulkAction::SetSeries(series) => {
+                BulkChange::Update(series_input(article, series), format!("series → {series}"))
+            }
+            BulkAction::RemoveSeries if article.series().is_none() => BulkChange::Skip("not in a series".to_string()),
+            BulkAction::RemoveSeries => BulkChange::Update(series_input(article, ""), "series removed".to_string()),
+            BulkAction::Delete => BulkChange::Delete,
+            BulkAction::Export(_) => BulkChange::Export,
+        }
+    }
+}
@@ -0,0 +1,451 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use serde::de::{DeserializeOwned, DeserializeSeed, Deserializer, Error as _, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+
+use crate::migrate::{self, CACHE_VERSION};
+use crate::{get_config_dir, Article};
+
+const ZSTD_LEVEL: i32 = 3;
+
+/// How the article cache is stored on disk, set with `cache_format` in the
+/// config. `compact` is zstd-compressed MessagePack, several times smaller
+/// and faster to parse than JSON once bodies add up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheFormat {
+    #[default]
+    Json,
+    Compact,
+}
+
+impl CacheFormat {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The article metadata, i.e. everything but the bodies.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            CacheFormat::Json => "articles_cache.json",
+            CacheFormat::Compact => "articles_cache.msgpack.zst",
+        }
+    }
+
+    /// The markdown bodies by article id, see [`CachedBodies`].
+    pub fn bodies_file_name(self) -> &'static str {
+        match self {
+            CacheFormat::Json => "articles_bodies.json",
+            CacheFormat::Compact => "articles_bodies.msgpack.zst",
+        }
+    }
+
+    /// The published articles, bodies included, for `search --include-published`.
+    pub fn published_file_name(self) -> &'static str {
+        match self {
+            CacheFormat::Json => "published.json",
+            CacheFormat::Compact => "published.msgpack.zst",
+        }
+    }
+
+    pub fn other(self) -> Self {
+        match self {
+            CacheFormat::Json => CacheFormat::Compact,
+            CacheFormat::Compact => CacheFormat::Json,
+        }
+    }
+
+    pub fn encode<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<u8>> {
+        match self {
+            CacheFormat::Json => Ok(serde_json::to_vec_pretty(value)?),
+            CacheFormat::Compact => {
+                // Named fields keep old caches readable when Article gains fields
+                let packed = rmp_serde::to_vec_named(value)?;
+                Ok(zstd::encode_all(packed.as_slice(), ZSTD_LEVEL)?)
+            }
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T> {
+        match self {
+            CacheFormat::Json => Ok(serde_json::from_slice(bytes)?),
+            CacheFormat::Compact => {
+                let packed = zstd::decode_all(bytes).context("Corrupt compact cache")?;
+                Ok(rmp_serde::from_slice(&packed)?)
+            }
+        }
+    }
+
+    /// The metadata cache as stored: [`CACHE_VERSION`] followed by the articles.
+    pub fn encode_articles(self, articles: &[Article]) -> Result<Vec<u8>> {
+        self.encode(&StoredCache { version: CACHE_VERSION, articles })
+    }
+
+    /// Decodes a metadata cache of any version, migrating older records in
+    /// memory. Also returns the version the cache was stored with.
+    pub fn decode_articles(self, bytes: &[u8]) -> Result<(Vec<Article>, u32)> {
+        if let Ok(LoadedCache { version: CACHE_VERSION, articles }) = self.decode(bytes) {
+            return Ok((articles, CACHE_VERSION));
+        }
+        let (version, records) = match self.decode(bytes)? {
+            serde_json::Value::Array(records) => (1, records),
+            serde_json::Value::Object(mut cache) => {
+                let version = cache.get("version").and_then(serde_json::Value::as_u64).unwrap_or(1);
+                let Some(serde_json::Value::Array(records)) = cache.remove("articles") else {
+                    return Err(anyhow::anyhow!("The article cache has no article list"));
+                };
+                (u32::try_from(version)?, records)
+            }
+            _ => return Err(anyhow::anyhow!("The article cache is neither a list nor a versioned cache")),
+        };
+        let articles = migrate::upgrade(records, version)?
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("Failed to read articles cached with version {version}"))?;
+        Ok((articles, version))
+    }
+
+    /// The cache version stored in `path`, reading only the start of the file.
+    pub fn stored_version(self, path: &Path) -> Result<u32> {
+        let mut version = None;
+        let peeked = self.stream(path, PeekVersion(&mut version));
+        match version {
+            Some(version) => Ok(version),
+            None => Err(peeked.err().unwrap_or_else(|| anyhow::anyhow!("No cache version in {}", path.display()))),
+        }
+    }
+
+    /// Calls `f` with each element of the list stored in `path`, or of the
+    /// article list of a versioned cache, decoding one element at a time from
+    /// the file instead of reading it whole.
+    pub fn for_each_element<T: DeserializeOwned>(self, path: &Path, f: impl FnMut(T)) -> Result<()> {
+        self.stream(path, EachElement(f, PhantomData))
+    }
+
+    /// Like [`Self::for_each_element`], for the entries of a stored map.
+    pub fn for_each_entry<K: DeserializeOwned, V: DeserializeOwned>(
+        self,
+        path: &Path,
+        f: impl FnMut(K, V),
+    ) -> Result<()> {
+        self.stream(path, EachEntry(f, PhantomData))
+    }
+
+    fn stream<V: for<'de> Visitor<'de, Value = ()>>(self, path: &Path, visitor: V) -> Result<()> {
+        let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        match self {
+            CacheFormat::Json => {
+                let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(file));
+                deserializer.deserialize_any(visitor)?;
+            }
+            CacheFormat::Compact => {
+                let decoder = zstd::stream::read::Decoder::new(file).context("Corrupt compact cache")?;
+                let mut deserializer = rmp_serde::Deserializer::new(decoder);
+                deserializer.deserialize_any(visitor)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for CacheFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(CacheFormat::Json),
+            "compact" => Ok(CacheFormat::Compact),
+            _ => Err(format!("unknown cache format '{s}' (expected json or compact)")),
+        }
+    }
+}
+
+impl fmt::Display for CacheFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CacheFormat::Json => "json",
+            CacheFormat::Compact => "compact",
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct StoredCache<'a> {
+    // First, so it can be read without decoding the articles
+    version: u32,
+    articles: &'a [Article],
+}
+
+#[derive(Deserialize)]
+struct LoadedCache<T> {
+    version: u32,
+    articles: T,
+}
+
+struct EachElement<T, F>(F, PhantomData<T>);
+
+impl<'de, T: DeserializeOwned, F: FnMut(T)> Visitor<'de> for EachElement<T, F> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list or a versioned cache")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(element) = seq.next_element()? {
+            (self.0)(element);
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "articles" {
+                map.next_value_seed(&mut self)?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'de, T: DeserializeOwned, F: FnMut(T)> DeserializeSeed<'de> for &mut EachElement<T, F> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T: DeserializeOwned, F: FnMut(T)> Visitor<'de> for &mut EachElement<T, F> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(element) = seq.next_element()? {
+            (self.0)(element);
+        }
+        Ok(())
+    }
+}
+
+// Stops decoding as soon as the version is known, by failing on purpose
+struct PeekVersion<'a>(&'a mut Option<u32>);
+
+impl<'de> Visitor<'de> for PeekVersion<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list or a versioned cache")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, _: A) -> Result<(), A::Error> {
+        *self.0 = Some(1);
+        Err(A::Error::custom("version found"))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        if map.next_key::<String>()?.as_deref() == Some("version") {
+            *self.0 = Some(map.next_value()?);
+        }
+        Err(A::Error::custom("version found"))
+    }
+}
+
+struct EachEntry<K, V, F>(F, PhantomData<(K, V)>);
+
+impl<'de, K: DeserializeOwned, V: DeserializeOwned, F: FnMut(K, V)> Visitor<'de> for EachEntry<K, V, F> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<(), A::Error> {
+        while let Some((key, value)) = map.next_entry()? {
+            (self.0)(key, value);
+        }
+        Ok(())
+    }
+}
+
+/// The format the article cache on disk is in, if there is a cache. Updates
+/// keep it; whole rewrites are given the configured format.
+pub fn stored_format() -> Result<Option<CacheFormat>> {
+    for format in [CacheFormat::Json, CacheFormat::Compact] {
+        if get_config_dir()?.join(format.file_name()).exists() {
+            return Ok(Some(format));
+        }
+    }
+    Ok(None)
+}
+
+/// Whether the article cache was written more than `ttl_secs` ago. Without a
+/// TTL, or a cache, it never is.
+pub fn is_expired(ttl_secs: Option<u64>) -> Result<bool> {
+    let (Some(ttl_secs), Some(format)) = (ttl_secs, stored_format()?) else {
+        return Ok(false);
+    };
+    let path = get_config_dir()?.join(format.file_name());
+    let written = fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .with_context(|| format!("Failed to read the age of {}", path.display()))?;
+    Ok(older_than(written, SystemTime::now(), ttl_secs))
+}
+
+/// `written` is more than `ttl_secs` before `now`; a time in the future is not.
+pub fn older_than(written: SystemTime, now: SystemTime, ttl_secs: u64) -> bool {
+    now.duration_since(written).is_ok_and(|age| age > Duration::from_secs(ttl_secs))
+}
+
+/// Article bodies, stored apart from the metadata so listing and sorting
+/// never deserialize the markdown. Commands that need a body load this and
+/// look it up with [`crate::Article::body`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct CachedBodies {
+    bodies: HashMap<u64, String>,
+}
+
+impl CachedBodies {
+    pub fn load() -> Result<Self> {
+        let format = stored_format()?.unwrap_or_default();
+        for format in [format, format.other()] {
+            let path = get_config_dir()?.join(format.bodies_file_name());
+            if path.exists() {
+                let bytes = fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+                return format.decode(&bytes).with_context(|| format!("Invalid cached bodies in {}", path.display()));
+            }
+        }
+        Ok(Self::default())
+    }
+
+    pub fn get(&self, id: u64) -> Option<&str> {
+        self.bodies.get(&id).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, id: u64, body: String) {
+        self.bodies.insert(id, body);
+    }
+
+    pub fn remove(&mut self, id: u64) -> Option<String> {
+        self.bodies.remove(&id)
+    }
+}
+
+pub fn get_checksums_file() -> Result<std::path::PathBuf> {
+    Ok(get_config_dir()?.join("articles_checksums.json"))
+}
+
+/// CRC-32 of a cache record: the metadata as JSON followed by the body.
+/// Written for every record when the cache is saved, so `cache verify` can
+/// spot damaged ones.
+pub fn checksum(metadata: &Article, body: Option<&str>) -> Result<u32> {
+    let mut crc = flate2::Crc::new();
+    crc.update(&serde_json::to_vec(metadata)?);
+    crc.update(body.unwrap_or_default().as_bytes());
+    Ok(crc.sum())
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct CacheChecksums {
+    checksums: HashMap<u64, u32>,
+}
+
+impl CacheChecksums {
+    pub fn insert(&mut self, id: u64, checksum: u32) {
+        self.checksums.insert(id, checksum);
+    }
+
+    /// `None` for caches written before checksums were added.
+    pub fn load() -> Result<Option<Self>> {
+        let path = get_checksums_file()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let checksums = serde_json::from_str(&content).with_context(|| format!("Invalid checksums in {}", path.display()))?;
+        Ok(Some(checksums))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(get_checksums_file()?, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheProblem {
+    /// The record doesn't match the checksum it was saved with.
+    Mismatch,
+    /// The record has no checksum.
+    Unverified,
+    /// A checksum exists but the record is gone.
+    Missing,
+}
+
+impl CacheProblem {
+    pub fn describe(self) -> &'static str {
+        match self {
+            CacheProblem::Mismatch => "checksum mismatch",
+            CacheProblem::Unverified => "no checksum",
+            CacheProblem::Missing => "missing from the cache",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DamagedRecord {
+    pub id: u64,
+    /// The cached title, unless the record is missing.
+    pub title: Option<String>,
+    pub problem: CacheProblem,
+}
+
+/// Compares every cached record (metadata as loaded, without bodies, plus
+/// its entry in `bodies`) with its saved checksum. Records are reported in
+/// cache order, followed by the missing ones by id.
+pub fn verify(metadata: &[Article], bodies: &CachedBodies, checksums: &CacheChecksums) -> Result<Vec<DamagedRecord>> {
+    let mut damaged = Vec::new();
+    for article in metadata {
+        let problem = match checksums.checksums.get(&article.id) {
+            None => Some(CacheProblem::Unverified),
+            Some(expected) if *expected != checksum(article, bodies.get(article.id))? => Some(CacheProblem::Mismatch),
+            Some(_) => None,
+        };
+        if let Some(problem) = problem {
+            damaged.push(DamagedRecord { id: article.id, title: Some(article.title.clone()), problem });
+        }
+    }
+    let cached: std::collections::HashSet<u64> = metadata.iter().map(|article| article.id).collect();
+    let mut missing: Vec<u64> = checksums
+        .checksums
+        .keys()
+        .filter(|id| !cached.contains(id))
+        .copied()
+        .collect();
+    missing.sort_unstable();
+    damaged.extend(missing.into_iter().map(|id| DamagedRecord { id, title: None, problem: CacheProblem::Missing }));
+    Ok(damaged)
+}
+
+/// The ids a checksum file lists, for re-fetching a cache that can't be read.
+pub fn checksummed_ids(checksums: &CacheChecksums) -> Vec<u64> {
+    let mut ids: Vec<u64> = checksums.checksums.keys().copied().collect();
+    ids.sort_unstable();
+    ids
+}
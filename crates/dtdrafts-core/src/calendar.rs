@@ -0,0 +1,104 @@
+//! The `calendar` view: a contribution grid, one cell per day and one column
+//! per week, of when drafts were created and last updated.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+
+use crate::Article;
+
+/// Cells for days with no activity, then increasing activity.
+#[cfg(feature = "terminal")]
+const LEVELS: [char; 5] = ['·', '░', '▒', '▓', '█'];
+#[cfg(feature = "terminal")]
+const WEEKDAYS: [&str; 7] = ["Mon", "", "Wed", "", "Fri", "", "Sun"];
+
+/// The most weeks `render` draws, ten years; longer spans are cut to it.
+pub const MAX_WEEKS: usize = 520;
+
+/// Drafts created and updated per day (UTC).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Activity {
+    pub created: BTreeMap<NaiveDate, usize>,
+    pub updated: BTreeMap<NaiveDate, usize>,
+}
+
+impl Activity {
+    /// Counts each draft on the day it was created and, if later, the day it
+    /// was last updated.
+    pub fn from_articles(articles: &[&Article]) -> Self {
+        let mut activity = Self::default();
+        for article in articles {
+            let created = article.created_at.map(|at| at.date_naive());
+            if let Some(day) = created {
+                *activity.created.entry(day).or_default() += 1;
+            }
+            if let Some(day) = article.updated_at.map(|at| at.date_naive()).filter(|day| Some(*day) != created) {
+                *activity.updated.entry(day).or_default() += 1;
+            }
+        }
+        activity
+    }
+
+    pub fn on(&self, day: NaiveDate) -> usize {
+        self.created.get(&day).unwrap_or(&0) + self.updated.get(&day).unwrap_or(&0)
+    }
+
+    #[cfg(feature = "terminal")]
+    fn between(counts: &BTreeMap<NaiveDate, usize>, start: NaiveDate, end: NaiveDate) -> usize {
+        counts.range(start..=end).map(|(_, count)| count).sum()
+    }
+}
+
+/// The grid for the `weeks` weeks (Monday to Sunday) up to `today`, with
+/// month names above it, a legend and the totals below. Busier days get
+/// denser blocks, relative to the busiest day shown.
+#[cfg(feature = "terminal")]
+pub fn render(activity: &Activity, today: NaiveDate, weeks: usize) -> String {
+    use chrono::{Datelike, Duration};
+    use colored::*;
+    let weeks = weeks.clamp(1, MAX_WEEKS);
+    let start = today - Duration::days(i64::from(today.weekday().num_days_from_monday()))
+        - Duration::weeks(weeks as i64 - 1);
+    let days = |week: usize| (0..7).map(move |weekday| start + Duration::days((week * 7 + weekday) as i64));
+    let busiest = (0..weeks).flat_map(days).filter(|day| *day <= today).map(|day| activity.on(day)).max().unwrap_or(0);
+
+    let mut months = String::new();
+    for week in 0..weeks {
+        let first = start + Duration::weeks(week as i64);
+        let last = first + Duration::days(6);
+        // A month is named above the week its first day falls in, unless
+        // that would run into the previous name
+        let new_month = last.month() != (first - Duration::days(1)).month();
+        if new_month && (months.is_empty() || months.chars().count() < week) {
+            months.push_str(&" ".repeat(week - months.chars().count()));
+            months.push_str(&last.format("%b").to_string());
+        }
+    }
+    let mut out = format!("    {}\n", months.trim_end());
+    for (weekday, label) in WEEKDAYS.iter().enumerate() {
+        let mut row = format!("{label:<4}");
+        for week in 0..weeks {
+            let day = start + Duration::days((week * 7 + weekday) as i64);
+            if day > today {
+                break;
+            }
+            let count = activity.on(day);
+            let level = if count == 0 { 0 } else { (count * 4).div_ceil(busiest).clamp(1, 4) };
+            let cell = LEVELS[level].to_string();
+            row.push_str(&if level == 0 { cell.dimmed().to_string() } else { cell.green().to_string() });
+        }
+        out.push_str(row.trim_end());
+        out.push('\n');
+    }
+    let legend: String = LEVELS.iter().map(|level| format!("{level} ")).collect();
+    out.push_str(&format!("\n    Less {}More\n", legend));
+    out.push_str(&format!(
+        "    {} created, {} updated from {} to {}\n",
+        Activity::between(&activity.created, start, today),
+        Activity::between(&activity.updated, start, today),
+        start,
+        today
+    ));
+    out
+}
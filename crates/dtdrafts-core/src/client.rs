@@ -0,0 +1,819 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Result, Context};
+use reqwest::header::HeaderMap;
+use reqwest::multipart::{Form, Part};
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+
+use crate::analytics::ArticleAnalytics;
+use crate::comments::Comment;
+use crate::fixtures::{Fixture, FixtureMode, Recorder, Replayer};
+use crate::tags::{FollowedTag, Tag};
+use crate::{Article, Config};
+
+pub const DEFAULT_BASE_URL: &str = "https://dev.to/api";
+pub const DEFAULT_USER_AGENT: &str = "dtdrafts/0.1.0";
+/// Tracing target of the fetch progress and rate limit notices, which the CLI
+/// shows unless `--quiet`.
+pub const PROGRESS_TARGET: &str = "dtdrafts_core::progress";
+
+/// Retries of a request answered with 429 Too Many Requests.
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+/// Longest pause a rate limit header can ask for; anything longer is cut to it.
+const MAX_RATE_LIMIT_DELAY: Duration = Duration::from_secs(5 * 60);
+
+pub struct DevToClient {
+    client: reqwest::Client,
+    pub api_key: String,
+    base_url: String,
+    user_agent: String,
+    show_progress: bool,
+    // Set when a response asked to pause before the next request
+    next_request_at: Mutex<Option<Instant>>,
+    recorder: Option<Recorder>,
+    replayer: Option<Replayer>,
+    strict: bool,
+    // Author of the articles that come without one
+    username: Option<String>,
+}
+
+/// Fields accepted by the create and update article endpoints. Unset fields
+/// are left out of the request so updates don't clear them.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ArticleInput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_markdown: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<String>,
+    /// The cover image URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub main_image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Organization {
+    pub id: u64,
+    pub name: String,
+    pub username: String,
+}
+
+/// The account an API key belongs to, from `/users/me`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct User {
+    pub id: u64,
+    pub username: String,
+    #[serde(default)]
+    pub name: String,
+    /// As dev.to formats it, e.g. `Apr 14, 2017`
+    #[serde(default)]
+    pub joined_at: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ArticleEnvelope<'a> {
+    article: &'a ArticleInput,
+}
+
+/// Network settings from the `http` section of the config file. Proxies
+/// from `HTTP_PROXY`/`HTTPS_PROXY` are used unless `proxy` overrides them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HttpConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+}
+
+impl HttpConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Configures a [`DevToClient`]. Requests time out after
+/// [`DEFAULT_TIMEOUT`] unless told otherwise.
+#[derive(Debug, Clone)]
+pub struct DevToClientBuilder {
+    api_key: String,
+    base_url: String,
+    user_agent: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<String>,
+    client: Option<reqwest::Client>,
+    fixtures: Option<FixtureMode>,
+    strict: bool,
+    username: Option<String>,
+}
+
+impl Default for DevToClientBuilder {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            timeout: None,
+            connect_timeout: None,
+            proxy: None,
+            client: None,
+            fixtures: None,
+            strict: false,
+            username: None,
+        }
+    }
+}
+
+impl DevToClientBuilder {
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = api_key.into();
+        self
+    }
+
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Sends requests through `client`, e.g. one set up with tracing or
+    /// recording. The client's own timeouts and proxy are used, so setting
+    /// them on the builder too (directly or with [`Self::http_config`]) is an
+    /// error at [`Self::build`]. It has to be a plain `reqwest::Client`;
+    /// middleware wrappers around one can't be injected.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Limit for a whole request, including reading the response.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Sends every request through this proxy instead of the one from the
+    /// environment.
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Applies the non-empty settings of the config file's `http` section.
+    pub fn http_config(mut self, http: &HttpConfig) -> Self {
+        if let Some(secs) = http.timeout_secs {
+            self.timeout = Some(Duration::from_secs(secs));
+        }
+        if let Some(secs) = http.connect_timeout_secs {
+            self.connect_timeout = Some(Duration::from_secs(secs));
+        }
+        if let Some(proxy) = &http.proxy {
+            self.proxy = Some(proxy.clone());
+        }
+        self
+    }
+
+    /// Applies the API key, base URL and `http` section of the config file.
+    pub fn config(mut self, config: &Config) -> Self {
+        self = self.api_key(config.api_key.as_str()).http_config(&config.http);
+        if let Some(username) = &config.username {
+            self = self.username(username.as_str());
+        }
+        if let Some(base_url) = &config.base_url {
+            self = self.base_url(base_url.as_str());
+        }
+        self
+    }
+
+    /// Records every API response to a directory, or answers requests from
+    /// such a recording without touching the network. See [`crate::fixtures`].
+    pub fn fixtures(mut self, mode: FixtureMode) -> Self {
+        self.fixtures = Some(mode);
+        self
+    }
+
+    /// The account the API key belongs to, filled in as the author of
+    /// articles that come without one so [`Article::edit_url`] still works.
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    /// Fails a whole listing when one article in it can't be parsed, instead
+    /// of skipping that article with a warning.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn build(self) -> Result<DevToClient> {
+        let client = match self.client {
+            Some(_) if self.timeout.is_some() || self.connect_timeout.is_some() || self.proxy.is_some() => {
+                return Err(anyhow::anyhow!(
+                    "Timeouts and proxies can't be applied to an injected HTTP client; configure them on the client itself"
+                ));
+            }
+            Some(client) => client,
+            None => {
+                let mut client = reqwest::Client::builder()
+                    .timeout(self.timeout.unwrap_or(DEFAULT_TIMEOUT))
+                    .connect_timeout(self.connect_timeout.unwrap_or(DEFAULT_CONNECT_TIMEOUT));
+                if let Some(proxy) = &self.proxy {
+                    let proxy = reqwest::Proxy::all(proxy).with_context(|| format!("Invalid proxy URL '{proxy}'"))?;
+                    client = client.proxy(proxy);
+                }
+                client.build().context("Failed to set up the HTTP client")?
+            }
+        };
+        let (recorder, replayer) = match &self.fixtures {
+            Some(FixtureMode::Record(dir)) => (Some(Recorder::new(dir, &self.api_key)?), None),
+            Some(FixtureMode::Replay(dir)) => (None, Some(Replayer::load(dir)?)),
+            None => (None, None),
+        };
+        Ok(DevToClient {
+            client,
+            api_key: self.api_key,
+            base_url: self.base_url,
+            user_agent: self.user_agent,
+            show_progress: true,
+            next_request_at: Mutex::new(None),
+            recorder,
+            replayer,
+            strict: self.strict,
+            username: self.username,
+        })
+    }
+}
+
+impl DevToClient {
+    /// Starts configuring a client, e.g.
+    /// `DevToClient::builder().api_key(key).user_agent("my-tool/1.0").client(http).build()`.
+    pub fn builder() -> DevToClientBuilder {
+        DevToClientBuilder::default()
+    }
+
+    /// A client with the default settings.
+    pub fn new(api_key: String) -> Self {
+        Self::builder()
+            .api_key(api_key)
+            .build()
+            .expect("the default HTTP client settings are valid")
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Self::builder().config(config).build()
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Whether responses come from a `--replay` directory rather than dev.to.
+    pub fn is_replaying(&self) -> bool {
+        self.replayer.is_some()
+    }
+
+    /// Controls the per-page progress and rate limit notices logged under
+    /// [`PROGRESS_TARGET`] while fetching.
+    pub fn with_progress(mut self, show_progress: bool) -> Self {
+        self.show_progress = show_progress;
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}{path}", self.base_url))
+            .header("api-key", &self.api_key)
+            .header("User-Agent", &self.user_agent)
+    }
+
+    // Waits out any pause the previous response asked for before sending, and
+    // retries 429s after their Retry-After delay (or an exponential backoff)
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let mut request = request.build()?;
+        let mut retries = 0;
+        loop {
+            let next_request_at = *self.next_request_at.lock().unwrap();
+            // Replayed sessions don't wait out the recorded rate limits
+            if let Some(at) = next_request_at.filter(|_| self.replayer.is_none()) {
+                tokio::time::sleep_until(at).await;
+            }
+            let retry = request.try_clone();
+            let (method, url) = (request.method().clone(), request.url().path().to_string());
+            let started = Instant::now();
+            let response = self.execute(request).await.inspect_err(|e| {
+                tracing::debug!(%method, url, error = %e, "request failed");
+            })?;
+            let delay = rate_limit_delay(response.headers());
+            let status = response.status();
+            tracing::debug!(%method, url, status = status.as_u16(), elapsed_ms = started.elapsed().as_millis() as u64, "request");
+            match retry {
+                Some(next) if status == StatusCode::TOO_MANY_REQUESTS && retries < MAX_RATE_LIMIT_RETRIES => {
+                    let delay = delay.unwrap_or(Duration::from_secs(1 << retries));
+                    if self.show_progress {
+                        tracing::warn!(target: PROGRESS_TARGET, url, retry_in_secs = delay.as_secs_f64(), "rate limited by the API, retrying");
+                    } else {
+                        tracing::debug!(url, retry_in_secs = delay.as_secs_f64(), "rate limited by the API, retrying");
+                    }
+                    *self.next_request_at.lock().unwrap() = Some(Instant::now() + delay);
+                    retries += 1;
+                    request = next;
+                }
+                _ => {
+                    if let Some(delay) = delay {
+                        tracing::info!(pause_secs = delay.as_secs_f64(), "rate limit quota used up, pausing");
+                    }
+                    *self.next_request_at.lock().unwrap() = delay.map(|delay| Instant::now() + delay);
+                    return Ok(response);
+                }
+            }
+        }
+    }
+
+    async fn execute(&self, request: reqwest::Request) -> Result<Response> {
+        let url = request.url().as_str();
+        let path = url.strip_prefix(self.base_url.as_str()).unwrap_or(url).to_string();
+        if let Some(replayer) = &self.replayer {
+            return replayer.take(request.method().as_str(), &path)?.to_response();
+        }
+        let Some(recorder) = &self.recorder else {
+            return Ok(self.client.execute(request).await?);
+        };
+        let method = request.method().to_string();
+        let request_body = request.body().and_then(|body| body.as_bytes()).map(<[u8]>::to_vec);
+        let response = self.client.execute(request).await?;
+        let (status, headers) = (response.status().as_u16(), response.headers().clone());
+        let body = response.bytes().await?;
+        let fixture = Fixture::new(&method, &path, request_body.as_deref(), status, &headers, &body);
+        let file = recorder.record(&fixture)?;
+        tracing::debug!(file = %file.display(), "recorded response");
+        fixture.to_response()
+    }
+
+    // The parsed articles of a page, and how many elements the page had
+    // including the skipped ones, which is what tells whether it was the last
+    fn parse_articles(&self, json: &str) -> Result<(Vec<Article>, usize)> {
+        let (articles, failures) = parse_articles(json, self.strict)?;
+        for failure in &failures {
+            tracing::warn!(id = failure.id, error = %failure.error, "skipping an article that could not be parsed");
+        }
+        let elements = articles.len() + failures.len();
+        Ok((articles.into_iter().map(|article| self.with_author(article)).collect(), elements))
+    }
+
+    fn with_author(&self, mut article: Article) -> Article {
+        if let Some(username) = self.username.as_ref().filter(|_| article.user.username.is_empty()) {
+            article.user.username = username.clone();
+        }
+        article
+    }
+
+    pub async fn get_my_articles(&self) -> Result<Vec<Article>> {
+        self.get_my_articles_in("unpublished").await
+    }
+
+    pub async fn get_my_published_articles(&self) -> Result<Vec<Article>> {
+        self.get_my_articles_in("published").await
+    }
+
+    // `scope` is the `/articles/me/<scope>` listing: unpublished, published or all
+    #[tracing::instrument(skip(self))]
+    async fn get_my_articles_in(&self, scope: &str) -> Result<Vec<Article>> {
+        let mut all_articles = Vec::new();
+        let mut page = 1;
+        let per_page = 1000;
+
+        loop {
+            let path = format!("/articles/me/{scope}?page={page}&per_page={per_page}");
+            let response = self
+                .send(self.request(Method::GET, &path))
+                .await
+                .context("Failed to fetch articles from dev.to API")?;
+
+            if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                return Err(anyhow::anyhow!("dev.to kept rate limiting the requests; try again in a minute"));
+            }
+            if !response.status().is_success() {
+                return Err(anyhow::anyhow!(
+                    "API request failed with status: {}. Please check your API key.",
+                    response.status()
+                ));
+            }
+
+            let text = response.text().await?;
+            let (articles, elements) = self.parse_articles(&text)?;
+
+            let last_page = elements < per_page;
+            all_articles.extend(articles);
+            if self.show_progress {
+                tracing::info!(target: PROGRESS_TARGET, "Page {}: Fetched {} articles so far...", page, all_articles.len());
+            } else {
+                tracing::debug!(page, total = all_articles.len(), "fetched page");
+            }
+            if last_page {
+                break;
+            }
+            page += 1;
+        }
+
+        if self.show_progress {
+            tracing::info!(target: PROGRESS_TARGET, "Done! Total {} articles fetched.", all_articles.len());
+        }
+        tracing::info!(scope, articles = all_articles.len(), "fetched articles");
+        Ok(all_articles)
+    }
+
+    /// View, reaction and comment totals of every published article.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_my_published_analytics(&self) -> Result<Vec<ArticleAnalytics>> {
+        let per_page = 1000;
+        let mut all = Vec::new();
+        for page in 1.. {
+            let path = format!("/articles/me/published?page={page}&per_page={per_page}");
+            let response = self
+                .send(self.request(Method::GET, &path))
+                .await
+                .context("Failed to fetch articles from dev.to API")?;
+            let response = ensure_success(response, "fetch published articles").await?;
+            let rows: Vec<ArticleAnalytics> = response
+                .json()
+                .await
+                .context("Failed to parse JSON response")?;
+            let last_page = rows.len() < per_page;
+            all.extend(rows);
+            if last_page {
+                break;
+            }
+        }
+        Ok(all)
+    }
+
+    /// Fetches one of the user's own articles (published or not) fresh from
+    /// the API. There is no single-draft endpoint, so this pages through
+    /// `/articles/me/all` until the id turns up.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_my_article(&self, id: u64) -> Result<Article> {
+        self.get_my_articles_by_id(&[id])
+            .await?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("Article {id} was not found in your dev.to articles"))
+    }
+
+    /// Like [`Self::get_my_article`] for several ids in one pass. Ids that
+    /// aren't found are left out of the result.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_my_articles_by_id(&self, ids: &[u64]) -> Result<Vec<Article>> {
+        let mut wanted: std::collections::HashSet<u64> = ids.iter().copied().collect();
+        let mut found = Vec::new();
+        let per_page = 1000;
+        for page in 1.. {
+            if wanted.is_empty() {
+                break;
+            }
+            let path = format!("/articles/me/all?page={page}&per_page={per_page}");
+            let response = self
+                .send(self.request(Method::GET, &path))
+                .await
+                .context("Failed to fetch articles from dev.to API")?;
+            let response = ensure_success(response, "fetch articles").await?;
+            let (articles, elements) = self.parse_articles(&response.text().await?)?;
+            if elements == 0 {
+                break;
+            }
+            found.extend(articles.into_iter().filter(|article| wanted.remove(&article.id)));
+        }
+        Ok(found)
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub async fn create_article(&self, input: &ArticleInput) -> Result<Article> {
+        let response = self
+            .send(self.request(Method::POST, "/articles").json(&ArticleEnvelope { article: input }))
+            .await
+            .context("Failed to create article on dev.to")?;
+        let response = ensure_success(response, "create article").await?;
+        let article = response.json().await.context("Failed to parse created article")?;
+        Ok(self.with_author(article))
+    }
+
+    #[tracing::instrument(skip(self, input))]
+    pub async fn update_article(&self, id: u64, input: &ArticleInput) -> Result<Article> {
+        let response = self
+            .send(self.request(Method::PUT, &format!("/articles/{id}")).json(&ArticleEnvelope { article: input }))
+            .await
+            .with_context(|| format!("Failed to update article {id} on dev.to"))?;
+        let response = ensure_success(response, "update article").await?;
+        let article = response.json().await.context("Failed to parse updated article")?;
+        Ok(self.with_author(article))
+    }
+
+    /// Top-level comments of an article, each with its replies.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_comments(&self, article_id: u64) -> Result<Vec<Comment>> {
+        let response = self
+            .send(self.request(Method::GET, &format!("/comments?a_id={article_id}")))
+            .await
+            .with_context(|| format!("Failed to fetch comments for article {article_id}"))?;
+        let response = ensure_success(response, "fetch comments").await?;
+        response.json().await.context("Failed to parse comments")
+    }
+
+    /// The `limit` most popular tags, most popular first.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_popular_tags(&self, limit: usize) -> Result<Vec<Tag>> {
+        let per_page = limit.min(1000);
+        let mut tags: Vec<Tag> = Vec::new();
+        for page in 1.. {
+            let path = format!("/tags?page={page}&per_page={per_page}");
+            let response = self
+                .send(self.request(Method::GET, &path))
+                .await
+                .context("Failed to fetch tags from dev.to API")?;
+            let response = ensure_success(response, "fetch tags").await?;
+            let batch: Vec<Tag> = response.json().await.context("Failed to parse tags")?;
+            let last_page = batch.len() < per_page;
+            tags.extend(batch);
+            if last_page || tags.len() >= limit {
+                break;
+            }
+        }
+        tags.truncate(limit);
+        Ok(tags)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_followed_tags(&self) -> Result<Vec<FollowedTag>> {
+        let response = self
+            .send(self.request(Method::GET, "/follows/tags"))
+            .await
+            .context("Failed to fetch followed tags from dev.to API")?;
+        let response = ensure_success(response, "fetch followed tags").await?;
+        response.json().await.context("Failed to parse followed tags")
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_organization(&self, username: &str) -> Result<Organization> {
+        let response = self
+            .send(self.request(Method::GET, &format!("/organizations/{username}")))
+            .await
+            .with_context(|| format!("Failed to fetch organization {username} from dev.to"))?;
+        let response = ensure_success(response, &format!("find organization '{username}'")).await?;
+        response.json().await.context("Failed to parse organization")
+    }
+
+    /// The user the API key belongs to; fails with a 401 for a bad key.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_me(&self) -> Result<User> {
+        let response = self
+            .send(self.request(Method::GET, "/users/me"))
+            .await
+            .context("Failed to fetch the authenticated user from dev.to")?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(anyhow::anyhow!("dev.to rejected the API key (401 Unauthorized)"));
+        }
+        let response = ensure_success(response, "fetch the authenticated user").await?;
+        response.json().await.context("Failed to parse the authenticated user")
+    }
+
+    /// Accepts an organization id or its username (`--org myorg`).
+    pub async fn resolve_organization_id(&self, org: &str) -> Result<u64> {
+        match org.parse() {
+            Ok(id) => Ok(id),
+            Err(_) => Ok(self.get_organization(org).await?.id),
+        }
+    }
+
+    /// The public Forem API does not document article deletion, so instances
+    /// without it answer with 404 or 405 and this returns an error.
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_article(&self, id: u64) -> Result<()> {
+        let response = self
+            .send(self.request(Method::DELETE, &format!("/articles/{id}")))
+            .await
+            .with_context(|| format!("Failed to delete article {id} on dev.to"))?;
+        ensure_success(response, "delete article").await?;
+        Ok(())
+    }
+
+    /// Uploads an image the way dev.to's editor does and returns its URL.
+    /// `/image_uploads` lives outside the documented API, so instances that
+    /// don't accept API keys there answer with an error status.
+    #[tracing::instrument(skip(self, bytes))]
+    pub async fn upload_image(&self, file_name: &str, bytes: Vec<u8>) -> Result<String> {
+        let site = self.base_url.strip_suffix("/api").unwrap_or(&self.base_url);
+        let form = Form::new().part("image", Part::bytes(bytes).file_name(file_name.to_string()));
+        let request = self
+            .client
+            .post(format!("{site}/image_uploads"))
+            .header("api-key", &self.api_key)
+            .header("User-Agent", &self.user_agent)
+            .multipart(form);
+        let response = self.send(request).await.context("Failed to upload the image to dev.to")?;
+        let response = ensure_success(response, "upload the image").await?;
+        let upload: ImageUpload = response.json().await.context("Failed to parse the image upload response")?;
+        upload
+            .links
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("dev.to accepted the image but returned no URL"))
+    }
+}
+
+#[derive(Deserialize)]
+struct ImageUpload {
+    links: Vec<String>,
+}
+
+/// An element of an article listing that didn't deserialize as an
+/// [`Article`], with its id when it has one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFailure {
+    pub id: Option<u64>,
+    pub error: String,
+}
+
+/// Parses an article listing one element at a time, so an article with an
+/// unexpected field shape is returned as a [`ParseFailure`] rather than
+/// failing the rest. With `strict` the first failure is an error.
+pub fn parse_articles(json: &str, strict: bool) -> Result<(Vec<Article>, Vec<ParseFailure>)> {
+    let elements: Vec<serde_json::Value> = serde_json::from_str(json).context("Failed to parse JSON response")?;
+    let mut articles = Vec::with_capacity(elements.len());
+    let mut failures = Vec::new();
+    for element in elements {
+        let id = element.get("id").and_then(serde_json::Value::as_u64);
+        match serde_json::from_value(element) {
+            Ok(article) => articles.push(article),
+            Err(e) if strict => {
+                let article = id.map(|id| format!("article {id}")).unwrap_or_else(|| "an article".to_string());
+                return Err(anyhow::Error::new(e).context(format!("Failed to parse JSON response: {article} is malformed")));
+            }
+            Err(e) => failures.push(ParseFailure { id, error: e.to_string() }),
+        }
+    }
+    Ok((articles, failures))
+}
+
+/// How long to wait before the next request: the `Retry-After` seconds if
+/// given, otherwise the time until the quota resets once `RateLimit-Remaining`
+/// (or `X-RateLimit-Remaining`) reaches zero, at most five minutes. `None`
+/// means no need to wait.
+pub fn rate_limit_delay(headers: &HeaderMap) -> Option<Duration> {
+    let number = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<u64>().ok();
+    let seconds = match number("retry-after") {
+        Some(seconds) => seconds,
+        None => {
+            let remaining = number("ratelimit-remaining").or_else(|| number("x-ratelimit-remaining"))?;
+            if remaining > 0 {
+                return None;
+            }
+            let reset = number("ratelimit-reset").or_else(|| number("x-ratelimit-reset"))?;
+            // Some servers send the reset time as a Unix timestamp rather than a delay
+            if reset > 1_000_000_000 {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+                reset.saturating_sub(now)
+            } else {
+                reset
+            }
+        }
+    };
+    Some(Duration::from_secs(seconds).min(MAX_RATE_LIMIT_DELAY))
+}
+
+async fn ensure_success(response: Response, action: &str) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(anyhow::anyhow!("Failed to {action}: API returned {status}. {}", body.trim()))
+}
+
+/// Outcome of checking a single URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkCheck {
+    Ok,
+    Redirect { status: u16, location: Option<String> },
+    Broken(u16),
+    /// The request itself failed (DNS, TLS, timeout, ...)
+    Failed(String),
+}
+
+/// Checks URLs concurrently with HEAD requests, falling back to GET for
+/// servers that reject HEAD. Redirects are reported rather than followed.
+pub struct LinkChecker {
+    client: reqwest::Client,
+    concurrency: usize,
+    min_interval: Duration,
+}
+
+impl LinkChecker {
+    pub fn new() -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(Duration::from_secs(15))
+            .user_agent(DEFAULT_USER_AGENT)
+            .build()
+            .context("Failed to build HTTP client")?;
+        Ok(Self { client, concurrency: 8, min_interval: Duration::from_millis(100) })
+    }
+
+    /// Maximum number of requests in flight.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Minimum delay between starting two requests.
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Returns one result per URL, in input order.
+    pub async fn check(&self, urls: &[String]) -> Vec<LinkCheck> {
+        let semaphore = Arc::new(Semaphore::new(self.concurrency));
+        let mut tasks = JoinSet::new();
+        let mut ticker = tokio::time::interval(self.min_interval.max(Duration::from_millis(1)));
+        for (index, url) in urls.iter().enumerate() {
+            ticker.tick().await;
+            let permit = Arc::clone(&semaphore).acquire_owned().await.expect("semaphore is never closed");
+            let client = self.client.clone();
+            let url = url.clone();
+            tasks.spawn(async move {
+                let result = check_link(&client, &url).await;
+                drop(permit);
+                (index, result)
+            });
+        }
+        let mut results = vec![LinkCheck::Failed("not checked".to_string()); urls.len()];
+        while let Some(joined) = tasks.join_next().await {
+            if let Ok((index, result)) = joined {
+                results[index] = result;
+            }
+        }
+        results
+    }
+}
+
+async fn check_link(client: &reqwest::Client, url: &str) -> LinkCheck {
+    let mut response = client.head(url).send().await;
+    if let Ok(head) = &response {
+        if matches!(head.status().as_u16(), 403 | 405 | 501) {
+            response = client.get(url).send().await;
+        }
+    }
+    match response {
+        Err(e) => LinkCheck::Failed(e.without_url().to_string()),
+        Ok(response) => {
+            let status = response.status();
+            if status.is_redirection() {
+                let location = response
+                    .headers()
+                    .get(reqwest::header::LOCATION)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                LinkCheck::Redirect { status: status.as_u16(), location }
+            } else if status.is_client_error() || status.is_server_error() {
+                LinkCheck::Broken(status.as_u16())
+            } else {
+                LinkCheck::Ok
+            }
+        }
+    }
+}
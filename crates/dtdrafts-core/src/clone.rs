@@ -0,0 +1,107 @@
+//! Cloning a draft as the start of a new one (`clone`), for recurring
+//! formats: the structure, tags and series are kept, and whatever sits
+//! between content markers is emptied.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::client::ArticleInput;
+use crate::frontmatter::parse_document;
+use crate::Article;
+
+pub const DEFAULT_CONTENT_START: &str = "<!-- content -->";
+pub const DEFAULT_CONTENT_END: &str = "<!-- /content -->";
+
+/// The markers around the content a clone leaves out, stored under `clone`
+/// in the config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CloneConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_start: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_end: Option<String>,
+}
+
+impl CloneConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The start and end markers. Empty markers, or the same one for both,
+    /// can't delimit anything and are rejected.
+    pub fn markers(&self) -> Result<(&str, &str)> {
+        let start = self.content_start.as_deref().unwrap_or(DEFAULT_CONTENT_START);
+        let end = self.content_end.as_deref().unwrap_or(DEFAULT_CONTENT_END);
+        if start.trim().is_empty() || end.trim().is_empty() {
+            return Err(anyhow::anyhow!("clone.content_start and clone.content_end must not be empty"));
+        }
+        if start == end {
+            return Err(anyhow::anyhow!("clone.content_start and clone.content_end must differ (both are '{start}')"));
+        }
+        Ok((start, end))
+    }
+}
+
+/// Empties every span between `start` and the next `end` marker, keeping
+/// the markers so the clone can be cloned in turn. A start marker without
+/// an end empties the rest of the body. Returns the body and how many
+/// spans were emptied; an empty `start` marks nothing.
+pub fn strip_content(markdown: &str, start: &str, end: &str) -> (String, usize) {
+    if start.is_empty() {
+        return (markdown.to_string(), 0);
+    }
+    let mut out = String::new();
+    let mut rest = markdown;
+    let mut stripped = 0;
+    while let Some(at) = rest.find(start) {
+        let after = at + start.len();
+        out.push_str(&rest[..after]);
+        stripped += 1;
+        match rest[after..].find(end) {
+            Some(offset) => {
+                out.push('\n');
+                rest = &rest[after + offset..];
+                out.push_str(&rest[..end.len()]);
+                rest = &rest[end.len()..];
+            }
+            None => {
+                out.push('\n');
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    (out, stripped)
+}
+
+/// The new draft: `original`'s body with its content stripped, its tags
+/// and series, under `title`. Front matter in the body is kept, with the
+/// title replaced, `published` turned off and the original's canonical URL
+/// and date dropped.
+pub fn clone_input(original: &Article, title: &str, config: &CloneConfig) -> Result<(ArticleInput, usize)> {
+    let (start, end) = config.markers()?;
+    let (body, stripped) = strip_content(original.body_markdown.as_deref().unwrap_or_default(), start, end);
+    let input = match parse_document(&body) {
+        (Some(mut front_matter), content) => {
+            front_matter.set("title", title);
+            front_matter.set("published", "false");
+            front_matter.remove("canonical_url");
+            front_matter.remove("date");
+            ArticleInput {
+                body_markdown: Some(format!("{}{content}", front_matter.render())),
+                published: Some(false),
+                ..Default::default()
+            }
+        }
+        (None, _) => ArticleInput {
+            title: Some(title.to_string()),
+            body_markdown: Some(body.clone()),
+            published: Some(false),
+            tags: original.tags.clone().filter(|tags| !tags.is_empty()),
+            series: original.series(),
+            ..Default::default()
+        },
+    };
+    Ok((input, stripped))
+}
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// A comment from `/comments?a_id=`, with its replies.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Comment {
+    pub id_code: String,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub body_html: String,
+    #[serde(default)]
+    pub user: CommentUser,
+    #[serde(default)]
+    pub children: Vec<Comment>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CommentUser {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub username: String,
+}
+
+impl Comment {
+    pub fn created_at_utc(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(self.created_at.as_deref()?)
+            .ok()
+            .map(|at| at.with_timezone(&Utc))
+    }
+
+    /// The comment body as markdown, which reads fine in a terminal.
+    pub fn body_text(&self) -> String {
+        htmd::convert(&self.body_html)
+            .unwrap_or_else(|_| self.body_html.clone())
+            .trim()
+            .to_string()
+    }
+}
+
+/// Counts comments including all nested replies.
+pub fn count_comments(comments: &[Comment]) -> usize {
+    comments
+        .iter()
+        .map(|comment| 1 + count_comments(&comment.children))
+        .sum()
+}
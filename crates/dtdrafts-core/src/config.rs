@@ -0,0 +1,507 @@
+//! The `~/.dtdrafts/config.toml` file and how [`Config`] is resolved from it.
+//!
+//! Settings are layered, later layers winning: built-in defaults, the config
+//! file, the selected `[profiles.<name>]` table of the file, `DTDRAFTS_*`
+//! environment variables (`DTDRAFTS_API__KEY`, `DTDRAFTS_CACHE__FORMAT`, ...,
+//! with `__` between section and key) and finally command-line flags. The
+//! `config.json` older versions wrote is still read when there is no TOML
+//! file, and replaced by one the first time the config is saved.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use figment::providers::{Env, Format, Serialized, Toml};
+use figment::Figment;
+use serde::{Deserialize, Serialize};
+
+use crate::cache::CacheFormat;
+use crate::client::HttpConfig;
+use crate::clone::CloneConfig;
+use crate::crosspost::CrosspostConfig;
+use crate::frontmatter::FrontMatterMapping;
+use crate::hooks::HooksConfig;
+use crate::text::WordCountOptions;
+use crate::{get_config_dir, Config};
+
+const ENV_PREFIX: &str = "DTDRAFTS_";
+
+/// The settings `config get`/`set` know about, as dotted paths into the file.
+/// A profile takes the same keys under `profiles.<name>.`.
+pub const KEYS: [&str; 26] = [
+    "profile",
+    "api.key",
+    "api.base_url",
+    "api.username",
+    "cache.format",
+    "cache.ttl_secs",
+    "output.template",
+    "output.word_count.include_code",
+    "output.word_count.include_liquid",
+    "output.word_count.include_front_matter",
+    "lint.language",
+    "search.history",
+    "editor.command",
+    "clone.content_start",
+    "clone.content_end",
+    "frontmatter.tag_list",
+    "frontmatter.draft",
+    "http.timeout_secs",
+    "http.connect_timeout_secs",
+    "http.proxy",
+    "crosspost.hashnode.token",
+    "crosspost.hashnode.publication_id",
+    "crosspost.medium.token",
+    "hooks.on_publish",
+    "hooks.on_push",
+    "hooks.on_refresh",
+];
+
+/// Tables with keys of the user's choosing, set one entry at a time as
+/// `<table>.<name>`, e.g. `frontmatter.rename.tags`.
+pub const TABLES: [&str; 3] = ["saved", "frontmatter.rename", "frontmatter.extra"];
+
+/// The config file as written, one table per section.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ConfigFile {
+    /// Profile used when neither `--profile` nor `DTDRAFTS_PROFILE` name one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    pub api: ApiSection,
+    #[serde(skip_serializing_if = "CacheSection::is_default")]
+    pub cache: CacheSection,
+    #[serde(skip_serializing_if = "OutputSection::is_default")]
+    pub output: OutputSection,
+    #[serde(skip_serializing_if = "LintSection::is_default")]
+    pub lint: LintSection,
+    #[serde(skip_serializing_if = "SearchSection::is_default")]
+    pub search: SearchSection,
+    #[serde(skip_serializing_if = "EditorSection::is_default")]
+    pub editor: EditorSection,
+    #[serde(skip_serializing_if = "CloneConfig::is_default")]
+    pub clone: CloneConfig,
+    /// The mapping `--frontmatter custom` uses
+    #[serde(skip_serializing_if = "FrontMatterMapping::is_default")]
+    pub frontmatter: FrontMatterMapping,
+    #[serde(skip_serializing_if = "HttpConfig::is_default")]
+    pub http: HttpConfig,
+    #[serde(skip_serializing_if = "CrosspostConfig::is_empty")]
+    pub crosspost: CrosspostConfig,
+    #[serde(skip_serializing_if = "HooksConfig::is_empty")]
+    pub hooks: HooksConfig,
+    /// Saved searches by name, see [`crate::saved`]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub saved: BTreeMap<String, String>,
+    /// Named sets of overrides, each shaped like the rest of the file, e.g.
+    /// `[profiles.work.api]` with another `key`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, toml::Table>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ApiSection {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CacheSection {
+    pub format: CacheFormat,
+    /// Refetch the articles once the cache is older than this
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl_secs: Option<u64>,
+}
+
+impl CacheSection {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct OutputSection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(skip_serializing_if = "WordCountOptions::is_default")]
+    pub word_count: WordCountOptions,
+}
+
+impl OutputSection {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LintSection {
+    /// Dictionary for `lint --spelling`, e.g. `en_GB`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+impl LintSection {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SearchSection {
+    /// `false` stops recording searches for `search --again`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<bool>,
+}
+
+impl SearchSection {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct EditorSection {
+    /// Used instead of `$VISUAL`/`$EDITOR`, e.g. `code --wait`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+impl EditorSection {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl From<ConfigFile> for Config {
+    fn from(file: ConfigFile) -> Self {
+        Config {
+            api_key: file.api.key,
+            base_url: file.api.base_url,
+            username: file.api.username,
+            word_count: file.output.word_count,
+            output_template: file.output.template,
+            spelling_language: file.lint.language,
+            search_history: file.search.history,
+            editor: file.editor.command,
+            clone: file.clone,
+            frontmatter: file.frontmatter,
+            http: file.http,
+            crosspost: file.crosspost,
+            hooks: file.hooks,
+            saved: file.saved,
+            cache_format: file.cache.format,
+            cache_ttl_secs: file.cache.ttl_secs,
+        }
+    }
+}
+
+impl ConfigFile {
+    /// Replaces the settings with those of `config`, keeping the profiles.
+    pub fn set(&mut self, config: &Config) {
+        self.api = ApiSection {
+            key: config.api_key.clone(),
+            base_url: config.base_url.clone(),
+            username: config.username.clone(),
+        };
+        self.cache = CacheSection { format: config.cache_format, ttl_secs: config.cache_ttl_secs };
+        self.output = OutputSection { template: config.output_template.clone(), word_count: config.word_count };
+        self.lint = LintSection { language: config.spelling_language.clone() };
+        self.search = SearchSection { history: config.search_history };
+        self.editor = EditorSection { command: config.editor.clone() };
+        self.clone = config.clone.clone();
+        self.frontmatter = config.frontmatter.clone();
+        self.http = config.http.clone();
+        self.crosspost = config.crosspost.clone();
+        self.hooks = config.hooks.clone();
+        self.saved = config.saved.clone();
+    }
+
+    /// Sets the API key and the username it belongs to at the top level, or
+    /// in `[profiles.<profile>.api]`. A username saved for a previous key is
+    /// removed when `username` is `None`.
+    pub fn set_api_key(&mut self, profile: Option<&str>, key: &str, username: Option<&str>) -> Result<()> {
+        *self = self.with_value(&api_key_path(profile, "key"), Some(toml::Value::String(key.to_string())))?;
+        self.set_username(profile, username)
+    }
+
+    pub fn set_username(&mut self, profile: Option<&str>, username: Option<&str>) -> Result<()> {
+        let username = username.map(|username| toml::Value::String(username.to_string()));
+        *self = self.with_value(&api_key_path(profile, "username"), username)?;
+        Ok(())
+    }
+
+    /// The API key stored at the top level, or in the profile.
+    pub fn api_key(&self, profile: Option<&str>) -> Result<Option<String>> {
+        Ok(self.value(&api_key_path(profile, "key"))?.and_then(|key| key.as_str().map(str::to_string)))
+    }
+
+    /// The value at a dotted `key`, such as `api.base_url`, if it is set.
+    pub fn value(&self, key: &str) -> Result<Option<toml::Value>> {
+        check_key(key)?;
+        let mut value = &toml::Value::Table(toml::Table::try_from(self)?);
+        for part in key.split('.') {
+            match value.get(part) {
+                Some(next) => value = next,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(value.clone()))
+    }
+
+    /// Sets a dotted `key` from its TOML form (`30`, `true`, `[...]`); text
+    /// that isn't valid TOML, or doesn't fit the setting as such, is taken as
+    /// a string.
+    pub fn set_value(&mut self, key: &str, raw: &str) -> Result<()> {
+        check_key(key)?;
+        if let Some(table) = TABLES.iter().find(|table| unprofiled(key) == **table) {
+            return Err(anyhow::anyhow!("{key} is a table; set its entries as {table}.<name>"));
+        }
+        let text = toml::Value::String(raw.to_string());
+        let parsed = toml::from_str::<toml::Table>(&format!("value = {raw}"))
+            .ok()
+            .and_then(|mut table| table.remove("value"))
+            .unwrap_or_else(|| text.clone());
+        *self = match self.with_value(key, Some(parsed.clone())) {
+            Ok(file) => file,
+            Err(_) if parsed != text => self.with_value(key, Some(text))?,
+            Err(e) => return Err(e),
+        };
+        Ok(())
+    }
+
+    /// Removes a dotted `key`, so its default applies again.
+    pub fn unset(&mut self, key: &str) -> Result<()> {
+        check_key(key)?;
+        *self = self.with_value(key, None)?;
+        Ok(())
+    }
+
+    /// Every setting in the file as `(dotted key, value)`, sorted by key.
+    pub fn settings(&self) -> Result<Vec<(String, toml::Value)>> {
+        let mut settings = Vec::new();
+        flatten("", &toml::Table::try_from(self)?, &mut settings);
+        Ok(settings)
+    }
+
+    fn with_value(&self, key: &str, value: Option<toml::Value>) -> Result<ConfigFile> {
+        let mut table = toml::Table::try_from(self)?;
+        let path: Vec<&str> = key.split('.').collect();
+        match value {
+            Some(value) => insert(&mut table, &path, value),
+            None => {
+                remove(&mut table, &path);
+            }
+        }
+        parse_table(table).with_context(|| format!("Invalid value for {key}"))
+    }
+}
+
+/// Parses a config file, including each profile.
+pub fn parse(text: &str) -> Result<ConfigFile> {
+    parse_table(toml::from_str(text)?)
+}
+
+fn parse_table(table: toml::Table) -> Result<ConfigFile> {
+    let file: ConfigFile = toml::Value::Table(table).try_into()?;
+    for (name, profile) in &file.profiles {
+        toml::Value::Table(profile.clone())
+            .try_into::<ConfigFile>()
+            .with_context(|| format!("Invalid profile '{name}'"))?;
+    }
+    Ok(file)
+}
+
+fn api_key_path(profile: Option<&str>, key: &str) -> String {
+    match profile {
+        Some(profile) => format!("profiles.{profile}.api.{key}"),
+        None => format!("api.{key}"),
+    }
+}
+
+// The key without its `profiles.<name>.` prefix
+fn unprofiled(key: &str) -> &str {
+    key.strip_prefix("profiles.").and_then(|rest| rest.split_once('.')).map_or(key, |(_, rest)| rest)
+}
+
+// A known key, a section of known keys (`api`, `output.word_count`), one of
+// the `TABLES` or an entry in it, or any of those under `profiles.<name>.`
+fn check_key(key: &str) -> Result<()> {
+    let known = |key: &str| {
+        KEYS.iter().any(|known| *known == key || known.starts_with(&format!("{key}.")))
+            || TABLES.iter().any(|table| {
+                *table == key
+                    || key
+                        .strip_prefix(&format!("{table}."))
+                        .is_some_and(|name| !name.is_empty() && !name.contains('.'))
+            })
+    };
+    let in_profile = key
+        .strip_prefix("profiles.")
+        .and_then(|rest| rest.split_once('.'))
+        .is_some_and(|(name, rest)| !name.is_empty() && rest != "profile" && known(rest));
+    if known(key) || in_profile {
+        Ok(())
+    } else {
+        let tables = TABLES.map(|table| format!("{table}.<name>"));
+        Err(anyhow::anyhow!("Unknown setting '{key}'. Known settings: {}, {}", KEYS.join(", "), tables.join(", ")))
+    }
+}
+
+fn insert(table: &mut toml::Table, path: &[&str], value: toml::Value) {
+    let (last, parents) = path.split_last().expect("keys are not empty");
+    let mut table = table;
+    for part in parents {
+        let entry = table.entry(part.to_string()).or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if !entry.is_table() {
+            *entry = toml::Value::Table(toml::Table::new());
+        }
+        table = entry.as_table_mut().expect("just made a table");
+    }
+    table.insert(last.to_string(), value);
+}
+
+// Removes the value at `path` and any tables left empty by that
+fn remove(table: &mut toml::Table, path: &[&str]) {
+    match path {
+        [] => {}
+        [last] => {
+            table.remove(*last);
+        }
+        [first, rest @ ..] => {
+            if let Some(child) = table.get_mut(*first).and_then(toml::Value::as_table_mut) {
+                remove(child, rest);
+                if child.is_empty() {
+                    table.remove(*first);
+                }
+            }
+        }
+    }
+}
+
+fn flatten(prefix: &str, table: &toml::Table, settings: &mut Vec<(String, toml::Value)>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() { key.clone() } else { format!("{prefix}.{key}") };
+        match value {
+            toml::Value::Table(table) => flatten(&key, table, settings),
+            value => settings.push((key, value.clone())),
+        }
+    }
+}
+
+/// The command-line layer of the config.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Overrides {
+    pub profile: Option<String>,
+    pub base_url: Option<String>,
+}
+
+pub fn get_config_file() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("config.toml"))
+}
+
+pub fn get_legacy_config_file() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("config.json"))
+}
+
+/// Whether there is a config file at all, TOML or legacy JSON.
+pub fn exists() -> Result<bool> {
+    Ok(get_config_file()?.exists() || get_legacy_config_file()?.exists())
+}
+
+/// The config file on its own, without environment or flag overrides; what
+/// [`update_file`] edits.
+pub fn load_file() -> Result<ConfigFile> {
+    Ok(file_layer()?.extract()?)
+}
+
+/// The profile [`resolve`] applies, if any.
+pub fn active_profile(overrides: &Overrides) -> Result<Option<String>> {
+    Ok(selected_profile(&file_layer()?, overrides))
+}
+
+fn selected_profile(figment: &Figment, overrides: &Overrides) -> Option<String> {
+    overrides
+        .profile
+        .clone()
+        .or_else(|| std::env::var(format!("{ENV_PREFIX}PROFILE")).ok())
+        .or_else(|| figment.extract_inner::<String>("profile").ok())
+        .filter(|profile| !profile.is_empty())
+}
+
+// The TOML file, or else the legacy JSON file in the same shape
+fn file_layer() -> Result<Figment> {
+    let path = get_config_file()?;
+    let defaults = Figment::from(Serialized::defaults(ConfigFile::default()));
+    if path.exists() {
+        return Ok(defaults.merge(Toml::file(path)));
+    }
+    let legacy = get_legacy_config_file()?;
+    if !legacy.exists() {
+        return Ok(defaults);
+    }
+    let content = fs::read_to_string(&legacy).with_context(|| format!("Failed to read {}", legacy.display()))?;
+    let config: Config = serde_json::from_str(&content).with_context(|| format!("Invalid config in {}", legacy.display()))?;
+    let mut file = ConfigFile::default();
+    file.set(&config);
+    Ok(defaults.merge(Serialized::defaults(file)))
+}
+
+pub fn load(overrides: &Overrides) -> Result<Config> {
+    Ok(resolve(overrides)?.into())
+}
+
+/// Resolves the settings from every layer. The profile is the one named by
+/// `overrides`, else `DTDRAFTS_PROFILE`, else the file's `profile` key.
+pub fn resolve(overrides: &Overrides) -> Result<ConfigFile> {
+    let mut figment = file_layer()?;
+    if let Some(profile) = selected_profile(&figment, overrides) {
+        let key = format!("profiles.{profile}");
+        if !figment.contains(&key) {
+            return Err(anyhow::anyhow!("Profile '{profile}' is not defined in {}", get_config_file()?.display()));
+        }
+        figment = figment.clone().merge(figment.focus(&key));
+    }
+    figment = figment.merge(Env::prefixed(ENV_PREFIX).split("__").ignore(&["profile"]));
+    if let Some(base_url) = &overrides.base_url {
+        figment = figment.merge(Serialized::default("api.base_url", base_url));
+    }
+    figment.extract().context("Invalid configuration")
+}
+
+/// Applies `edit` to the config file and saves it as TOML. A legacy JSON
+/// file is converted and kept as `config.json.bak`.
+pub fn update_file(edit: impl FnOnce(&mut ConfigFile)) -> Result<PathBuf> {
+    let mut file = load_file()?;
+    edit(&mut file);
+    write_file(&toml::to_string_pretty(&file)?)
+}
+
+/// Replaces the config file with `text`, which must be a valid config; used
+/// after editing it by hand, so comments and layout are kept.
+pub fn replace_file(text: &str) -> Result<PathBuf> {
+    parse(text)?;
+    write_file(text)
+}
+
+fn write_file(text: &str) -> Result<PathBuf> {
+    fs::create_dir_all(get_config_dir()?)?;
+    let path = get_config_file()?;
+    fs::write(&path, text).with_context(|| format!("Failed to write {}", path.display()))?;
+    let legacy = get_legacy_config_file()?;
+    if legacy.exists() {
+        let backup = legacy.with_extension("json.bak");
+        fs::rename(&legacy, &backup)?;
+        tracing::info!(path = %path.display(), backup = %backup.display(), "converted the JSON config to TOML");
+    }
+    Ok(path)
+}
@@ -0,0 +1,174 @@
+//! Title-card cover images rendered locally: the draft's title over a plain
+//! background, drawn with an 8x8 bitmap font so no font files are needed.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use font8x8::{UnicodeFonts, BASIC_FONTS, LATIN_FONTS};
+
+/// dev.to shows covers at 1000x420.
+pub const COVER_WIDTH: u32 = 1000;
+pub const COVER_HEIGHT: u32 = 420;
+const MARGIN: u32 = 60;
+const GLYPH: u32 = 8;
+const SUBTITLE_SCALE: u32 = 3;
+const MAX_TITLE_LINES: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color(pub [u8; 3]);
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.trim_start_matches('#');
+        let channel = |i: usize| hex.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok());
+        match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Color([r, g, b])),
+            _ => Err(format!("invalid color '{s}' (expected #rrggbb)")),
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b] = self.0;
+        write!(f, "#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+/// The layout: an accent bar, the title as large as fits in four lines, and
+/// a subtitle (tags, author) along the bottom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TitleCard {
+    pub title: String,
+    pub subtitle: String,
+    pub background: Color,
+    pub foreground: Color,
+    pub accent: Color,
+}
+
+impl TitleCard {
+    pub fn new(title: impl Into<String>, subtitle: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            subtitle: subtitle.into(),
+            background: Color([0x0f, 0x17, 0x2a]),
+            foreground: Color([0xf8, 0xfa, 0xfc]),
+            accent: Color([0x3b, 0x82, 0xf6]),
+        }
+    }
+
+    /// The card as a PNG file.
+    pub fn render_png(&self) -> Result<Vec<u8>> {
+        let mut canvas = Canvas::new(self.background);
+        canvas.fill_rect(MARGIN, MARGIN, 80, 8, self.accent);
+
+        let (scale, lines) = self.title_layout();
+        let mut y = MARGIN + 40;
+        for line in &lines {
+            canvas.draw_text(line, MARGIN, y, scale, self.foreground);
+            y += GLYPH * scale + 2 * scale;
+        }
+        let max_chars = ((COVER_WIDTH - 2 * MARGIN) / (GLYPH * SUBTITLE_SCALE)) as usize;
+        let subtitle: String = self.subtitle.chars().take(max_chars).collect();
+        let y = COVER_HEIGHT - MARGIN - GLYPH * SUBTITLE_SCALE;
+        canvas.draw_text(&subtitle, MARGIN, y, SUBTITLE_SCALE, self.accent);
+        canvas.encode()
+    }
+
+    // The largest scale the title fits at, with its lines
+    fn title_layout(&self) -> (u32, Vec<String>) {
+        let height = COVER_HEIGHT - 2 * MARGIN - 40 - GLYPH * SUBTITLE_SCALE - 20;
+        for scale in (3..=7).rev() {
+            let max_chars = ((COVER_WIDTH - 2 * MARGIN) / (GLYPH * scale)) as usize;
+            let lines = wrap_words(&self.title, max_chars);
+            let lines_height = lines.len() as u32 * (GLYPH * scale + 2 * scale);
+            if lines.len() <= MAX_TITLE_LINES && lines_height <= height {
+                return (scale, lines);
+            }
+        }
+        let max_chars = ((COVER_WIDTH - 2 * MARGIN) / (GLYPH * 3)) as usize;
+        let mut lines = wrap_words(&self.title, max_chars);
+        lines.truncate(MAX_TITLE_LINES);
+        (3, lines)
+    }
+}
+
+/// Breaks `text` into lines of at most `max_chars`, between words where
+/// possible.
+pub fn wrap_words(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > max_chars {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            lines.push(word.drain(..max_chars).collect());
+        }
+        let word: String = word.into_iter().collect();
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_chars {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+struct Canvas {
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(background: Color) -> Self {
+        Self { pixels: background.0.repeat((COVER_WIDTH * COVER_HEIGHT) as usize) }
+    }
+
+    fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: Color) {
+        for row in y..(y + height).min(COVER_HEIGHT) {
+            for column in x..(x + width).min(COVER_WIDTH) {
+                let offset = ((row * COVER_WIDTH + column) * 3) as usize;
+                self.pixels[offset..offset + 3].copy_from_slice(&color.0);
+            }
+        }
+    }
+
+    // Characters the font lacks are drawn as `?`
+    fn draw_text(&mut self, text: &str, x: u32, y: u32, scale: u32, color: Color) {
+        for (i, c) in text.chars().enumerate() {
+            let glyph = BASIC_FONTS
+                .get(c)
+                .or_else(|| LATIN_FONTS.get(c))
+                .or_else(|| BASIC_FONTS.get('?'))
+                .unwrap_or_default();
+            let left = x + i as u32 * GLYPH * scale;
+            for (row, bits) in glyph.iter().enumerate() {
+                for column in 0..GLYPH {
+                    if bits & (1 << column) != 0 {
+                        self.fill_rect(left + column * scale, y + row as u32 * scale, scale, scale, color);
+                    }
+                }
+            }
+        }
+    }
+
+    fn encode(&self) -> Result<Vec<u8>> {
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, COVER_WIDTH, COVER_HEIGHT);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().context("Failed to encode the cover image")?;
+        writer.write_image_data(&self.pixels).context("Failed to encode the cover image")?;
+        writer.finish().context("Failed to encode the cover image")?;
+        Ok(png)
+    }
+}
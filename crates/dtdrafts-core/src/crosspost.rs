@@ -0,0 +1,218 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::client::DEFAULT_USER_AGENT;
+use crate::frontmatter::parse_document;
+use crate::lint::PublishMetadata;
+use crate::Article;
+
+pub const HASHNODE_API_URL: &str = "https://gql.hashnode.com";
+pub const MEDIUM_API_URL: &str = "https://api.medium.com/v1";
+
+/// Credentials for the cross-posting targets, stored under `crosspost` in the
+/// config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CrosspostConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hashnode: Option<HashnodeConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub medium: Option<MediumConfig>,
+}
+
+impl CrosspostConfig {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HashnodeConfig {
+    /// Personal access token from hashnode.com/settings/developer
+    pub token: String,
+    pub publication_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MediumConfig {
+    /// Integration token from medium.com/me/settings
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Hashnode,
+    Medium,
+}
+
+impl FromStr for Platform {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "hashnode" => Ok(Platform::Hashnode),
+            "medium" => Ok(Platform::Medium),
+            _ => Err(format!("unknown platform '{s}' (expected hashnode or medium)")),
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Platform::Hashnode => "hashnode",
+            Platform::Medium => "medium",
+        })
+    }
+}
+
+/// A dev.to article prepared for another platform. `canonical_url` points
+/// back at dev.to so search engines treat dev.to as the original.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrosspostInput {
+    pub title: String,
+    pub body_markdown: String,
+    pub tags: Vec<String>,
+    pub canonical_url: String,
+}
+
+impl CrosspostInput {
+    pub fn from_article(article: &Article) -> Self {
+        let metadata = PublishMetadata::of(article);
+        let (_, body) = parse_document(article.body_markdown.as_deref().unwrap_or_default());
+        Self {
+            title: metadata.title,
+            body_markdown: body.trim_start().to_string(),
+            tags: metadata.tags,
+            canonical_url: article.url.clone(),
+        }
+    }
+}
+
+fn http_client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .user_agent(DEFAULT_USER_AGENT)
+        .build()
+        .context("Failed to build HTTP client")
+}
+
+pub struct HashnodeClient {
+    client: reqwest::Client,
+    config: HashnodeConfig,
+    api_url: String,
+}
+
+impl HashnodeClient {
+    pub fn new(config: HashnodeConfig) -> Result<Self> {
+        Ok(Self { client: http_client()?, config, api_url: HASHNODE_API_URL.to_string() })
+    }
+
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into();
+        self
+    }
+
+    /// Publishes the post and returns its Hashnode URL.
+    pub async fn publish(&self, input: &CrosspostInput) -> Result<String> {
+        let tags: Vec<Value> = input
+            .tags
+            .iter()
+            .map(|tag| json!({ "slug": tag.to_lowercase(), "name": tag }))
+            .collect();
+        let query = "mutation PublishPost($input: PublishPostInput!) { publishPost(input: $input) { post { url } } }";
+        let body = json!({
+            "query": query,
+            "variables": {
+                "input": {
+                    "title": input.title,
+                    "contentMarkdown": input.body_markdown,
+                    "publicationId": self.config.publication_id,
+                    "tags": tags,
+                    "originalArticleURL": input.canonical_url,
+                }
+            }
+        });
+        let response: Value = self
+            .client
+            .post(&self.api_url)
+            .header("Authorization", &self.config.token)
+            .json(&body)
+            .send()
+            .await
+            .context("Failed to reach Hashnode")?
+            .error_for_status()
+            .context("Hashnode rejected the request")?
+            .json()
+            .await
+            .context("Failed to parse Hashnode response")?;
+        if let Some(errors) = response.get("errors").and_then(Value::as_array) {
+            let messages: Vec<&str> = errors.iter().filter_map(|e| e["message"].as_str()).collect();
+            return Err(anyhow::anyhow!("Hashnode returned errors: {}", messages.join("; ")));
+        }
+        response["data"]["publishPost"]["post"]["url"]
+            .as_str()
+            .map(str::to_string)
+            .context("Hashnode response did not include the post URL")
+    }
+}
+
+pub struct MediumClient {
+    client: reqwest::Client,
+    config: MediumConfig,
+    api_url: String,
+}
+
+impl MediumClient {
+    pub fn new(config: MediumConfig) -> Result<Self> {
+        Ok(Self { client: http_client()?, config, api_url: MEDIUM_API_URL.to_string() })
+    }
+
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    async fn get_json(&self, request: reqwest::RequestBuilder, action: &str) -> Result<Value> {
+        request
+            .bearer_auth(&self.config.token)
+            .send()
+            .await
+            .with_context(|| format!("Failed to {action} on Medium"))?
+            .error_for_status()
+            .with_context(|| format!("Medium rejected the request to {action}"))?
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse Medium response to {action}"))
+    }
+
+    /// Creates the post as a Medium draft and returns its URL. Medium only
+    /// accepts up to 5 tags.
+    pub async fn publish(&self, input: &CrosspostInput) -> Result<String> {
+        let me = self
+            .get_json(self.client.get(format!("{}/me", self.api_url)), "look up your user")
+            .await?;
+        let user_id = me["data"]["id"].as_str().context("Medium response did not include a user id")?;
+        let content = format!("# {}\n\n{}", input.title, input.body_markdown);
+        let tags: Vec<&String> = input.tags.iter().take(5).collect();
+        let request = self
+            .client
+            .post(format!("{}/users/{user_id}/posts", self.api_url))
+            .json(&json!({
+                "title": input.title,
+                "contentFormat": "markdown",
+                "content": content,
+                "tags": tags,
+                "canonicalUrl": input.canonical_url,
+                "publishStatus": "draft",
+            }));
+        let post = self.get_json(request, "create the post").await?;
+        post["data"]["url"]
+            .as_str()
+            .map(str::to_string)
+            .context("Medium response did not include the post URL")
+    }
+}
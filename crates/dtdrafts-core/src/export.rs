@@ -0,0 +1,165 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+
+use crate::frontmatter::parse_document;
+use crate::lint::PublishMetadata;
+use crate::Article;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Hugo,
+    Jekyll,
+    Zola,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "hugo" => Ok(ExportFormat::Hugo),
+            "jekyll" => Ok(ExportFormat::Jekyll),
+            "zola" => Ok(ExportFormat::Zola),
+            _ => Err(format!("unknown export format '{s}' (expected hugo, jekyll or zola)")),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ExportFormat::Hugo => "hugo",
+            ExportFormat::Jekyll => "jekyll",
+            ExportFormat::Zola => "zola",
+        })
+    }
+}
+
+impl ExportFormat {
+    pub fn exporter(self) -> Box<dyn Exporter> {
+        match self {
+            ExportFormat::Hugo => Box::new(Hugo),
+            ExportFormat::Jekyll => Box::new(Jekyll),
+            ExportFormat::Zola => Box::new(Zola),
+        }
+    }
+}
+
+/// What an exporter needs to know about a draft, with dev.to's embedded
+/// front matter already taken apart.
+#[derive(Debug, Clone)]
+pub struct ExportPost<'a> {
+    pub article: &'a Article,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    pub date: DateTime<Utc>,
+    pub body: &'a str,
+}
+
+impl<'a> ExportPost<'a> {
+    /// `now` is used as the date for drafts without timestamps.
+    pub fn new(article: &'a Article, now: DateTime<Utc>) -> Self {
+        let metadata = PublishMetadata::of(article);
+        let (front_matter, body) = parse_document(article.body_markdown.as_deref().unwrap_or_default());
+        let description = front_matter
+            .as_ref()
+            .and_then(|front_matter| front_matter.get("description").map(str::to_string))
+            .or_else(|| article.description.clone())
+            .filter(|description| !description.is_empty());
+        Self {
+            article,
+            title: metadata.title,
+            tags: metadata.tags,
+            description,
+            date: article.created_at_utc().or_else(|| article.updated_at_utc()).unwrap_or(now),
+            body,
+        }
+    }
+}
+
+/// One static site generator's file layout and front matter dialect.
+pub trait Exporter {
+    /// Path of the post relative to the output directory.
+    fn file_name(&self, post: &ExportPost) -> String;
+    fn render(&self, post: &ExportPost) -> String;
+}
+
+// JSON strings are valid double-quoted YAML and TOML strings
+fn quoted(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn quoted_list(values: &[String]) -> String {
+    let items: Vec<String> = values.iter().map(|value| quoted(value)).collect();
+    format!("[{}]", items.join(", "))
+}
+
+pub struct Hugo;
+
+impl Exporter for Hugo {
+    fn file_name(&self, post: &ExportPost) -> String {
+        format!("{}.md", post.article.slug)
+    }
+
+    fn render(&self, post: &ExportPost) -> String {
+        let mut out = String::from("---\n");
+        out.push_str(&format!("title: {}\n", quoted(&post.title)));
+        out.push_str(&format!("date: {}\n", post.date.to_rfc3339()));
+        out.push_str("draft: true\n");
+        out.push_str(&format!("slug: {}\n", quoted(&post.article.slug)));
+        out.push_str(&format!("tags: {}\n", quoted_list(&post.tags)));
+        if let Some(description) = &post.description {
+            out.push_str(&format!("description: {}\n", quoted(description)));
+        }
+        out.push_str("---\n");
+        out.push_str(post.body);
+        out
+    }
+}
+
+pub struct Jekyll;
+
+impl Exporter for Jekyll {
+    fn file_name(&self, post: &ExportPost) -> String {
+        format!("{}-{}.md", post.date.format("%Y-%m-%d"), post.article.slug)
+    }
+
+    fn render(&self, post: &ExportPost) -> String {
+        let mut out = String::from("---\nlayout: post\n");
+        out.push_str(&format!("title: {}\n", quoted(&post.title)));
+        out.push_str(&format!("date: {}\n", post.date.format("%Y-%m-%d %H:%M:%S %z")));
+        out.push_str("published: false\n");
+        out.push_str(&format!("tags: {}\n", quoted_list(&post.tags)));
+        if let Some(description) = &post.description {
+            out.push_str(&format!("description: {}\n", quoted(description)));
+        }
+        out.push_str("---\n");
+        out.push_str(post.body);
+        out
+    }
+}
+
+pub struct Zola;
+
+impl Exporter for Zola {
+    fn file_name(&self, post: &ExportPost) -> String {
+        format!("{}.md", post.article.slug)
+    }
+
+    fn render(&self, post: &ExportPost) -> String {
+        let mut out = String::from("+++\n");
+        out.push_str(&format!("title = {}\n", quoted(&post.title)));
+        out.push_str(&format!("date = {}\n", post.date.to_rfc3339()));
+        out.push_str("draft = true\n");
+        if let Some(description) = &post.description {
+            out.push_str(&format!("description = {}\n", quoted(description)));
+        }
+        out.push_str(&format!("\n[taxonomies]\ntags = {}\n", quoted_list(&post.tags)));
+        out.push_str("+++\n");
+        out.push_str(post.body);
+        out
+    }
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::render::{escape_html, markdown_to_html};
+use crate::Article;
+
+/// An Atom feed of `articles`, most recently updated first. Entry ids are
+/// derived from article ids, so readers keep track of a draft across title
+/// and slug changes and show it as updated whenever it's edited.
+pub fn atom_feed(articles: &[&Article], now: DateTime<Utc>) -> String {
+    let mut entries = articles.to_vec();
+    entries.sort_by_key(|article| std::cmp::Reverse(last_touched(article)));
+    let updated = entries.first().and_then(|article| last_touched(article)).unwrap_or(now);
+    let author = entries.first().map(|article| article.user.username.as_str());
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    out.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    match author {
+        Some(author) => {
+            out.push_str(&format!("  <title>dev.to drafts of {}</title>\n", escape_html(author)));
+            out.push_str(&format!("  <id>urn:dtdrafts:{}:drafts</id>\n", escape_html(author)));
+        }
+        None => {
+            out.push_str("  <title>dev.to drafts</title>\n");
+            out.push_str("  <id>urn:dtdrafts:drafts</id>\n");
+        }
+    }
+    out.push_str("  <link href=\"https://dev.to/dashboard\"/>\n");
+    out.push_str(&format!("  <updated>{}</updated>\n", timestamp(updated)));
+    out.push_str("  <generator>dtdrafts</generator>\n");
+    for article in entries {
+        out.push_str(&entry(article, now));
+    }
+    out.push_str("</feed>\n");
+    out
+}
+
+fn entry(article: &Article, now: DateTime<Utc>) -> String {
+    // Drafts have no public page; the preview URL works for anyone with the link
+    let link = article.url_with_preview.as_deref().unwrap_or(&article.url);
+    let mut out = String::from("  <entry>\n");
+    out.push_str(&format!("    <title>{}</title>\n", escape_html(&article.title)));
+    out.push_str(&format!("    <id>urn:dtdrafts:article:{}</id>\n", article.id));
+    out.push_str(&format!("    <link href=\"{}\"/>\n", escape_html(link)));
+    out.push_str(&format!("    <link rel=\"edit\" href=\"{}\"/>\n", escape_html(&article.edit_url())));
+    out.push_str(&format!("    <updated>{}</updated>\n", timestamp(last_touched(article).unwrap_or(now))));
+    if let Some(created) = article.created_at_utc() {
+        out.push_str(&format!("    <published>{}</published>\n", timestamp(created)));
+    }
+    out.push_str(&format!(
+        "    <author><name>{}</name></author>\n",
+        escape_html(&article.user.username)
+    ));
+    for tag in article.tags.as_deref().unwrap_or_default() {
+        out.push_str(&format!("    <category term=\"{}\"/>\n", escape_html(tag)));
+    }
+    if let Some(description) = article.description.as_deref().filter(|description| !description.is_empty()) {
+        out.push_str(&format!("    <summary>{}</summary>\n", escape_html(description)));
+    }
+    if let Some(body) = &article.body_markdown {
+        out.push_str(&format!(
+            "    <content type=\"html\">{}</content>\n",
+            escape_html(&markdown_to_html(body))
+        ));
+    }
+    out.push_str("  </entry>\n");
+    out
+}
+
+fn last_touched(article: &Article) -> Option<DateTime<Utc>> {
+    article.updated_at_utc().or_else(|| article.created_at_utc())
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
@@ -0,0 +1,302 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use crate::Article;
+
+/// Composable article filter. An empty filter matches every article; each
+/// builder call narrows the selection further.
+#[derive(Debug, Clone, Default)]
+pub struct ArticleFilter {
+    tags: Vec<String>,
+    series: Option<String>,
+    organization: Option<String>,
+    ids: Option<HashSet<u64>>,
+    published: Option<bool>,
+    created_after: Option<DateTime<Utc>>,
+    created_before: Option<DateTime<Utc>>,
+    updated_after: Option<DateTime<Utc>>,
+    updated_before: Option<DateTime<Utc>>,
+}
+
+impl ArticleFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn drafts() -> Self {
+        Self::new().published(false)
+    }
+
+    /// Requires the article to carry `tag` (case-insensitive). Repeated calls
+    /// require all given tags.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into().to_lowercase());
+        self
+    }
+
+    pub fn tags<I, S>(self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        tags.into_iter().fold(self, |filter, tag| filter.tag(tag))
+    }
+
+    /// Requires the article to be part of `series` (case-insensitive).
+    pub fn series(mut self, series: impl Into<String>) -> Self {
+        self.series = Some(series.into().to_lowercase());
+        self
+    }
+
+    /// Requires the article to belong to the organization with this username
+    /// (case-insensitive).
+    pub fn organization(mut self, username: impl Into<String>) -> Self {
+        self.organization = Some(username.into().to_lowercase());
+        self
+    }
+
+    /// Restricts the selection to these article ids, e.g. those carrying a
+    /// local status. Repeated calls intersect.
+    pub fn ids(mut self, ids: impl IntoIterator<Item = u64>) -> Self {
+        let ids: HashSet<u64> = ids.into_iter().collect();
+        self.ids = Some(match self.ids {
+            Some(existing) => existing.intersection(&ids).copied().collect(),
+            None => ids,
+        });
+        self
+    }
+
+    pub fn published(mut self, published: bool) -> Self {
+        self.published = Some(published);
+        self
+    }
+
+    /// Matches drafts and published articles alike, undoing [`Self::drafts`].
+    pub fn include_published(mut self) -> Self {
+        self.published = None;
+        self
+    }
+
+    pub fn created_after(mut self, at: DateTime<Utc>) -> Self {
+        self.created_after = Some(at);
+        self
+    }
+
+    pub fn created_before(mut self, at: DateTime<Utc>) -> Self {
+        self.created_before = Some(at);
+        self
+    }
+
+    pub fn updated_after(mut self, at: DateTime<Utc>) -> Self {
+        self.updated_after = Some(at);
+        self
+    }
+
+    pub fn updated_before(mut self, at: DateTime<Utc>) -> Self {
+        self.updated_before = Some(at);
+        self
+    }
+
+    pub fn matches(&self, article: &Article) -> bool {
+        if self.published.is_some_and(|published| article.published != published) {
+            return false;
+        }
+        if self.ids.as_ref().is_some_and(|ids| !ids.contains(&article.id)) {
+            return false;
+        }
+        if !self.tags.is_empty() {
+            let article_tags: Vec<String> = article
+                .tags
+                .iter()
+                .flatten()
+                .map(|tag| tag.to_lowercase())
+                .collect();
+            if !self.tags.iter().all(|tag| article_tags.contains(tag)) {
+                return false;
+            }
+        }
+        if let Some(series) = &self.series {
+            if article.series().is_none_or(|name| name.to_lowercase() != *series) {
+                return false;
+            }
+        }
+        if let Some(username) = &self.organization {
+            let belongs = article
+                .organization
+                .as_ref()
+                .is_some_and(|organization| organization.username.to_lowercase() == *username);
+            if !belongs {
+                return false;
+            }
+        }
+        in_range(article.created_at_utc(), self.created_after, self.created_before)
+            && in_range(article.updated_at_utc(), self.updated_after, self.updated_before)
+    }
+}
+
+/// Keys understood by [`parse_filter`].
+pub const FILTER_KEYS: [&str; 8] =
+    ["tag", "series", "org", "id", "created-after", "created-before", "updated-after", "updated-before"];
+
+/// Parses a filter expression of space-separated `key:value` terms over
+/// drafts, e.g. `tag:rust updated-before:2022-01-01`. All terms must match;
+/// values with spaces are quoted (`series:"Advent of Rust"`).
+pub fn parse_filter(expression: &str) -> Result<ArticleFilter> {
+    let mut filter = ArticleFilter::drafts();
+    for term in split_terms(expression)? {
+        let (key, value) = term
+            .split_once(':')
+            .filter(|(_, value)| !value.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Invalid filter term '{term}'. Expected key:value"))?;
+        filter = apply_term(filter, key, value)?;
+    }
+    Ok(filter)
+}
+
+/// Parses a search: [`parse_filter`] terms mixed with keywords, which are
+/// terms without a colon, e.g. `"error handling" tag:rust`. Returns the
+/// keywords, joined with spaces, and the filter.
+pub fn parse_search(expression: &str) -> Result<(String, ArticleFilter)> {
+    let mut filter = ArticleFilter::drafts();
+    let mut keywords = Vec::new();
+    for term in split_terms(expression)? {
+        match term.split_once(':') {
+            Some((key, value)) if !value.is_empty() => filter = apply_term(filter, key, value)?,
+            Some(_) => return Err(anyhow::anyhow!("Invalid filter term '{term}'. Expected key:value")),
+            None => keywords.push(term),
+        }
+    }
+    Ok((keywords.join(" "), filter))
+}
+
+fn apply_term(filter: ArticleFilter, key: &str, value: &str) -> Result<ArticleFilter> {
+    Ok(match key {
+        "tag" => filter.tag(value),
+        "series" => filter.series(value),
+        "org" => filter.organization(value),
+        "id" => filter.ids([value.parse().map_err(|_| anyhow::anyhow!("Invalid article id '{value}'"))?]),
+        "created-after" => filter.created_after(parse_date(value)?),
+        "created-before" => filter.created_before(parse_date_end(value)?),
+        "updated-after" => filter.updated_after(parse_date(value)?),
+        "updated-before" => filter.updated_before(parse_date_end(value)?),
+        _ => return Err(anyhow::anyhow!("Unknown filter key '{key}'. Expected one of: {}", FILTER_KEYS.join(", "))),
+    })
+}
+
+fn split_terms(expression: &str) -> Result<Vec<String>> {
+    let mut terms = Vec::new();
+    let mut term = String::new();
+    let mut quoted = false;
+    for c in expression.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !term.is_empty() {
+                    terms.push(std::mem::take(&mut term));
+                }
+            }
+            c => term.push(c),
+        }
+    }
+    if quoted {
+        return Err(anyhow::anyhow!("Unclosed quote in filter '{expression}'"));
+    }
+    if !term.is_empty() {
+        terms.push(term);
+    }
+    Ok(terms)
+}
+
+pub fn filter_articles<'a>(articles: &'a [Article], filter: &ArticleFilter) -> Vec<&'a Article> {
+    articles
+        .iter()
+        .filter(|article| filter.matches(article))
+        .collect()
+}
+
+// Both bounds are inclusive. Articles without a parseable timestamp never
+// satisfy a date bound.
+fn in_range(
+    at: Option<DateTime<Utc>>,
+    after: Option<DateTime<Utc>>,
+    before: Option<DateTime<Utc>>,
+) -> bool {
+    if after.is_none() && before.is_none() {
+        return true;
+    }
+    let Some(at) = at else {
+        return false;
+    };
+    after.is_none_or(|after| at >= after) && before.is_none_or(|before| at <= before)
+}
+
+/// Parses a user supplied date bound: `YYYY-MM-DD` (midnight UTC), a full
+/// RFC 3339 timestamp, or an age before now such as `-7d` (see [`parse_age`]).
+pub fn parse_date(input: &str) -> Result<DateTime<Utc>> {
+    if let Some(age) = input.strip_prefix('-') {
+        return Utc::now()
+            .checked_sub_signed(parse_age(age)?)
+            .ok_or_else(|| anyhow::anyhow!("Age '{age}' reaches back before the earliest supported date"));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+    DateTime::parse_from_rfc3339(input)
+        .map(|at| at.with_timezone(&Utc))
+        .map_err(|_| anyhow::anyhow!("Invalid date '{input}'. Expected YYYY-MM-DD, an RFC 3339 timestamp or an age such as -7d"))
+}
+
+/// [`parse_date`] for an upper bound: a `YYYY-MM-DD` date stands for the
+/// whole day, so it ends at the day's last instant rather than its midnight.
+pub fn parse_date_end(input: &str) -> Result<DateTime<Utc>> {
+    match NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        Ok(date) => Ok(date.and_hms_nano_opt(23, 59, 59, 999_999_999).unwrap_or_default().and_utc()),
+        Err(_) => parse_date(input),
+    }
+}
+
+/// Parses an age such as `90d`, `12w`, `6m` (30 days) or `1y` (365 days).
+pub fn parse_age(input: &str) -> Result<Duration> {
+    let input = input.trim();
+    let invalid = || anyhow::anyhow!("Invalid age '{input}'. Expected a number followed by d, w, m or y (e.g. 90d)");
+    let unit = input.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = input[..input.len() - unit.len_utf8()].parse().map_err(|_| invalid())?;
+    if amount <= 0 {
+        return Err(anyhow::anyhow!("Age '{input}' must be longer than zero"));
+    }
+    let days_per_unit = match unit {
+        'd' => 1,
+        'w' => 7,
+        'm' => 30,
+        'y' => 365,
+        _ => return Err(invalid()),
+    };
+    amount
+        .checked_mul(days_per_unit)
+        .and_then(Duration::try_days)
+        .ok_or_else(|| anyhow::anyhow!("Age '{input}' is too long"))
+}
+
+/// Parses a polling interval such as `90s`, `15m` (minutes), `1h` or `1d`.
+pub fn parse_interval(input: &str) -> Result<std::time::Duration> {
+    let input = input.trim();
+    let invalid = || anyhow::anyhow!("Invalid interval '{input}'. Expected a number followed by s, m, h or d (e.g. 1h)");
+    let unit = input.chars().last().ok_or_else(invalid)?;
+    let amount: u64 = input[..input.len() - unit.len_utf8()].parse().map_err(|_| invalid())?;
+    let seconds_per_unit = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    let seconds = amount
+        .checked_mul(seconds_per_unit)
+        .ok_or_else(|| anyhow::anyhow!("Interval '{input}' is too long"))?;
+    if seconds == 0 {
+        return Err(anyhow::anyhow!("Interval '{input}' must be longer than zero"));
+    }
+    Ok(std::time::Duration::from_secs(seconds))
+}
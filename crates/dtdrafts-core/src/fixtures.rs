@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use reqwest::Response;
+use serde::{Deserialize, Serialize};
+
+pub const REDACTED: &str = "[REDACTED]";
+
+// Cookies don't belong in a bug report, and the length changes when JSON
+// bodies are re-serialized on replay
+const SKIPPED_HEADERS: [&str; 4] = ["set-cookie", "api-key", "content-length", "transfer-encoding"];
+
+/// `--record <DIR>` saves every dev.to API response into `DIR`;
+/// `--replay <DIR>` answers requests from those files instead of the network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixtureMode {
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+/// One request and its response, stored as `NNNN-<method>-<path>.json`.
+/// `path` is relative to the API base URL and includes the query string.
+/// JSON bodies are kept as JSON so the files are readable and editable.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Fixture {
+    pub method: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<serde_json::Value>,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    pub body: serde_json::Value,
+}
+
+impl Fixture {
+    pub fn new(
+        method: &str,
+        path: &str,
+        request_body: Option<&[u8]>,
+        status: u16,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Self {
+        let headers = headers
+            .iter()
+            .filter(|(name, _)| !SKIPPED_HEADERS.contains(&name.as_str()))
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        Self {
+            method: method.to_string(),
+            path: path.to_string(),
+            request_body: request_body.map(body_value),
+            status,
+            headers,
+            body: body_value(body),
+        }
+    }
+
+    pub fn file_name(&self, sequence: usize) -> String {
+        let slug: String = self
+            .path
+            .split('?')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        format!("{sequence:04}-{}-{}.json", self.method.to_lowercase(), slug.trim_matches('_'))
+    }
+
+    pub fn body_bytes(&self) -> Vec<u8> {
+        match &self.body {
+            // Bodies that weren't JSON are stored as a plain string
+            serde_json::Value::String(text) if !self.is_json() => text.clone().into_bytes(),
+            serde_json::Value::Null if !self.is_json() => Vec::new(),
+            body => body.to_string().into_bytes(),
+        }
+    }
+
+    fn is_json(&self) -> bool {
+        self.headers
+            .get(CONTENT_TYPE.as_str())
+            .is_some_and(|content_type| content_type.contains("json"))
+    }
+
+    pub fn to_response(&self) -> Result<Response> {
+        let mut response = http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            response = response.header(name, value);
+        }
+        let response = response
+            .body(self.body_bytes())
+            .with_context(|| format!("Invalid fixture for {} {}", self.method, self.path))?;
+        Ok(Response::from(response))
+    }
+}
+
+fn body_value(bytes: &[u8]) -> serde_json::Value {
+    if bytes.is_empty() {
+        return serde_json::Value::Null;
+    }
+    serde_json::from_slice(bytes)
+        .unwrap_or_else(|_| serde_json::Value::String(String::from_utf8_lossy(bytes).into_owned()))
+}
+
+/// Replaces every occurrence of `secret` in `text`, e.g. an API key echoed in
+/// an error message.
+pub fn scrub(text: &str, secret: &str) -> String {
+    if secret.is_empty() {
+        return text.to_string();
+    }
+    text.replace(secret, REDACTED)
+}
+
+/// Writes fixtures to a directory, numbered after any already in it so
+/// several sessions can be recorded into the same place.
+#[derive(Debug)]
+pub struct Recorder {
+    dir: PathBuf,
+    secret: String,
+    next: AtomicUsize,
+}
+
+impl Recorder {
+    pub fn new(dir: &Path, secret: &str) -> Result<Self> {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        let existing = fixture_files(dir)?.len();
+        Ok(Self { dir: dir.to_path_buf(), secret: secret.to_string(), next: AtomicUsize::new(existing + 1) })
+    }
+
+    pub fn record(&self, fixture: &Fixture) -> Result<PathBuf> {
+        let sequence = self.next.fetch_add(1, Ordering::Relaxed);
+        let path = self.dir.join(scrub(&fixture.file_name(sequence), &self.secret));
+        let json = scrub(&serde_json::to_string_pretty(fixture)?, &self.secret);
+        fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// Serves recorded fixtures back. Each request gets the first unused fixture
+/// with the same method and path, so repeated requests replay in order.
+#[derive(Debug)]
+pub struct Replayer {
+    fixtures: Mutex<Vec<Fixture>>,
+}
+
+impl Replayer {
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut fixtures = Vec::new();
+        for path in fixture_files(dir)? {
+            let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+            let fixture = serde_json::from_str(&content).with_context(|| format!("Invalid fixture {}", path.display()))?;
+            fixtures.push(fixture);
+        }
+        Ok(Self::new(fixtures))
+    }
+
+    pub fn new(fixtures: Vec<Fixture>) -> Self {
+        Self { fixtures: Mutex::new(fixtures) }
+    }
+
+    pub fn take(&self, method: &str, path: &str) -> Result<Fixture> {
+        let mut fixtures = self.fixtures.lock().unwrap();
+        let index = fixtures
+            .iter()
+            .position(|fixture| fixture.method.eq_ignore_ascii_case(method) && fixture.path == path)
+            .ok_or_else(|| anyhow::anyhow!("No recorded response left for {method} {path}"))?;
+        Ok(fixtures.remove(index))
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.fixtures.lock().unwrap().len()
+    }
+}
+
+fn fixture_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read fixtures from {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
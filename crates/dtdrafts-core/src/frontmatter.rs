@@ -0,0 +1,364 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::ArticleInput;
+use crate::text::split_front_matter;
+use crate::Article;
+
+/// A flat `key: value` front matter block, the subset of YAML that dev.to's
+/// editor understands. Key order is preserved.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrontMatter {
+    fields: Vec<(String, String)>,
+}
+
+impl FrontMatter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parse(block: &str) -> Self {
+        let fields = block
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_string(), unquote(value.trim()).to_string()))
+            .collect();
+        Self { fields }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn set(&mut self, key: &str, value: impl Into<String>) {
+        let value = value.into();
+        match self.fields.iter_mut().find(|(k, _)| k == key) {
+            Some((_, existing)) => *existing = value,
+            None => self.fields.push((key.to_string(), value)),
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let index = self.fields.iter().position(|(k, _)| k == key)?;
+        Some(self.fields.remove(index).1)
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Comma separated `tags: a, b` (dev.to style) or `[a, b]` lists.
+    pub fn tags(&self) -> Option<Vec<String>> {
+        let raw = self.get("tags")?;
+        let raw = raw.trim_start_matches('[').trim_end_matches(']');
+        Some(
+            raw.split(',')
+                .map(|tag| unquote(tag.trim()).to_string())
+                .filter(|tag| !tag.is_empty())
+                .collect(),
+        )
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::from("---\n");
+        for (key, value) in &self.fields {
+            out.push_str(key);
+            out.push(':');
+            if !value.is_empty() {
+                out.push(' ');
+                out.push_str(value);
+            }
+            out.push('\n');
+        }
+        out.push_str("---\n");
+        out
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return &value[1..value.len() - 1];
+        }
+    }
+    value
+}
+
+/// Splits a markdown document into its parsed front matter and body.
+pub fn parse_document(markdown: &str) -> (Option<FrontMatter>, &str) {
+    let (front_matter, body) = split_front_matter(markdown);
+    (front_matter.map(FrontMatter::parse), body)
+}
+
+pub fn has_embedded_front_matter(article: &Article) -> bool {
+    article
+        .body_markdown
+        .as_deref()
+        .is_some_and(|body| split_front_matter(body).0.is_some())
+}
+
+/// Renders an article as a single markdown document for local editing.
+///
+/// Drafts written in dev.to's front matter editor already carry their metadata
+/// in the body, which is used verbatim. Other drafts get a generated block.
+pub fn draft_document(article: &Article) -> String {
+    let body = article.body_markdown.as_deref().unwrap_or_default();
+    if has_embedded_front_matter(article) {
+        return body.to_string();
+    }
+    let mut front_matter = FrontMatter::new();
+    front_matter.set("title", article.title.as_str());
+    front_matter.set("published", article.published.to_string());
+    front_matter.set("tags", article.tags.as_deref().unwrap_or_default().join(", "));
+    if let Some(description) = &article.description {
+        front_matter.set("description", description.as_str());
+    }
+    if let Some(canonical_url) = &article.canonical_url {
+        front_matter.set("canonical_url", canonical_url.as_str());
+    }
+    if let Some(series) = &article.series {
+        front_matter.set("series", series.as_str());
+    }
+    format!("{}{body}", front_matter.render())
+}
+
+/// Inverse of [`draft_document`]. With `embedded` front matter the whole
+/// document is sent as the body and dev.to reads the metadata from it.
+pub fn document_to_input(document: &str, embedded: bool) -> ArticleInput {
+    if embedded {
+        return ArticleInput {
+            body_markdown: Some(document.to_string()),
+            ..Default::default()
+        };
+    }
+    let (front_matter, body) = parse_document(document);
+    let front_matter = front_matter.unwrap_or_default();
+    ArticleInput {
+        title: front_matter.get("title").map(str::to_string),
+        body_markdown: Some(body.to_string()),
+        published: front_matter.get("published").map(|value| value == "true"),
+        tags: front_matter.tags(),
+        description: front_matter
+            .get("description")
+            .filter(|value| !value.is_empty())
+            .map(str::to_string),
+        canonical_url: front_matter
+            .get("canonical_url")
+            .filter(|value| !value.is_empty())
+            .map(str::to_string),
+        series: front_matter.get("series").map(str::to_string),
+        main_image: None,
+        organization_id: None,
+    }
+}
+
+/// An update that puts an article back into the state `document` describes.
+/// Unlike [`document_to_input`], fields the document lacks are cleared.
+pub fn restore_input(document: &str, embedded: bool) -> ArticleInput {
+    let mut input = document_to_input(document, embedded);
+    if !embedded {
+        input.description.get_or_insert_default();
+        input.canonical_url.get_or_insert_default();
+        input.series.get_or_insert_default();
+    }
+    input
+}
+
+/// All of `article`'s content and state, to restore it or to create it anew.
+pub fn article_input(article: &Article) -> ArticleInput {
+    let embedded = has_embedded_front_matter(article);
+    let mut input = restore_input(&draft_document(article), embedded);
+    input.published = Some(article.published);
+    input
+}
+
+/// An update setting the article's tags. Embedded front matter wins over the
+/// request field, so it is updated in place.
+pub fn tags_input(article: &Article, tags: &[String]) -> ArticleInput {
+    let input = ArticleInput { tags: Some(tags.to_vec()), ..Default::default() };
+    with_front_matter_field(article, "tags", &tags.join(", "), input)
+}
+
+/// An update moving the article into `series`, like [`tags_input`]. An empty
+/// series removes the article from its series.
+pub fn series_input(article: &Article, series: &str) -> ArticleInput {
+    let input = ArticleInput { series: Some(series.to_string()), ..Default::default() };
+    with_front_matter_field(article, "series", series, input)
+}
+
+/// An update pointing the article's canonical URL at `url`, like
+/// [`tags_input`]. An empty URL removes it.
+pub fn canonical_input(article: &Article, url: &str) -> ArticleInput {
+    let input = ArticleInput { canonical_url: Some(url.to_string()), ..Default::default() };
+    with_front_matter_field(article, "canonical_url", url, input)
+}
+
+/// An update setting the article's cover image, like [`tags_input`].
+pub fn cover_input(article: &Article, url: &str) -> ArticleInput {
+    let input = ArticleInput { main_image: Some(url.to_string()), ..Default::default() };
+    with_front_matter_field(article, "cover_image", url, input)
+}
+
+// Also writes `key` into embedded front matter, which dev.to prefers over `input`
+fn with_front_matter_field(article: &Article, key: &str, value: &str, input: ArticleInput) -> ArticleInput {
+    if !has_embedded_front_matter(article) {
+        return input;
+    }
+    let (front_matter, rest) = parse_document(article.body_markdown.as_deref().unwrap_or_default());
+    let mut front_matter = front_matter.unwrap_or_default();
+    front_matter.set(key, value);
+    ArticleInput { body_markdown: Some(format!("{}{rest}", front_matter.render())), ..input }
+}
+
+/// The front matter dialect of local draft files. dev.to's is used as is;
+/// the others are converted by a [`FrontMatterMapping`] on the way in and
+/// back again on the way out, so one folder can feed dev.to and a static site.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FrontMatterStyle {
+    #[default]
+    DevTo,
+    Hugo,
+    Jekyll,
+    /// The `[frontmatter]` mapping from the config
+    Custom,
+}
+
+impl FromStr for FrontMatterStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "devto" | "dev.to" => Ok(FrontMatterStyle::DevTo),
+            "hugo" => Ok(FrontMatterStyle::Hugo),
+            "jekyll" => Ok(FrontMatterStyle::Jekyll),
+            "custom" => Ok(FrontMatterStyle::Custom),
+            _ => Err(format!("unknown front matter style '{s}' (expected devto, hugo, jekyll or custom)")),
+        }
+    }
+}
+
+impl fmt::Display for FrontMatterStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FrontMatterStyle::DevTo => "devto",
+            FrontMatterStyle::Hugo => "hugo",
+            FrontMatterStyle::Jekyll => "jekyll",
+            FrontMatterStyle::Custom => "custom",
+        })
+    }
+}
+
+impl FrontMatterStyle {
+    pub fn mapping(self, custom: &FrontMatterMapping) -> FrontMatterMapping {
+        match self {
+            FrontMatterStyle::DevTo => FrontMatterMapping::default(),
+            FrontMatterStyle::Hugo => FrontMatterMapping { tag_list: true, draft: true, ..Default::default() },
+            FrontMatterStyle::Jekyll => FrontMatterMapping {
+                tag_list: true,
+                extra: BTreeMap::from([("layout".to_string(), "post".to_string())]),
+                ..Default::default()
+            },
+            FrontMatterStyle::Custom => custom.clone(),
+        }
+    }
+}
+
+/// How dev.to front matter keys and values are written in local files.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FrontMatterMapping {
+    /// dev.to key to the key used locally, e.g. `canonical_url = "canonicalURL"`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub rename: BTreeMap<String, String>,
+    /// Write tags as a `["a", "b"]` list instead of `a, b`
+    pub tag_list: bool,
+    /// Write `draft: true` instead of `published: false`
+    pub draft: bool,
+    /// Fixed keys added to local files and dropped before uploading
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, String>,
+}
+
+impl FrontMatterMapping {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Converts a document in dev.to's dialect into the local one.
+    pub fn to_local(&self, document: &str) -> String {
+        let (Some(front_matter), body) = parse_document(document) else {
+            return document.to_string();
+        };
+        if self.is_default() {
+            return document.to_string();
+        }
+        let mut local = FrontMatter::new();
+        for (key, value) in &self.extra {
+            local.set(key, value.as_str());
+        }
+        for (key, value) in front_matter.fields() {
+            let (key, value) = match key {
+                "published" if self.draft => ("draft", (value != "true").to_string()),
+                "tags" if self.tag_list => (key, quoted_list(&front_matter.tags().unwrap_or_default())),
+                _ => (key, yaml_value(value)),
+            };
+            local.set(self.rename.get(key).map_or(key, String::as_str), value);
+        }
+        format!("{}{body}", local.render())
+    }
+
+    /// Inverse of [`FrontMatterMapping::to_local`].
+    pub fn to_devto(&self, document: &str) -> String {
+        let (Some(front_matter), body) = parse_document(document) else {
+            return document.to_string();
+        };
+        if self.is_default() {
+            return document.to_string();
+        }
+        let mut devto = FrontMatter::new();
+        for (local_key, value) in front_matter.fields() {
+            let renamed = self.rename.iter().find(|(_, local)| *local == local_key).map(|(key, _)| key.as_str());
+            if renamed.is_none() && self.extra.contains_key(local_key) {
+                continue;
+            }
+            let key = renamed.unwrap_or(local_key);
+            let (key, value) = match key {
+                "draft" if self.draft => ("published", (value != "true").to_string()),
+                "tags" if self.tag_list => {
+                    let tags = FrontMatter::parse(&format!("tags: {value}")).tags().unwrap_or_default();
+                    (key, tags.join(", "))
+                }
+                _ => (key, yaml_value(value)),
+            };
+            devto.set(key, value);
+        }
+        format!("{}{body}", devto.render())
+    }
+}
+
+// Parsing drops quotes; put them back where YAML needs them
+fn yaml_value(value: &str) -> String {
+    let needs_quotes = value.contains(": ")
+        || value.contains(" #")
+        || value.ends_with(':')
+        || value.starts_with(['"', '\'', '{', '[', '&', '*', '!', '|', '>', '%', '@', '`', '#']);
+    if needs_quotes {
+        serde_json::to_string(value).unwrap_or_default()
+    } else {
+        value.to_string()
+    }
+}
+
+// JSON strings are valid double-quoted YAML strings
+fn quoted_list(values: &[String]) -> String {
+    let items: Vec<String> = values.iter().map(|value| serde_json::to_string(value).unwrap_or_default()).collect();
+    format!("[{}]", items.join(", "))
+}
@@ -0,0 +1,126 @@
+//! Writing goals: a target number of articles to publish per month, checked
+//! against the publish dates of your published articles. The targets and the
+//! best streak reached are kept in `~/.dtdrafts/goals.json`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{get_config_dir, Article};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Goals {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publish_per_month: Option<u32>,
+    /// Longest run of consecutive months that met the target, in months
+    #[serde(default)]
+    pub best_streak: u32,
+}
+
+pub fn get_goals_file() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("goals.json"))
+}
+
+impl Goals {
+    pub fn load() -> Result<Self> {
+        let path = get_goals_file()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid goals in {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(get_config_dir()?)?;
+        fs::write(get_goals_file()?, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// When each article went live, for the articles dev.to reports a
+/// `published_at` for.
+pub fn publish_dates(articles: &[Article]) -> Vec<DateTime<Utc>> {
+    articles.iter().filter(|article| article.published).filter_map(Article::published_at).collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonthProgress {
+    /// The first day of the month
+    pub month: NaiveDate,
+    pub published: u32,
+    pub met: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoalStatus {
+    pub target: u32,
+    /// The last `months` months, oldest first, ending with the current one
+    pub months: Vec<MonthProgress>,
+    /// Consecutive months meeting the target up to now. The current month
+    /// only counts once it meets the target, so the streak isn't broken
+    /// before the month is over.
+    pub streak: u32,
+    pub best_streak: u32,
+}
+
+impl GoalStatus {
+    pub fn current(&self) -> Option<&MonthProgress> {
+        self.months.last()
+    }
+}
+
+fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("every month has a first day")
+}
+
+fn previous_month(month: NaiveDate) -> NaiveDate {
+    month_start(month.pred_opt().expect("dates are far from the minimum"))
+}
+
+/// Compares the articles published each month with `target`. `best_streak`
+/// is the best streak recorded so far; the result's is never lower.
+pub fn status(target: u32, published: &[DateTime<Utc>], today: NaiveDate, months: usize, best_streak: u32) -> GoalStatus {
+    let mut per_month: BTreeMap<NaiveDate, u32> = BTreeMap::new();
+    for at in published {
+        *per_month.entry(month_start(at.date_naive())).or_default() += 1;
+    }
+    let met = |month: NaiveDate| per_month.get(&month).copied().unwrap_or(0) >= target;
+
+    let this_month = month_start(today);
+    let mut streak = 0;
+    let mut month = if met(this_month) { this_month } else { previous_month(this_month) };
+    while met(month) && month >= per_month.keys().next().copied().unwrap_or(this_month) {
+        streak += 1;
+        month = previous_month(month);
+    }
+
+    let mut best = best_streak.max(streak);
+    let mut run = 0;
+    if let Some(first) = per_month.keys().next().copied() {
+        let mut month = first;
+        while month <= this_month {
+            run = if met(month) { run + 1 } else { 0 };
+            best = best.max(run);
+            month = (month + chrono::Duration::days(32)).with_day(1).expect("every month has a first day");
+        }
+    }
+
+    let mut shown = vec![this_month];
+    while shown.len() < months.max(1) {
+        shown.push(previous_month(*shown.last().expect("not empty")));
+    }
+    let months = shown
+        .into_iter()
+        .rev()
+        .map(|month| {
+            let published = per_month.get(&month).copied().unwrap_or(0);
+            MonthProgress { month, published, met: published >= target }
+        })
+        .collect();
+    GoalStatus { target, months, streak, best_streak: best }
+}
@@ -0,0 +1,127 @@
+//! Grouped listings (`list --group-by`): drafts in a section per tag,
+//! series, month of their last update or workflow status.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::status::{StatusStore, WorkflowStatus};
+use crate::Article;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    Tag,
+    Series,
+    Month,
+    Status,
+}
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "tag" => Ok(GroupBy::Tag),
+            "series" => Ok(GroupBy::Series),
+            "month" => Ok(GroupBy::Month),
+            "status" => Ok(GroupBy::Status),
+            _ => Err(format!("unknown grouping '{s}' (expected tag, series, month or status)")),
+        }
+    }
+}
+
+impl fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            GroupBy::Tag => "tag",
+            GroupBy::Series => "series",
+            GroupBy::Month => "month",
+            GroupBy::Status => "status",
+        })
+    }
+}
+
+/// One section of a grouped listing; `name` is `None` for the drafts that
+/// have no tag, series, date or status.
+#[derive(Debug)]
+pub struct Group<'a> {
+    pub name: Option<String>,
+    pub articles: Vec<&'a Article>,
+}
+
+/// Splits `articles` into groups, keeping their order within each group.
+/// Tags and series come largest group first, months newest first and
+/// statuses in workflow order; the ungrouped drafts come last. A draft with
+/// several tags is listed under each of them. Empty groups are left out.
+pub fn group_articles<'a>(articles: &[&'a Article], by: GroupBy, statuses: &StatusStore) -> Vec<Group<'a>> {
+    let mut named: BTreeMap<String, Vec<&'a Article>> = BTreeMap::new();
+    // Series names differing only in case are one series, as for `--series`
+    let mut series_names: BTreeMap<String, String> = BTreeMap::new();
+    let mut rest = Vec::new();
+    for article in articles {
+        let keys: Vec<String> = match by {
+            GroupBy::Tag => article.tags.clone().unwrap_or_default(),
+            GroupBy::Series => article
+                .series()
+                .map(|name| series_names.entry(name.to_lowercase()).or_insert(name).clone())
+                .into_iter()
+                .collect(),
+            GroupBy::Month => article
+                .updated_at
+                .or(article.created_at)
+                .map(|at| at.format("%Y-%m").to_string())
+                .into_iter()
+                .collect(),
+            GroupBy::Status => statuses.get(article.id).map(|status| status.to_string()).into_iter().collect(),
+        };
+        if keys.is_empty() {
+            rest.push(*article);
+        }
+        for key in keys {
+            named.entry(key).or_default().push(*article);
+        }
+    }
+
+    let mut groups: Vec<Group> =
+        named.into_iter().map(|(name, articles)| Group { name: Some(name), articles }).collect();
+    match by {
+        GroupBy::Tag | GroupBy::Series => groups.sort_by_key(|group| std::cmp::Reverse(group.articles.len())),
+        GroupBy::Month => groups.reverse(),
+        GroupBy::Status => groups.sort_by_key(|group| {
+            WorkflowStatus::ALL.iter().position(|status| group.name.as_deref() == Some(&status.to_string()))
+        }),
+    }
+    if !rest.is_empty() {
+        groups.push(Group { name: None, articles: rest });
+    }
+    groups
+}
+
+/// A heading with the count for each group, followed by its drafts.
+#[cfg(feature = "terminal")]
+pub fn format_groups(groups: &[Group], by: GroupBy) -> String {
+    use colored::*;
+    if groups.is_empty() {
+        return format!("{}\n", "No draft articles found.".yellow());
+    }
+    let mut out = String::new();
+    for group in groups {
+        let name = match (&group.name, by) {
+            (Some(tag), GroupBy::Tag) => format!("#{tag}"),
+            (Some(name), GroupBy::Status) => name.to_uppercase(),
+            (Some(name), _) => name.clone(),
+            (None, GroupBy::Tag) => "untagged".to_string(),
+            (None, GroupBy::Series) => "no series".to_string(),
+            (None, GroupBy::Month) => "no date".to_string(),
+            (None, GroupBy::Status) => "no status".to_string(),
+        };
+        let heading = format!("{name} ({})", group.articles.len());
+        let heading = if group.name.is_some() { heading.green().bold() } else { heading.dimmed().bold() };
+        out.push_str(&format!("{heading}\n"));
+        for article in &group.articles {
+            out.push_str(&format!("  {} {}\n", article.title.cyan(), format!("({})", article.id).dimmed()));
+        }
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,179 @@
+//! Local version history of drafts, which dev.to doesn't keep.
+//!
+//! Every time a draft is pulled into the cache or pushed to dev.to its
+//! document is stored under `~/.dtdrafts/history/objects/<sha256>`, so
+//! identical versions are stored once, and listed per article in
+//! `~/.dtdrafts/history/index.json`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::frontmatter::{draft_document, has_embedded_front_matter};
+use crate::{get_config_dir, Article};
+
+/// Older versions of a draft are dropped, with their documents, beyond this many.
+pub const MAX_VERSIONS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Pull,
+    Push,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Direction::Pull => "pulled",
+            Direction::Push => "pushed",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Version {
+    /// sha256 of the document, naming its object file
+    pub hash: String,
+    pub at: DateTime<Utc>,
+    pub direction: Direction,
+    pub title: String,
+    /// The body carried its own front matter, see [`draft_document`]
+    pub embedded: bool,
+}
+
+impl Version {
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(8)]
+    }
+}
+
+/// Versions by article id, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct History {
+    #[serde(default)]
+    pub versions: BTreeMap<u64, Vec<Version>>,
+}
+
+pub fn get_history_dir() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("history"))
+}
+
+fn get_index_file() -> Result<PathBuf> {
+    Ok(get_history_dir()?.join("index.json"))
+}
+
+fn get_object_file(hash: &str) -> Result<PathBuf> {
+    Ok(get_history_dir()?.join("objects").join(hash))
+}
+
+pub fn content_hash(document: &str) -> String {
+    Sha256::digest(document.as_bytes()).iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+impl History {
+    pub fn load() -> Result<Self> {
+        let path = get_index_file()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid history in {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(get_history_dir()?)?;
+        fs::write(get_index_file()?, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, id: u64) -> &[Version] {
+        self.versions.get(&id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Adds a version of `article` unless it is the same as the latest one,
+    /// dropping the oldest beyond [`MAX_VERSIONS`]. Returns the new version and
+    /// its document, which still has to be stored.
+    pub fn add(&mut self, article: &Article, direction: Direction, at: DateTime<Utc>) -> Option<(&Version, String)> {
+        article.body_markdown.as_ref()?;
+        let document = draft_document(article);
+        let hash = content_hash(&document);
+        let versions = self.versions.entry(article.id).or_default();
+        if versions.last().is_some_and(|latest| latest.hash == hash) {
+            return None;
+        }
+        versions.push(Version {
+            hash,
+            at,
+            direction,
+            title: article.title.clone(),
+            embedded: has_embedded_front_matter(article),
+        });
+        let excess = versions.len().saturating_sub(MAX_VERSIONS);
+        versions.drain(..excess);
+        versions.last().map(|version| (version, document))
+    }
+
+    /// The hashes of every version kept, i.e. the documents still needed.
+    pub fn hashes(&self) -> BTreeSet<&str> {
+        self.versions.values().flatten().map(|version| version.hash.as_str()).collect()
+    }
+
+    /// A version by its number (1 is the oldest) or a prefix of its hash.
+    pub fn find(&self, id: u64, version: &str) -> Result<&Version> {
+        let versions = self.get(id);
+        if versions.is_empty() {
+            return Err(anyhow::anyhow!("No history for article {id}"));
+        }
+        if let Ok(number) = version.parse::<usize>() {
+            if let Some(found) = number.checked_sub(1).and_then(|index| versions.get(index)) {
+                return Ok(found);
+            }
+        }
+        let matches: Vec<&Version> = versions.iter().filter(|found| found.hash.starts_with(version)).collect();
+        match matches.as_slice() {
+            [found] => Ok(found),
+            [found, ..] if matches.iter().all(|other| other.hash == found.hash) => Ok(found),
+            [] => Err(anyhow::anyhow!("Article {id} has no version '{version}' (see `dtdrafts history {id}`)")),
+            _ => Err(anyhow::anyhow!("'{version}' matches several versions; give more of the hash")),
+        }
+    }
+}
+
+/// Stores the versions of `articles` that differ from their latest one, and
+/// deletes the documents of versions dropped to make room.
+pub fn record(articles: &[&Article], direction: Direction) -> Result<()> {
+    let mut history = History::load()?;
+    let kept: BTreeSet<String> = history.hashes().into_iter().map(str::to_string).collect();
+    let at = Utc::now();
+    let mut changed = false;
+    for article in articles {
+        let Some((version, document)) = history.add(article, direction, at) else { continue };
+        let path = get_object_file(&version.hash)?;
+        if !path.exists() {
+            fs::create_dir_all(get_history_dir()?.join("objects"))?;
+            fs::write(&path, document).with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        changed = true;
+    }
+    if changed {
+        history.save()?;
+        let needed = history.hashes();
+        for hash in kept.iter().filter(|hash| !needed.contains(hash.as_str())) {
+            fs::remove_file(get_object_file(hash)?).ok();
+        }
+    }
+    Ok(())
+}
+
+/// The document stored for `version`.
+pub fn load_document(version: &Version) -> Result<String> {
+    let path = get_object_file(&version.hash)?;
+    fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))
+}
@@ -0,0 +1,175 @@
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+
+use crate::client::DEFAULT_USER_AGENT;
+use crate::Article;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Something to run on a lifecycle event: a shell command, which gets the
+/// payload on stdin, or a URL the payload is POSTed to as JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Hook {
+    Webhook { url: String },
+    Command { command: String },
+}
+
+/// Hooks by event, stored under `hooks` in the config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub on_publish: Vec<Hook>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub on_push: Vec<Hook>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub on_refresh: Vec<Hook>,
+    /// Sends what command hooks print to stderr, for when stdout carries a
+    /// protocol (`--serve-stdio`).
+    #[serde(skip)]
+    pub stdout_to_stderr: bool,
+}
+
+impl HooksConfig {
+    pub fn is_empty(&self) -> bool {
+        self.on_publish.is_empty() && self.on_push.is_empty() && self.on_refresh.is_empty()
+    }
+
+    pub fn hooks(&self, event: HookEvent) -> &[Hook] {
+        match event {
+            HookEvent::Publish => &self.on_publish,
+            HookEvent::Push => &self.on_push,
+            HookEvent::Refresh => &self.on_refresh,
+        }
+    }
+}
+
+/// `Publish` when a draft goes live, `Push` when local edits are uploaded and
+/// `Refresh` when the article cache is re-fetched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    Publish,
+    Push,
+    Refresh,
+}
+
+impl HookEvent {
+    pub fn name(self) -> &'static str {
+        match self {
+            HookEvent::Publish => "publish",
+            HookEvent::Push => "push",
+            HookEvent::Refresh => "refresh",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HookPayload {
+    pub event: &'static str,
+    pub at: DateTime<Utc>,
+    pub articles: Vec<HookArticle>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HookArticle {
+    pub id: u64,
+    pub title: String,
+    pub url: String,
+    pub edit_url: String,
+    pub published: bool,
+    pub tags: Vec<String>,
+}
+
+impl HookPayload {
+    pub fn new(event: HookEvent, articles: &[&Article], at: DateTime<Utc>) -> Self {
+        let articles = articles
+            .iter()
+            .map(|article| HookArticle {
+                id: article.id,
+                title: article.title.clone(),
+                url: article.url.clone(),
+                edit_url: article.edit_url(),
+                published: article.published,
+                tags: article.tags.clone().unwrap_or_default(),
+            })
+            .collect();
+        Self { event: event.name(), at, articles }
+    }
+}
+
+/// Runs every hook configured for `event`. The action that triggered the
+/// event has already happened, so failing hooks are only logged as warnings.
+pub async fn fire(config: &HooksConfig, event: HookEvent, articles: &[&Article]) {
+    let hooks = config.hooks(event);
+    if hooks.is_empty() {
+        return;
+    }
+    let payload = HookPayload::new(event, articles, Utc::now());
+    for hook in hooks {
+        tracing::debug!(event = event.name(), ?hook, "running hook");
+        if let Err(e) = run_hook(hook, &payload, config.stdout_to_stderr).await {
+            tracing::warn!(event = event.name(), ?hook, "hook failed: {e:#}");
+        }
+    }
+}
+
+pub async fn run_hook(hook: &Hook, payload: &HookPayload, stdout_to_stderr: bool) -> Result<()> {
+    match hook {
+        Hook::Webhook { url } => post_webhook(url, payload).await,
+        Hook::Command { command } => run_command(command, payload, stdout_to_stderr).await,
+    }
+}
+
+async fn post_webhook(url: &str, payload: &HookPayload) -> Result<()> {
+    reqwest::Client::builder()
+        .user_agent(DEFAULT_USER_AGENT)
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()?
+        .post(url)
+        .json(payload)
+        .send()
+        .await
+        .with_context(|| format!("Failed to POST to {url}"))?
+        .error_for_status()?;
+    Ok(())
+}
+
+// The first article is also exposed through environment variables, which is
+// all single-article events need in a one-line shell command.
+async fn run_command(command: &str, payload: &HookPayload, stdout_to_stderr: bool) -> Result<()> {
+    let mut process = if cfg!(windows) {
+        let mut process = tokio::process::Command::new("cmd");
+        process.arg("/C").arg(command);
+        process
+    } else {
+        let mut process = tokio::process::Command::new("sh");
+        process.arg("-c").arg(command);
+        process
+    };
+    process.env("DTDRAFTS_EVENT", payload.event).stdin(Stdio::piped());
+    if stdout_to_stderr {
+        process.stdout(std::io::stderr());
+    }
+    if let Some(article) = payload.articles.first() {
+        process
+            .env("DTDRAFTS_ARTICLE_ID", article.id.to_string())
+            .env("DTDRAFTS_ARTICLE_TITLE", &article.title)
+            .env("DTDRAFTS_ARTICLE_URL", &article.url);
+    }
+    let mut child = process.spawn().with_context(|| format!("Failed to run '{command}'"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // Commands that ignore stdin may exit before reading it
+        let _ = stdin.write_all(&serde_json::to_vec(payload)?).await;
+    }
+    let status = child.wait().await?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("'{command}' exited with {status}"));
+    }
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::client::ArticleInput;
+use crate::lint::MAX_TAGS;
+
+/// A post read from another platform's export, converted to markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedPost {
+    /// Where the post came from inside the export (file name or slug)
+    pub source: String,
+    pub title: String,
+    pub body_markdown: String,
+    pub tags: Vec<String>,
+}
+
+impl ImportedPost {
+    /// An unpublished draft. Tags are reduced to what dev.to accepts, which
+    /// can make tags that only differed in case or punctuation the same.
+    pub fn to_input(&self) -> ArticleInput {
+        let mut seen = std::collections::HashSet::new();
+        let mut tags: Vec<String> =
+            self.tags.iter().filter_map(|tag| dev_to_tag(tag)).filter(|tag| seen.insert(tag.clone())).collect();
+        tags.truncate(MAX_TAGS);
+        ArticleInput {
+            title: Some(self.title.clone()),
+            body_markdown: Some(self.body_markdown.clone()),
+            published: Some(false),
+            tags: (!tags.is_empty()).then_some(tags),
+            ..Default::default()
+        }
+    }
+}
+
+fn dev_to_tag(tag: &str) -> Option<String> {
+    let tag: String = tag.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// Reads a Medium export (`.zip`) or a Ghost export (`.json`).
+pub fn read_export(path: &Path) -> Result<Vec<ImportedPost>> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+    match extension.as_str() {
+        "zip" => {
+            let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+            read_medium_export(file)
+        }
+        "json" => {
+            let json = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+            read_ghost_export(&json)
+        }
+        _ => Err(anyhow::anyhow!(
+            "Unsupported export {}: expected a Medium .zip or a Ghost .json export",
+            path.display()
+        )),
+    }
+}
+
+fn html_to_markdown(html: &str) -> Result<String> {
+    let markdown = htmd::convert(html).context("Failed to convert HTML to markdown")?;
+    Ok(markdown.trim().to_string())
+}
+
+/// Medium's export has one HTML file per post under `posts/`; unpublished
+/// posts are prefixed with `draft_`. Medium does not export tags.
+pub fn read_medium_export<R: Read + Seek>(reader: R) -> Result<Vec<ImportedPost>> {
+    static TITLE: OnceLock<Regex> = OnceLock::new();
+    static BODY: OnceLock<Regex> = OnceLock::new();
+    let title_re = TITLE.get_or_init(|| Regex::new(r#"(?s)<h1 class="p-name">(.*?)</h1>"#).expect("valid regex"));
+    let body_re = BODY.get_or_init(|| {
+        Regex::new(r#"(?s)<section data-field="body"[^>]*>(.*)</section>"#).expect("valid regex")
+    });
+
+    let mut archive = zip::ZipArchive::new(reader).context("Not a zip archive")?;
+    let mut posts = Vec::new();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let name = entry.name()?.to_string();
+        if !(name.starts_with("posts/") && name.ends_with(".html")) {
+            continue;
+        }
+        let mut html = String::new();
+        entry.read_to_string(&mut html).with_context(|| format!("Failed to read {name}"))?;
+
+        let title = match title_re.captures(&html) {
+            Some(found) => html_to_markdown(&found[1])?,
+            None => String::new(),
+        };
+        let body_html = body_re.captures(&html).map_or(html.as_str(), |found| found.get(1).map_or("", |m| m.as_str()));
+        let mut body_markdown = html_to_markdown(body_html)?;
+        // Medium repeats the title as the first heading of the body
+        if let Some(first_line) = body_markdown.lines().next() {
+            if !title.is_empty() && first_line.trim_start_matches('#').trim() == title {
+                body_markdown = body_markdown[first_line.len()..].trim_start().to_string();
+            }
+        }
+        let title = if title.is_empty() { name.clone() } else { title };
+        posts.push(ImportedPost { source: name, title, body_markdown, tags: Vec::new() });
+    }
+    posts.sort_by(|a, b| a.source.cmp(&b.source));
+    Ok(posts)
+}
+
+#[derive(Deserialize)]
+struct GhostExport {
+    db: Vec<GhostDb>,
+}
+
+#[derive(Deserialize)]
+struct GhostDb {
+    data: GhostData,
+}
+
+#[derive(Deserialize)]
+struct GhostData {
+    #[serde(default)]
+    posts: Vec<GhostPost>,
+    #[serde(default)]
+    tags: Vec<GhostTag>,
+    #[serde(default)]
+    posts_tags: Vec<GhostPostTag>,
+}
+
+#[derive(Deserialize)]
+struct GhostPost {
+    id: String,
+    title: String,
+    slug: String,
+    html: Option<String>,
+    plaintext: Option<String>,
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GhostTag {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GhostPostTag {
+    post_id: String,
+    tag_id: String,
+}
+
+/// Reads the JSON file from Ghost's Labs > Export. Pages are skipped, and
+/// internal `#tags` are dropped.
+pub fn read_ghost_export(json: &str) -> Result<Vec<ImportedPost>> {
+    let export: GhostExport = serde_json::from_str(json).context("Not a Ghost export")?;
+    let mut posts = Vec::new();
+    for db in export.db {
+        let data = db.data;
+        for post in &data.posts {
+            if post.kind.as_deref().is_some_and(|kind| kind != "post") {
+                continue;
+            }
+            let body_markdown = match (&post.html, &post.plaintext) {
+                (Some(html), _) => html_to_markdown(html)?,
+                (None, Some(plaintext)) => plaintext.trim().to_string(),
+                (None, None) => String::new(),
+            };
+            let tags = data
+                .posts_tags
+                .iter()
+                .filter(|link| link.post_id == post.id)
+                .filter_map(|link| data.tags.iter().find(|tag| tag.id == link.tag_id))
+                .filter(|tag| !tag.name.starts_with('#'))
+                .map(|tag| tag.name.clone())
+                .collect();
+            posts.push(ImportedPost { source: post.slug.clone(), title: post.title.clone(), body_markdown, tags });
+        }
+    }
+    Ok(posts)
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tantivy::collector::TopDocs;
+use tantivy::query::QueryParser;
+use tantivy::schema::{
+    Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, Value, INDEXED, STORED,
+};
+use tantivy::{doc, Index, IndexWriter, TantivyDocument};
+
+use crate::{get_config_dir, Article, ArticleFilter};
+
+const WRITER_MEMORY_BYTES: usize = 50_000_000;
+
+pub fn get_index_dir() -> Result<PathBuf> {
+    let mut index_dir = get_config_dir()?;
+    index_dir.push("index");
+    Ok(index_dir)
+}
+
+struct Fields {
+    id: Field,
+    title: Field,
+    body: Field,
+    tags: Field,
+}
+
+fn schema() -> (Schema, Fields) {
+    let stemmed = TextOptions::default().set_indexing_options(
+        TextFieldIndexing::default()
+            .set_tokenizer("en_stem")
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+    );
+    let mut builder = Schema::builder();
+    let fields = Fields {
+        id: builder.add_u64_field("id", INDEXED | STORED),
+        title: builder.add_text_field("title", stemmed.clone()),
+        body: builder.add_text_field("body", stemmed.clone()),
+        tags: builder.add_text_field("tags", stemmed),
+    };
+    (builder.build(), fields)
+}
+
+/// Rebuilds the index in `dir` from scratch.
+pub fn build_index(articles: &[Article], dir: &Path) -> Result<()> {
+    if dir.exists() {
+        fs::remove_dir_all(dir).context("Failed to remove old search index")?;
+    }
+    fs::create_dir_all(dir)?;
+    let (schema, fields) = schema();
+    let index = Index::create_in_dir(dir, schema).context("Failed to create search index")?;
+    let mut writer: IndexWriter = index.writer(WRITER_MEMORY_BYTES)?;
+    for article in articles {
+        writer.add_document(doc!(
+            fields.id => article.id,
+            fields.title => article.title.as_str(),
+            fields.body => article.body_markdown.as_deref().unwrap_or_default(),
+            fields.tags => article.tags.as_deref().unwrap_or_default().join(" "),
+        ))?;
+    }
+    writer.commit().context("Failed to write search index")?;
+    Ok(())
+}
+
+/// Rebuilds the index only if one exists already, so refreshing the cache
+/// doesn't cost anything for users who never asked for the index engine.
+pub fn refresh_index_if_present(articles: &[Article]) -> Result<()> {
+    let dir = get_index_dir()?;
+    if dir.exists() {
+        build_index(articles, &dir)?;
+    }
+    Ok(())
+}
+
+/// Returns `(article id, score)` pairs, best match first. Titles weigh more
+/// than tags, which weigh more than bodies.
+pub fn search_index(dir: &Path, query: &str, limit: usize) -> Result<Vec<(u64, f32)>> {
+    let index = Index::open_in_dir(dir).context("Failed to open search index")?;
+    let (_, fields) = schema();
+    let reader = index.reader()?;
+    let searcher = reader.searcher();
+    let mut parser = QueryParser::for_index(&index, vec![fields.title, fields.body, fields.tags]);
+    parser.set_field_boost(fields.title, 3.0);
+    parser.set_field_boost(fields.tags, 2.0);
+    let (query, _errors) = parser.parse_query_lenient(query);
+
+    let top_docs = searcher.search(&query, &TopDocs::with_limit(limit.max(1)).order_by_score())?;
+    let mut results = Vec::with_capacity(top_docs.len());
+    for (score, address) in top_docs {
+        let doc: TantivyDocument = searcher.doc(address)?;
+        if let Some(id) = doc.get_first(fields.id).and_then(|value| value.as_u64()) {
+            results.push((id, score));
+        }
+    }
+    Ok(results)
+}
+
+/// Ranked search over `articles` using the on-disk index in the cache
+/// directory, building it first if it doesn't exist yet.
+pub fn search_articles_ranked<'a>(
+    articles: &'a [Article],
+    query: &str,
+    filter: &ArticleFilter,
+) -> Result<Vec<&'a Article>> {
+    let dir = get_index_dir()?;
+    if !dir.exists() {
+        build_index(articles, &dir)?;
+    }
+    let by_id: HashMap<u64, &Article> = articles.iter().map(|article| (article.id, article)).collect();
+    Ok(search_index(&dir, query, articles.len())?
+        .into_iter()
+        .filter_map(|(id, _)| by_id.get(&id).copied())
+        .filter(|article| filter.matches(article))
+        .collect())
+}
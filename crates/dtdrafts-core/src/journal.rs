@@ -0,0 +1,144 @@
+//! A journal of the mutating API operations dtdrafts made (publish, bulk
+//! changes, retag, replace), keeping each draft as it was before, so that
+//! `undo` can put it back.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::client::ArticleInput;
+use crate::frontmatter::article_input;
+use crate::history::content_hash;
+use crate::{get_config_dir, Article};
+
+/// Older entries are dropped beyond this many.
+pub const MAX_ENTRIES: usize = 20;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JournalChange {
+    /// The draft before the operation, body included
+    pub before: Article,
+    /// The operation deleted the draft, so undoing it re-creates it
+    #[serde(default)]
+    pub deleted: bool,
+    /// What the operation left on dev.to; missing for deletions and in
+    /// journals written before it was kept
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<Written>,
+}
+
+/// A draft as an operation left it: enough to tell whether it was edited
+/// since, without keeping a second copy of the body.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Written {
+    pub updated_at: Option<DateTime<Utc>>,
+    pub body_hash: String,
+}
+
+impl Written {
+    pub fn of(article: &Article) -> Self {
+        Self { updated_at: article.updated_at, body_hash: content_hash(article.body_markdown.as_deref().unwrap_or_default()) }
+    }
+}
+
+impl JournalChange {
+    /// A draft the operation updated from `before` to `after`.
+    pub fn updated(before: Article, after: &Article) -> Self {
+        Self { before, deleted: false, after: Some(Written::of(after)) }
+    }
+
+    pub fn deleted(before: Article) -> Self {
+        Self { before, deleted: true, after: None }
+    }
+
+    /// Whether `current` differs from what the operation wrote, so undoing it
+    /// would throw away later edits. Unknown when nothing was kept.
+    pub fn changed_since(&self, current: &Article) -> bool {
+        self.after.as_ref().is_some_and(|after| {
+            let touched = matches!((after.updated_at, current.updated_at), (Some(then), Some(now)) if then != now);
+            let rewritten = current.body_markdown.as_deref().is_some_and(|body| content_hash(body) != after.body_hash);
+            touched || rewritten
+        })
+    }
+
+    /// The update (or, for a deleted draft, the new draft) that restores it.
+    pub fn restore_input(&self) -> ArticleInput {
+        article_input(&self.before)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JournalEntry {
+    pub at: DateTime<Utc>,
+    /// What was done, e.g. `publish` or `add-tag:rust`
+    pub operation: String,
+    pub changes: Vec<JournalChange>,
+}
+
+/// Entries oldest first, kept in `~/.dtdrafts/journal.json`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Journal {
+    #[serde(default)]
+    pub entries: Vec<JournalEntry>,
+}
+
+pub fn get_journal_file() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("journal.json"))
+}
+
+impl Journal {
+    pub fn load() -> Result<Self> {
+        let path = get_journal_file()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid journal in {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(get_config_dir()?)?;
+        fs::write(get_journal_file()?, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn push(&mut self, entry: JournalEntry) {
+        self.entries.push(entry);
+        let excess = self.entries.len().saturating_sub(MAX_ENTRIES);
+        self.entries.drain(..excess);
+    }
+
+    pub fn last(&self) -> Option<&JournalEntry> {
+        self.entries.last()
+    }
+
+    pub fn pop(&mut self) -> Option<JournalEntry> {
+        self.entries.pop()
+    }
+
+    /// Drops the deletions of draft `id`, once it was brought back some other
+    /// way (`trash restore`), so `undo` doesn't re-create it a second time.
+    /// Entries left without changes are dropped too. Returns whether any was.
+    pub fn forget_deletion(&mut self, id: u64) -> bool {
+        let before = self.entries.iter().map(|entry| entry.changes.len()).sum::<usize>();
+        for entry in &mut self.entries {
+            entry.changes.retain(|change| !(change.deleted && change.before.id == id));
+        }
+        self.entries.retain(|entry| !entry.changes.is_empty());
+        self.entries.iter().map(|entry| entry.changes.len()).sum::<usize>() < before
+    }
+}
+
+/// Appends an operation that changed `changes` to the journal. Operations
+/// that changed nothing are not recorded.
+pub fn record(operation: &str, changes: Vec<JournalChange>, at: DateTime<Utc>) -> Result<()> {
+    if changes.is_empty() {
+        return Ok(());
+    }
+    let mut journal = Journal::load()?;
+    journal.push(JournalEntry { at, operation: operation.to_string(), changes });
+    journal.save()
+}
@@ -0,0 +1,669 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+pub mod analytics;
+pub mod api;
+pub mod backup;
+pub mod bulk;
+pub mod cache;
+pub mod calendar;
+pub mod client;
+pub mod clone;
+pub mod comments;
+pub mod config;
+pub mod cover;
+pub mod crosspost;
+pub mod export;
+pub mod feed;
+pub mod filter;
+pub mod fixtures;
+pub mod frontmatter;
+pub mod goals;
+pub mod group;
+pub mod history;
+pub mod hooks;
+pub mod import;
+pub mod journal;
+#[cfg(feature = "index")]
+pub mod index;
+pub mod links;
+pub mod lint;
+pub mod merge;
+pub mod migrate;
+pub mod notes;
+pub mod output;
+pub mod render;
+pub mod replace;
+pub mod report;
+pub mod resolve;
+pub mod rpc;
+pub mod saved;
+pub mod schedule;
+pub mod search_history;
+pub mod seo;
+pub mod series;
+pub mod similarity;
+pub mod sort;
+pub mod spelling;
+pub mod split;
+pub mod stale;
+pub mod stats;
+pub mod status;
+pub mod style;
+pub mod sync;
+pub mod tags;
+pub mod template;
+#[cfg(feature = "terminal")]
+pub mod terminal;
+pub mod text;
+pub mod timestamp;
+pub mod toc;
+pub mod trash;
+pub mod vocab;
+
+pub use client::{ArticleInput, DevToClient, DevToClientBuilder, HttpConfig, LinkCheck, LinkChecker, Organization, DEFAULT_BASE_URL};
+pub use filter::{filter_articles, parse_age, parse_date, parse_date_end, parse_interval, ArticleFilter};
+pub use lint::{lint_article, LintIssue, Severity};
+pub use resolve::resolve_article;
+pub use sort::{sort_articles, SortKey};
+pub use stale::{stale_report, StaleReport};
+pub use stats::{compute_stats, ArticleStats, StatsSummary};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Article {
+    pub id: u64,
+    pub title: String,
+    pub description: Option<String>,
+    pub body_markdown: Option<String>,
+    pub url: String,
+    pub canonical_url: Option<String>,
+    pub url_with_preview: Option<String>,
+    pub published: bool,
+    #[serde(default, with = "timestamp")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "timestamp")]
+    pub updated_at: Option<DateTime<Utc>>,
+    pub tags: Option<Vec<String>>,
+    pub slug: String,
+    #[serde(default)]
+    pub user: ArticleUser,
+    #[serde(default)]
+    pub series: Option<String>,
+    #[serde(default)]
+    pub organization: Option<ArticleOrganization>,
+    // Left out when unset so records cached before these fields existed keep
+    // their checksums
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_image: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub social_image: Option<String>,
+    /// dev.to's estimate; [`stats::reading_time_minutes`] computes one locally.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reading_time_minutes: Option<u32>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub comments_count: u32,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub public_reactions_count: u32,
+    /// Fields dev.to returns that this struct doesn't model, kept so they
+    /// survive cache round-trips. See [`Article::extra`].
+    #[serde(flatten, default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+fn is_zero(count: &u32) -> bool {
+    *count == 0
+}
+
+impl Article {
+    /// The series name, from the API field or else from embedded front matter.
+    pub fn series(&self) -> Option<String> {
+        self.series
+            .clone()
+            .or_else(|| {
+                let (front_matter, _) = frontmatter::parse_document(self.body_markdown.as_deref()?);
+                front_matter?.get("series").map(str::to_string)
+            })
+            .filter(|series| !series.is_empty())
+    }
+
+    /// The markdown body, from the article itself or else from `bodies` when
+    /// it was loaded without one.
+    pub fn body<'a>(&'a self, bodies: &'a cache::CachedBodies) -> Option<&'a str> {
+        self.body_markdown.as_deref().or_else(|| bodies.get(self.id))
+    }
+
+    /// A field of the API response with no counterpart in `Article`, e.g.
+    /// `path` or `flare_tag`.
+    pub fn extra(&self, name: &str) -> Option<&serde_json::Value> {
+        self.extra.get(name)
+    }
+
+    /// Articles fetched without their author get the account username from
+    /// the config, see [`client::DevToClientBuilder::username`].
+    pub fn edit_url(&self) -> String {
+        format!("https://dev.to/{}/{}/edit", self.user.username, self.slug)
+    }
+
+    pub fn created_at_utc(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    pub fn updated_at_utc(&self) -> Option<DateTime<Utc>> {
+        self.updated_at
+    }
+
+    /// When the article went live, from dev.to's `published_at`.
+    pub fn published_at(&self) -> Option<DateTime<Utc>> {
+        self.extra("published_at")?.as_str().and_then(timestamp::parse)
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ArticleUser {
+    pub username: String,
+}
+
+/// The organization an article is published under.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ArticleOrganization {
+    pub name: String,
+    pub username: String,
+}
+
+/// The body-free subset of an article that listings and completions need.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ArticleMeta {
+    pub id: u64,
+    pub title: String,
+    pub url: String,
+    pub published: bool,
+    #[serde(default, with = "timestamp")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "timestamp")]
+    pub updated_at: Option<DateTime<Utc>>,
+    pub tags: Option<Vec<String>>,
+    pub slug: String,
+    pub username: String,
+    #[serde(default)]
+    pub series: Option<String>,
+    #[serde(default)]
+    pub organization: Option<ArticleOrganization>,
+}
+
+impl From<&Article> for ArticleMeta {
+    fn from(article: &Article) -> Self {
+        Self {
+            id: article.id,
+            title: article.title.clone(),
+            url: article.url.clone(),
+            published: article.published,
+            created_at: article.created_at,
+            updated_at: article.updated_at,
+            tags: article.tags.clone(),
+            slug: article.slug.clone(),
+            username: article.user.username.clone(),
+            series: article.series(),
+            organization: article.organization.clone(),
+        }
+    }
+}
+
+impl From<ArticleMeta> for Article {
+    fn from(meta: ArticleMeta) -> Self {
+        Self {
+            id: meta.id,
+            title: meta.title,
+            description: None,
+            body_markdown: None,
+            url: meta.url,
+            canonical_url: None,
+            url_with_preview: None,
+            published: meta.published,
+            created_at: meta.created_at,
+            updated_at: meta.updated_at,
+            tags: meta.tags,
+            slug: meta.slug,
+            user: ArticleUser { username: meta.username },
+            series: meta.series,
+            organization: meta.organization,
+            cover_image: None,
+            social_image: None,
+            reading_time_minutes: None,
+            comments_count: 0,
+            public_reactions_count: 0,
+            extra: serde_json::Map::new(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Config {
+    pub api_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// The dev.to user the API key belongs to, saved by `--set-api-key`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "text::WordCountOptions::is_default")]
+    pub word_count: text::WordCountOptions,
+    /// Default per-article line for listings, see [`output::OutputTemplate`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_template: Option<String>,
+    /// Dictionary for `lint --spelling`, [`spelling::DEFAULT_LANGUAGE`] if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spelling_language: Option<String>,
+    /// Whether searches are recorded in [`search_history`]; unless `false`, they are.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_history: Option<bool>,
+    /// Editor command used instead of `$VISUAL`/`$EDITOR`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+    /// Content markers for `clone`, see [`clone::CloneConfig`].
+    #[serde(default, skip_serializing_if = "clone::CloneConfig::is_default")]
+    pub clone: clone::CloneConfig,
+    /// Front matter mapping for `sync --frontmatter custom`.
+    #[serde(default, skip_serializing_if = "frontmatter::FrontMatterMapping::is_default")]
+    pub frontmatter: frontmatter::FrontMatterMapping,
+    #[serde(default, skip_serializing_if = "client::HttpConfig::is_default")]
+    pub http: client::HttpConfig,
+    #[serde(default, skip_serializing_if = "crosspost::CrosspostConfig::is_empty")]
+    pub crosspost: crosspost::CrosspostConfig,
+    #[serde(default, skip_serializing_if = "hooks::HooksConfig::is_empty")]
+    pub hooks: hooks::HooksConfig,
+    /// Saved searches by name, see [`saved`].
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub saved: std::collections::BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "cache::CacheFormat::is_default")]
+    pub cache_format: cache::CacheFormat,
+    /// Age in seconds after which the cache is refetched, see [`cache::is_expired`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_ttl_secs: Option<u64>,
+}
+
+pub fn get_config_dir() -> Result<PathBuf> {
+    let home_dir = dirs::home_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
+    let config_dir = home_dir.join(".dtdrafts");
+    Ok(config_dir)
+}
+
+pub use config::get_config_file;
+
+/// The cache file, in the [`cache::CacheFormat`] it is stored in.
+pub fn get_cache_file() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join(cache::stored_format()?.unwrap_or_default().file_name()))
+}
+
+fn existing_cache_file() -> Result<Option<(PathBuf, cache::CacheFormat)>> {
+    match cache::stored_format()? {
+        Some(format) => Ok(Some((get_config_dir()?.join(format.file_name()), format))),
+        None => Ok(None),
+    }
+}
+
+pub fn has_articles_cache() -> Result<bool> {
+    Ok(existing_cache_file()?.is_some())
+}
+
+/// Converts an existing cache in the other format to `format`, which later
+/// updates then keep. Returns whether a cache was converted.
+pub fn use_cache_format(format: cache::CacheFormat) -> Result<bool> {
+    match existing_cache_file()? {
+        Some((_, existing)) if existing != format => {
+            let articles = load_articles_cache()?;
+            save_articles_cache(&articles, format)?;
+            tracing::info!(from = %existing, to = %format, "converted the article cache");
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+pub fn get_meta_file() -> Result<PathBuf> {
+    let mut meta_file = get_config_dir()?;
+    meta_file.push("articles_meta.json");
+    Ok(meta_file)
+}
+
+pub fn save_config(config: &Config) -> Result<()> {
+    config::update_file(|file| file.set(config))?;
+    Ok(())
+}
+
+pub fn load_config() -> Result<Config> {
+    load_config_with(&config::Overrides::default())
+}
+
+/// Resolves the config from the file, environment and `overrides`; see
+/// [`config`] for the layering.
+pub fn load_config_with(overrides: &config::Overrides) -> Result<Config> {
+    let config = config::load(overrides)?;
+    if config.api_key.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No API key found. Please set it first with: dtdrafts --set-api-key YOUR_API_KEY"
+        ));
+    }
+    Ok(config)
+}
+
+/// Writes the whole cache in `format`, removing a cache in the other one.
+pub fn save_articles_cache(articles: &[Article], format: cache::CacheFormat) -> Result<()> {
+    let config_dir = get_config_dir()?;
+    fs::create_dir_all(&config_dir)?;
+    let mut bodies = cache::CachedBodies::default();
+    let mut checksums = cache::CacheChecksums::default();
+    let metadata: Vec<Article> = articles
+        .iter()
+        .map(|article| {
+            let mut metadata = article.clone();
+            // Resolved while the body is at hand, as it may come from front matter
+            metadata.series = article.series();
+            let body = metadata.body_markdown.take();
+            checksums.insert(article.id, cache::checksum(&metadata, body.as_deref())?);
+            if let Some(body) = body {
+                bodies.insert(article.id, body);
+            }
+            Ok(metadata)
+        })
+        .collect::<Result<_>>()?;
+    // Bodies first: the metadata file is what watchers and the sidecar check
+    fs::write(config_dir.join(format.bodies_file_name()), format.encode(&bodies)?)?;
+    let cache_file = config_dir.join(format.file_name());
+    fs::write(&cache_file, format.encode_articles(&metadata)?)?;
+    tracing::debug!(path = %cache_file.display(), articles = articles.len(), %format, "saved article cache");
+    checksums.save()?;
+    for stale_file in [format.other().file_name(), format.other().bodies_file_name()] {
+        let stale_file = config_dir.join(stale_file);
+        if stale_file.exists() {
+            fs::remove_file(&stale_file)?;
+        }
+    }
+    // The metadata sidecar older versions wrote is superseded by the split cache
+    let legacy_meta_file = get_meta_file()?;
+    if legacy_meta_file.exists() {
+        fs::remove_file(&legacy_meta_file)?;
+    }
+    #[cfg(feature = "index")]
+    index::refresh_index_if_present(articles)?;
+    Ok(())
+}
+
+/// Replaces the cached copy of `article` (or appends it) after a remote update.
+pub fn upsert_cached_article(article: Article) -> Result<()> {
+    update_cached_articles(vec![article], &[])
+}
+
+/// Applies several remote updates and deletions to the cache in one write,
+/// keeping the format it is stored in.
+pub fn update_cached_articles(updated: Vec<Article>, deleted: &[u64]) -> Result<()> {
+    let format = cache::stored_format()?.unwrap_or_default();
+    let mut articles = load_articles_cache()?;
+    articles.retain(|cached| !deleted.contains(&cached.id));
+    for article in updated {
+        match articles.iter_mut().find(|cached| cached.id == article.id) {
+            Some(cached) => *cached = article,
+            None => articles.push(article),
+        }
+    }
+    save_articles_cache(&articles, format)
+}
+
+/// Published articles are cached apart from the drafts, which every other
+/// command works on, for `search --include-published`. Stored like the
+/// drafts cache, in a [`cache::CacheFormat`] and with its version.
+pub fn get_published_cache_file() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join(cache::stored_format()?.unwrap_or_default().published_file_name()))
+}
+
+pub fn save_published_cache(articles: &[Article], format: cache::CacheFormat) -> Result<()> {
+    let config_dir = get_config_dir()?;
+    fs::create_dir_all(&config_dir)?;
+    fs::write(config_dir.join(format.published_file_name()), format.encode_articles(articles)?)?;
+    let stale_file = config_dir.join(format.other().published_file_name());
+    if stale_file.exists() {
+        fs::remove_file(&stale_file)?;
+    }
+    Ok(())
+}
+
+/// The cached published articles, or `None` if they were never fetched.
+/// Caches of older versions, or in the other format, are read as well.
+pub fn load_published_cache() -> Result<Option<Vec<Article>>> {
+    let format = cache::stored_format()?.unwrap_or_default();
+    for format in [format, format.other()] {
+        let path = get_config_dir()?.join(format.published_file_name());
+        if path.exists() {
+            let (articles, _) = format
+                .decode_articles(&fs::read(&path)?)
+                .with_context(|| format!("Invalid published articles cache in {}", path.display()))?;
+            return Ok(Some(articles));
+        }
+    }
+    Ok(None)
+}
+
+/// The listing fields of every cached article, read without the bodies.
+pub fn load_articles_meta() -> Result<Vec<ArticleMeta>> {
+    Ok(load_articles_cache_without_bodies()?.iter().map(ArticleMeta::from).collect())
+}
+
+/// Loads the cached articles with their bodies.
+pub fn load_articles_cache() -> Result<Vec<Article>> {
+    let mut articles = load_articles_cache_without_bodies()?;
+    let mut bodies = cache::CachedBodies::load()?;
+    for article in &mut articles {
+        // Caches written before bodies were split out still carry them inline
+        if article.body_markdown.is_none() {
+            article.body_markdown = bodies.remove(article.id);
+        }
+    }
+    Ok(articles)
+}
+
+/// Loads the cached articles, leaving out the bodies; see [`Article::body`].
+pub fn load_articles_cache_without_bodies() -> Result<Vec<Article>> {
+    let Some((cache_file, format)) = existing_cache_file()? else {
+        return Ok(Vec::new());
+    };
+    let (articles, version) = format.decode_articles(&fs::read(&cache_file)?)?;
+    tracing::debug!(path = %cache_file.display(), articles = articles.len(), %format, version, "loaded article cache");
+    Ok(articles)
+}
+
+/// Rewrites a cache stored with an older [`migrate::CACHE_VERSION`] in the
+/// current one. Returns the version it was upgraded from, if it was.
+pub fn upgrade_articles_cache() -> Result<Option<u32>> {
+    let Some((cache_file, format)) = existing_cache_file()? else {
+        return Ok(None);
+    };
+    let version = format.stored_version(&cache_file)?;
+    if version >= migrate::CACHE_VERSION {
+        return Ok(None);
+    }
+    save_articles_cache(&load_articles_cache()?, format)?;
+    Ok(Some(version))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchEngine {
+    /// Case-insensitive substring scan over title, body and tags
+    #[default]
+    Linear,
+    /// Ranked, stemmed full-text search (requires the `index` feature)
+    Index,
+    /// The linear scan, reading the cache one article at a time (low memory)
+    Streamed,
+}
+
+impl std::str::FromStr for SearchEngine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "linear" => Ok(SearchEngine::Linear),
+            "index" => Ok(SearchEngine::Index),
+            "streamed" => Ok(SearchEngine::Streamed),
+            _ => Err(format!("unknown search engine '{s}' (expected linear, index or streamed)")),
+        }
+    }
+}
+
+pub fn search_articles<'a>(articles: &'a [Article], query: &str) -> Vec<&'a Article> {
+    search_articles_with(articles, query, &ArticleFilter::drafts())
+}
+
+pub fn search_articles_with<'a>(
+    articles: &'a [Article],
+    query: &str,
+    filter: &ArticleFilter,
+) -> Vec<&'a Article> {
+    let query_lower = query.to_lowercase();
+    let matches = |article: &&Article| filter.matches(article) && matches_query(article, &query_lower);
+    // Lowercasing every body dominates on large caches; rayon keeps the order
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        articles.par_iter().filter(matches).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    articles.iter().filter(matches).collect()
+}
+
+fn matches_query(article: &Article, query_lower: &str) -> bool {
+    article.title.to_lowercase().contains(query_lower) ||
+    article.body_markdown.as_ref().is_some_and(|body| {
+        body.to_lowercase().contains(query_lower)
+    }) ||
+    article.tags.as_ref().unwrap_or(&vec![]).iter().any(|tag| tag.to_lowercase().contains(query_lower))
+}
+
+/// [`search_articles_with`] straight from the cache files, decoding one
+/// article and one body at a time. Only the metadata of drafts passing
+/// `filter` is held in memory, so huge caches can be searched with little
+/// RAM. Articles in `also` (e.g. note matches) are included when they pass
+/// the filter; matches carry their bodies only `with_bodies`.
+pub fn search_cache_streamed(
+    query: &str,
+    filter: &ArticleFilter,
+    also: &[u64],
+    with_bodies: bool,
+) -> Result<Vec<Article>> {
+    let Some((cache_file, format)) = existing_cache_file()? else {
+        return Ok(Vec::new());
+    };
+    if format.stored_version(&cache_file)? != migrate::CACHE_VERSION {
+        // Records of other versions need migrating, which the full load does
+        let articles = load_articles_cache()?;
+        let query_lower = query.to_lowercase();
+        return Ok(articles
+            .into_iter()
+            .filter(|article| filter.matches(article) && (also.contains(&article.id) || matches_query(article, &query_lower)))
+            .map(|mut article| {
+                if !with_bodies {
+                    article.body_markdown = None;
+                }
+                article
+            })
+            .collect());
+    }
+    let query_lower = query.to_lowercase();
+    let mut candidates: Vec<(Article, bool)> = Vec::new();
+    format.for_each_element(&cache_file, |mut article: Article| {
+        if filter.matches(&article) {
+            // Caches from before the split carry bodies inline
+            let matched = also.contains(&article.id) || matches_query(&article, &query_lower);
+            if !with_bodies {
+                article.body_markdown = None;
+            }
+            candidates.push((article, matched));
+        }
+    })?;
+
+    let bodies_file = get_config_dir()?.join(format.bodies_file_name());
+    let needs_bodies = candidates.iter().any(|(article, matched)| !matched || (with_bodies && article.body_markdown.is_none()));
+    if needs_bodies && bodies_file.exists() {
+        let positions: std::collections::HashMap<u64, usize> =
+            candidates.iter().enumerate().map(|(i, (article, _))| (article.id, i)).collect();
+        format.for_each_entry(&bodies_file, |id: u64, body: String| {
+            let Some(&i) = positions.get(&id) else {
+                return;
+            };
+            let (article, matched) = &mut candidates[i];
+            if !*matched {
+                *matched = body.to_lowercase().contains(&query_lower);
+            }
+            if *matched && with_bodies && article.body_markdown.is_none() {
+                article.body_markdown = Some(body);
+            }
+        })?;
+    }
+    Ok(candidates.into_iter().filter(|(_, matched)| *matched).map(|(article, _)| article).collect())
+}
+
+pub fn get_draft_articles(articles: &[Article]) -> Vec<&Article> {
+    filter_articles(articles, &ArticleFilter::drafts())
+}
+
+#[cfg(feature = "terminal")]
+pub fn display_articles(articles: &[&Article]) {
+    print!("{}", format_articles(articles));
+}
+
+/// The listing [`display_articles`] prints, for callers that page or capture it.
+#[cfg(feature = "terminal")]
+pub fn format_articles(articles: &[&Article]) -> String {
+    use colored::*;
+    let mut out = String::new();
+    if articles.is_empty() {
+        out.push_str(&format!("{}\n", "No draft articles found.".yellow()));
+        return out;
+    }
+    out.push_str(&format!("{} draft article(s) found:\n\n", articles.len().to_string().green().bold()));
+    for (i, article) in articles.iter().enumerate() {
+        out.push_str(&format!("{}. {}\n", i + 1, article.title.cyan().bold()));
+        if let Some(organization) = &article.organization {
+            out.push_str(&format!("{}\n", format!("Organization: {}", organization.name).dimmed()));
+        }
+        if let Some(series) = article.series() {
+            out.push_str(&format!("{}\n", format!("Series: {series}").dimmed()));
+        }
+        out.push_str(&format!("{}\n", article.edit_url().blue().underline()));
+        out.push('\n');
+    }
+    out
+}
+
+/// Like [`format_articles`] for drafts mixed with published articles, which
+/// are labelled and link to their public page.
+#[cfg(feature = "terminal")]
+pub fn format_labelled_articles(articles: &[&Article]) -> String {
+    use colored::*;
+    let mut out = String::new();
+    if articles.is_empty() {
+        out.push_str(&format!("{}\n", "No articles found.".yellow()));
+        return out;
+    }
+    let live = articles.iter().filter(|article| article.published).count();
+    out.push_str(&format!(
+        "{} article(s) found ({} draft(s), {live} live):\n\n",
+        articles.len().to_string().green().bold(),
+        articles.len() - live
+    ));
+    for (i, article) in articles.iter().enumerate() {
+        let (label, url) = if article.published {
+            ("LIVE ".green().bold(), article.url.clone())
+        } else {
+            ("DRAFT".yellow().bold(), article.edit_url())
+        };
+        out.push_str(&format!("{}. {label} {}\n", i + 1, article.title.cyan().bold()));
+        if let Some(series) = article.series() {
+            out.push_str(&format!("{}\n", format!("Series: {series}").dimmed()));
+        }
+        out.push_str(&format!("{}\n", url.blue().underline()));
+        out.push('\n');
+    }
+    out
+}
\ No newline at end of file
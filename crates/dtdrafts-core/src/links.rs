@@ -0,0 +1,78 @@
+use std::sync::OnceLock;
+
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use regex::Regex;
+
+/// An http(s) URL found in a draft body, with the 1-based line it appears on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub line: usize,
+    pub url: String,
+    pub image: bool,
+}
+
+pub(crate) fn bare_url() -> &'static Regex {
+    static BARE_URL: OnceLock<Regex> = OnceLock::new();
+    BARE_URL.get_or_init(|| Regex::new(r#"https?://[^\s<>()\[\]{}"'`]+"#).expect("valid regex"))
+}
+
+fn img_src() -> &'static Regex {
+    static IMG_SRC: OnceLock<Regex> = OnceLock::new();
+    IMG_SRC.get_or_init(|| Regex::new(r#"(?i)<img\b[^>]*\bsrc\s*=\s*["']([^"']+)["']"#).expect("valid regex"))
+}
+
+pub fn is_http(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+/// Extracts links, images and bare URLs from markdown. URLs inside code
+/// blocks and inline code are skipped, as are relative links.
+pub fn extract_links(markdown: &str) -> Vec<Link> {
+    let line_of = |offset: usize| markdown[..offset].matches('\n').count() + 1;
+    let mut links: Vec<Link> = Vec::new();
+    let mut push = |line: usize, url: &str, image: bool| {
+        if is_http(url) && !links.iter().any(|link| link.line == line && link.url == url) {
+            links.push(Link { line, url: url.to_string(), image });
+        }
+    };
+
+    let mut in_code_block = false;
+    let mut in_link = false;
+    let parser = Parser::new_ext(markdown, Options::ENABLE_TABLES | Options::ENABLE_FOOTNOTES).into_offset_iter();
+    for (event, range) in parser {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                in_link = true;
+                push(line_of(range.start), &dest_url, false);
+            }
+            Event::End(TagEnd::Link) => in_link = false,
+            Event::Start(Tag::Image { dest_url, .. }) => push(line_of(range.start), &dest_url, true),
+            Event::Html(html) | Event::InlineHtml(html) if !in_code_block => {
+                for found in img_src().captures_iter(&html) {
+                    let start = found.get(0).map_or(0, |m| m.start());
+                    push(line_of(range.start) + html[..start].matches('\n').count(), &found[1], true);
+                }
+                for found in bare_url().find_iter(&html) {
+                    let url = found.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?']);
+                    push(line_of(range.start) + html[..found.start()].matches('\n').count(), url, false);
+                }
+            }
+            Event::Text(text) if !in_code_block && !in_link => {
+                for found in bare_url().find_iter(&text) {
+                    let url = found.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?']);
+                    push(line_of(range.start) + text[..found.start()].matches('\n').count(), url, false);
+                }
+            }
+            _ => {}
+        }
+    }
+    links.sort_by_key(|link| link.line);
+    links
+}
+
+/// Just the images: markdown `![]()` and HTML `<img src>`.
+pub fn extract_images(markdown: &str) -> Vec<Link> {
+    extract_links(markdown).into_iter().filter(|link| link.image).collect()
+}
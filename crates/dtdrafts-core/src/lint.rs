@@ -0,0 +1,629 @@
+use std::fmt;
+use std::sync::OnceLock;
+
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use regex::Regex;
+
+use crate::client::LinkCheck;
+use crate::frontmatter::parse_document;
+use crate::links::{bare_url, is_http, Link};
+use crate::spelling::{prose_words, SpellChecker, Word};
+use crate::text::{split_front_matter, Fence};
+use crate::Article;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// A single problem found in a draft. `line` is 1-based and counts from the
+/// top of the body, front matter included; `None` means the whole draft.
+/// `column` counts characters from 1, for checks that point at a word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub severity: Severity,
+    pub rule: &'static str,
+    pub message: String,
+}
+
+impl LintIssue {
+    fn new(line: Option<usize>, severity: Severity, rule: &'static str, message: impl Into<String>) -> Self {
+        Self { line, column: None, severity, rule, message: message.into() }
+    }
+
+    fn at(word: &Word, severity: Severity, rule: &'static str, message: impl Into<String>) -> Self {
+        Self { column: Some(word.column), ..Self::new(Some(word.line), severity, rule, message) }
+    }
+}
+
+/// What a liquid tag expects after its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LiquidArgs {
+    /// Nothing, e.g. `{% raw %}`
+    None,
+    /// An optional argument, e.g. `{% katex inline %}`
+    Optional,
+    /// Any non-empty argument, e.g. `{% details Click me %}`
+    Text,
+    /// A single token such as a video or tweet id
+    Id,
+    /// An http(s) URL
+    Url,
+}
+
+/// Liquid tags supported by dev.to's editor, with the shape of their arguments.
+const LIQUID_TAGS: &[(&str, LiquidArgs)] = &[
+    ("asciinema", LiquidArgs::Id),
+    ("blogcast", LiquidArgs::Id),
+    ("codepen", LiquidArgs::Url),
+    ("codesandbox", LiquidArgs::Id),
+    ("comment", LiquidArgs::Id),
+    ("devcomment", LiquidArgs::Id),
+    ("dotnetfiddle", LiquidArgs::Url),
+    ("embed", LiquidArgs::Url),
+    ("gist", LiquidArgs::Url),
+    ("github", LiquidArgs::Text),
+    ("glitch", LiquidArgs::Id),
+    ("instagram", LiquidArgs::Id),
+    ("jsfiddle", LiquidArgs::Url),
+    ("kotlin", LiquidArgs::Url),
+    ("link", LiquidArgs::Text),
+    ("listing", LiquidArgs::Text),
+    ("medium", LiquidArgs::Url),
+    ("next_tech", LiquidArgs::Url),
+    ("org", LiquidArgs::Id),
+    ("organization", LiquidArgs::Id),
+    ("podcast", LiquidArgs::Url),
+    ("post", LiquidArgs::Text),
+    ("reddit", LiquidArgs::Url),
+    ("replit", LiquidArgs::Id),
+    ("slideshare", LiquidArgs::Id),
+    ("soundcloud", LiquidArgs::Url),
+    ("speakerdeck", LiquidArgs::Id),
+    ("spotify", LiquidArgs::Id),
+    ("stackblitz", LiquidArgs::Text),
+    ("stackexchange", LiquidArgs::Text),
+    ("stackoverflow", LiquidArgs::Id),
+    ("tag", LiquidArgs::Id),
+    ("twitch", LiquidArgs::Id),
+    ("tweet", LiquidArgs::Id),
+    ("twitter", LiquidArgs::Id),
+    ("user", LiquidArgs::Id),
+    ("vimeo", LiquidArgs::Id),
+    ("wikipedia", LiquidArgs::Url),
+    ("youtube", LiquidArgs::Text),
+];
+
+/// Block tags that wrap content and need a matching `end<name>` tag.
+const LIQUID_BLOCK_TAGS: &[(&str, LiquidArgs)] = &[
+    ("collapsible", LiquidArgs::Text),
+    ("cta", LiquidArgs::Url),
+    ("details", LiquidArgs::Text),
+    ("katex", LiquidArgs::Optional),
+    ("raw", LiquidArgs::None),
+    ("runkit", LiquidArgs::None),
+    ("spoiler", LiquidArgs::Text),
+];
+
+/// Checks `{% ... %}` tags against the set dev.to understands: unknown names,
+/// missing or malformed arguments, unterminated tags and unbalanced blocks.
+/// Tags inside code fences and inline code are ignored.
+pub fn lint_liquid_tags(markdown: &str) -> Vec<LintIssue> {
+    let (front_matter, body) = split_front_matter(markdown);
+    // Front matter plus its two `---` lines
+    let offset = front_matter.map_or(0, |block| block.lines().count() + 2);
+
+    let mut issues = Vec::new();
+    let mut open_blocks: Vec<(&str, usize)> = Vec::new();
+    let mut fence: Option<Fence> = None;
+    for (index, line) in body.lines().enumerate() {
+        let line_no = offset + index + 1;
+        if let Some(open) = fence {
+            if open.is_closed_by(line) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(open) = Fence::parse(line) {
+            fence = Some(open);
+            continue;
+        }
+
+        let line = strip_inline_code(line);
+        let mut rest = line.as_str();
+        while let Some(start) = rest.find("{%") {
+            let Some(end) = rest[start..].find("%}") else {
+                issues.push(LintIssue::new(Some(line_no), Severity::Error, "liquid", "unterminated liquid tag (missing `%}`)"));
+                break;
+            };
+            let inner = rest[start + 2..start + end].trim().trim_matches('-').trim();
+            rest = &rest[start + end + 2..];
+            let (name, args) = inner.split_once(char::is_whitespace).unwrap_or((inner, ""));
+            let args = args.trim();
+            if name.is_empty() {
+                issues.push(LintIssue::new(Some(line_no), Severity::Error, "liquid", "empty liquid tag"));
+                continue;
+            }
+
+            if let Some(block) = name.strip_prefix("end") {
+                if LIQUID_BLOCK_TAGS.iter().any(|(tag, _)| *tag == block) {
+                    match open_blocks.iter().rposition(|(open, _)| *open == block) {
+                        Some(position) => {
+                            open_blocks.truncate(position);
+                        }
+                        None => issues.push(LintIssue::new(
+                            Some(line_no),
+                            Severity::Error,
+                            "liquid",
+                            format!("`{{% {name} %}}` without a matching `{{% {block} %}}`"),
+                        )),
+                    }
+                    continue;
+                }
+            }
+
+            let shape = if let Some((tag, shape)) = LIQUID_BLOCK_TAGS.iter().find(|(tag, _)| *tag == name) {
+                open_blocks.push((tag, line_no));
+                *shape
+            } else if let Some((_, shape)) = LIQUID_TAGS.iter().find(|(tag, _)| *tag == name) {
+                *shape
+            } else {
+                issues.push(LintIssue::new(
+                    Some(line_no),
+                    Severity::Warning,
+                    "liquid",
+                    format!("unknown liquid tag `{name}`"),
+                ));
+                continue;
+            };
+            if let Some(problem) = check_args(shape, args) {
+                issues.push(LintIssue::new(Some(line_no), Severity::Error, "liquid", format!("`{name}` {problem}")));
+            }
+        }
+    }
+    for (name, line_no) in open_blocks {
+        issues.push(LintIssue::new(
+            Some(line_no),
+            Severity::Error,
+            "liquid",
+            format!("`{{% {name} %}}` is never closed with `{{% end{name} %}}`"),
+        ));
+    }
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+fn check_args(shape: LiquidArgs, args: &str) -> Option<&'static str> {
+    match shape {
+        LiquidArgs::None if !args.is_empty() => Some("takes no arguments"),
+        LiquidArgs::None | LiquidArgs::Optional => None,
+        _ if args.is_empty() => Some("is missing its argument"),
+        LiquidArgs::Id if args.split_whitespace().count() > 1 => Some("expects a single id"),
+        LiquidArgs::Url => {
+            let url = args.split_whitespace().next().unwrap_or_default();
+            (!(url.starts_with("https://") || url.starts_with("http://"))).then_some("expects an http(s) URL")
+        }
+        _ => None,
+    }
+}
+
+fn strip_inline_code(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut in_code = false;
+    for c in line.chars() {
+        if c == '`' {
+            in_code = !in_code;
+        } else if !in_code {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Paragraphs longer than this are hard to read on a phone.
+pub const MAX_PARAGRAPH_WORDS: usize = 150;
+
+/// Checks the structure of the markdown for common dev.to pitfalls: an H1 in
+/// the body (dev.to shows `title` as the page's H1), skipped heading levels,
+/// bare URLs, very long paragraphs, images without alt text and unclosed code
+/// fences. Not part of [`lint_article`], so it only stops a publish when asked.
+pub fn lint_markdown(markdown: &str, title: &str) -> Vec<LintIssue> {
+    let (offset, body) = body_with_offset(markdown);
+    let line_of = |position: usize| offset + body[..position].matches('\n').count() + 1;
+    let warning = |position: usize, message: String| {
+        LintIssue::new(Some(line_of(position)), Severity::Warning, "markdown", message)
+    };
+
+    let liquid_spans: Vec<_> = liquid_tag().find_iter(body).map(|found| found.range()).collect();
+    let mut issues = Vec::new();
+    let mut previous_level = 1;
+    // The start of the element whose text is being collected, and the text
+    let mut heading: Option<(usize, u8, String)> = None;
+    let mut paragraph: Option<(usize, usize)> = None;
+    let mut image: Option<(usize, String)> = None;
+    let mut in_code = false;
+    let mut in_link = false;
+    let parser = Parser::new_ext(body, Options::ENABLE_TABLES | Options::ENABLE_FOOTNOTES).into_offset_iter();
+    for (event, range) in parser {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => heading = Some((range.start, level as u8, String::new())),
+            Event::End(TagEnd::Heading(_)) => {
+                let Some((start, level, text)) = heading.take() else { continue };
+                let text = text.trim();
+                if level == 1 && text.eq_ignore_ascii_case(title.trim()) {
+                    issues.push(warning(start, "H1 repeats the title, which dev.to already shows as the H1".to_string()));
+                } else if level == 1 {
+                    issues.push(warning(start, format!("H1 `{text}`: dev.to shows the title as the H1, use ## for sections")));
+                } else if level > previous_level + 1 {
+                    issues.push(warning(start, format!("heading jumps from h{previous_level} to h{level} (`{text}`)")));
+                }
+                previous_level = level;
+            }
+            Event::Start(Tag::Paragraph) => paragraph = Some((range.start, 0)),
+            Event::End(TagEnd::Paragraph) => {
+                if let Some((start, words)) = paragraph.take().filter(|(_, words)| *words > MAX_PARAGRAPH_WORDS) {
+                    issues.push(warning(start, format!("paragraph of {words} words (over {MAX_PARAGRAPH_WORDS}); consider splitting it")));
+                }
+            }
+            Event::Start(Tag::Image { .. }) => image = Some((range.start, String::new())),
+            Event::End(TagEnd::Image) => {
+                if let Some((start, _)) = image.take().filter(|(_, alt)| alt.trim().is_empty()) {
+                    issues.push(warning(start, "image without alt text".to_string()));
+                }
+            }
+            Event::Start(Tag::CodeBlock(_)) => in_code = true,
+            Event::End(TagEnd::CodeBlock) => in_code = false,
+            Event::Start(Tag::Link { .. }) => in_link = true,
+            Event::End(TagEnd::Link) => in_link = false,
+            Event::Html(html) | Event::InlineHtml(html) if !in_code => {
+                for tag in img_tag().find_iter(&html) {
+                    if !tag.as_str().to_lowercase().contains("alt=") {
+                        issues.push(warning(range.start, "image without alt text".to_string()));
+                    }
+                }
+            }
+            Event::Text(ref text) | Event::Code(ref text) if !in_code => {
+                let is_text = matches!(event, Event::Text(_));
+                if let Some((_, _, heading)) = &mut heading {
+                    heading.push_str(text);
+                }
+                if let Some((_, alt)) = &mut image {
+                    alt.push_str(text);
+                } else if let Some((_, words)) = &mut paragraph {
+                    *words += text.split_whitespace().count();
+                }
+                if is_text && !in_link && image.is_none() {
+                    for found in bare_url().find_iter(text) {
+                        // `{% embed https://... %}` and friends take bare URLs
+                        let position = range.start + found.start();
+                        if liquid_spans.iter().any(|span| span.contains(&position)) {
+                            continue;
+                        }
+                        let url = found.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?']);
+                        issues.push(warning(range.start, format!("bare URL {url}; write it as [text]({url})")));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    issues.extend(fenced_blocks(markdown).into_iter().filter(|block| !block.closed).map(|block| {
+        LintIssue::new(Some(block.line), Severity::Error, "markdown", "code fence is never closed")
+    }));
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+fn liquid_tag() -> &'static Regex {
+    static LIQUID_TAG: OnceLock<Regex> = OnceLock::new();
+    LIQUID_TAG.get_or_init(|| Regex::new(r"\{%.*?%\}").expect("valid regex"))
+}
+
+fn img_tag() -> &'static Regex {
+    static IMG_TAG: OnceLock<Regex> = OnceLock::new();
+    IMG_TAG.get_or_init(|| Regex::new(r"(?i)<img\b[^>]*>").expect("valid regex"))
+}
+
+// Line numbers count the front matter, which the checks skip
+fn body_with_offset(markdown: &str) -> (usize, &str) {
+    let (front_matter, body) = split_front_matter(markdown);
+    (front_matter.map_or(0, |block| block.lines().count() + 2), body)
+}
+
+// A fenced code block: its opening line (1-based, in the whole document), the
+// info string after the fence and the code. A fence still open at the end of
+// the document isn't `closed`.
+struct FencedBlock {
+    line: usize,
+    info: String,
+    code: String,
+    closed: bool,
+}
+
+fn fenced_blocks(markdown: &str) -> Vec<FencedBlock> {
+    let (offset, body) = body_with_offset(markdown);
+    let mut blocks = Vec::new();
+    let mut open: Option<(Fence, FencedBlock)> = None;
+    for (index, line) in body.lines().enumerate() {
+        match open.take() {
+            Some((fence, mut block)) if fence.is_closed_by(line) => {
+                block.closed = true;
+                blocks.push(block);
+            }
+            Some((fence, mut block)) => {
+                block.code.push_str(line);
+                block.code.push('\n');
+                open = Some((fence, block));
+            }
+            None => {
+                let Some(fence) = Fence::parse(line) else { continue };
+                let info = line.trim().trim_start_matches(['`', '~']).trim().to_string();
+                open = Some((fence, FencedBlock { line: offset + index + 1, info, code: String::new(), closed: false }));
+            }
+        }
+    }
+    blocks.extend(open.map(|(_, block)| block));
+    blocks
+}
+
+/// Language names dev.to's highlighter (Rouge) knows, lexer tags and aliases.
+pub const HIGHLIGHTED_LANGUAGES: &[&str] = &[
+    "abap", "actionscript", "ada", "apache", "apex", "applescript", "armasm", "awk", "bash", "bat", "batchfile",
+    "bibtex", "bpf", "brainfuck", "c", "c++", "ceylon", "cfscript", "clojure", "cmake", "coffee", "coffeescript",
+    "common_lisp", "conf", "console", "coq", "cpp", "crystal", "cs", "csharp", "css", "cuda", "cypher", "cython",
+    "d", "dart", "diff", "docker", "dockerfile", "dot", "elisp", "elixir", "elm", "emacs-lisp", "erb", "erl",
+    "erlang", "ex", "exs", "fortran", "fsharp", "gdscript", "gherkin", "glsl", "go", "golang", "gradle", "graphql",
+    "groovy", "hack", "haml", "handlebars", "haskell", "haxe", "hcl", "hlsl", "hs", "html", "http", "ini", "irb",
+    "java", "javascript", "jinja", "js", "json", "jsonc", "jsonnet", "jsp", "jsx", "julia", "kotlin", "kt",
+    "latex", "liquid", "lisp", "llvm", "lua", "make", "makefile", "markdown", "matlab", "md", "meson", "nasm",
+    "nginx", "nim", "nix", "objc", "objective_c", "objective_cpp", "ocaml", "pascal", "patch", "perl", "php",
+    "plaintext", "plist", "powershell", "prolog", "prometheus", "properties", "proto", "protobuf", "ps1", "puppet",
+    "py", "python", "qml", "r", "racket", "rb", "reasonml", "rego", "rescript", "rs", "ruby", "rust", "sas", "sass",
+    "scala", "scheme", "scss", "sed", "sh", "shell", "shell-session", "smalltalk", "sml", "solidity", "sparql",
+    "sql", "ssh", "swift", "systemd", "tcl", "terminal", "terraform", "tex", "text", "tf", "toml", "ts", "tsx",
+    "twig", "txt", "typescript", "vala", "vb", "verilog", "vhdl", "vim", "viml", "vue", "xml", "yaml", "yml", "zig",
+    "zsh",
+];
+
+/// Checks fenced code blocks: a language dev.to can highlight is given and,
+/// for Rust, the snippet parses as a file, a list of statements or an
+/// expression.
+pub fn lint_code_blocks(markdown: &str) -> Vec<LintIssue> {
+    fenced_blocks(markdown)
+        .into_iter()
+        .filter(|block| block.closed)
+        .filter_map(|block| {
+            let message = check_code_block(&block.info, &block.code)?;
+            Some(LintIssue::new(Some(block.line), Severity::Warning, "code", message))
+        })
+        .collect()
+}
+
+fn check_code_block(info: &str, code: &str) -> Option<String> {
+    // `rust,ignore` and `js {3-5}` style info strings
+    let mut words = info.split(|c: char| c == ',' || c.is_whitespace()).filter(|word| !word.is_empty());
+    let Some(language) = words.next() else {
+        return Some("code block has no language, so dev.to won't highlight it".to_string());
+    };
+    let language = language.to_lowercase();
+    if !HIGHLIGHTED_LANGUAGES.contains(&language.as_str()) {
+        return Some(format!("dev.to doesn't highlight `{language}`"));
+    }
+    let skipped = words.any(|word| matches!(word, "ignore" | "compile_fail" | "no_run" | "text"));
+    if matches!(language.as_str(), "rust" | "rs") && !skipped {
+        return rust_parse_error(code).map(|e| format!("rust snippet doesn't parse: {e}"));
+    }
+    None
+}
+
+// Snippets are often just a few statements or an expression
+fn rust_parse_error(code: &str) -> Option<syn::Error> {
+    let error = syn::parse_file(code).err()?;
+    let parses = syn::parse_str::<syn::Block>(&format!("{{\n{code}\n}}")).is_ok() || syn::parse_str::<syn::Expr>(code).is_ok();
+    (!parses).then_some(error)
+}
+
+/// Runs every offline check on a draft: its metadata and its body.
+pub fn lint_article(article: &Article) -> Vec<LintIssue> {
+    let mut issues = validate_metadata(&PublishMetadata::of(article));
+    issues.extend(lint_liquid_tags(article.body_markdown.as_deref().unwrap_or_default()));
+    issues
+}
+
+/// Flags the words `checker` doesn't know, with a few suggestions each.
+pub fn spelling_issues(checker: &SpellChecker, markdown: &str) -> Vec<LintIssue> {
+    checker
+        .misspellings(markdown)
+        .iter()
+        .map(|word| {
+            let suggestions = checker.suggest(&word.text, 3);
+            let message = if suggestions.is_empty() {
+                format!("unknown word `{}`", word.text)
+            } else {
+                format!("unknown word `{}` (did you mean {}?)", word.text, suggestions.join(", "))
+            };
+            LintIssue::at(word, Severity::Warning, "spelling", message)
+        })
+        .collect()
+}
+
+/// Words that rarely add meaning, after write-good and Vale's default style.
+pub const WEASEL_WORDS: &[&str] = &[
+    "actually", "basically", "clearly", "easily", "extremely", "fairly", "just", "obviously", "quite", "really",
+    "simply", "very",
+];
+
+/// Style checks in the spirit of Vale's default rules: a word written twice
+/// in a row, and weasel words.
+pub fn lint_prose(markdown: &str) -> Vec<LintIssue> {
+    let words = prose_words(markdown);
+    let mut issues = Vec::new();
+    for (index, word) in words.iter().enumerate() {
+        let lower = word.text.to_lowercase();
+        let previous = index.checked_sub(1).map(|previous| &words[previous]);
+        // Only when nothing but spaces separates them, so "it. It" passes
+        let repeated = previous.is_some_and(|previous| {
+            previous.line == word.line
+                && previous.text.to_lowercase() == lower
+                && markdown_gap(markdown, previous, word).is_some_and(|gap| gap.trim().is_empty())
+        });
+        if repeated {
+            issues.push(LintIssue::at(word, Severity::Warning, "prose", format!("`{}` is repeated", word.text)));
+        } else if WEASEL_WORDS.contains(&lower.as_str()) {
+            let message = format!("`{}` adds little; consider cutting it", word.text);
+            issues.push(LintIssue::at(word, Severity::Warning, "prose", message));
+        }
+    }
+    issues
+}
+
+// The text between two words on the same line
+fn markdown_gap<'a>(markdown: &'a str, before: &Word, after: &Word) -> Option<&'a str> {
+    let line = markdown.lines().nth(before.line - 1)?;
+    let start = line.char_indices().nth(before.column - 1 + before.text.chars().count())?.0;
+    let end = line.char_indices().nth(after.column - 1)?.0;
+    line.get(start..end)
+}
+
+// Limits enforced by Forem when an article is saved
+pub const MAX_TITLE_CHARS: usize = 128;
+pub const MAX_TAGS: usize = 4;
+pub const MAX_TAG_CHARS: usize = 30;
+
+/// The metadata dev.to will publish a draft with. Front matter in the body
+/// takes precedence over the article fields, as it does on dev.to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishMetadata {
+    pub title: String,
+    pub tags: Vec<String>,
+    pub cover_image: Option<String>,
+    pub canonical_url: Option<String>,
+}
+
+impl PublishMetadata {
+    pub fn of(article: &Article) -> Self {
+        let (front_matter, _) = parse_document(article.body_markdown.as_deref().unwrap_or_default());
+        let front_matter = front_matter.unwrap_or_default();
+        Self {
+            title: front_matter
+                .get("title")
+                .map_or_else(|| article.title.clone(), str::to_string),
+            tags: front_matter
+                .tags()
+                .unwrap_or_else(|| article.tags.clone().unwrap_or_default()),
+            cover_image: front_matter
+                .get("cover_image")
+                .filter(|url| !url.is_empty())
+                .map(str::to_string),
+            canonical_url: match front_matter.get("canonical_url") {
+                Some(url) => Some(url.to_string()),
+                None => article.canonical_url.clone(),
+            }
+            .filter(|url| !url.is_empty()),
+        }
+    }
+}
+
+/// Checks title length and tag count and format against dev.to's rules.
+pub fn validate_metadata(metadata: &PublishMetadata) -> Vec<LintIssue> {
+    let error = |message: String| LintIssue::new(None, Severity::Error, "metadata", message);
+    let mut issues = Vec::new();
+    let title_chars = metadata.title.trim().chars().count();
+    if title_chars == 0 {
+        issues.push(error("title is empty".to_string()));
+    } else if title_chars > MAX_TITLE_CHARS {
+        issues.push(error(format!("title is {title_chars} characters long (max {MAX_TITLE_CHARS})")));
+    }
+    if metadata.tags.len() > MAX_TAGS {
+        issues.push(error(format!("{} tags given (max {MAX_TAGS})", metadata.tags.len())));
+    }
+    for tag in &metadata.tags {
+        if !tag.chars().all(char::is_alphanumeric) {
+            issues.push(error(format!("tag `{tag}` may only contain letters and digits")));
+        } else if tag.chars().count() > MAX_TAG_CHARS {
+            issues.push(error(format!("tag `{tag}` is longer than {MAX_TAG_CHARS} characters")));
+        }
+    }
+    if metadata.cover_image.as_deref().is_some_and(|url| !is_http(url)) {
+        issues.push(error("cover_image is not an http(s) URL".to_string()));
+    }
+    if metadata.canonical_url.as_deref().is_some_and(|url| !is_http(url)) {
+        issues.push(error("canonical_url is not an http(s) URL".to_string()));
+    }
+    issues
+}
+
+/// For a draft that is a copy of a post published elsewhere: without a
+/// canonical_url search engines may rank the copy above the original.
+pub fn missing_canonical_issue(metadata: &PublishMetadata) -> Option<LintIssue> {
+    metadata.canonical_url.is_none().then(|| {
+        let message = "no canonical_url; set it to the original post so search engines credit it".to_string();
+        LintIssue::new(None, Severity::Warning, "canonical", message)
+    })
+}
+
+/// Turns the result of requesting the canonical URL into an issue.
+pub fn canonical_url_issue(url: &str, check: &LinkCheck) -> Option<LintIssue> {
+    let message = match check {
+        LinkCheck::Ok | LinkCheck::Redirect { .. } => return None,
+        LinkCheck::Broken(status) => format!("canonical_url {url} does not resolve ({status})"),
+        LinkCheck::Failed(reason) => format!("canonical_url {url} could not be fetched: {reason}"),
+    };
+    Some(LintIssue::new(None, Severity::Error, "canonical", message))
+}
+
+/// Warns about tags outside dev.to's most popular tags: few readers follow
+/// them, so they bring little traffic. `popular` holds lowercase names.
+pub fn tag_usage_issues(tags: &[String], popular: &[String]) -> Vec<LintIssue> {
+    tags.iter()
+        .filter(|tag| !popular.contains(&tag.to_lowercase()))
+        .map(|tag| {
+            let message = format!(
+                "tag `{tag}` is not among dev.to's {} most popular tags; few readers follow it",
+                popular.len()
+            );
+            LintIssue::new(None, Severity::Warning, "tags", message)
+        })
+        .collect()
+}
+
+/// Turns the result of requesting the cover image into an issue.
+pub fn cover_image_issue(url: &str, check: &LinkCheck) -> Option<LintIssue> {
+    let message = match check {
+        LinkCheck::Ok | LinkCheck::Redirect { .. } => return None,
+        LinkCheck::Broken(status) => format!("cover_image {url} does not resolve ({status})"),
+        LinkCheck::Failed(reason) => format!("cover_image {url} could not be fetched: {reason}"),
+    };
+    Some(LintIssue::new(None, Severity::Error, "metadata", message))
+}
+
+/// Turns a link check into an issue: broken links and failed requests are
+/// errors, redirects are warnings.
+pub fn link_issue(link: &Link, check: &LinkCheck) -> Option<LintIssue> {
+    let kind = if link.image { "image" } else { "link" };
+    let (severity, message) = match check {
+        LinkCheck::Ok => return None,
+        LinkCheck::Redirect { status, location } => (
+            Severity::Warning,
+            match location {
+                Some(location) => format!("{kind} {} redirects ({status}) to {location}", link.url),
+                None => format!("{kind} {} redirects ({status})", link.url),
+            },
+        ),
+        LinkCheck::Broken(status) => (Severity::Error, format!("broken {kind} {} ({status})", link.url)),
+        LinkCheck::Failed(reason) => (Severity::Error, format!("{kind} {} could not be checked: {reason}", link.url)),
+    };
+    Some(LintIssue::new(Some(link.line), severity, "links", message))
+}
@@ -0,0 +1,79 @@
+//! Merging drafts into one (`merge`), the inverse of `split`: each draft
+//! becomes a section headed by its title.
+
+use crate::client::ArticleInput;
+use crate::lint::MAX_TAGS;
+use crate::text::{split_front_matter, Fence};
+use crate::Article;
+
+/// What a merge would create, and the tags that didn't fit.
+#[derive(Debug, Clone)]
+pub struct MergePlan {
+    pub input: ArticleInput,
+    /// Tags beyond dev.to's limit of [`MAX_TAGS`], in the order they came
+    pub dropped_tags: Vec<String>,
+}
+
+/// Concatenates the bodies of `articles`, in order, each under a `## title`
+/// heading with its own headings moved a level down so they stay beneath
+/// it. Front matter is left out. Tags are combined in the order they first
+/// appear, and the series is kept if every draft is in the same one.
+pub fn plan_merge(articles: &[&Article], title: &str) -> MergePlan {
+    let mut body = String::new();
+    for article in articles {
+        let (_, content) = split_front_matter(article.body_markdown.as_deref().unwrap_or_default());
+        body.push_str(&format!("## {}\n\n", article.title));
+        let content = demote_headings(content.trim());
+        if !content.is_empty() {
+            body.push_str(&content);
+            body.push_str("\n\n");
+        }
+    }
+
+    let mut tags: Vec<String> = Vec::new();
+    for tag in articles.iter().flat_map(|article| article.tags.iter().flatten()) {
+        if !tags.iter().any(|seen| seen.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.clone());
+        }
+    }
+    let dropped_tags = tags.split_off(tags.len().min(MAX_TAGS));
+
+    let series = articles.first().and_then(|article| article.series());
+    let series = series.filter(|series| articles.iter().all(|article| article.series().as_ref() == Some(series)));
+
+    MergePlan {
+        input: ArticleInput {
+            title: Some(title.to_string()),
+            body_markdown: Some(format!("{}\n", body.trim_end())),
+            published: Some(false),
+            tags: (!tags.is_empty()).then_some(tags),
+            series,
+            ..Default::default()
+        },
+        dropped_tags,
+    }
+}
+
+// Adds a `#` to ATX headings outside code blocks, up to level 6
+fn demote_headings(markdown: &str) -> String {
+    let mut out = Vec::new();
+    let mut fence: Option<Fence> = None;
+    for line in markdown.lines() {
+        if let Some(open) = fence {
+            if open.is_closed_by(line) {
+                fence = None;
+            }
+            out.push(line.to_string());
+            continue;
+        }
+        if let Some(open) = Fence::parse(line) {
+            fence = Some(open);
+            out.push(line.to_string());
+            continue;
+        }
+        let level = line.chars().take_while(|c| *c == '#').count();
+        let is_heading = (1..6).contains(&level) && line[level..].chars().next().is_none_or(|c| c == ' ');
+        out.push(if is_heading { format!("#{line}") } else { line.to_string() });
+    }
+    out.join("\n")
+}
@@ -0,0 +1,53 @@
+//! Upgrades of cached article records between cache versions.
+//!
+//! The metadata cache is stored as `{"version": N, "articles": [...]}`;
+//! caches from before versioning are a bare list and count as version 1.
+//! When the `Article` model or the cache layout changes in a way old records
+//! can't be read as, bump [`CACHE_VERSION`] and append a step to
+//! [`MIGRATIONS`] that rewrites a record from the previous version. Old
+//! caches are then upgraded in place on the next run instead of failing to
+//! parse or being re-fetched.
+
+use anyhow::Result;
+use serde_json::{Map, Value};
+
+use crate::timestamp;
+
+/// The version of the cache layout this build writes.
+pub const CACHE_VERSION: u32 = 2;
+
+type Migration = fn(&mut Map<String, Value>);
+
+/// `MIGRATIONS[i]` upgrades a record from version `i + 1` to `i + 2`.
+const MIGRATIONS: [Migration; (CACHE_VERSION - 1) as usize] = [timestamps_to_utc];
+
+/// Brings `records` written at cache `version` up to [`CACHE_VERSION`].
+pub fn upgrade(mut records: Vec<Value>, version: u32) -> Result<Vec<Value>> {
+    if version > CACHE_VERSION {
+        return Err(anyhow::anyhow!(
+            "The article cache has version {version}, newer than this dtdrafts supports ({CACHE_VERSION}). \
+             Upgrade dtdrafts or re-fetch the cache with: dtdrafts --refresh"
+        ));
+    }
+    let steps = MIGRATIONS.iter().enumerate().skip(version.saturating_sub(1) as usize);
+    for (step, migration) in steps {
+        tracing::debug!(from = step + 1, to = step + 2, records = records.len(), "migrating cached articles");
+        for record in &mut records {
+            if let Value::Object(record) = record {
+                migration(record);
+            }
+        }
+    }
+    Ok(records)
+}
+
+// Version 1 kept timestamps as dev.to sent them; version 2 stores RFC 3339 in
+// UTC, and drops values that aren't timestamps at all
+fn timestamps_to_utc(record: &mut Map<String, Value>) {
+    for field in ["created_at", "updated_at"] {
+        if let Some(Value::String(value)) = record.get(field) {
+            let normalized = timestamp::parse(value).map_or(Value::Null, |at| Value::String(timestamp::format(at)));
+            record.insert(field.to_string(), normalized);
+        }
+    }
+}
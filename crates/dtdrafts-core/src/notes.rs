@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::get_config_dir;
+
+/// An editorial to-do attached to a draft, kept out of the article body.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Note {
+    pub text: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Notes by article id, oldest first, kept in `~/.dtdrafts/notes.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct NoteStore {
+    #[serde(default)]
+    pub notes: BTreeMap<u64, Vec<Note>>,
+}
+
+pub fn get_notes_file() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("notes.json"))
+}
+
+impl NoteStore {
+    pub fn load() -> Result<Self> {
+        let path = get_notes_file()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid notes in {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(get_config_dir()?)?;
+        fs::write(get_notes_file()?, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, id: u64) -> &[Note] {
+        self.notes.get(&id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn add(&mut self, id: u64, text: impl Into<String>, at: DateTime<Utc>) {
+        self.notes.entry(id).or_default().push(Note { text: text.into(), created_at: at });
+    }
+
+    /// Removes the `number`th note (1-based, as listed) of an article.
+    pub fn remove(&mut self, id: u64, number: usize) -> Option<Note> {
+        let notes = self.notes.get_mut(&id)?;
+        let index = number.checked_sub(1).filter(|index| *index < notes.len())?;
+        let note = notes.remove(index);
+        if notes.is_empty() {
+            self.notes.remove(&id);
+        }
+        Some(note)
+    }
+
+    pub fn clear(&mut self, id: u64) -> Vec<Note> {
+        self.notes.remove(&id).unwrap_or_default()
+    }
+
+    /// Ids of articles with a note containing `query` (case-insensitive).
+    pub fn matching_ids(&self, query: &str) -> Vec<u64> {
+        let query = query.to_lowercase();
+        self.notes
+            .iter()
+            .filter(|(_, notes)| notes.iter().any(|note| note.text.to_lowercase().contains(&query)))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+}
@@ -0,0 +1,364 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::stats::reading_time_minutes;
+use crate::text::{word_count_with, WordCountOptions};
+use crate::{stale, timestamp, Article};
+
+/// Placeholders understood by [`OutputTemplate`].
+pub const FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "slug",
+    "url",
+    "edit_url",
+    "published",
+    "tags",
+    "series",
+    "organization",
+    "description",
+    "canonical_url",
+    "username",
+    "created_at",
+    "updated_at",
+    "age_days",
+    "word_count",
+    "reading_time",
+    "reading_time_minutes",
+    "cover_image",
+    "social_image",
+    "comments_count",
+    "public_reactions_count",
+];
+
+/// How listings are printed: the usual human-readable list, the JSON
+/// script-filter schema Alfred and Raycast launchers read, or, for scripts,
+/// just the number of matches or their ids.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListFormat {
+    #[default]
+    Text,
+    Alfred,
+    Count,
+    Ids,
+    Table,
+}
+
+impl ListFormat {
+    /// The listing for the formats that don't go through a template.
+    pub fn render(self, articles: &[&Article]) -> Option<String> {
+        match self {
+            ListFormat::Text | ListFormat::Table => None,
+            ListFormat::Alfred => Some(serde_json::to_string(&alfred_items(articles)).unwrap_or_default() + "\n"),
+            ListFormat::Count => Some(format!("{}\n", articles.len())),
+            ListFormat::Ids => Some(articles.iter().map(|article| format!("{}\n", article.id)).collect()),
+        }
+    }
+}
+
+impl FromStr for ListFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(ListFormat::Text),
+            "alfred" => Ok(ListFormat::Alfred),
+            "count" => Ok(ListFormat::Count),
+            "ids" => Ok(ListFormat::Ids),
+            "table" => Ok(ListFormat::Table),
+            _ => Err(format!("unknown format '{s}' (expected text, table, alfred, count or ids)")),
+        }
+    }
+}
+
+impl fmt::Display for ListFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ListFormat::Text => "text",
+            ListFormat::Alfred => "alfred",
+            ListFormat::Count => "count",
+            ListFormat::Ids => "ids",
+            ListFormat::Table => "table",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AlfredItems {
+    pub items: Vec<AlfredItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AlfredItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
+    pub title: String,
+    pub subtitle: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arg: Option<String>,
+    pub valid: bool,
+}
+
+/// Script-filter items whose `arg` is the edit URL, so the launcher's default
+/// action opens the draft in the dev.to editor. An empty result is a single
+/// non-actionable item, which launchers show instead of their fallbacks.
+pub fn alfred_items(articles: &[&Article]) -> AlfredItems {
+    if articles.is_empty() {
+        let item = AlfredItem {
+            uid: None,
+            title: "No matching drafts".to_string(),
+            subtitle: "Try another query, or refresh with dtdrafts --refresh".to_string(),
+            arg: None,
+            valid: false,
+        };
+        return AlfredItems { items: vec![item] };
+    }
+    let items = articles
+        .iter()
+        .map(|article| AlfredItem {
+            uid: Some(article.id.to_string()),
+            title: article.title.clone(),
+            subtitle: alfred_subtitle(article),
+            arg: Some(article.edit_url()),
+            valid: true,
+        })
+        .collect();
+    AlfredItems { items }
+}
+
+fn alfred_subtitle(article: &Article) -> String {
+    let mut parts = Vec::new();
+    if let Some(tags) = article.tags.as_deref().filter(|tags| !tags.is_empty()) {
+        parts.push(tags.iter().map(|tag| format!("#{tag}")).collect::<Vec<_>>().join(" "));
+    }
+    if let Some(series) = article.series() {
+        parts.push(format!("Series: {series}"));
+    }
+    if let Some(updated) = article.updated_at.or(article.created_at) {
+        parts.push(format!("Updated {}", updated.format("%Y-%m-%d")));
+    }
+    parts.join(" · ")
+}
+
+/// A per-article output line such as `{id}\t{title}\t{edit_url}`.
+///
+/// `{field}` is replaced with the article's value (empty when missing),
+/// `{{` and `}}` are literal braces, and `\t`, `\n` and `\\` are unescaped so
+/// templates can be written in single quotes on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputTemplate {
+    source: String,
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Field(String),
+}
+
+impl OutputTemplate {
+    /// Word counts need article bodies, which listings otherwise skip.
+    pub fn needs_body(&self) -> bool {
+        self.fields().any(|field| field == "word_count" || field == "reading_time")
+    }
+
+    fn fields(&self) -> impl Iterator<Item = &str> {
+        self.parts.iter().filter_map(|part| match part {
+            Part::Field(name) => Some(name.as_str()),
+            Part::Literal(_) => None,
+        })
+    }
+
+    pub fn render(&self, article: &Article, options: &WordCountOptions, now: DateTime<Utc>) -> String {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => out.push_str(text),
+                Part::Field(name) => out.push_str(&field_value(article, name, options, now)),
+            }
+        }
+        out
+    }
+}
+
+/// Tags wider than this are cut in [`format_table`].
+#[cfg(feature = "terminal")]
+const MAX_TAGS_WIDTH: usize = 30;
+/// Titles are never cut shorter than this, even if the table gets wider than
+/// `width`.
+#[cfg(feature = "terminal")]
+const MIN_TITLE_WIDTH: usize = 20;
+
+/// One aligned row per article (number, id, title, tags, last update, words)
+/// under a header, with the title cut to fit `width` columns.
+#[cfg(feature = "terminal")]
+pub fn format_table(articles: &[&Article], options: &WordCountOptions, width: usize) -> String {
+    use colored::*;
+    if articles.is_empty() {
+        return format!("{}\n", "No draft articles found.".yellow());
+    }
+    let rows: Vec<[String; 6]> = articles
+        .iter()
+        .enumerate()
+        .map(|(index, article)| {
+            let tags = article.tags.as_deref().unwrap_or_default();
+            let updated = article.updated_at.or(article.created_at);
+            let words = article.body_markdown.as_deref().map(|body| word_count_with(body, options)).unwrap_or(0);
+            [
+                (index + 1).to_string(),
+                article.id.to_string(),
+                article.title.clone(),
+                truncate(&tags.iter().map(|tag| format!("#{tag}")).collect::<Vec<_>>().join(" "), MAX_TAGS_WIDTH),
+                updated.map(|at| at.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "-".to_string()),
+                words.to_string(),
+            ]
+        })
+        .collect();
+    let header = ["#", "ID", "TITLE", "TAGS", "UPDATED", "WORDS"];
+    let mut widths = header.map(|name| name.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let others: usize = widths.iter().enumerate().filter(|(column, _)| *column != 2).map(|(_, width)| width).sum();
+    let available = width.saturating_sub(others + 2 * (widths.len() - 1));
+    widths[2] = widths[2].min(available.max(MIN_TITLE_WIDTH));
+
+    let line = |cells: [String; 6]| {
+        let [number, id, title, tags, updated, words] = cells;
+        let title = truncate(&title, widths[2]);
+        (
+            format!("{number:>0$}", widths[0]),
+            format!("{id:>0$}", widths[1]),
+            format!("{title:<0$}", widths[2]),
+            format!("{tags:<0$}", widths[3]),
+            format!("{updated:<0$}", widths[4]),
+            format!("{words:>0$}", widths[5]),
+        )
+    };
+    let (number, id, title, tags, updated, words) = line(header.map(str::to_string));
+    let mut out = format!("{}\n", format!("{number}  {id}  {title}  {tags}  {updated}  {words}").bold());
+    for row in rows {
+        let (number, id, title, tags, updated, words) = line(row);
+        out.push_str(&format!(
+            "{}  {}  {}  {}  {updated}  {words}\n",
+            number.dimmed(),
+            id.dimmed(),
+            title.cyan(),
+            tags.blue()
+        ));
+    }
+    out
+}
+
+// Cuts `text` to `width` characters, ending it with an ellipsis when cut.
+#[cfg(feature = "terminal")]
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+fn field_value(article: &Article, name: &str, options: &WordCountOptions, now: DateTime<Utc>) -> String {
+    let words = || article.body_markdown.as_deref().map(|body| word_count_with(body, options)).unwrap_or(0);
+    match name {
+        "id" => article.id.to_string(),
+        "title" => article.title.clone(),
+        "slug" => article.slug.clone(),
+        "url" => article.url.clone(),
+        "edit_url" => article.edit_url(),
+        "published" => article.published.to_string(),
+        "tags" => article.tags.as_deref().unwrap_or_default().join(", "),
+        "series" => article.series().unwrap_or_default(),
+        "organization" => article
+            .organization
+            .as_ref()
+            .map(|organization| organization.username.clone())
+            .unwrap_or_default(),
+        "description" => article.description.clone().unwrap_or_default(),
+        "canonical_url" => article.canonical_url.clone().unwrap_or_default(),
+        "username" => article.user.username.clone(),
+        "created_at" => article.created_at.map(timestamp::format).unwrap_or_default(),
+        "updated_at" => article.updated_at.map(timestamp::format).unwrap_or_default(),
+        "age_days" => stale::age_days(article, now).map(|days| days.to_string()).unwrap_or_default(),
+        "word_count" => words().to_string(),
+        "reading_time" => reading_time_minutes(words()).to_string(),
+        "reading_time_minutes" => article.reading_time_minutes.map(|minutes| minutes.to_string()).unwrap_or_default(),
+        "cover_image" => article.cover_image.clone().unwrap_or_default(),
+        "social_image" => article.social_image.clone().unwrap_or_default(),
+        "comments_count" => article.comments_count.to_string(),
+        "public_reactions_count" => article.public_reactions_count.to_string(),
+        _ => unreachable!("fields are validated when the template is parsed"),
+    }
+}
+
+impl FromStr for OutputTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('t') => literal.push('\t'),
+                    Some('n') => literal.push('\n'),
+                    Some(other) => {
+                        if other != '\\' {
+                            literal.push('\\');
+                        }
+                        literal.push(other);
+                    }
+                    None => literal.push('\\'),
+                },
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(format!("unclosed '{{' in template '{s}'")),
+                        }
+                    }
+                    let name = name.trim().to_lowercase();
+                    if !FIELDS.contains(&name.as_str()) {
+                        return Err(format!("unknown field '{{{name}}}' (expected one of {})", FIELDS.join(", ")));
+                    }
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Field(name));
+                }
+                '}' => return Err(format!("unmatched '}}' in template '{s}' (use '}}}}' for a literal brace)")),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(Self { source: s.to_string(), parts })
+    }
+}
+
+impl fmt::Display for OutputTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
@@ -0,0 +1,110 @@
+use pulldown_cmark::{html, Options, Parser};
+
+use crate::frontmatter::parse_document;
+
+const PREVIEW_CSS: &str = r#"
+body { margin: 0; background: #f5f5f5; color: #171717;
+  font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, Helvetica, Arial, sans-serif; }
+main { max-width: 760px; margin: 2rem auto; background: #fff; border-radius: 8px;
+  box-shadow: 0 0 0 1px rgba(23, 23, 23, 0.05); padding: 2rem 4rem; }
+h1.title { font-size: 3rem; line-height: 1.25; font-weight: 800; margin: 0 0 1rem; }
+.tags { margin-bottom: 2rem; color: #575757; }
+.tags span { margin-right: 0.75rem; }
+article { font-size: 1.25rem; line-height: 1.6; }
+article h1, article h2, article h3 { font-weight: 700; line-height: 1.25; }
+article a { color: #3b49df; }
+article img { max-width: 100%; }
+article code { background: rgba(0, 0, 0, 0.1); border-radius: 6px; padding: 0.1em 0.25em; font-size: 0.875em; }
+article pre { background: #08090a; color: #f8f8f2; border-radius: 6px; padding: 1rem; overflow-x: auto; }
+article pre code { background: none; padding: 0; }
+article blockquote { border-left: 4px solid #d6d6d7; margin-left: 0; padding-left: 1rem; }
+article table { border-collapse: collapse; }
+article th, article td { border: 1px solid #d6d6d7; padding: 0.25rem 0.5rem; }
+.liquid-tag { border: 1px dashed #a3a3a3; border-radius: 6px; padding: 0.75rem 1rem;
+  color: #575757; font-size: 1rem; margin: 1rem 0; }
+.liquid-tag b { color: #171717; }
+"#;
+
+const LIVE_RELOAD_JS: &str = r#"
+(function () {
+  var version = null;
+  setInterval(function () {
+    fetch("/__version").then(function (r) { return r.text(); }).then(function (v) {
+      if (version === null) { version = v; } else if (v !== version) { location.reload(); }
+    }).catch(function () {});
+  }, 1000);
+})();
+"#;
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Renders a dev.to markdown body to HTML. Front matter is dropped and liquid
+/// tags become visible placeholders, since only dev.to can expand them.
+pub fn markdown_to_html(markdown: &str) -> String {
+    let (_, body) = parse_document(markdown);
+    let body = replace_liquid_tags(body);
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_TASKLISTS;
+    let mut out = String::new();
+    html::push_html(&mut out, Parser::new_ext(&body, options));
+    out
+}
+
+fn replace_liquid_tags(body: &str) -> String {
+    let mut out = String::with_capacity(body.len());
+    let mut rest = body;
+    while let Some(start) = rest.find("{%") {
+        let Some(end) = rest[start..].find("%}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let inner = rest[start + 2..start + end].trim();
+        let (name, args) = inner.split_once(char::is_whitespace).unwrap_or((inner, ""));
+        out.push_str(&format!(
+            "<div class=\"liquid-tag\"><b>{}</b> {}</div>",
+            escape_html(name),
+            escape_html(args.trim())
+        ));
+        rest = &rest[start + end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// A standalone HTML page styled after dev.to's article view. With
+/// `live_reload`, the page polls `/__version` and reloads when it changes.
+pub fn preview_page(title: &str, tags: &[String], markdown: &str, live_reload: bool) -> String {
+    let tags: String = tags
+        .iter()
+        .map(|tag| format!("<span>#{}</span>", escape_html(tag)))
+        .collect();
+    let script = if live_reload {
+        format!("<script>{LIVE_RELOAD_JS}</script>")
+    } else {
+        String::new()
+    };
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title} (preview)</title>\n<style>{PREVIEW_CSS}</style>\n</head>\n<body>\n<main>\n\
+         <h1 class=\"title\">{title}</h1>\n<div class=\"tags\">{tags}</div>\n\
+         <article>\n{body}</article>\n</main>\n{script}\n</body>\n</html>\n",
+        title = escape_html(title),
+        body = markdown_to_html(markdown),
+    )
+}
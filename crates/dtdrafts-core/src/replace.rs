@@ -0,0 +1,52 @@
+use anyhow::{Context, Result};
+use regex::{NoExpand, Regex};
+
+use crate::bulk::BulkChange;
+use crate::client::ArticleInput;
+use crate::Article;
+
+/// A find-and-replace over draft bodies. Literal by default; as a regex,
+/// `with` may refer to groups as `$1` or `${name}`.
+#[derive(Debug, Clone)]
+pub struct Replacement {
+    pattern: Regex,
+    with: String,
+    regex: bool,
+}
+
+impl Replacement {
+    pub fn new(pattern: &str, with: &str, regex: bool) -> Result<Self> {
+        if pattern.is_empty() {
+            return Err(anyhow::anyhow!("The pattern to replace is empty"));
+        }
+        let source = if regex { pattern.to_string() } else { regex::escape(pattern) };
+        let pattern = Regex::new(&source).with_context(|| format!("Invalid regex '{pattern}'"))?;
+        Ok(Self { pattern, with: with.to_string(), regex })
+    }
+
+    /// The text with every match replaced and the number of matches, or
+    /// `None` when nothing matches.
+    pub fn apply(&self, text: &str) -> Option<(String, usize)> {
+        let count = self.pattern.find_iter(text).count();
+        if count == 0 {
+            return None;
+        }
+        let replaced = if self.regex {
+            self.pattern.replace_all(text, self.with.as_str())
+        } else {
+            self.pattern.replace_all(text, NoExpand(&self.with))
+        };
+        Some((replaced.into_owned(), count))
+    }
+
+    /// An update of the article's body, which has to be loaded.
+    pub fn plan(&self, article: &Article) -> BulkChange {
+        match self.apply(article.body_markdown.as_deref().unwrap_or_default()) {
+            Some((body, count)) => {
+                let input = ArticleInput { body_markdown: Some(body), ..Default::default() };
+                BulkChange::Update(input, format!("{count} replacement(s)"))
+            }
+            None => BulkChange::Skip("no match".to_string()),
+        }
+    }
+}
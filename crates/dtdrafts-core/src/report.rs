@@ -0,0 +1,160 @@
+use chrono::{DateTime, Utc};
+
+use crate::render::escape_html;
+use crate::stats::{compute_stats, ArticleStats};
+use crate::text::WordCountOptions;
+use crate::{stale, Article};
+
+const MAX_CHARTED_TAGS: usize = 15;
+
+// Upper bounds (exclusive) of the histogram buckets; the last one is open.
+const AGE_EDGES: [(i64, &str); 5] = [
+    (7, "< 1 week"),
+    (30, "1–4 weeks"),
+    (90, "1–3 months"),
+    (180, "3–6 months"),
+    (365, "6–12 months"),
+];
+const WORD_EDGES: [(usize, &str); 5] = [
+    (300, "< 300"),
+    (800, "300–799"),
+    (1500, "800–1,499"),
+    (3000, "1,500–2,999"),
+    (5000, "3,000–4,999"),
+];
+
+const REPORT_CSS: &str = r#"
+body { margin: 0; background: #f5f5f5; color: #171717;
+  font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, Helvetica, Arial, sans-serif; }
+main { max-width: 960px; margin: 2rem auto; padding: 0 1rem; }
+h1 { font-size: 2rem; margin: 0 0 0.25rem; }
+.generated { color: #575757; margin-bottom: 1.5rem; }
+.totals { display: flex; gap: 1rem; flex-wrap: wrap; margin-bottom: 1.5rem; }
+.totals div { background: #fff; border-radius: 8px; padding: 1rem 1.5rem; box-shadow: 0 0 0 1px rgba(23, 23, 23, 0.05); }
+.totals b { display: block; font-size: 1.75rem; }
+.charts { display: grid; grid-template-columns: repeat(auto-fit, minmax(280px, 1fr)); gap: 1rem; margin-bottom: 1.5rem; }
+section { background: #fff; border-radius: 8px; padding: 1rem 1.5rem; box-shadow: 0 0 0 1px rgba(23, 23, 23, 0.05); }
+h2 { font-size: 1.1rem; margin: 0 0 0.75rem; }
+.bar { display: grid; grid-template-columns: 8rem 1fr 2.5rem; align-items: center; gap: 0.5rem; font-size: 0.9rem; margin: 0.25rem 0; }
+.bar span:first-child { overflow: hidden; text-overflow: ellipsis; white-space: nowrap; }
+.bar .fill { background: #3b49df; height: 0.9rem; border-radius: 3px; min-width: 1px; }
+.bar .count { text-align: right; color: #575757; }
+table { width: 100%; border-collapse: collapse; font-size: 0.9rem; }
+th, td { text-align: left; padding: 0.4rem 0.5rem; border-bottom: 1px solid #eee; }
+td.number, th.number { text-align: right; }
+a { color: #3b49df; }
+.tags { color: #575757; }
+"#;
+
+/// One bar of a chart: a label and how many drafts fall under it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bar {
+    pub label: String,
+    pub count: usize,
+}
+
+/// Drafts per time since they were last touched. Undated drafts are left out.
+pub fn staleness_histogram(articles: &[&Article], now: DateTime<Utc>) -> Vec<Bar> {
+    let ages: Vec<i64> = articles.iter().filter_map(|article| stale::age_days(article, now)).collect();
+    histogram(&ages, &AGE_EDGES, "1 year+")
+}
+
+pub fn word_count_histogram(stats: &[ArticleStats]) -> Vec<Bar> {
+    let words: Vec<usize> = stats.iter().map(|stats| stats.words).collect();
+    histogram(&words, &WORD_EDGES, "5,000+")
+}
+
+fn histogram<T: PartialOrd + Copy>(values: &[T], edges: &[(T, &str)], last: &str) -> Vec<Bar> {
+    let mut bars: Vec<Bar> = edges
+        .iter()
+        .map(|(_, label)| Bar { label: label.to_string(), count: 0 })
+        .chain(std::iter::once(Bar { label: last.to_string(), count: 0 }))
+        .collect();
+    for value in values {
+        let index = edges.iter().position(|(edge, _)| value < edge).unwrap_or(edges.len());
+        bars[index].count += 1;
+    }
+    bars
+}
+
+/// A self-contained HTML page (inline CSS, no scripts or external assets)
+/// summarizing `drafts`: totals, charts by tag, staleness and length, and the
+/// draft list with links to the editor.
+pub fn html_report(drafts: &[&Article], options: &WordCountOptions, now: DateTime<Utc>) -> String {
+    let stats = compute_stats(drafts, options);
+    let tags: Vec<Bar> = stats
+        .tags
+        .iter()
+        .take(MAX_CHARTED_TAGS)
+        .map(|tag| Bar { label: format!("#{}", tag.tag), count: tag.count })
+        .collect();
+
+    let mut rows: Vec<(&Article, &ArticleStats, Option<i64>)> = drafts
+        .iter()
+        .zip(&stats.articles)
+        .map(|(article, article_stats)| (*article, article_stats, stale::age_days(article, now)))
+        .collect();
+    // Most recently touched first, undated drafts last
+    rows.sort_by_key(|(_, _, age)| age.unwrap_or(i64::MAX));
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    out.push_str(&format!("<title>Draft report</title>\n<style>{REPORT_CSS}</style>\n</head>\n<body>\n<main>\n"));
+    out.push_str("<h1>Draft report</h1>\n");
+    out.push_str(&format!(
+        "<div class=\"generated\">Generated {} by dtdrafts</div>\n",
+        now.format("%Y-%m-%d %H:%M UTC")
+    ));
+
+    out.push_str("<div class=\"totals\">\n");
+    for (value, label) in [
+        (stats.drafts, "drafts"),
+        (stats.total_words, "words"),
+        (stats.average_words, "words on average"),
+        (stats.total_reading_time_minutes, "minutes of reading"),
+    ] {
+        out.push_str(&format!("<div><b>{value}</b>{label}</div>\n"));
+    }
+    out.push_str("</div>\n");
+
+    out.push_str("<div class=\"charts\">\n");
+    out.push_str(&chart("Tags", &tags));
+    out.push_str(&chart("Last touched", &staleness_histogram(drafts, now)));
+    out.push_str(&chart("Word count", &word_count_histogram(&stats.articles)));
+    out.push_str("</div>\n");
+
+    out.push_str("<section>\n<h2>Drafts</h2>\n<table>\n");
+    out.push_str("<tr><th>Title</th><th>Tags</th><th class=\"number\">Words</th><th class=\"number\">Days since update</th></tr>\n");
+    for (article, article_stats, age) in rows {
+        let tags: Vec<String> = article.tags.iter().flatten().map(|tag| format!("#{tag}")).collect();
+        out.push_str(&format!(
+            "<tr><td><a href=\"{}\">{}</a></td><td class=\"tags\">{}</td><td class=\"number\">{}</td><td class=\"number\">{}</td></tr>\n",
+            escape_html(&article.edit_url()),
+            escape_html(&article.title),
+            escape_html(&tags.join(" ")),
+            article_stats.words,
+            age.map(|days| days.to_string()).unwrap_or_else(|| "–".to_string()),
+        ));
+    }
+    out.push_str("</table>\n</section>\n</main>\n</body>\n</html>\n");
+    out
+}
+
+fn chart(title: &str, bars: &[Bar]) -> String {
+    let max = bars.iter().map(|bar| bar.count).max().unwrap_or(0).max(1);
+    let mut out = format!("<section>\n<h2>{}</h2>\n", escape_html(title));
+    if bars.is_empty() {
+        out.push_str("<p>Nothing to show.</p>\n");
+    }
+    for bar in bars {
+        out.push_str(&format!(
+            "<div class=\"bar\"><span>{}</span><div class=\"fill\" style=\"width: {}%\"></div><span class=\"count\">{}</span></div>\n",
+            escape_html(&bar.label),
+            bar.count * 100 / max,
+            bar.count,
+        ));
+    }
+    out.push_str("</section>\n");
+    out
+}
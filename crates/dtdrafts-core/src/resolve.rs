@@ -0,0 +1,87 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+
+use crate::{get_config_dir, Article};
+
+const MAX_LISTED_CANDIDATES: usize = 5;
+
+pub fn get_last_results_file() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("last_results.json"))
+}
+
+/// Remembers the ids of the articles a listing printed, in order, so a later
+/// command can refer to them by their 1-based position.
+pub fn save_last_results(articles: &[&Article]) -> Result<()> {
+    fs::create_dir_all(get_config_dir()?)?;
+    let ids: Vec<u64> = articles.iter().map(|article| article.id).collect();
+    fs::write(get_last_results_file()?, serde_json::to_string(&ids)?)?;
+    Ok(())
+}
+
+pub fn load_last_results() -> Result<Vec<u64>> {
+    let path = get_last_results_file()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Finds the article `reference` points at. Tried in order: numeric id, index
+/// into `last_results`, exact slug, exact title and finally a unique title
+/// prefix. Slug and title matches are case-insensitive.
+pub fn resolve_article<'a>(articles: &'a [Article], reference: &str, last_results: &[u64]) -> Result<&'a Article> {
+    let reference = reference.trim();
+    if reference.is_empty() {
+        bail!("No article given");
+    }
+
+    if let Ok(number) = reference.parse::<u64>() {
+        if let Some(article) = articles.iter().find(|article| article.id == number) {
+            return Ok(article);
+        }
+        let index = usize::try_from(number).unwrap_or(usize::MAX);
+        if let Some(id) = index.checked_sub(1).and_then(|i| last_results.get(i)) {
+            return articles.iter().find(|article| article.id == *id).ok_or_else(|| {
+                anyhow::anyhow!("Result #{index} (article {id}) is no longer in the cache (try --refresh)")
+            });
+        }
+        bail!("Article {number} not found in the cache (try --refresh)");
+    }
+
+    let lowered = reference.to_lowercase();
+    if let Some(article) = articles.iter().find(|article| article.slug.to_lowercase() == lowered) {
+        return Ok(article);
+    }
+    let exact: Vec<&Article> = articles
+        .iter()
+        .filter(|article| article.title.to_lowercase() == lowered)
+        .collect();
+    if let [article] = exact.as_slice() {
+        return Ok(article);
+    }
+    let candidates = if exact.is_empty() {
+        articles
+            .iter()
+            .filter(|article| article.title.to_lowercase().starts_with(&lowered))
+            .collect()
+    } else {
+        exact
+    };
+    match candidates.as_slice() {
+        [] => bail!("No article matches '{reference}' by id, slug or title (try --refresh)"),
+        [article] => Ok(article),
+        _ => {
+            let mut message = format!("'{reference}' matches {} articles:", candidates.len());
+            for article in candidates.iter().take(MAX_LISTED_CANDIDATES) {
+                message.push_str(&format!("\n  {}  {}", article.id, article.title));
+            }
+            if candidates.len() > MAX_LISTED_CANDIDATES {
+                message.push_str(&format!("\n  ... and {} more", candidates.len() - MAX_LISTED_CANDIDATES));
+            }
+            message.push_str("\nUse the id or a longer title prefix");
+            bail!(message)
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Line-delimited JSON-RPC 2.0 messages for `--serve-stdio`: one request
+//! object per line on stdin, one response per line on stdout.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// Anything that went wrong while running a valid request (API errors,
+/// unknown articles, failed checks).
+pub const SERVER_ERROR: i64 = -32000;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RpcRequest {
+    pub jsonrpc: String,
+    /// Absent for notifications, which get no response.
+    #[serde(default)]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcResponse {
+    pub jsonrpc: &'static str,
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcResponse {
+    pub fn success(id: Value, result: Value) -> Self {
+        Self { jsonrpc: "2.0", id, result: Some(result), error: None }
+    }
+
+    pub fn failure(id: Value, code: i64, message: impl Into<String>) -> Self {
+        Self { jsonrpc: "2.0", id, result: None, error: Some(RpcError { code, message: message.into() }) }
+    }
+}
+
+/// Parses one line, or returns the error response to send back for it.
+pub fn parse_request(line: &str) -> Result<RpcRequest, RpcResponse> {
+    let value: Value = serde_json::from_str(line)
+        .map_err(|e| RpcResponse::failure(Value::Null, PARSE_ERROR, format!("Parse error: {e}")))?;
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    let request: RpcRequest = serde_json::from_value(value)
+        .map_err(|e| RpcResponse::failure(id.clone(), INVALID_REQUEST, format!("Invalid request: {e}")))?;
+    if request.jsonrpc != "2.0" {
+        return Err(RpcResponse::failure(id, INVALID_REQUEST, "Invalid request: jsonrpc must be \"2.0\""));
+    }
+    Ok(request)
+}
@@ -0,0 +1,100 @@
+//! Saved searches: named [`parse_search`] expressions kept in the `[saved]`
+//! config section, and the drafts each one found when it last ran, kept in
+//! `~/.dtdrafts/saved.json` so a run can tell what changed.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::filter::parse_search;
+use crate::{get_config_dir, search_articles_with, Article};
+
+const MAX_NOTIFIED_TITLES: usize = 5;
+
+/// Names are used as config keys, so they are kept to letters, digits, `-`
+/// and `_`.
+pub fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(anyhow::anyhow!("Invalid name '{name}'. Use letters, digits, '-' and '_'"));
+    }
+    Ok(())
+}
+
+/// The drafts among `articles` a saved search expression matches.
+pub fn run<'a>(articles: &'a [Article], expression: &str) -> Result<Vec<&'a Article>> {
+    let (keywords, filter) = parse_search(expression)?;
+    Ok(search_articles_with(articles, &keywords, &filter))
+}
+
+/// How the results of a saved search differ from its previous run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResultChange {
+    pub added: Vec<u64>,
+    pub removed: Vec<u64>,
+}
+
+impl ResultChange {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// The ids each saved search found when it last ran.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SavedResults {
+    #[serde(default)]
+    pub results: BTreeMap<String, Vec<u64>>,
+}
+
+pub fn get_saved_results_file() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("saved.json"))
+}
+
+impl SavedResults {
+    pub fn load() -> Result<Self> {
+        let path = get_saved_results_file()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid saved search results in {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(get_config_dir()?)?;
+        fs::write(get_saved_results_file()?, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Records what `name` found and returns the change since its previous
+    /// run, or `None` on its first run.
+    pub fn update(&mut self, name: &str, found: &[&Article]) -> Option<ResultChange> {
+        let mut ids: Vec<u64> = found.iter().map(|article| article.id).collect();
+        ids.sort_unstable();
+        let previous = self.results.insert(name.to_string(), ids.clone())?;
+        Some(ResultChange {
+            added: ids.iter().filter(|id| !previous.contains(id)).copied().collect(),
+            removed: previous.into_iter().filter(|id| !ids.contains(id)).collect(),
+        })
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.results.remove(name);
+    }
+}
+
+/// Summary and body of the notification for a change in a saved search;
+/// the body lists the drafts that newly match.
+pub fn notification_text(name: &str, change: &ResultChange, found: &[&Article]) -> (String, String) {
+    let summary = format!("Saved search '{name}': {} new, {} gone", change.added.len(), change.removed.len());
+    let added: Vec<&&Article> = found.iter().filter(|article| change.added.contains(&article.id)).collect();
+    let mut body: Vec<String> = added.iter().take(MAX_NOTIFIED_TITLES).map(|article| article.title.clone()).collect();
+    if added.len() > MAX_NOTIFIED_TITLES {
+        body.push(format!("… and {} more", added.len() - MAX_NOTIFIED_TITLES));
+    }
+    (summary, body.join("\n"))
+}
@@ -0,0 +1,77 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::get_config_dir;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ScheduledPublish {
+    pub id: u64,
+    pub title: String,
+    pub at: DateTime<Utc>,
+    /// Why the last attempt failed; the entry is retried on the next run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// Pending publishes, kept in `~/.dtdrafts/schedule.json` and ordered by time.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Schedule {
+    #[serde(default)]
+    pub entries: Vec<ScheduledPublish>,
+}
+
+pub fn get_schedule_file() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("schedule.json"))
+}
+
+impl Schedule {
+    pub fn load() -> Result<Self> {
+        let path = get_schedule_file()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid schedule in {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(get_config_dir()?)?;
+        fs::write(get_schedule_file()?, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Schedules `id`, replacing any earlier schedule for the same draft.
+    pub fn add(&mut self, entry: ScheduledPublish) {
+        self.entries.retain(|existing| existing.id != entry.id);
+        self.entries.push(entry);
+        self.entries.sort_by_key(|entry| entry.at);
+    }
+
+    pub fn cancel(&mut self, id: u64) -> Option<ScheduledPublish> {
+        let index = self.entries.iter().position(|entry| entry.id == id)?;
+        Some(self.entries.remove(index))
+    }
+
+    pub fn due(&self, now: DateTime<Utc>) -> Vec<ScheduledPublish> {
+        self.entries.iter().filter(|entry| entry.at <= now).cloned().collect()
+    }
+}
+
+/// Parses `YYYY-MM-DD HH:MM` in local time, or a full RFC 3339 timestamp.
+pub fn parse_schedule_time(input: &str) -> Result<DateTime<Utc>> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(input) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    let naive = NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M")
+        .or_else(|_| NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M"))
+        .map_err(|_| anyhow::anyhow!("Invalid time '{input}'. Expected \"YYYY-MM-DD HH:MM\" or an RFC 3339 timestamp"))?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|at| at.with_timezone(&Utc))
+        .ok_or_else(|| anyhow::anyhow!("'{input}' does not exist in the local time zone"))
+}
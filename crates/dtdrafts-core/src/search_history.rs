@@ -0,0 +1,152 @@
+//! Recent searches, kept as the command-line arguments they were run with in
+//! `~/.dtdrafts/searches.json`, so `search --again` and `search '!N'` can run
+//! one again. Recording can be turned off with `search.history = false`.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::get_config_dir;
+
+/// Older searches are dropped beyond this many.
+pub const MAX_ENTRIES: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SearchEntry {
+    pub at: DateTime<Utc>,
+    /// The arguments after the program name, e.g. `["search", "rust", "--tag", "cli"]`
+    pub args: Vec<String>,
+}
+
+impl SearchEntry {
+    /// The arguments as they would be typed, quoted where needed.
+    pub fn command_line(&self) -> String {
+        self.args
+            .iter()
+            .map(|arg| {
+                if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || "'\"!$`\\".contains(c)) {
+                    format!("'{}'", arg.replace('\'', r"'\''"))
+                } else {
+                    arg.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Entries oldest first; [`SearchHistory::get`] numbers them newest first.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SearchHistory {
+    #[serde(default)]
+    pub entries: Vec<SearchEntry>,
+}
+
+pub fn get_search_history_file() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("searches.json"))
+}
+
+impl SearchHistory {
+    pub fn load() -> Result<Self> {
+        let path = get_search_history_file()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid search history in {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(get_config_dir()?)?;
+        fs::write(get_search_history_file()?, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Appends a search, unless it repeats the latest one, which is only
+    /// moved to `entry.at`.
+    pub fn push(&mut self, entry: SearchEntry) {
+        match self.entries.last_mut() {
+            Some(last) if last.args == entry.args => last.at = entry.at,
+            _ => self.entries.push(entry),
+        }
+        let excess = self.entries.len().saturating_sub(MAX_ENTRIES);
+        self.entries.drain(..excess);
+    }
+
+    /// Searches newest first, numbered from 1 as `!N` refers to them.
+    pub fn recent(&self) -> impl Iterator<Item = (usize, &SearchEntry)> {
+        self.entries.iter().rev().enumerate().map(|(index, entry)| (index + 1, entry))
+    }
+
+    /// The `number`th most recent search (1 is the last one).
+    pub fn get(&self, number: usize) -> Result<&SearchEntry> {
+        if self.entries.is_empty() {
+            return Err(anyhow::anyhow!("No searches recorded yet"));
+        }
+        number
+            .checked_sub(1)
+            .and_then(|index| self.entries.iter().rev().nth(index))
+            .ok_or_else(|| anyhow::anyhow!("No search !{number}; there are {} (see `dtdrafts searches`)", self.entries.len()))
+    }
+}
+
+/// The number in a `!N` query, if `query` is one.
+pub fn rerun_number(query: &str) -> Option<usize> {
+    query.strip_prefix('!').filter(|digits| !digits.is_empty()).and_then(|digits| digits.parse().ok())
+}
+
+/// Finds a request to rerun a search in the raw arguments: `--again` or a
+/// `!N` query when the subcommand is `search`, or a `!N` given to the
+/// top-level `-q`. Returns the token and the number of the search it asks
+/// for. The arguments are inspected before they are parsed, so options that
+/// only make sense together with the recorded ones (e.g. `--desc` for a
+/// recorded `--sort`) can be added. `takes_value` tells which top-level
+/// options consume the next argument, so the subcommand can be told apart
+/// from their values.
+pub fn rerun_trigger(args: &[String], takes_value: impl Fn(&str) -> bool) -> Option<(&str, usize)> {
+    let mut index = 0;
+    while let Some(arg) = args.get(index).filter(|arg| arg.starts_with('-')) {
+        let value = args.get(index + 1);
+        if matches!(arg.as_str(), "-q" | "--query") {
+            if let Some(number) = value.and_then(|value| rerun_number(value)) {
+                return Some((value?.as_str(), number));
+            }
+        }
+        index += if !arg.contains('=') && takes_value(arg) { 2 } else { 1 };
+    }
+    if args.get(index)? != "search" {
+        return None;
+    }
+    args[index + 1..].iter().find_map(|arg| match arg.as_str() {
+        "--again" => Some((arg.as_str(), 1)),
+        _ => rerun_number(arg).map(|number| (arg.as_str(), number)),
+    })
+}
+
+/// Rewrites the arguments of a search that reruns `entry`: the token that
+/// asked for the rerun (`--again`, or the `!N` query with its `-q` flag) and
+/// the `search` subcommand are replaced by the recorded arguments, and
+/// anything else given is appended, e.g. `search --again --open`.
+pub fn expand(args: &[String], trigger: &str, entry: &SearchEntry) -> Vec<String> {
+    let mut rest = args.to_vec();
+    if let Some(position) = rest.iter().position(|arg| arg == trigger) {
+        rest.remove(position);
+        if position > 0 && matches!(rest[position - 1].as_str(), "-q" | "--query") {
+            rest.remove(position - 1);
+        }
+    }
+    if let Some(position) = rest.iter().position(|arg| arg == "search") {
+        rest.remove(position);
+    }
+    entry.args.iter().cloned().chain(rest).collect()
+}
+
+/// Adds a search to the history.
+pub fn record(args: &[String], at: DateTime<Utc>) -> Result<()> {
+    let mut history = SearchHistory::load()?;
+    history.push(SearchEntry { at, args: args.to_vec() });
+    history.save()
+}
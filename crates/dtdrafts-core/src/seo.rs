@@ -0,0 +1,231 @@
+//! On-page SEO checks for a draft: title and description lengths, how a
+//! target keyword is used, and how easy the text is to read.
+
+use std::ops::RangeInclusive;
+
+use serde::Serialize;
+
+use crate::frontmatter::parse_document;
+use crate::lint::PublishMetadata;
+use crate::text::prose_blocks;
+use crate::Article;
+
+/// Title lengths that show whole in search results; longer ones are cut off
+/// and shorter ones leave room unused.
+pub const TITLE_CHARS: RangeInclusive<usize> = 40..=70;
+/// Descriptions are shown in link previews and search snippets.
+pub const DESCRIPTION_CHARS: RangeInclusive<usize> = 50..=160;
+/// Keyword density, in percent of words, that reads naturally.
+pub const KEYWORD_DENSITY: RangeInclusive<f64> = 0.5..=2.5;
+/// Flesch reading ease of "standard" text and easier.
+pub const MIN_READING_EASE: f64 = 60.0;
+/// The keyword should appear this early.
+pub const INTRO_WORDS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Readability {
+    pub words: usize,
+    pub sentences: usize,
+    pub syllables: usize,
+    /// Flesch reading ease: 100 is very easy, below 30 very hard
+    pub reading_ease: f64,
+}
+
+impl Readability {
+    pub fn words_per_sentence(&self) -> f64 {
+        self.words as f64 / self.sentences.max(1) as f64
+    }
+
+    /// The usual name of the reading ease band, e.g. "fairly easy".
+    pub fn level(&self) -> &'static str {
+        match self.reading_ease {
+            score if score >= 90.0 => "very easy",
+            score if score >= 80.0 => "easy",
+            score if score >= 70.0 => "fairly easy",
+            score if score >= 60.0 => "standard",
+            score if score >= 50.0 => "fairly difficult",
+            score if score >= 30.0 => "difficult",
+            _ => "very difficult",
+        }
+    }
+}
+
+/// Counts words, sentences and syllables in the prose of `markdown` and
+/// computes the Flesch reading ease from them.
+pub fn readability(markdown: &str) -> Readability {
+    let mut words = 0;
+    let mut sentences = 0;
+    let mut syllables = 0;
+    for block in prose_blocks(markdown) {
+        // A heading or list item without a full stop still ends a sentence
+        for sentence in block.split_inclusive(['.', '!', '?']) {
+            let sentence_words: Vec<String> = words_of(sentence).collect();
+            if sentence_words.is_empty() {
+                continue;
+            }
+            sentences += 1;
+            words += sentence_words.len();
+            syllables += sentence_words.iter().map(|word| count_syllables(word)).sum::<usize>();
+        }
+    }
+    let reading_ease = if words == 0 {
+        0.0
+    } else {
+        206.835 - 1.015 * (words as f64 / sentences as f64) - 84.6 * (syllables as f64 / words as f64)
+    };
+    Readability { words, sentences, syllables, reading_ease }
+}
+
+// Lowercase words, without surrounding punctuation
+fn words_of(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+}
+
+/// Estimates syllables from groups of vowels, ignoring a silent final `e`.
+pub fn count_syllables(word: &str) -> usize {
+    let letters: Vec<char> = word.to_lowercase().chars().filter(char::is_ascii_alphabetic).collect();
+    if letters.is_empty() {
+        return 1;
+    }
+    let is_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let mut count = 0;
+    let mut previous_vowel = false;
+    for &c in &letters {
+        let vowel = is_vowel(c);
+        if vowel && !previous_vowel {
+            count += 1;
+        }
+        previous_vowel = vowel;
+    }
+    let len = letters.len();
+    if count > 1 && letters[len - 1] == 'e' && !(len > 2 && letters[len - 2] == 'l' && !is_vowel(letters[len - 3])) {
+        count -= 1;
+    }
+    count.max(1)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KeywordUsage {
+    pub phrase: String,
+    pub occurrences: usize,
+    /// Words taken up by the phrase, in percent of all words
+    pub density: f64,
+    pub in_title: bool,
+    pub in_description: bool,
+    pub in_intro: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SeoCheck {
+    pub passed: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeoReport {
+    pub id: u64,
+    pub title: String,
+    pub title_chars: usize,
+    pub description: Option<String>,
+    pub cover_image: bool,
+    pub keyword: Option<KeywordUsage>,
+    pub readability: Readability,
+    pub checks: Vec<SeoCheck>,
+}
+
+/// Analyzes a draft, whose body has to be loaded, optionally for a target
+/// keyword phrase.
+pub fn analyze(article: &Article, keyword: Option<&str>) -> SeoReport {
+    let markdown = article.body_markdown.as_deref().unwrap_or_default();
+    let metadata = PublishMetadata::of(article);
+    let (front_matter, _) = parse_document(markdown);
+    let description = front_matter
+        .as_ref()
+        .and_then(|front_matter| front_matter.get("description"))
+        .map(str::to_string)
+        .or_else(|| article.description.clone())
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty());
+    let words: Vec<String> = prose_blocks(markdown).iter().flat_map(|block| words_of(block).collect::<Vec<_>>()).collect();
+    let keyword = keyword.map(|phrase| keyword_usage(phrase, &words, &metadata.title, description.as_deref()));
+
+    let mut report = SeoReport {
+        id: article.id,
+        title_chars: metadata.title.trim().chars().count(),
+        title: metadata.title,
+        description,
+        cover_image: metadata.cover_image.is_some(),
+        keyword,
+        readability: readability(markdown),
+        checks: Vec::new(),
+    };
+    report.checks = checklist(&report);
+    report
+}
+
+fn keyword_usage(phrase: &str, words: &[String], title: &str, description: Option<&str>) -> KeywordUsage {
+    let phrase_words: Vec<String> = words_of(phrase).collect();
+    let contains = |text: &str| {
+        let text: Vec<String> = words_of(text).collect();
+        !phrase_words.is_empty() && text.windows(phrase_words.len()).any(|window| window == phrase_words)
+    };
+    let starts: Vec<usize> = if phrase_words.is_empty() {
+        Vec::new()
+    } else {
+        words.windows(phrase_words.len()).enumerate().filter(|(_, window)| *window == phrase_words).map(|(i, _)| i).collect()
+    };
+    let density = if words.is_empty() {
+        0.0
+    } else {
+        (starts.len() * phrase_words.len()) as f64 * 100.0 / words.len() as f64
+    };
+    KeywordUsage {
+        phrase: phrase.trim().to_string(),
+        occurrences: starts.len(),
+        density,
+        in_title: contains(title),
+        in_description: description.is_some_and(contains),
+        in_intro: starts.first().is_some_and(|start| *start < INTRO_WORDS),
+    }
+}
+
+fn checklist(report: &SeoReport) -> Vec<SeoCheck> {
+    let check = |passed: bool, message: String| SeoCheck { passed, message };
+    let (min, max) = (TITLE_CHARS.start(), TITLE_CHARS.end());
+    let mut checks = vec![check(
+        TITLE_CHARS.contains(&report.title_chars),
+        format!("Title is {} characters ({min}–{max} fit in search results)", report.title_chars),
+    )];
+    let (min, max) = (DESCRIPTION_CHARS.start(), DESCRIPTION_CHARS.end());
+    checks.push(match &report.description {
+        Some(description) => {
+            let chars = description.chars().count();
+            check(DESCRIPTION_CHARS.contains(&chars), format!("Description is {chars} characters ({min}–{max})"))
+        }
+        None => check(false, "No description; dev.to shows it in link previews and search snippets".to_string()),
+    });
+    checks.push(check(report.cover_image, "Cover image, shown on social cards".to_string()));
+    if let Some(keyword) = &report.keyword {
+        let phrase = &keyword.phrase;
+        checks.push(check(keyword.in_title, format!("\"{phrase}\" in the title")));
+        checks.push(check(keyword.in_description, format!("\"{phrase}\" in the description")));
+        checks.push(check(keyword.in_intro, format!("\"{phrase}\" in the first {INTRO_WORDS} words")));
+        let (min, max) = (KEYWORD_DENSITY.start(), KEYWORD_DENSITY.end());
+        checks.push(check(
+            KEYWORD_DENSITY.contains(&keyword.density),
+            format!("Keyword density {:.1}% ({min}–{max}%, {} use(s))", keyword.density, keyword.occurrences),
+        ));
+    }
+    let readability = &report.readability;
+    checks.push(check(
+        readability.reading_ease >= MIN_READING_EASE,
+        format!(
+            "Reading ease {:.0} ({}; aim for {MIN_READING_EASE:.0} or more)",
+            readability.reading_ease,
+            readability.level()
+        ),
+    ));
+    checks
+}
@@ -0,0 +1,38 @@
+use std::cmp::Ordering;
+
+use crate::Article;
+
+/// The articles of one series in reading order.
+#[derive(Debug)]
+pub struct Series<'a> {
+    pub name: String,
+    pub articles: Vec<&'a Article>,
+}
+
+/// Groups articles by series name, skipping those without a series. Names
+/// are compared ignoring case, like the `--series` filter, and each series
+/// keeps the spelling it was first seen with. Series are sorted by name;
+/// parts by creation date (undated parts last), which is the order dev.to
+/// lists them in.
+pub fn group_by_series<'a>(articles: &[&'a Article]) -> Vec<Series<'a>> {
+    let mut groups: Vec<Series<'a>> = Vec::new();
+    for article in articles {
+        let Some(name) = article.series() else {
+            continue;
+        };
+        match groups.iter_mut().find(|series| series.name.to_lowercase() == name.to_lowercase()) {
+            Some(series) => series.articles.push(article),
+            None => groups.push(Series { name, articles: vec![article] }),
+        }
+    }
+    for series in &mut groups {
+        series.articles.sort_by(|a, b| match (a.created_at_utc(), b.created_at_utc()) {
+            (Some(a_at), Some(b_at)) => a_at.cmp(&b_at),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.id.cmp(&b.id),
+        });
+    }
+    groups.sort_by_cached_key(|series| series.name.to_lowercase());
+    groups
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use crate::text::{split_front_matter, strip_liquid_tags, Fence};
+use crate::Article;
+
+pub const STOPWORDS: &[&str] = &[
+    "a", "about", "after", "all", "also", "an", "and", "any", "are", "as", "at", "be", "because", "been", "but",
+    "by", "can", "could", "do", "does", "don", "each", "for", "from", "get", "had", "has", "have", "here", "how",
+    "if", "in", "into", "is", "it", "its", "just", "let", "like", "ll", "make", "more", "most", "my", "no", "not",
+    "now", "of", "on", "one", "only", "or", "other", "our", "out", "over", "re", "so", "some", "than", "that",
+    "the", "their", "them", "then", "there", "these", "they", "this", "to", "up", "us", "use", "using", "ve",
+    "was", "we", "what", "when", "where", "which", "while", "who", "why", "will", "with", "would", "you", "your",
+];
+
+/// Lowercase words of two or more characters, without stopwords and numbers.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 2 && !word.chars().all(|c| c.is_ascii_digit()))
+        .map(str::to_lowercase)
+        .filter(|word| !STOPWORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// The prose of an article: the title (counted twice, as it says most about
+/// the topic) and the body without front matter, code, liquid tags or URLs.
+/// Code block languages are kept since they name the technology.
+pub fn article_terms(article: &Article) -> Vec<String> {
+    let mut text = format!("{0}\n{0}\n", article.title);
+    let (_, body) = split_front_matter(article.body_markdown.as_deref().unwrap_or_default());
+    let mut fence: Option<Fence> = None;
+    for line in body.lines() {
+        if let Some(open) = fence {
+            if open.is_closed_by(line) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(open) = Fence::parse(line) {
+            fence = Some(open);
+            text.push_str(line.trim_start().trim_start_matches(['`', '~']));
+            text.push('\n');
+            continue;
+        }
+        for word in strip_liquid_tags(line).split_whitespace() {
+            if !word.contains("://") {
+                text.push_str(word);
+                text.push(' ');
+            }
+        }
+        text.push('\n');
+    }
+    tokenize(&text)
+}
+
+/// A sparse, L2-normalized TF-IDF vector.
+#[derive(Debug, Clone, Default)]
+pub struct TermVector {
+    weights: HashMap<String, f64>,
+}
+
+impl TermVector {
+    pub fn weight(&self, term: &str) -> f64 {
+        self.weights.get(term).copied().unwrap_or(0.0)
+    }
+
+    /// Cosine similarity in `0.0..=1.0`.
+    pub fn cosine(&self, other: &TermVector) -> f64 {
+        let (small, large) = if self.weights.len() <= other.weights.len() { (self, other) } else { (other, self) };
+        small.weights.iter().map(|(term, weight)| weight * large.weight(term)).sum()
+    }
+
+    /// Terms by descending weight.
+    pub fn top_terms(&self, n: usize) -> Vec<(&str, f64)> {
+        let mut terms: Vec<(&str, f64)> = self.weights.iter().map(|(term, weight)| (term.as_str(), *weight)).collect();
+        terms.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        terms.truncate(n);
+        terms
+    }
+}
+
+/// Document frequencies over a set of articles, used to weight terms so
+/// that words common to every draft count for little.
+#[derive(Debug, Clone, Default)]
+pub struct Corpus {
+    documents: usize,
+    document_frequency: HashMap<String, usize>,
+}
+
+impl Corpus {
+    pub fn new(documents: &[Vec<String>]) -> Self {
+        let mut document_frequency: HashMap<String, usize> = HashMap::new();
+        for terms in documents {
+            let mut unique: Vec<&String> = terms.iter().collect();
+            unique.sort();
+            unique.dedup();
+            for term in unique {
+                *document_frequency.entry(term.clone()).or_default() += 1;
+            }
+        }
+        Self { documents: documents.len(), document_frequency }
+    }
+
+    pub fn idf(&self, term: &str) -> f64 {
+        let df = self.document_frequency.get(term).copied().unwrap_or(0);
+        ((self.documents as f64 + 1.0) / (df as f64 + 1.0)).ln() + 1.0
+    }
+
+    pub fn vector(&self, terms: &[String]) -> TermVector {
+        let mut weights: HashMap<String, f64> = HashMap::new();
+        for term in terms {
+            *weights.entry(term.clone()).or_default() += 1.0;
+        }
+        for (term, weight) in weights.iter_mut() {
+            *weight *= self.idf(term);
+        }
+        let norm = weights.values().map(|weight| weight * weight).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            weights.values_mut().for_each(|weight| *weight /= norm);
+        }
+        TermVector { weights }
+    }
+}
+
+/// TF-IDF vectors for a set of articles, weighted against each other.
+pub struct SimilarityIndex<'a> {
+    articles: Vec<&'a Article>,
+    vectors: Vec<TermVector>,
+}
+
+impl<'a> SimilarityIndex<'a> {
+    pub fn new(articles: &[&'a Article]) -> Self {
+        let terms: Vec<Vec<String>> = articles.iter().map(|article| article_terms(article)).collect();
+        let corpus = Corpus::new(&terms);
+        let vectors = terms.iter().map(|terms| corpus.vector(terms)).collect();
+        Self { articles: articles.to_vec(), vectors }
+    }
+
+    /// The other articles ranked by similarity to the one with `id`, most
+    /// similar first, leaving out those with nothing in common.
+    pub fn most_similar(&self, id: u64) -> Vec<(&'a Article, f64)> {
+        let Some(index) = self.articles.iter().position(|article| article.id == id) else {
+            return Vec::new();
+        };
+        let mut ranked: Vec<(&'a Article, f64)> = self
+            .articles
+            .iter()
+            .zip(&self.vectors)
+            .filter(|(article, _)| article.id != id)
+            .map(|(article, vector)| (*article, self.vectors[index].cosine(vector)))
+            .filter(|(_, similarity)| *similarity > 0.0)
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked
+    }
+
+    /// Every pair scoring at least `threshold`, most similar first.
+    pub fn pairs_above(&self, threshold: f64) -> Vec<(&'a Article, &'a Article, f64)> {
+        let mut pairs = Vec::new();
+        for i in 0..self.vectors.len() {
+            for j in i + 1..self.vectors.len() {
+                let similarity = self.vectors[i].cosine(&self.vectors[j]);
+                if similarity >= threshold {
+                    pairs.push((self.articles[i], self.articles[j], similarity));
+                }
+            }
+        }
+        pairs.sort_by(|a, b| b.2.total_cmp(&a.2));
+        pairs
+    }
+}
+
+/// Articles connected by pairwise similarity at or above a threshold.
+#[derive(Debug)]
+pub struct DuplicateCluster<'a> {
+    pub articles: Vec<&'a Article>,
+    /// The highest similarity between two members.
+    pub max_similarity: f64,
+}
+
+/// Groups near-duplicates: two articles land in the same cluster when they
+/// are linked by a chain of pairs scoring at least `threshold`. Clusters are
+/// ordered by their closest pair.
+pub fn find_duplicates<'a>(articles: &[&'a Article], threshold: f64) -> Vec<DuplicateCluster<'a>> {
+    let mut clusters: Vec<DuplicateCluster<'a>> = Vec::new();
+    for (a, b, similarity) in SimilarityIndex::new(articles).pairs_above(threshold) {
+        let find = |clusters: &[DuplicateCluster], id: u64| {
+            clusters.iter().position(|cluster| cluster.articles.iter().any(|article| article.id == id))
+        };
+        match (find(&clusters, a.id), find(&clusters, b.id)) {
+            (Some(i), Some(j)) if i == j => {}
+            (Some(i), Some(j)) => {
+                let merged = clusters.remove(i.max(j));
+                let kept = &mut clusters[i.min(j)];
+                kept.articles.extend(merged.articles);
+                kept.max_similarity = kept.max_similarity.max(merged.max_similarity);
+            }
+            (Some(i), None) => clusters[i].articles.push(b),
+            (None, Some(j)) => clusters[j].articles.push(a),
+            (None, None) => clusters.push(DuplicateCluster { articles: vec![a, b], max_similarity: similarity }),
+        }
+    }
+    clusters
+}
@@ -0,0 +1,80 @@
+use std::cmp::{Ordering, Reverse};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{text, Article};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Title,
+    Created,
+    Updated,
+    Words,
+}
+
+impl SortKey {
+    pub fn needs_body(self) -> bool {
+        self == SortKey::Words
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "title" => Ok(SortKey::Title),
+            "created" => Ok(SortKey::Created),
+            "updated" => Ok(SortKey::Updated),
+            "words" => Ok(SortKey::Words),
+            _ => Err(format!("unknown sort key '{s}' (expected title, created, updated or words)")),
+        }
+    }
+}
+
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SortKey::Title => "title",
+            SortKey::Created => "created",
+            SortKey::Updated => "updated",
+            SortKey::Words => "words",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Sorts in place. Articles without a timestamp always sort last, whichever
+/// direction is requested, and ties keep their original order.
+pub fn sort_articles(articles: &mut [&Article], key: SortKey, descending: bool) {
+    match key {
+        SortKey::Title => sort_by_cached(articles, descending, |article| article.title.to_lowercase()),
+        SortKey::Words => sort_by_cached(articles, descending, |article| {
+            article.body_markdown.as_deref().map(text::word_count).unwrap_or(0)
+        }),
+        SortKey::Created => {
+            articles.sort_by(|a, b| cmp_missing_last(a.created_at_utc(), b.created_at_utc(), descending))
+        }
+        SortKey::Updated => {
+            articles.sort_by(|a, b| cmp_missing_last(a.updated_at_utc(), b.updated_at_utc(), descending))
+        }
+    }
+}
+
+fn sort_by_cached<K: Ord>(articles: &mut [&Article], descending: bool, key: impl Fn(&Article) -> K) {
+    if descending {
+        articles.sort_by_cached_key(|article| Reverse(key(article)));
+    } else {
+        articles.sort_by_cached_key(|article| key(article));
+    }
+}
+
+fn cmp_missing_last<T: Ord>(a: Option<T>, b: Option<T>, descending: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if descending => b.cmp(&a),
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
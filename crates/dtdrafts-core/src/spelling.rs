@@ -0,0 +1,161 @@
+//! Spell checking of draft bodies against Hunspell dictionaries.
+//!
+//! Dictionaries (`<language>.aff` plus `<language>.dic`) are looked up in
+//! `~/.dtdrafts/dictionaries` first, then where Hunspell and macOS keep them.
+//! Words in `~/.dtdrafts/dictionary.txt`, one per line, are always accepted.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use spellbook::Dictionary;
+
+use crate::get_config_dir;
+use crate::text::prose_spans;
+
+pub const DEFAULT_LANGUAGE: &str = "en_US";
+
+const SYSTEM_DICTIONARY_DIRS: &[&str] = &[
+    "/usr/share/hunspell",
+    "/usr/share/myspell",
+    "/usr/share/myspell/dicts",
+    "/usr/local/share/hunspell",
+    "/opt/homebrew/share/hunspell",
+    "/Library/Spelling",
+];
+
+/// A word of prose, with its 1-based line (front matter included) and
+/// character column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Word {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+}
+
+/// The user's own words, accepted in every language.
+pub fn get_custom_dictionary_file() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("dictionary.txt"))
+}
+
+/// Where dictionaries are searched, in order.
+pub fn dictionary_dirs() -> Result<Vec<PathBuf>> {
+    let mut dirs = vec![get_config_dir()?.join("dictionaries")];
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join("Library/Spelling"));
+    }
+    dirs.extend(SYSTEM_DICTIONARY_DIRS.iter().map(PathBuf::from));
+    Ok(dirs)
+}
+
+/// The `.aff` and `.dic` files for `language`, if installed.
+pub fn find_dictionary(language: &str) -> Result<Option<(PathBuf, PathBuf)>> {
+    Ok(dictionary_dirs()?.into_iter().find_map(|dir| {
+        let aff = dir.join(format!("{language}.aff"));
+        let dic = dir.join(format!("{language}.dic"));
+        (aff.is_file() && dic.is_file()).then_some((aff, dic))
+    }))
+}
+
+pub struct SpellChecker {
+    dictionary: Dictionary,
+}
+
+impl SpellChecker {
+    pub fn new(aff: &str, dic: &str) -> Result<Self> {
+        let dictionary = Dictionary::new(aff, dic).map_err(|e| anyhow::anyhow!("Invalid dictionary: {e}"))?;
+        Ok(Self { dictionary })
+    }
+
+    /// Loads the installed dictionary for `language` plus the custom words.
+    pub fn load(language: &str) -> Result<Self> {
+        let Some((aff, dic)) = find_dictionary(language)? else {
+            let dirs: Vec<String> = dictionary_dirs()?.iter().map(|dir| dir.display().to_string()).collect();
+            return Err(anyhow::anyhow!(
+                "No {language} dictionary found. Install Hunspell's (e.g. the hunspell-en-us package) or copy \
+                 {language}.aff and {language}.dic into one of: {}",
+                dirs.join(", ")
+            ));
+        };
+        let read = |path: &PathBuf| fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()));
+        let mut checker = Self::new(&read(&aff)?, &read(&dic)?)
+            .with_context(|| format!("Failed to load the dictionary {}", dic.display()))?;
+        let custom = get_custom_dictionary_file()?;
+        if custom.exists() {
+            checker.add_words(&read(&custom)?)?;
+        }
+        Ok(checker)
+    }
+
+    /// Accepts the words in `list`, one per line; blank lines and lines
+    /// starting with `#` are skipped.
+    pub fn add_words(&mut self, list: &str) -> Result<()> {
+        for word in list.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+            self.dictionary.add(word).map_err(|e| anyhow::anyhow!("Invalid custom word '{word}': {e}"))?;
+        }
+        Ok(())
+    }
+
+    pub fn check(&self, word: &str) -> bool {
+        self.dictionary.check(&word.replace('’', "'"))
+    }
+
+    /// Up to `limit` corrections for `word`, best first.
+    pub fn suggest(&self, word: &str, limit: usize) -> Vec<String> {
+        let mut suggestions = Vec::new();
+        self.dictionary.suggest(word, &mut suggestions);
+        suggestions.truncate(limit);
+        suggestions
+    }
+
+    /// The words of `markdown` the dictionary doesn't know.
+    pub fn misspellings(&self, markdown: &str) -> Vec<Word> {
+        prose_words(markdown).into_iter().filter(|word| is_checked(&word.text) && !self.check(&word.text)).collect()
+    }
+}
+
+// Identifiers, acronyms and words with digits are left alone
+fn is_checked(word: &str) -> bool {
+    word.chars().count() > 1
+        && word.chars().all(|c| c.is_alphabetic() || c == '\'' || c == '’')
+        && !word.chars().skip(1).any(char::is_uppercase)
+}
+
+fn skipped_spans() -> &'static Regex {
+    static SKIPPED: OnceLock<Regex> = OnceLock::new();
+    // Liquid tags, URLs and emails, which markdown leaves in the text
+    SKIPPED.get_or_init(|| Regex::new(r"\{%.*?%\}|https?://\S+|\S+@\S+\.\S+").expect("valid regex"))
+}
+
+fn word_pattern() -> &'static Regex {
+    static WORD: OnceLock<Regex> = OnceLock::new();
+    WORD.get_or_init(|| Regex::new(r"[\p{L}\p{N}_]+(?:['’][\p{L}]+)*").expect("valid regex"))
+}
+
+/// The words of `markdown` outside front matter, code, liquid tags, HTML and
+/// URLs, which are what spelling and prose rules look at. Built on the same
+/// prose as [`crate::text::prose_blocks`].
+pub fn prose_words(markdown: &str) -> Vec<Word> {
+    let line_starts: Vec<usize> =
+        std::iter::once(0).chain(markdown.match_indices('\n').map(|(index, _)| index + 1)).collect();
+    let mut words = Vec::new();
+    for (offset, text) in prose_spans(markdown).into_iter().flatten() {
+        // Escapes and entities make the text differ from the source, so its
+        // words can only be placed at the start of the run
+        let verbatim = markdown.get(offset..offset + text.len()) == Some(text.as_str());
+        // Blank out what isn't prose, keeping the positions
+        let masked = skipped_spans().replace_all(&text, |caps: &regex::Captures| " ".repeat(caps[0].len()));
+        for found in word_pattern().find_iter(&masked) {
+            let position = if verbatim { offset + found.start() } else { offset };
+            let line = line_starts.partition_point(|start| *start <= position);
+            words.push(Word {
+                line,
+                column: markdown[line_starts[line - 1]..position].chars().count() + 1,
+                text: found.as_str().to_string(),
+            });
+        }
+    }
+    words
+}
@@ -0,0 +1,81 @@
+//! Splitting a long draft into a series (`split`): one new draft per
+//! section at a heading level, titled after its heading.
+
+use std::str::FromStr;
+
+use crate::client::ArticleInput;
+use crate::text::{split_front_matter, word_count};
+use crate::toc::located_headings;
+use crate::Article;
+
+/// The heading level sections are split at, `h1` to `h6`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitLevel(pub u8);
+
+impl FromStr for SplitLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.to_lowercase()
+            .strip_prefix('h')
+            .and_then(|level| level.parse().ok())
+            .filter(|level| (1..=6).contains(level))
+            .map(SplitLevel)
+            .ok_or_else(|| format!("unknown heading level '{s}' (expected h1 to h6)"))
+    }
+}
+
+/// One of the drafts a split would create.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitPart {
+    pub title: String,
+    /// The section without its heading, which becomes the title
+    pub body: String,
+}
+
+impl SplitPart {
+    pub fn words(&self) -> usize {
+        word_count(&self.body)
+    }
+}
+
+/// Cuts the body of `markdown` at every heading of `level` or higher. Text
+/// before the first such heading stays at the top of the first part;
+/// front matter is left out. Fewer than two parts means there is nothing to
+/// split.
+pub fn plan_split(markdown: &str, level: SplitLevel) -> Vec<SplitPart> {
+    let (_, body) = split_front_matter(markdown);
+    let body_start = markdown.len() - body.len();
+    let cuts: Vec<_> = located_headings(markdown).into_iter().filter(|(_, heading)| heading.level <= level.0).collect();
+    let Some((first, _)) = cuts.first() else {
+        return Vec::new();
+    };
+    let intro = markdown[body_start..first.start].trim();
+
+    cuts.iter()
+        .enumerate()
+        .map(|(index, (range, heading))| {
+            let end = cuts.get(index + 1).map_or(markdown.len(), |(next, _)| next.start);
+            let section = markdown[range.end..end].trim();
+            let body = if index == 0 && !intro.is_empty() {
+                format!("{intro}\n\n{section}\n")
+            } else {
+                format!("{section}\n")
+            };
+            SplitPart { title: heading.text.clone(), body }
+        })
+        .collect()
+}
+
+/// The new draft for `part`, in `series` and with the tags of the draft it
+/// was split from.
+pub fn part_input(original: &Article, part: &SplitPart, series: &str) -> ArticleInput {
+    ArticleInput {
+        title: Some(part.title.clone()),
+        body_markdown: Some(part.body.clone()),
+        published: Some(false),
+        tags: original.tags.clone().filter(|tags| !tags.is_empty()),
+        series: Some(series.to_string()),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,141 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::Article;
+
+const MAX_NOTIFIED_TITLES: usize = 5;
+
+// Bucket edges (in days) used above the requested threshold.
+const BUCKET_EDGES: [i64; 5] = [30, 90, 180, 365, 730];
+
+#[derive(Debug)]
+pub struct AgeBucket<'a> {
+    pub min_days: i64,
+    pub max_days: Option<i64>,
+    pub articles: Vec<&'a Article>,
+}
+
+impl AgeBucket<'_> {
+    pub fn label(&self) -> String {
+        match self.max_days {
+            Some(max) => format!("{}–{}", format_days(self.min_days), format_days(max)),
+            None => format!("{}+", format_days(self.min_days)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StaleReport<'a> {
+    pub older_than_days: i64,
+    pub buckets: Vec<AgeBucket<'a>>,
+    /// Drafts with neither an `updated_at` nor a `created_at` timestamp.
+    pub undated: Vec<&'a Article>,
+}
+
+impl StaleReport<'_> {
+    pub fn total(&self) -> usize {
+        self.buckets.iter().map(|bucket| bucket.articles.len()).sum()
+    }
+}
+
+/// The age of a draft is measured from `updated_at`, falling back to
+/// `created_at` for drafts that were never edited.
+pub fn last_touched(article: &Article) -> Option<DateTime<Utc>> {
+    article.updated_at_utc().or_else(|| article.created_at_utc())
+}
+
+pub fn age_days(article: &Article, now: DateTime<Utc>) -> Option<i64> {
+    last_touched(article).map(|at| (now - at).num_days())
+}
+
+/// Groups unpublished drafts not touched for at least `older_than` into age
+/// buckets, oldest draft first within each bucket. Empty buckets are kept so
+/// renderers can show a complete histogram.
+pub fn stale_report<'a>(articles: &'a [Article], older_than: Duration, now: DateTime<Utc>) -> StaleReport<'a> {
+    let older_than_days = older_than.num_days();
+    let mut edges = vec![older_than_days];
+    edges.extend(BUCKET_EDGES.iter().copied().filter(|edge| *edge > older_than_days));
+
+    let mut buckets: Vec<AgeBucket> = edges
+        .iter()
+        .enumerate()
+        .map(|(i, min_days)| AgeBucket {
+            min_days: *min_days,
+            max_days: edges.get(i + 1).copied(),
+            articles: Vec::new(),
+        })
+        .collect();
+    let mut undated = Vec::new();
+
+    for article in articles.iter().filter(|article| !article.published) {
+        let Some(age) = age_days(article, now) else {
+            undated.push(article);
+            continue;
+        };
+        if let Some(bucket) = buckets
+            .iter_mut()
+            .find(|bucket| age >= bucket.min_days && bucket.max_days.is_none_or(|max| age < max))
+        {
+            bucket.articles.push(article);
+        }
+    }
+    for bucket in &mut buckets {
+        bucket.articles.sort_by_key(|article| last_touched(article));
+    }
+
+    StaleReport { older_than_days, buckets, undated }
+}
+
+/// A neglected draft, as reported by `remind` (and its `--json` output).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Reminder {
+    pub id: u64,
+    pub title: String,
+    pub edit_url: String,
+    pub age_days: i64,
+    pub last_touched: DateTime<Utc>,
+}
+
+/// Drafts not touched for at least `older_than`, oldest first. Undated drafts
+/// are left out since there is nothing to remind about.
+pub fn reminders(articles: &[Article], older_than: Duration, now: DateTime<Utc>) -> Vec<Reminder> {
+    let mut reminders: Vec<Reminder> = articles
+        .iter()
+        .filter(|article| !article.published)
+        .filter_map(|article| {
+            let last_touched = last_touched(article)?;
+            let age_days = (now - last_touched).num_days();
+            (age_days >= older_than.num_days()).then(|| Reminder {
+                id: article.id,
+                title: article.title.clone(),
+                edit_url: article.edit_url(),
+                age_days,
+                last_touched,
+            })
+        })
+        .collect();
+    reminders.sort_by_key(|reminder| reminder.last_touched);
+    reminders
+}
+
+/// Summary and body for a desktop notification about `reminders`.
+pub fn notification_text(reminders: &[Reminder]) -> (String, String) {
+    let summary = format!("{} draft(s) need attention", reminders.len());
+    let mut body: Vec<String> = reminders
+        .iter()
+        .take(MAX_NOTIFIED_TITLES)
+        .map(|reminder| format!("{} ({}d)", reminder.title, reminder.age_days))
+        .collect();
+    if reminders.len() > MAX_NOTIFIED_TITLES {
+        body.push(format!("… and {} more", reminders.len() - MAX_NOTIFIED_TITLES));
+    }
+    (summary, body.join("\n"))
+}
+
+fn format_days(days: i64) -> String {
+    if days >= 365 && days % 365 == 0 {
+        format!("{}y", days / 365)
+    } else {
+        format!("{days}d")
+    }
+}
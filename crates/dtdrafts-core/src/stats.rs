@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::lint::WEASEL_WORDS;
+use crate::seo::readability;
+use crate::spelling::prose_words;
+use crate::text::{self, WordCountOptions};
+use crate::Article;
+
+/// dev.to estimates reading time at 275 words per minute.
+pub const WORDS_PER_MINUTE: usize = 275;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArticleStats {
+    pub id: u64,
+    pub title: String,
+    pub words: usize,
+    pub reading_time_minutes: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsSummary {
+    pub drafts: usize,
+    pub total_words: usize,
+    pub average_words: usize,
+    pub total_reading_time_minutes: usize,
+    /// Drafts that have a cover image set.
+    pub with_cover_image: usize,
+    pub total_comments: u64,
+    pub total_reactions: u64,
+    pub tags: Vec<TagCount>,
+    pub articles: Vec<ArticleStats>,
+    /// Filled in by `stats --readability`, oldest draft first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub readability: Vec<ReadabilityStats>,
+}
+
+/// How a draft reads, see [`crate::seo::Readability`]; weasel words (see
+/// [`WEASEL_WORDS`]) stand in for its tone.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReadabilityStats {
+    pub id: u64,
+    pub title: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub reading_ease: f64,
+    pub level: &'static str,
+    pub words_per_sentence: f64,
+    pub weasel_words_per_1000: f64,
+}
+
+impl ReadabilityStats {
+    /// `None` for drafts without any prose yet.
+    pub fn compute(article: &Article) -> Option<Self> {
+        let body = article.body_markdown.as_deref()?;
+        let readability = readability(body);
+        if readability.words == 0 {
+            return None;
+        }
+        let weasel_words = prose_words(body)
+            .iter()
+            .filter(|word| WEASEL_WORDS.contains(&word.text.to_lowercase().as_str()))
+            .count();
+        Some(Self {
+            id: article.id,
+            title: article.title.clone(),
+            created_at: article.created_at,
+            reading_ease: readability.reading_ease,
+            level: readability.level(),
+            words_per_sentence: readability.words_per_sentence(),
+            weasel_words_per_1000: weasel_words as f64 * 1000.0 / readability.words as f64,
+        })
+    }
+}
+
+/// Readability of each draft with prose, oldest first, so the values can be
+/// read as a trend. Drafts without a creation date come last.
+pub fn readability_trend(articles: &[&Article]) -> Vec<ReadabilityStats> {
+    let mut stats: Vec<ReadabilityStats> = articles.iter().filter_map(|article| ReadabilityStats::compute(article)).collect();
+    stats.sort_by_key(|stats| (stats.created_at.is_none(), stats.created_at));
+    stats
+}
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One block per value, from lowest (`▁`) to highest (`█`) among them.
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|value| {
+            let level = if max > min { ((value - min) / (max - min) * 7.0).round() as usize } else { 3 };
+            SPARKS[level.min(7)]
+        })
+        .collect()
+}
+
+pub fn reading_time_minutes(words: usize) -> usize {
+    words.div_ceil(WORDS_PER_MINUTE)
+}
+
+impl ArticleStats {
+    pub fn compute(article: &Article, options: &WordCountOptions) -> Self {
+        let words = article
+            .body_markdown
+            .as_deref()
+            .map(|body| text::word_count_with(body, options))
+            .unwrap_or(0);
+        Self {
+            id: article.id,
+            title: article.title.clone(),
+            words,
+            reading_time_minutes: reading_time_minutes(words),
+        }
+    }
+}
+
+/// Tags are counted case-insensitively and ordered by frequency, then name.
+pub fn tag_frequency(articles: &[&Article]) -> Vec<TagCount> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for tag in articles.iter().flat_map(|article| article.tags.iter().flatten()) {
+        *counts.entry(tag.to_lowercase()).or_default() += 1;
+    }
+    let mut tags: Vec<TagCount> = counts
+        .into_iter()
+        .map(|(tag, count)| TagCount { tag, count })
+        .collect();
+    tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+    tags
+}
+
+pub fn compute_stats(articles: &[&Article], options: &WordCountOptions) -> StatsSummary {
+    let per_article: Vec<ArticleStats> = articles
+        .iter()
+        .map(|article| ArticleStats::compute(article, options))
+        .collect();
+    let total_words: usize = per_article.iter().map(|stats| stats.words).sum();
+    StatsSummary {
+        drafts: per_article.len(),
+        total_words,
+        average_words: total_words.checked_div(per_article.len()).unwrap_or(0),
+        total_reading_time_minutes: per_article.iter().map(|stats| stats.reading_time_minutes).sum(),
+        with_cover_image: articles.iter().filter(|article| article.cover_image.is_some()).count(),
+        total_comments: articles.iter().map(|article| u64::from(article.comments_count)).sum(),
+        total_reactions: articles.iter().map(|article| u64::from(article.public_reactions_count)).sum(),
+        tags: tag_frequency(articles),
+        articles: per_article,
+        readability: Vec::new(),
+    }
+}
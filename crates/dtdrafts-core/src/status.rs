@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{get_config_dir, Article};
+
+/// Where a draft is in the writing workflow. dev.to has no such concept, so
+/// these only exist locally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkflowStatus {
+    Idea,
+    Writing,
+    Review,
+    Ready,
+}
+
+impl WorkflowStatus {
+    pub const ALL: [WorkflowStatus; 4] =
+        [WorkflowStatus::Idea, WorkflowStatus::Writing, WorkflowStatus::Review, WorkflowStatus::Ready];
+}
+
+impl FromStr for WorkflowStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "idea" => Ok(WorkflowStatus::Idea),
+            "writing" => Ok(WorkflowStatus::Writing),
+            "review" => Ok(WorkflowStatus::Review),
+            "ready" => Ok(WorkflowStatus::Ready),
+            _ => Err(format!("unknown status '{s}' (expected idea, writing, review or ready)")),
+        }
+    }
+}
+
+impl fmt::Display for WorkflowStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            WorkflowStatus::Idea => "idea",
+            WorkflowStatus::Writing => "writing",
+            WorkflowStatus::Review => "review",
+            WorkflowStatus::Ready => "ready",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Workflow statuses by article id, kept in `~/.dtdrafts/status.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StatusStore {
+    #[serde(default)]
+    pub statuses: BTreeMap<u64, WorkflowStatus>,
+}
+
+pub fn get_status_file() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("status.json"))
+}
+
+impl StatusStore {
+    pub fn load() -> Result<Self> {
+        let path = get_status_file()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid statuses in {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(get_config_dir()?)?;
+        fs::write(get_status_file()?, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, id: u64) -> Option<WorkflowStatus> {
+        self.statuses.get(&id).copied()
+    }
+
+    pub fn set(&mut self, id: u64, status: WorkflowStatus) {
+        self.statuses.insert(id, status);
+    }
+
+    pub fn clear(&mut self, id: u64) -> Option<WorkflowStatus> {
+        self.statuses.remove(&id)
+    }
+
+    pub fn ids_with(&self, status: WorkflowStatus) -> impl Iterator<Item = u64> + '_ {
+        self.statuses
+            .iter()
+            .filter(move |(_, s)| **s == status)
+            .map(|(id, _)| *id)
+    }
+}
+
+/// One board column; `status` is `None` for drafts without a status.
+#[derive(Debug)]
+pub struct BoardColumn<'a> {
+    pub status: Option<WorkflowStatus>,
+    pub articles: Vec<&'a Article>,
+}
+
+/// Groups drafts into a column per status in workflow order, followed by the
+/// drafts without one. Empty columns are kept.
+pub fn board<'a>(articles: &[&'a Article], store: &StatusStore) -> Vec<BoardColumn<'a>> {
+    let mut columns: Vec<BoardColumn> = WorkflowStatus::ALL
+        .iter()
+        .map(|status| BoardColumn { status: Some(*status), articles: Vec::new() })
+        .chain([BoardColumn { status: None, articles: Vec::new() }])
+        .collect();
+    for article in articles {
+        let index = match store.get(article.id) {
+            Some(status) => WorkflowStatus::ALL.iter().position(|s| *s == status).unwrap_or_default(),
+            None => WorkflowStatus::ALL.len(),
+        };
+        columns[index].articles.push(article);
+    }
+    columns
+}
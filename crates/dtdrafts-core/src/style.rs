@@ -0,0 +1,75 @@
+use std::env;
+use std::fmt;
+use std::io::IsTerminal;
+use std::str::FromStr;
+
+/// When to emit ANSI colors: `auto` colors terminals unless `NO_COLOR` is set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether a stream gets colors. `no_color` is the `NO_COLOR` convention,
+    /// which only an explicit `always` overrides.
+    pub fn enabled(self, is_terminal: bool, no_color: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => is_terminal && !no_color,
+        }
+    }
+
+    pub fn stdout_enabled(self) -> bool {
+        self.enabled(std::io::stdout().is_terminal(), no_color_env())
+    }
+
+    pub fn stderr_enabled(self) -> bool {
+        self.enabled(std::io::stderr().is_terminal(), no_color_env())
+    }
+
+    /// Applies the choice to everything printed through `colored`.
+    #[cfg(feature = "terminal")]
+    pub fn apply(self) {
+        colored::control::set_override(self.stdout_enabled());
+    }
+}
+
+/// Whether `output` goes through a pager: only when paging is `enabled`,
+/// stdout is a terminal and the text has at least as many lines as the
+/// terminal is tall. An unknown `height` never pages.
+pub fn should_page(output: &str, enabled: bool, is_terminal: bool, height: Option<usize>) -> bool {
+    enabled && is_terminal && height.is_some_and(|height| output.lines().count() >= height)
+}
+
+/// `NO_COLOR` set to any non-empty value (https://no-color.org).
+pub fn no_color_env() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!("unknown color choice '{s}' (expected auto, always or never)")),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ColorChoice::Auto => "auto",
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+        };
+        f.write_str(name)
+    }
+}
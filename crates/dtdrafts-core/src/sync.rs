@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::frontmatter::FrontMatterStyle;
+use crate::Article;
+
+/// What was last synced for each draft. The stored `base` document is the
+/// common ancestor for telling local edits from remote ones, and for merging.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SyncState {
+    #[serde(default)]
+    pub drafts: BTreeMap<u64, SyncedDraft>,
+    /// The `--frontmatter` style the files were written in, if not dev.to's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frontmatter: Option<FrontMatterStyle>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SyncedDraft {
+    /// Path relative to the repository root
+    pub file: String,
+    pub base: String,
+}
+
+impl SyncState {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let state: Self =
+            serde_json::from_str(&content).with_context(|| format!("Invalid sync state in {}", path.display()))?;
+        tracing::debug!(path = %path.display(), drafts = state.drafts.len(), "loaded sync state");
+        Ok(state)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+pub fn draft_file_name(article: &Article) -> String {
+    format!("{}-{}.md", article.id, article.slug)
+}
+
+/// Inverse of [`draft_file_name`]: the id prefix of `<id>-<slug>.md` (or `<id>.md`).
+pub fn article_id_from_file_name(path: &Path) -> Option<u64> {
+    if path.extension()? != "md" {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?;
+    stem.split('-').next()?.parse().ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncAction {
+    /// Local file and dev.to agree
+    Unchanged,
+    /// First sync of this draft: write it
+    Add,
+    /// Only dev.to changed: overwrite the local file
+    Pull,
+    /// Only the local file changed: upload it
+    Push,
+    /// Both changed since the last sync
+    Conflict,
+}
+
+/// Decides what to do with one draft from the last synced `base`, the
+/// `local` file (if any) and the `remote` document.
+pub fn classify(base: Option<&str>, local: Option<&str>, remote: &str) -> SyncAction {
+    match (base, local) {
+        (_, None) => SyncAction::Add,
+        (_, Some(local)) if local == remote => SyncAction::Unchanged,
+        (Some(base), Some(local)) if local == base => SyncAction::Pull,
+        (Some(base), Some(_)) if remote == base => SyncAction::Push,
+        // Changed on both sides, or a local file we never synced
+        _ => SyncAction::Conflict,
+    }
+}
+
+/// How `sync` resolves a draft that changed on both sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStrategy {
+    /// Keep the local file
+    Ours,
+    /// Take the dev.to version
+    Theirs,
+    /// Three-way merge, writing conflict markers where the edits overlap
+    Merge,
+}
+
+impl FromStr for SyncStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ours" => Ok(SyncStrategy::Ours),
+            "theirs" => Ok(SyncStrategy::Theirs),
+            "merge" => Ok(SyncStrategy::Merge),
+            _ => Err(format!("unknown strategy '{s}' (expected ours, theirs or merge)")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeOutcome {
+    Clean(String),
+    /// The merged text with `<<<<<<<`/`>>>>>>>` conflict markers
+    Conflicted(String),
+}
+
+/// Three-way merge of the local and remote documents against their common
+/// `base`. Local edits are "ours", dev.to's are "theirs".
+pub fn merge_documents(base: &str, local: &str, remote: &str) -> MergeOutcome {
+    match diffy::merge(base, local, remote) {
+        Ok(merged) => MergeOutcome::Clean(merged),
+        Err(conflicted) => MergeOutcome::Conflicted(conflicted),
+    }
+}
+
+pub fn has_conflict_markers(document: &str) -> bool {
+    document
+        .lines()
+        .any(|line| line.starts_with("<<<<<<< ") || line.starts_with(">>>>>>> "))
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::lint::PublishMetadata;
+use crate::similarity::{article_terms, Corpus};
+use crate::Article;
+
+/// How many of dev.to's most popular tags count as "in use" for lookups
+/// and the low-usage lint.
+pub const POPULAR_TAG_LIMIT: usize = 1000;
+
+/// A tag from `/tags`, which lists tags by popularity.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Tag {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub bg_color_hex: Option<String>,
+    #[serde(default)]
+    pub text_color_hex: Option<String>,
+}
+
+/// A tag the user follows, from `/follows/tags`. Points weigh the tag in
+/// the user's feed.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FollowedTag {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub points: f64,
+}
+
+/// 1-based popularity rank of `tag` within `popular` (case-insensitive).
+pub fn popularity_rank(popular: &[Tag], tag: &str) -> Option<usize> {
+    popular
+        .iter()
+        .position(|candidate| candidate.name.eq_ignore_ascii_case(tag))
+        .map(|index| index + 1)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagSuggestion {
+    pub tag: String,
+    pub score: f64,
+}
+
+/// Ranks tags for `article` using the user's own articles as the corpus:
+/// every other tagged article votes for its tags with its TF-IDF cosine
+/// similarity to the draft, and a tag that appears as a word in the draft
+/// adds that word's weight.
+pub fn suggest_tags(article: &Article, articles: &[Article], limit: usize) -> Vec<TagSuggestion> {
+    let terms: Vec<Vec<String>> = articles.iter().map(article_terms).collect();
+    let corpus = Corpus::new(&terms);
+    let draft = corpus.vector(&article_terms(article));
+
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    for tag in PublishMetadata::of(article).tags {
+        scores.entry(tag.to_lowercase()).or_default();
+    }
+    for (other, other_terms) in articles.iter().zip(&terms) {
+        if other.id == article.id {
+            continue;
+        }
+        let similarity = draft.cosine(&corpus.vector(other_terms));
+        for tag in PublishMetadata::of(other).tags {
+            *scores.entry(tag.to_lowercase()).or_default() += similarity;
+        }
+    }
+    let mut suggestions: Vec<TagSuggestion> = scores
+        .into_iter()
+        .map(|(tag, score)| TagSuggestion { score: score + draft.weight(&tag), tag })
+        .filter(|suggestion| suggestion.score > 0.0)
+        .collect();
+    suggestions.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.tag.cmp(&b.tag)));
+    suggestions.truncate(limit);
+    suggestions
+}
@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::get_config_dir;
+
+/// Used by `new` without `--template` and as the starting point for `template add`.
+pub const DEFAULT_TEMPLATE: &str = "\
+---
+title: {{title}}
+published: false
+tags:
+series: {{series}}
+---
+## Introduction
+
+## Conclusion
+";
+
+pub fn get_templates_dir() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("templates"))
+}
+
+pub fn template_path(name: &str) -> Result<PathBuf> {
+    anyhow::ensure!(
+        !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_'),
+        "Invalid template name '{name}' (use letters, digits, - and _)"
+    );
+    Ok(get_templates_dir()?.join(format!("{name}.md")))
+}
+
+/// Template names, sorted.
+pub fn list_templates() -> Result<Vec<String>> {
+    let dir = get_templates_dir()?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut names: Vec<String> = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+        .filter_map(|path| path.file_stem().and_then(|stem| stem.to_str()).map(str::to_string))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+pub fn load_template(name: &str) -> Result<String> {
+    let path = template_path(name)?;
+    fs::read_to_string(&path).with_context(|| {
+        format!("Template '{name}' not found (expected {}; see `dtdrafts template list`)", path.display())
+    })
+}
+
+/// Writes a template and returns its path.
+pub fn save_template(name: &str, content: &str) -> Result<PathBuf> {
+    let path = template_path(name)?;
+    fs::create_dir_all(get_templates_dir()?)?;
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+pub fn template_exists(name: &str) -> Result<bool> {
+    Ok(template_path(name)?.exists())
+}
+
+/// Replaces `{{name}}` (spaces inside the braces allowed) with `vars[name]`.
+/// Placeholders without a value are left as they are.
+pub fn render_template(template: &str, vars: &BTreeMap<String, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let placeholder = &rest[start..start + end + 2];
+        match vars.get(placeholder[2..placeholder.len() - 2].trim()) {
+            Some(value) => out.push_str(value),
+            None => out.push_str(placeholder),
+        }
+        rest = &rest[start + end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,231 @@
+//! Markdown rendered for the terminal, as `show` prints drafts.
+
+use std::sync::OnceLock;
+
+use colored::*;
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use syntect::easy::HighlightLines;
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
+use syntect::util::{as_24_bit_terminal_escaped, LinesWithEndings};
+
+/// Renders markdown for the terminal: styled headings and emphasis, indented
+/// lists and quotes, and syntax highlighted code blocks.
+pub fn render_markdown(markdown: &str) -> String {
+    let mut renderer = Renderer::default();
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    for event in Parser::new_ext(markdown, options) {
+        renderer.event(event);
+    }
+    renderer.out.trim_end().to_string() + "\n"
+}
+
+#[derive(Default)]
+struct Renderer {
+    out: String,
+    heading: Option<HeadingLevel>,
+    bold: usize,
+    italic: usize,
+    strikethrough: usize,
+    quote_depth: usize,
+    // One entry per open list: the next number for ordered lists
+    lists: Vec<Option<u64>>,
+    link: Option<String>,
+    code_block: Option<(String, String)>,
+    at_line_start: bool,
+}
+
+impl Renderer {
+    fn event(&mut self, event: Event) {
+        if let Some((_, code)) = &mut self.code_block {
+            match event {
+                Event::Text(text) => code.push_str(&text),
+                Event::End(TagEnd::CodeBlock) => {
+                    let (lang, code) = self.code_block.take().unwrap_or_default();
+                    self.push_code_block(&lang, &code);
+                }
+                _ => {}
+            }
+            return;
+        }
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.push_text(&text),
+            Event::Code(code) => {
+                let code = format!("`{code}`");
+                self.push_styled(code.cyan().to_string());
+            }
+            Event::Html(html) | Event::InlineHtml(html) => self.push_styled(html.dimmed().to_string()),
+            Event::SoftBreak => self.push_text(" "),
+            Event::HardBreak => self.newline(),
+            Event::Rule => {
+                self.push_styled("─".repeat(40).dimmed().to_string());
+                self.blank_line();
+            }
+            Event::TaskListMarker(done) => self.push_text(if done { "[x] " } else { "[ ] " }),
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.heading = Some(level);
+                let marker = "#".repeat(level as usize);
+                self.push_styled(format!("{marker} ").yellow().bold().to_string());
+            }
+            Tag::Strong => self.bold += 1,
+            Tag::Emphasis => self.italic += 1,
+            Tag::Strikethrough => self.strikethrough += 1,
+            Tag::BlockQuote(_) => self.quote_depth += 1,
+            Tag::List(start) => {
+                if !self.lists.is_empty() {
+                    self.newline();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let bullet = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}.", *number - 1)
+                    }
+                    _ => "•".to_string(),
+                };
+                self.push_styled(format!("{indent}{} ", bullet.yellow()));
+            }
+            Tag::CodeBlock(kind) => {
+                let lang = match kind {
+                    CodeBlockKind::Fenced(info) => info.split_whitespace().next().unwrap_or_default().to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                self.code_block = Some((lang, String::new()));
+            }
+            Tag::Link { dest_url, .. } => self.link = Some(dest_url.to_string()),
+            Tag::Image { dest_url, .. } => {
+                self.push_styled(format!("[image: {dest_url}] ").magenta().to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Heading(_) => {
+                self.heading = None;
+                self.blank_line();
+            }
+            TagEnd::Paragraph => {
+                if self.lists.is_empty() {
+                    self.blank_line();
+                } else {
+                    self.newline();
+                }
+            }
+            TagEnd::Strong => self.bold -= 1,
+            TagEnd::Emphasis => self.italic -= 1,
+            TagEnd::Strikethrough => self.strikethrough -= 1,
+            TagEnd::BlockQuote(_) => {
+                self.quote_depth -= 1;
+                self.blank_line();
+            }
+            TagEnd::List(_) => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+            }
+            TagEnd::Item => self.newline(),
+            TagEnd::Link => {
+                if let Some(url) = self.link.take() {
+                    self.push_styled(format!(" ({})", url.blue().underline()));
+                }
+            }
+            TagEnd::Table | TagEnd::TableRow | TagEnd::TableHead => self.newline(),
+            TagEnd::TableCell => self.push_text(" | "),
+            _ => {}
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let mut styled = text.normal();
+        if self.heading.is_some() {
+            styled = styled.yellow().bold();
+        }
+        if self.bold > 0 {
+            styled = styled.bold();
+        }
+        if self.italic > 0 {
+            styled = styled.italic();
+        }
+        if self.strikethrough > 0 {
+            styled = styled.strikethrough();
+        }
+        if self.link.is_some() {
+            styled = styled.underline();
+        }
+        self.push_styled(styled.to_string());
+    }
+
+    fn push_styled(&mut self, text: String) {
+        if self.at_line_start || self.out.is_empty() {
+            if self.quote_depth > 0 {
+                self.out.push_str(&"│ ".repeat(self.quote_depth).dimmed().to_string());
+            }
+            self.at_line_start = false;
+        }
+        self.out.push_str(&text);
+    }
+
+    fn newline(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+        self.at_line_start = true;
+    }
+
+    fn blank_line(&mut self) {
+        self.newline();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn push_code_block(&mut self, lang: &str, code: &str) {
+        self.newline();
+        if !colored::control::SHOULD_COLORIZE.should_colorize() {
+            for line in code.lines() {
+                self.out.push_str("    ");
+                self.out.push_str(line);
+                self.out.push('\n');
+            }
+            self.blank_line();
+            return;
+        }
+        let (syntaxes, themes) = highlighting();
+        let syntax = syntaxes
+            .find_syntax_by_token(lang)
+            .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+        let mut highlighter = HighlightLines::new(syntax, &themes.themes["base16-ocean.dark"]);
+        for line in LinesWithEndings::from(code) {
+            self.out.push_str("    ");
+            match highlighter.highlight_line(line, syntaxes) {
+                Ok(ranges) => {
+                    let escaped = as_24_bit_terminal_escaped(&ranges, false);
+                    self.out.push_str(escaped.trim_end_matches('\n'));
+                    self.out.push_str("\x1b[0m\n");
+                }
+                Err(_) => self.out.push_str(line),
+            }
+        }
+        self.blank_line();
+    }
+}
+
+// Loading the syntaxes and themes takes a while, so it happens once
+fn highlighting() -> &'static (SyntaxSet, ThemeSet) {
+    static SETS: OnceLock<(SyntaxSet, ThemeSet)> = OnceLock::new();
+    SETS.get_or_init(|| (SyntaxSet::load_defaults_newlines(), ThemeSet::load_defaults()))
+}
@@ -0,0 +1,160 @@
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+
+/// Controls which parts of a dev.to markdown body count towards the word total.
+/// Everything that isn't prose is excluded by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct WordCountOptions {
+    pub include_code: bool,
+    pub include_liquid: bool,
+    pub include_front_matter: bool,
+}
+
+impl WordCountOptions {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+pub fn word_count(markdown: &str) -> usize {
+    word_count_with(markdown, &WordCountOptions::default())
+}
+
+pub fn word_count_with(markdown: &str, options: &WordCountOptions) -> usize {
+    let (front_matter, body) = split_front_matter(markdown);
+    let mut count = 0;
+    if options.include_front_matter {
+        count += front_matter.map(count_words).unwrap_or(0);
+    }
+
+    let mut fence: Option<Fence> = None;
+    for line in body.lines() {
+        if let Some(open) = fence {
+            if open.is_closed_by(line) {
+                fence = None;
+            } else if options.include_code {
+                count += count_words(line);
+            }
+            continue;
+        }
+        if let Some(open) = Fence::parse(line) {
+            fence = Some(open);
+            continue;
+        }
+        if options.include_liquid {
+            count += count_words(line);
+        } else {
+            count += count_words(&strip_liquid_tags(line));
+        }
+    }
+    count
+}
+
+/// Splits a leading `---` delimited front matter block from the body.
+/// Returns `(None, markdown)` when there is no complete front matter block.
+pub fn split_front_matter(markdown: &str) -> (Option<&str>, &str) {
+    let rest = match markdown
+        .strip_prefix("---\n")
+        .or_else(|| markdown.strip_prefix("---\r\n"))
+    {
+        Some(rest) => rest,
+        None => return (None, markdown),
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let front_matter = &rest[..offset];
+            let body = &rest[offset + line.len()..];
+            return (Some(front_matter), body);
+        }
+        offset += line.len();
+    }
+    (None, markdown)
+}
+
+/// Removes `{% ... %}` liquid tags, keeping the surrounding text.
+pub fn strip_liquid_tags(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find("{%") {
+        match rest[start..].find("%}") {
+            Some(end) => {
+                out.push_str(&rest[..start]);
+                out.push(' ');
+                rest = &rest[start + end + 2..];
+            }
+            None => break,
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The prose of a markdown body as plain text, one entry per paragraph,
+/// heading, list item or table cell. Front matter, code, HTML and liquid tags
+/// are left out.
+pub fn prose_blocks(markdown: &str) -> Vec<String> {
+    prose_spans(markdown)
+        .iter()
+        .map(|spans| spans.iter().map(|(_, text)| strip_liquid_tags(text)).collect::<String>().trim().to_string())
+        .filter(|block| !block.is_empty())
+        .collect()
+}
+
+/// The runs of prose text in `markdown`, grouped per paragraph, heading, list
+/// item or table cell, each with its byte offset in `markdown`. Line breaks
+/// are a single space. Front matter, code and HTML are left out; liquid tags
+/// are kept, for the callers to drop as they need.
+pub(crate) fn prose_spans(markdown: &str) -> Vec<Vec<(usize, String)>> {
+    let (_, body) = split_front_matter(markdown);
+    let start = markdown.len() - body.len();
+    let mut blocks = Vec::new();
+    let mut current = Vec::new();
+    let mut in_code_block = false;
+    for (event, range) in Parser::new_ext(body, Options::ENABLE_TABLES).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Text(text) if !in_code_block => current.push((start + range.start, text.into_string())),
+            Event::SoftBreak | Event::HardBreak => current.push((start + range.start, " ".to_string())),
+            Event::End(TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::TableCell)
+                if !current.is_empty() =>
+            {
+                blocks.push(std::mem::take(&mut current));
+            }
+            _ => {}
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+    blocks
+}
+
+fn count_words(text: &str) -> usize {
+    text.split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Fence {
+    marker: char,
+    len: usize,
+}
+
+impl Fence {
+    pub(crate) fn parse(line: &str) -> Option<Self> {
+        let trimmed = line.trim_start();
+        let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+        let len = trimmed.chars().take_while(|c| *c == marker).count();
+        (len >= 3).then_some(Self { marker, len })
+    }
+
+    pub(crate) fn is_closed_by(&self, line: &str) -> bool {
+        let trimmed = line.trim();
+        let len = trimmed.chars().take_while(|c| *c == self.marker).count();
+        len >= self.len && len == trimmed.chars().count()
+    }
+}
@@ -0,0 +1,48 @@
+//! Serde support for the `Option<DateTime<Utc>>` timestamps on [`crate::Article`],
+//! used as `#[serde(default, with = "crate::timestamp")]`.
+//!
+//! Timestamps are written as RFC 3339 in UTC. Reading is lenient so caches
+//! written while timestamps were plain strings still load: any UTC offset,
+//! naive date-times (taken as UTC) and bare dates are accepted, and values
+//! that don't parse at all load as `None` instead of failing the whole cache.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Deserializer, Serializer};
+
+const NAIVE_FORMATS: [&str; 2] = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"];
+
+pub fn parse(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Some(at.with_timezone(&Utc));
+    }
+    NAIVE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))
+        .map(|at| at.and_utc())
+}
+
+/// RFC 3339 with a `Z` suffix, e.g. `2024-05-01T09:30:00Z`, the format
+/// dev.to uses.
+pub fn format(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+pub fn serialize<S: Serializer>(at: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error> {
+    match at {
+        Some(at) => serializer.serialize_some(&format(*at)),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error> {
+    let value = Option::<String>::deserialize(deserializer)?;
+    Ok(value.as_deref().and_then(|value| {
+        let at = parse(value);
+        if at.is_none() && !value.is_empty() {
+            tracing::debug!(value, "ignoring unparseable timestamp");
+        }
+        at
+    }))
+}
@@ -0,0 +1,158 @@
+//! Tables of contents built from a draft's headings, kept between
+//! `<!-- toc -->` and `<!-- tocstop -->` markers in the body, and outlines
+//! of the sections under them.
+
+use std::ops::Range;
+
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+use crate::text::{split_front_matter, word_count_with, WordCountOptions};
+
+pub const TOC_START: &str = "<!-- toc -->";
+pub const TOC_END: &str = "<!-- tocstop -->";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    pub level: u8,
+    pub text: String,
+    /// The fragment dev.to links the heading under, without the `#`
+    pub anchor: String,
+}
+
+/// The headings of a markdown body, outside code blocks, with unique anchors.
+pub fn headings(markdown: &str) -> Vec<Heading> {
+    located_headings(markdown).into_iter().map(|(_, heading)| heading).collect()
+}
+
+// With the byte range of each heading in `markdown`
+pub(crate) fn located_headings(markdown: &str) -> Vec<(Range<usize>, Heading)> {
+    let (_, body) = split_front_matter(markdown);
+    let body_start = markdown.len() - body.len();
+    let mut headings: Vec<(Range<usize>, Heading)> = Vec::new();
+    let mut current: Option<(usize, u8, String)> = None;
+    for (event, range) in Parser::new_ext(body, Options::ENABLE_TABLES).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => current = Some((body_start + range.start, level as u8, String::new())),
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, _, heading)) = &mut current {
+                    heading.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                let Some((start, level, text)) = current.take() else { continue };
+                let end = body_start + range.end;
+                let text = text.trim().to_string();
+                let base = anchor(&text);
+                let taken = |candidate: &str| headings.iter().any(|(_, heading)| heading.anchor == candidate);
+                let mut unique = base.clone();
+                let mut suffix = 1;
+                while taken(&unique) {
+                    unique = format!("{base}-{suffix}");
+                    suffix += 1;
+                }
+                headings.push((start..end, Heading { level, text, anchor: unique }));
+            }
+            _ => {}
+        }
+    }
+    headings
+}
+
+/// The anchor dev.to gives a heading: lowercase, punctuation dropped and
+/// spaces turned into hyphens.
+pub fn anchor(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-' || *c == '_')
+        .map(|c| if c == ' ' { '-' } else { c })
+        .collect()
+}
+
+/// A nested markdown list linking to every heading down to `max_level`,
+/// indented from the highest level present.
+pub fn render_toc(headings: &[Heading], max_level: u8) -> String {
+    let included: Vec<&Heading> = headings.iter().filter(|heading| heading.level <= max_level).collect();
+    let top = included.iter().map(|heading| heading.level).min().unwrap_or(1);
+    included
+        .iter()
+        .map(|heading| {
+            let indent = "  ".repeat(usize::from(heading.level - top));
+            format!("{indent}- [{}](#{})\n", heading.text, heading.anchor)
+        })
+        .collect()
+}
+
+/// Puts `toc` between the markers, replacing what was there before. A lone
+/// start marker gets its end marker; without markers the TOC goes in before
+/// the first heading, or at the top of the body.
+pub fn insert_toc(markdown: &str, toc: &str) -> String {
+    let block = format!("{TOC_START}\n{toc}{TOC_END}");
+    if let Some(start) = markdown.find(TOC_START) {
+        let after = start + TOC_START.len();
+        let end = markdown[after..].find(TOC_END).map_or(after, |end| after + end + TOC_END.len());
+        return format!("{}{block}{}", &markdown[..start], &markdown[end..]);
+    }
+
+    let (_, body) = split_front_matter(markdown);
+    let at = located_headings(markdown).first().map_or(markdown.len() - body.len(), |(range, _)| range.start);
+    format!("{}{block}\n\n{}", &markdown[..at], &markdown[at..])
+}
+
+/// A heading with the sections nested under it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub heading: Heading,
+    /// Words between the heading and the next one, counted as
+    /// [`extract_outline_with`] was told to
+    pub words: usize,
+    pub subsections: Vec<Section>,
+}
+
+impl Section {
+    /// Words in the section and all its subsections.
+    pub fn total_words(&self) -> usize {
+        self.words + self.subsections.iter().map(Section::total_words).sum::<usize>()
+    }
+}
+
+/// The heading hierarchy of a markdown body. A heading goes under the
+/// closest heading of a higher level before it, so skipped levels (an `###`
+/// right under a `#`) still nest. Text before the first heading isn't part
+/// of any section.
+pub fn extract_outline(markdown: &str) -> Vec<Section> {
+    extract_outline_with(markdown, &WordCountOptions::default())
+}
+
+/// [`extract_outline`] with the sections' words counted like `stats` does.
+pub fn extract_outline_with(markdown: &str, options: &WordCountOptions) -> Vec<Section> {
+    let located = located_headings(markdown);
+    let mut flat: Vec<Section> = Vec::new();
+    for (index, (range, heading)) in located.iter().enumerate() {
+        let end = located.get(index + 1).map_or(markdown.len(), |(next, _)| next.start);
+        let words = word_count_with(&markdown[range.end..end], options);
+        flat.push(Section { heading: heading.clone(), words, subsections: Vec::new() });
+    }
+
+    // Each open section is closed, into its parent, once a heading of the
+    // same or a higher level comes along
+    let mut roots = Vec::new();
+    let mut open: Vec<Section> = Vec::new();
+    for section in flat {
+        while open.last().is_some_and(|last| last.heading.level >= section.heading.level) {
+            close_section(&mut open, &mut roots);
+        }
+        open.push(section);
+    }
+    while !open.is_empty() {
+        close_section(&mut open, &mut roots);
+    }
+    roots
+}
+
+fn close_section(open: &mut Vec<Section>, roots: &mut Vec<Section>) {
+    let Some(section) = open.pop() else { return };
+    match open.last_mut() {
+        Some(parent) => parent.subsections.push(section),
+        None => roots.push(section),
+    }
+}
@@ -0,0 +1,76 @@
+//! Drafts deleted through dtdrafts, kept in full under
+//! `~/.dtdrafts/trash/<id>.json` so they can be re-created.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{get_config_dir, Article};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrashedDraft {
+    pub deleted_at: DateTime<Utc>,
+    /// The draft as it was deleted, body included
+    pub article: Article,
+}
+
+pub fn get_trash_dir() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("trash"))
+}
+
+fn get_trash_file(id: u64) -> Result<PathBuf> {
+    Ok(get_trash_dir()?.join(format!("{id}.json")))
+}
+
+/// Archives `article` before it is deleted.
+pub fn put(article: &Article, deleted_at: DateTime<Utc>) -> Result<PathBuf> {
+    fs::create_dir_all(get_trash_dir()?)?;
+    let path = get_trash_file(article.id)?;
+    let trashed = TrashedDraft { deleted_at, article: article.clone() };
+    fs::write(&path, serde_json::to_string_pretty(&trashed)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Everything in the trash, most recently deleted first.
+pub fn list() -> Result<Vec<TrashedDraft>> {
+    let dir = get_trash_dir()?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut trashed = Vec::new();
+    for entry in fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "json") {
+            trashed.push(read(&path)?);
+        }
+    }
+    trashed.sort_by_key(|draft| std::cmp::Reverse(draft.deleted_at));
+    Ok(trashed)
+}
+
+pub fn get(id: u64) -> Result<TrashedDraft> {
+    let path = get_trash_file(id)?;
+    if !path.exists() {
+        return Err(anyhow::anyhow!("Article {id} is not in the trash (see `dtdrafts trash list`)"));
+    }
+    read(&path)
+}
+
+fn read(path: &Path) -> Result<TrashedDraft> {
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Invalid trashed draft in {}", path.display()))
+}
+
+/// Takes a draft out of the trash. Returns whether it was there.
+pub fn remove(id: u64) -> Result<bool> {
+    let path = get_trash_file(id)?;
+    if !path.exists() {
+        return Ok(false);
+    }
+    fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    Ok(true)
+}
@@ -0,0 +1,148 @@
+//! Vocabulary analysis (`vocab`): the words and phrases used most across
+//! drafts, to spot the ones you lean on too often. Only prose is counted;
+//! code, front matter and liquid tags are left out.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::analytics::csv_field;
+use crate::similarity::STOPWORDS;
+use crate::text::prose_blocks;
+use crate::Article;
+
+/// The longest phrases counted.
+pub const MAX_NGRAM: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VocabSortKey {
+    Count,
+    Drafts,
+    Term,
+}
+
+impl FromStr for VocabSortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "count" => Ok(VocabSortKey::Count),
+            "drafts" => Ok(VocabSortKey::Drafts),
+            "term" => Ok(VocabSortKey::Term),
+            _ => Err(format!("unknown sort key '{s}' (expected count, drafts or term)")),
+        }
+    }
+}
+
+impl fmt::Display for VocabSortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            VocabSortKey::Count => "count",
+            VocabSortKey::Drafts => "drafts",
+            VocabSortKey::Term => "term",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermCount {
+    /// A word, or words separated by single spaces
+    pub term: String,
+    pub count: usize,
+    /// How many drafts use it
+    pub drafts: usize,
+    /// Uses per 1000 words of prose
+    pub per_1000: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VocabOptions {
+    /// Words per term, from 1 to [`MAX_NGRAM`]
+    pub ngram: usize,
+    /// Drops single stop words, and phrases starting or ending with one
+    pub skip_stopwords: bool,
+    /// Terms used fewer times are left out
+    pub min_count: usize,
+}
+
+impl Default for VocabOptions {
+    fn default() -> Self {
+        Self { ngram: 1, skip_stopwords: true, min_count: 2 }
+    }
+}
+
+/// Lowercase words of prose, in runs that phrases don't cross: a run ends
+/// with each sentence, clause or block.
+fn word_runs(article: &Article) -> Vec<Vec<String>> {
+    let mut runs = Vec::new();
+    for block in prose_blocks(article.body_markdown.as_deref().unwrap_or_default()) {
+        for clause in block.split(['.', '!', '?', ',', ';', ':', '(', ')', '"', '“', '”']) {
+            let words: Vec<String> = clause
+                .split_whitespace()
+                .filter(|word| !word.contains("://"))
+                .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+                .filter(|word| word.chars().any(char::is_alphabetic))
+                .collect();
+            if !words.is_empty() {
+                runs.push(words);
+            }
+        }
+    }
+    runs
+}
+
+fn is_stopword(word: &str) -> bool {
+    STOPWORDS.contains(&word) || word.chars().count() < 2
+}
+
+/// Counts the terms of `options.ngram` words in the prose of `articles`,
+/// most used first (ties A to Z).
+pub fn count_terms(articles: &[&Article], options: &VocabOptions) -> Vec<TermCount> {
+    let n = options.ngram.clamp(1, MAX_NGRAM);
+    let mut counts: BTreeMap<String, (usize, BTreeSet<u64>)> = BTreeMap::new();
+    let mut total_words = 0;
+    for article in articles {
+        for run in word_runs(article) {
+            total_words += run.len();
+            for window in run.windows(n) {
+                let (first, last) = (&window[0], &window[n - 1]);
+                if options.skip_stopwords && (is_stopword(first) || is_stopword(last)) {
+                    continue;
+                }
+                let (count, drafts) = counts.entry(window.join(" ")).or_default();
+                *count += 1;
+                drafts.insert(article.id);
+            }
+        }
+    }
+    let mut terms: Vec<TermCount> = counts
+        .into_iter()
+        .filter(|(_, (count, _))| *count >= options.min_count.max(1))
+        .map(|(term, (count, drafts))| TermCount {
+            term,
+            count,
+            drafts: drafts.len(),
+            per_1000: count as f64 * 1000.0 / total_words.max(1) as f64,
+        })
+        .collect();
+    sort_terms(&mut terms, VocabSortKey::Count);
+    terms
+}
+
+/// Counts sort highest first, terms A to Z; ties keep their order.
+pub fn sort_terms(terms: &mut [TermCount], key: VocabSortKey) {
+    match key {
+        VocabSortKey::Count => terms.sort_by_key(|term| Reverse(term.count)),
+        VocabSortKey::Drafts => terms.sort_by_key(|term| Reverse(term.drafts)),
+        VocabSortKey::Term => terms.sort_by(|a, b| a.term.cmp(&b.term)),
+    }
+}
+
+pub fn to_csv(terms: &[TermCount]) -> String {
+    let mut out = String::from("term,count,drafts,per_1000_words\n");
+    for term in terms {
+        out.push_str(&format!("{},{},{},{:.2}\n", csv_field(&term.term), term.count, term.drafts, term.per_1000));
+    }
+    out
+}
@@ -0,0 +1,445 @@
+use dtdrafts_core::client::{ArticleInput, DevToClient};
+use dtdrafts_core::crosspost::{CrosspostInput, HashnodeClient, HashnodeConfig, MediumClient, MediumConfig};
+use serde_json::{json, Value};
+use wiremock::matchers::{body_json, body_partial_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const API_KEY: &str = "test-key";
+
+fn article_json(id: u64) -> Value {
+    json!({
+        "id": id,
+        "title": format!("Draft {id}"),
+        "description": null,
+        "body_markdown": format!("Body of draft {id}"),
+        "url": format!("https://dev.to/user/draft-{id}"),
+        "canonical_url": null,
+        "url_with_preview": null,
+        "published": false,
+        "created_at": "2024-05-01T00:00:00Z",
+        "updated_at": null,
+        "tags": ["rust"],
+        "slug": format!("draft-{id}"),
+        "user": {"username": "user"}
+    })
+}
+
+fn articles_json(ids: std::ops::RangeInclusive<u64>) -> Value {
+    Value::Array(ids.map(article_json).collect())
+}
+
+fn client(server: &MockServer) -> DevToClient {
+    DevToClient::builder()
+        .api_key(API_KEY)
+        .base_url(format!("{}/api", server.uri()))
+        .build()
+        .unwrap()
+        .with_progress(false)
+}
+
+#[tokio::test]
+async fn test_get_my_articles_follows_pagination() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/unpublished"))
+        .and(query_param("page", "1"))
+        .and(query_param("per_page", "1000"))
+        .and(header("api-key", API_KEY))
+        .respond_with(ResponseTemplate::new(200).set_body_json(articles_json(1..=1000)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/unpublished"))
+        .and(query_param("page", "2"))
+        .and(header("api-key", API_KEY))
+        .respond_with(ResponseTemplate::new(200).set_body_json(articles_json(1001..=1003)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let articles = client(&server).get_my_articles().await.unwrap();
+    assert_eq!(articles.len(), 1003);
+    assert_eq!(articles.last().unwrap().title, "Draft 1003");
+}
+
+#[tokio::test]
+async fn test_articles_without_an_author_get_the_account_username() {
+    let server = MockServer::start().await;
+    let mut anonymous = article_json(2);
+    anonymous.as_object_mut().unwrap().remove("user");
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/unpublished"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([article_json(1), anonymous])))
+        .mount(&server)
+        .await;
+
+    let client = DevToClient::builder()
+        .api_key(API_KEY)
+        .base_url(format!("{}/api", server.uri()))
+        .username("me")
+        .build()
+        .unwrap()
+        .with_progress(false);
+    let articles = client.get_my_articles().await.unwrap();
+    assert_eq!(articles[0].edit_url(), "https://dev.to/user/draft-1/edit");
+    assert_eq!(articles[1].edit_url(), "https://dev.to/me/draft-2/edit");
+}
+
+#[tokio::test]
+async fn test_unauthorized_requests_point_at_the_api_key() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/unpublished"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({"error": "unauthorized", "status": 401})))
+        .mount(&server)
+        .await;
+
+    let error = client(&server).get_my_articles().await.unwrap_err();
+    let message = format!("{error:#}");
+    assert!(message.contains("401"), "{message}");
+    assert!(message.contains("check your API key"), "{message}");
+}
+
+#[tokio::test]
+async fn test_rate_limited_requests_are_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/unpublished"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/unpublished"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(articles_json(1..=2)))
+        .mount(&server)
+        .await;
+
+    let articles = client(&server).get_my_articles().await.unwrap();
+    assert_eq!(articles.len(), 2);
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_persistent_rate_limiting_gives_up() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/unpublished"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+        .mount(&server)
+        .await;
+
+    let error = client(&server).get_my_articles().await.unwrap_err();
+    assert!(format!("{error:#}").contains("kept rate limiting"));
+    // The first attempt plus three retries
+    assert_eq!(server.received_requests().await.unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn test_huge_retry_after_does_not_overflow() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/unpublished"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("retry-after", "18446744073709551615")
+                .set_body_json(articles_json(1..=2)),
+        )
+        .mount(&server)
+        .await;
+
+    let articles = client(&server).get_my_articles().await.unwrap();
+    assert_eq!(articles.len(), 2);
+}
+
+#[tokio::test]
+async fn test_get_me_checks_the_api_key() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/users/me"))
+        .and(header("api-key", API_KEY))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 42, "username": "user", "name": "A User"})))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/users/me"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({"error": "unauthorized", "status": 401})))
+        .mount(&server)
+        .await;
+
+    let user = client(&server).get_me().await.unwrap();
+    assert_eq!((user.id, user.username.as_str()), (42, "user"));
+    let other = DevToClient::builder().api_key("wrong").base_url(format!("{}/api", server.uri())).build().unwrap();
+    assert!(format!("{:#}", other.get_me().await.unwrap_err()).contains("rejected the API key"));
+}
+
+#[tokio::test]
+async fn test_malformed_json_is_reported() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/unpublished"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(r#"[{"id": 1, "title": "#, "application/json"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/all"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{"id": 1}])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/all"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let error = client.get_my_articles().await.unwrap_err();
+    assert!(format!("{error:#}").contains("Failed to parse JSON response"));
+    // Valid JSON that isn't an article is skipped, unless in strict mode
+    let error = client.get_my_article(1).await.unwrap_err();
+    assert!(format!("{error:#}").contains("was not found"));
+    let strict = DevToClient::builder()
+        .base_url(format!("{}/api", server.uri()))
+        .strict(true)
+        .build()
+        .unwrap();
+    let error = strict.get_my_article(1).await.unwrap_err();
+    assert!(format!("{error:#}").contains("article 1 is malformed"));
+}
+
+#[tokio::test]
+async fn test_malformed_articles_are_skipped() {
+    let server = MockServer::start().await;
+    let mut articles = articles_json(1..=3);
+    articles[1]["tags"] = json!("rust, cli");
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/unpublished"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(articles))
+        .mount(&server)
+        .await;
+
+    let articles = client(&server).get_my_articles().await.unwrap();
+    let ids: Vec<u64> = articles.iter().map(|article| article.id).collect();
+    assert_eq!(ids, [1, 3]);
+}
+
+#[tokio::test]
+async fn test_malformed_article_on_a_full_page_does_not_end_paging() {
+    let server = MockServer::start().await;
+    let mut first_page = articles_json(1..=1000);
+    first_page[500]["tags"] = json!("rust, cli");
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/unpublished"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(first_page))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/unpublished"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(articles_json(1001..=1002)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let articles = client(&server).get_my_articles().await.unwrap();
+    assert_eq!(articles.len(), 1001);
+    assert!(!articles.iter().any(|article| article.id == 501));
+    assert_eq!(articles.last().unwrap().id, 1002);
+}
+
+#[tokio::test]
+async fn test_get_my_articles_by_id_reads_past_a_page_that_failed_to_parse() {
+    let server = MockServer::start().await;
+    let mut first_page = articles_json(1..=2);
+    first_page[0]["tags"] = json!("rust");
+    first_page[1]["tags"] = json!("cli");
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/all"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(first_page))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/all"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(articles_json(3..=3)))
+        .mount(&server)
+        .await;
+
+    assert_eq!(client(&server).get_my_article(3).await.unwrap().id, 3);
+}
+
+#[tokio::test]
+async fn test_get_my_article_pages_until_found() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/all"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(articles_json(1..=3)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/all"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(articles_json(4..=6)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/articles/me/all"))
+        .and(query_param("page", "3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        // Only the lookup of a missing id gets past the page with the article
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    assert_eq!(client.get_my_article(5).await.unwrap().title, "Draft 5");
+    assert!(client.get_my_article(42).await.is_err());
+}
+
+#[tokio::test]
+async fn test_update_article_sends_the_envelope() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/api/articles/7"))
+        .and(header("api-key", API_KEY))
+        .and(body_json(json!({"article": {"title": "New title", "published": false}})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(article_json(7)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/api/articles/8"))
+        .respond_with(ResponseTemplate::new(422).set_body_string("Title can't be blank"))
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let input = ArticleInput { title: Some("New title".to_string()), published: Some(false), ..Default::default() };
+    assert_eq!(client.update_article(7, &input).await.unwrap().id, 7);
+    let error = client.update_article(8, &ArticleInput::default()).await.unwrap_err();
+    let message = format!("{error:#}");
+    assert!(message.contains("422") && message.contains("Title can't be blank"), "{message}");
+}
+
+#[tokio::test]
+async fn test_upload_image_posts_to_image_uploads() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/image_uploads"))
+        .and(header("api-key", API_KEY))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"links": ["https://media.dev.to/cover.png"]})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let url = client(&server).upload_image("cover.png", b"png".to_vec()).await.unwrap();
+    assert_eq!(url, "https://media.dev.to/cover.png");
+}
+
+fn crosspost_input() -> CrosspostInput {
+    CrosspostInput {
+        title: "Hello".to_string(),
+        body_markdown: "Body".to_string(),
+        tags: vec!["Rust".to_string()],
+        canonical_url: "https://dev.to/user/hello".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_hashnode_publish_sends_the_mutation() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(header("Authorization", "hn-token"))
+        .and(body_partial_json(json!({"variables": {"input": {
+            "title": "Hello",
+            "contentMarkdown": "Body",
+            "publicationId": "pub-1",
+            "tags": [{"slug": "rust", "name": "Rust"}],
+            "originalArticleURL": "https://dev.to/user/hello",
+        }}})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"data": {"publishPost": {"post": {"url": "https://blog.example/hello"}}}})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = HashnodeConfig { token: "hn-token".to_string(), publication_id: "pub-1".to_string() };
+    let client = HashnodeClient::new(config).unwrap().with_api_url(format!("{}/", server.uri()));
+    assert_eq!(client.publish(&crosspost_input()).await.unwrap(), "https://blog.example/hello");
+}
+
+#[tokio::test]
+async fn test_hashnode_graphql_errors_are_reported() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"errors": [{"message": "Invalid token"}], "data": null})),
+        )
+        .mount(&server)
+        .await;
+
+    let config = HashnodeConfig { token: "bad".to_string(), publication_id: "pub-1".to_string() };
+    let client = HashnodeClient::new(config).unwrap().with_api_url(server.uri());
+    let error = client.publish(&crosspost_input()).await.unwrap_err();
+    assert!(format!("{error:#}").contains("Invalid token"), "{error:#}");
+}
+
+#[tokio::test]
+async fn test_medium_publish_creates_a_draft_for_the_user() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me"))
+        .and(header("Authorization", "Bearer md-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"data": {"id": "u1"}})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/users/u1/posts"))
+        .and(header("Authorization", "Bearer md-token"))
+        .and(body_json(json!({
+            "title": "Hello",
+            "contentFormat": "markdown",
+            "content": "# Hello\n\nBody",
+            "tags": ["Rust"],
+            "canonicalUrl": "https://dev.to/user/hello",
+            "publishStatus": "draft",
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({"data": {"url": "https://medium.com/p/1"}})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = MediumClient::new(MediumConfig { token: "md-token".to_string() })
+        .unwrap()
+        .with_api_url(format!("{}/v1/", server.uri()));
+    assert_eq!(client.publish(&crosspost_input()).await.unwrap(), "https://medium.com/p/1");
+}
+
+#[tokio::test]
+async fn test_medium_rejection_is_reported() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+
+    let client = MediumClient::new(MediumConfig { token: "bad".to_string() })
+        .unwrap()
+        .with_api_url(format!("{}/v1", server.uri()));
+    let error = client.publish(&crosspost_input()).await.unwrap_err();
+    let message = format!("{error:#}");
+    assert!(message.contains("Medium rejected the request to look up your user") && message.contains("401"), "{message}");
+}
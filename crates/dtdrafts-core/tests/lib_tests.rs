@@ -0,0 +1,2401 @@
+use dtdrafts_core::*;
+
+fn sample_articles() -> Vec<Article> {
+    vec![
+        Article {
+            id: 1,
+            title: "Rust Tips".to_string(),
+            description: Some("Learn Rust".to_string()),
+            body_markdown: Some("Rust is great for CLI tools.".to_string()),
+            url: "https://dev.to/user/rust-tips".to_string(),
+            canonical_url: None,
+            url_with_preview: None,
+            published: false,
+            created_at: None,
+            updated_at: None,
+            tags: Some(vec!["rust".to_string(), "cli".to_string()]),
+            slug: "rust-tips".to_string(),
+            user: ArticleUser { username: "user".to_string() },
+            series: None,
+            organization: None,
+            cover_image: None,
+            social_image: None,
+            reading_time_minutes: None,
+            comments_count: 0,
+            public_reactions_count: 0,
+            extra: Default::default(),
+        },
+        Article {
+            id: 2,
+            title: "Kotlin Guide".to_string(),
+            description: Some("Kotlin basics".to_string()),
+            body_markdown: Some("Kotlin is a modern language.".to_string()),
+            url: "https://dev.to/user/kotlin-guide".to_string(),
+            canonical_url: None,
+            url_with_preview: None,
+            published: true,
+            created_at: None,
+            updated_at: None,
+            tags: Some(vec!["kotlin".to_string(), "android".to_string()]),
+            slug: "kotlin-guide".to_string(),
+            user: ArticleUser { username: "user".to_string() },
+            series: None,
+            organization: None,
+            cover_image: None,
+            social_image: None,
+            reading_time_minutes: None,
+            comments_count: 0,
+            public_reactions_count: 0,
+            extra: Default::default(),
+        },
+        Article {
+            id: 3,
+            title: "CLI Tricks".to_string(),
+            description: Some("Tips for CLI".to_string()),
+            body_markdown: Some("Use Rust or Python for CLI.".to_string()),
+            url: "https://dev.to/user/cli-tricks".to_string(),
+            canonical_url: None,
+            url_with_preview: None,
+            published: false,
+            created_at: None,
+            updated_at: None,
+            tags: Some(vec!["cli".to_string(), "tools".to_string()]),
+            slug: "cli-tricks".to_string(),
+            user: ArticleUser { username: "user".to_string() },
+            series: None,
+            organization: None,
+            cover_image: None,
+            social_image: None,
+            reading_time_minutes: None,
+            comments_count: 0,
+            public_reactions_count: 0,
+            extra: Default::default(),
+        },
+    ]
+}
+
+/// Renders with colours forced on or off. The override is process-wide, so
+/// the tests that set it take turns rather than switching it under each other.
+#[cfg(feature = "terminal")]
+fn with_colors<T>(enabled: bool, render: impl FnOnce() -> T) -> T {
+    static OVERRIDE: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    let _turn = OVERRIDE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    colored::control::set_override(enabled);
+    let rendered = render();
+    colored::control::unset_override();
+    rendered
+}
+
+#[cfg(feature = "terminal")]
+fn without_colors<T>(render: impl FnOnce() -> T) -> T {
+    with_colors(false, render)
+}
+
+#[test]
+fn test_search_by_title() {
+    let articles = sample_articles();
+    let found = search_articles(&articles, "rust");
+    assert_eq!(found.len(), 2);
+    let titles: Vec<_> = found.iter().map(|a| a.title.as_str()).collect();
+    assert!(titles.contains(&"Rust Tips"));
+    assert!(titles.contains(&"CLI Tricks"));
+}
+
+#[test]
+fn test_search_by_body_markdown() {
+    let articles = sample_articles();
+    let found = search_articles(&articles, "modern language");
+    // Only published article has this, so should not be found
+    assert_eq!(found.len(), 0);
+    let found2 = search_articles(&articles, "python");
+    assert_eq!(found2.len(), 1);
+    assert_eq!(found2[0].title, "CLI Tricks");
+}
+
+#[test]
+fn test_search_by_tag() {
+    let articles = sample_articles();
+    let found = search_articles(&articles, "cli");
+    // Two unpublished articles have 'cli' tag
+    assert_eq!(found.len(), 2);
+    let titles: Vec<_> = found.iter().map(|a| a.title.as_str()).collect();
+    assert!(titles.contains(&"Rust Tips"));
+    assert!(titles.contains(&"CLI Tricks"));
+}
+
+#[test]
+fn test_get_draft_articles() {
+    let articles = sample_articles();
+    let drafts = get_draft_articles(&articles);
+    assert_eq!(drafts.len(), 2);
+    let titles: Vec<_> = drafts.iter().map(|a| a.title.as_str()).collect();
+    assert!(titles.contains(&"Rust Tips"));
+    assert!(titles.contains(&"CLI Tricks"));
+}
+
+#[test]
+fn test_word_count_excludes_code_liquid_and_front_matter() {
+    let body = "---\ntitle: Hello World\ntags: rust\n---\nSome prose here.\n\n```rust\nfn main() {}\n```\n\n{% embed https://example.com %}\nMore words {% github owner/repo %} after.\n";
+    assert_eq!(dtdrafts_core::text::word_count(body), 6);
+}
+
+#[test]
+fn test_word_count_with_options() {
+    use dtdrafts_core::text::{word_count_with, WordCountOptions};
+    let body = "---\ntitle: Hello\n---\nOne two.\n~~~~\nlet x = 1;\n~~~~\n{% youtube abc %}\n";
+    let all = WordCountOptions { include_code: true, include_liquid: true, include_front_matter: true };
+    assert_eq!(word_count_with(body, &all), 2 + 2 + 3 + 2);
+    let code_only = WordCountOptions { include_code: true, ..Default::default() };
+    assert_eq!(word_count_with(body, &code_only), 2 + 3);
+}
+
+#[test]
+fn test_word_count_ignores_markdown_symbols_and_unclosed_front_matter() {
+    let body = "---\nnot front matter\n## Heading\n- item one\n";
+    assert_eq!(dtdrafts_core::text::word_count(body), 6);
+}
+
+#[test]
+fn test_filter_requires_all_tags() {
+    let articles = sample_articles();
+    let filter = ArticleFilter::drafts().tag("CLI").tag("rust");
+    let found = filter_articles(&articles, &filter);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].title, "Rust Tips");
+}
+
+#[test]
+fn test_filter_published_state() {
+    let articles = sample_articles();
+    let published = filter_articles(&articles, &ArticleFilter::new().published(true));
+    assert_eq!(published.len(), 1);
+    assert_eq!(published[0].title, "Kotlin Guide");
+    assert_eq!(filter_articles(&articles, &ArticleFilter::new()).len(), 3);
+}
+
+#[test]
+fn test_search_with_tag_filter() {
+    let articles = sample_articles();
+    let filter = ArticleFilter::drafts().tag("tools");
+    let found = search_articles_with(&articles, "rust", &filter);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].title, "CLI Tricks");
+}
+
+#[test]
+fn test_article_timestamp_accessors() {
+    let mut articles = sample_articles();
+    articles[0].created_at = timestamp::parse("2024-03-01T10:00:00+09:00");
+    articles[0].updated_at = timestamp::parse("not a date");
+    assert_eq!(
+        articles[0].created_at_utc(),
+        Some(parse_date("2024-03-01T01:00:00Z").unwrap())
+    );
+    assert_eq!(articles[0].updated_at_utc(), None);
+}
+
+#[test]
+fn test_article_timestamps_load_from_string_caches() {
+    let mut json = serde_json::to_value(&sample_articles()[0]).unwrap();
+    json["created_at"] = serde_json::json!("2024-03-01T10:00:00.000+09:00");
+    json["updated_at"] = serde_json::json!("not a date");
+    let article: Article = serde_json::from_value(json).unwrap();
+    assert_eq!(article.created_at, parse_date("2024-03-01T01:00:00Z").ok());
+    assert_eq!(article.updated_at, None);
+    assert_eq!(timestamp::parse("2024-03-01 01:00:00"), parse_date("2024-03-01T01:00:00Z").ok());
+    assert_eq!(timestamp::parse("2024-03-01"), parse_date("2024-03-01T00:00:00Z").ok());
+
+    let saved = serde_json::to_value(&article).unwrap();
+    assert_eq!(saved["created_at"], "2024-03-01T01:00:00Z");
+    assert_eq!(saved["updated_at"], serde_json::Value::Null);
+    let compact = dtdrafts_core::cache::CacheFormat::Compact;
+    let decoded: Vec<Article> = compact.decode(&compact.encode(&[&article]).unwrap()).unwrap();
+    assert_eq!(decoded[0].created_at, article.created_at);
+}
+
+#[test]
+fn test_filter_by_date_range() {
+    let mut articles = sample_articles();
+    articles[0].updated_at = timestamp::parse("2022-05-01T00:00:00Z");
+    articles[2].updated_at = timestamp::parse("2024-02-01T00:00:00Z");
+
+    let stale = ArticleFilter::drafts().updated_before(parse_date("2023-06-30").unwrap());
+    let found = filter_articles(&articles, &stale);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].title, "Rust Tips");
+
+    let recent = ArticleFilter::drafts().updated_after(parse_date("2024-01-01").unwrap());
+    let found = filter_articles(&articles, &recent);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].title, "CLI Tricks");
+
+    // Both bounds include a draft dated exactly on them
+    let on_the_day = ArticleFilter::drafts()
+        .updated_after(parse_date("2024-02-01").unwrap())
+        .updated_before(parse_date_end("2024-02-01").unwrap());
+    let found = filter_articles(&articles, &on_the_day);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].title, "CLI Tricks");
+
+    // A date-only upper bound takes in the whole day
+    articles[0].updated_at = timestamp::parse("2023-06-30T10:00:00Z");
+    let found = filter_articles(&articles, &stale);
+    assert!(found.is_empty());
+    let stale = ArticleFilter::drafts().updated_before(parse_date_end("2023-06-30").unwrap());
+    let found = filter_articles(&articles, &stale);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].title, "Rust Tips");
+    let parsed = filter::parse_filter("updated-before:2023-06-30").unwrap();
+    assert_eq!(filter_articles(&articles, &parsed).len(), 1);
+    assert_eq!(parse_date_end("2023-06-30T10:00:00Z").unwrap(), parse_date("2023-06-30T10:00:00Z").unwrap());
+
+    // Articles without timestamps never match a date bound
+    let created = ArticleFilter::drafts().created_after(parse_date("2000-01-01").unwrap());
+    assert!(filter_articles(&articles, &created).is_empty());
+}
+
+#[test]
+fn test_parse_date_rejects_garbage() {
+    assert!(parse_date("yesterday").is_err());
+    assert!(parse_date("2024-13-01").is_err());
+    assert!(parse_date("-9999999y").is_err());
+    assert!(filter::parse_filter("updated-after:-9999999y").is_err());
+}
+
+#[test]
+fn test_parse_filter_expression() {
+    let articles = sample_articles();
+    let ids = |expression: &str| -> Vec<u64> {
+        let filter = filter::parse_filter(expression).unwrap();
+        filter_articles(&articles, &filter).iter().map(|article| article.id).collect()
+    };
+    assert_eq!(ids(""), [1, 3]);
+    assert_eq!(ids("tag:cli"), [1, 3]);
+    assert_eq!(ids("tag:cli  tag:tools"), [3]);
+    assert_eq!(ids("id:1"), [1]);
+    assert!(ids("series:\"Advent of Rust\"").is_empty());
+    assert!(filter::parse_filter("tag").is_err());
+    assert!(filter::parse_filter("color:red").is_err());
+    assert!(filter::parse_filter("updated-before:soon").is_err());
+    assert!(filter::parse_filter("series:\"open").is_err());
+}
+
+#[test]
+fn test_bulk_action_plans() {
+    use dtdrafts_core::bulk::{BulkAction, BulkChange};
+
+    let articles = sample_articles();
+    let action: BulkAction = "add-tag:#Archive".parse().unwrap();
+    assert_eq!(action, BulkAction::AddTag("archive".to_string()));
+    assert_eq!(action.to_string(), "add-tag:archive");
+    match action.plan(&articles[0]) {
+        BulkChange::Update(input, _) => assert_eq!(input.tags.unwrap(), ["rust", "cli", "archive"]),
+        change => panic!("unexpected {change:?}"),
+    }
+    assert!(matches!("add-tag:rust".parse::<BulkAction>().unwrap().plan(&articles[0]), BulkChange::Skip(_)));
+    assert!(matches!("remove-tag:go".parse::<BulkAction>().unwrap().plan(&articles[0]), BulkChange::Skip(_)));
+    assert!(matches!("remove-series".parse::<BulkAction>().unwrap().plan(&articles[0]), BulkChange::Skip(_)));
+    match "set-series:Tips".parse::<BulkAction>().unwrap().plan(&articles[0]) {
+        BulkChange::Update(input, _) => assert_eq!(input.series.as_deref(), Some("Tips")),
+        change => panic!("unexpected {change:?}"),
+    }
+    assert_eq!("export:hugo".parse::<BulkAction>().unwrap(), BulkAction::Export(export::ExportFormat::Hugo));
+    let rename: BulkAction = "rename-tag:CLI:#tools".parse().unwrap();
+    assert_eq!(rename, BulkAction::rename_tag("cli", "tools"));
+    match rename.plan(&articles[0]) {
+        BulkChange::Update(input, _) => assert_eq!(input.tags.unwrap(), ["rust", "tools"]),
+        change => panic!("unexpected {change:?}"),
+    }
+    // The target tag is already there, so the old one is just dropped
+    match rename.plan(&articles[2]) {
+        BulkChange::Update(input, _) => assert_eq!(input.tags.unwrap(), ["tools"]),
+        change => panic!("unexpected {change:?}"),
+    }
+    assert!("rename-tag:cli".parse::<BulkAction>().is_err());
+    assert!("add-tag".parse::<BulkAction>().is_err());
+    assert!("export:pdf".parse::<BulkAction>().is_err());
+    assert!("archive".parse::<BulkAction>().is_err());
+}
+
+#[test]
+fn test_article_meta_round_trip_drops_body_only() {
+    let articles = sample_articles();
+    let meta = ArticleMeta::from(&articles[0]);
+    let json = serde_json::to_string(&meta).unwrap();
+    assert!(!json.contains("CLI tools"));
+    let restored = Article::from(meta);
+    assert_eq!(restored.id, 1);
+    assert_eq!(restored.title, "Rust Tips");
+    assert_eq!(restored.user.username, "user");
+    assert!(restored.body_markdown.is_none());
+    let drafts = filter_articles(std::slice::from_ref(&restored), &ArticleFilter::drafts().tag("rust"));
+    assert_eq!(drafts.len(), 1);
+}
+
+#[test]
+fn test_article_input_omits_unset_fields() {
+    let input = ArticleInput {
+        body_markdown: Some("body".to_string()),
+        published: Some(false),
+        ..Default::default()
+    };
+    let json = serde_json::to_value(&input).unwrap();
+    assert_eq!(json, serde_json::json!({ "body_markdown": "body", "published": false }));
+}
+
+#[test]
+fn test_config_without_base_url_uses_default_instance() {
+    let config: Config = serde_json::from_str(r#"{ "api_key": "key" }"#).unwrap();
+    assert!(config.base_url.is_none());
+    assert_eq!(DevToClient::from_config(&config).unwrap().base_url(), DEFAULT_BASE_URL);
+
+    let config = Config {
+        api_key: "key".to_string(),
+        base_url: Some("http://localhost:3000/api/".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(DevToClient::from_config(&config).unwrap().base_url(), "http://localhost:3000/api");
+}
+
+#[test]
+fn test_sort_articles_by_title_and_words() {
+    let articles = sample_articles();
+    let mut drafts: Vec<&Article> = articles.iter().collect();
+    sort_articles(&mut drafts, SortKey::Title, false);
+    let titles: Vec<_> = drafts.iter().map(|a| a.title.as_str()).collect();
+    assert_eq!(titles, ["CLI Tricks", "Kotlin Guide", "Rust Tips"]);
+
+    sort_articles(&mut drafts, SortKey::Words, true);
+    let titles: Vec<_> = drafts.iter().map(|a| a.title.as_str()).collect();
+    // Six-word ties keep the previous (title) order
+    assert_eq!(titles, ["CLI Tricks", "Rust Tips", "Kotlin Guide"]);
+}
+
+#[test]
+fn test_sort_articles_by_updated_puts_missing_dates_last() {
+    let mut articles = sample_articles();
+    articles[0].updated_at = timestamp::parse("2024-01-01T00:00:00Z");
+    articles[2].updated_at = timestamp::parse("2021-01-01T00:00:00Z");
+    let mut drafts: Vec<&Article> = articles.iter().collect();
+
+    sort_articles(&mut drafts, SortKey::Updated, false);
+    let ids: Vec<_> = drafts.iter().map(|a| a.id).collect();
+    assert_eq!(ids, [3, 1, 2]);
+
+    sort_articles(&mut drafts, SortKey::Updated, true);
+    let ids: Vec<_> = drafts.iter().map(|a| a.id).collect();
+    assert_eq!(ids, [1, 3, 2]);
+}
+
+#[test]
+fn test_sort_key_from_str() {
+    assert_eq!("Updated".parse::<SortKey>(), Ok(SortKey::Updated));
+    assert!("size".parse::<SortKey>().is_err());
+}
+
+#[test]
+fn test_parse_age_units() {
+    assert_eq!(parse_age("90d").unwrap().num_days(), 90);
+    assert_eq!(parse_age("2w").unwrap().num_days(), 14);
+    assert_eq!(parse_age("6m").unwrap().num_days(), 180);
+    assert_eq!(parse_age("1y").unwrap().num_days(), 365);
+    assert!(parse_age("90").is_err());
+    assert!(parse_age("d").is_err());
+    assert!(parse_age("0d").is_err());
+    assert!(parse_age("-3w").is_err());
+    assert!(parse_age("9999999999999999y").is_err());
+    assert!(parse_age("99999999999999d").is_err());
+}
+
+#[test]
+fn test_parse_interval_units() {
+    assert_eq!(parse_interval("90s").unwrap().as_secs(), 90);
+    assert_eq!(parse_interval("15m").unwrap().as_secs(), 15 * 60);
+    assert_eq!(parse_interval("1h").unwrap().as_secs(), 3600);
+    assert_eq!(parse_interval("2d").unwrap().as_secs(), 2 * 86_400);
+    assert!(parse_interval("18446744073709551615d").is_err());
+    assert!(parse_interval("307445734561825861h").is_err());
+    assert!(parse_interval("0h").is_err());
+    assert!(parse_interval("1w").is_err());
+}
+
+#[test]
+fn test_stale_report_buckets_drafts_by_age() {
+    let mut articles = sample_articles();
+    let now = parse_date("2024-06-01").unwrap();
+    // 100 days old, via updated_at
+    articles[0].updated_at = timestamp::parse("2024-02-22T00:00:00Z");
+    // Published articles are never stale drafts
+    articles[1].updated_at = timestamp::parse("2020-01-01T00:00:00Z");
+    // 800 days old, falling back to created_at
+    articles[2].created_at = timestamp::parse("2022-03-24T00:00:00Z");
+
+    let report = stale_report(&articles, parse_age("90d").unwrap(), now);
+    assert_eq!(report.total(), 2);
+    let labels: Vec<_> = report.buckets.iter().map(|b| b.label()).collect();
+    assert_eq!(labels, ["90d–180d", "180d–1y", "1y–2y", "2y+"]);
+    assert_eq!(report.buckets[0].articles[0].title, "Rust Tips");
+    assert_eq!(report.buckets[3].articles[0].title, "CLI Tricks");
+    assert!(report.undated.is_empty());
+
+    let report = stale_report(&articles, parse_age("1y").unwrap(), now);
+    assert_eq!(report.total(), 1);
+}
+
+#[test]
+fn test_reminders_list_oldest_drafts_first() {
+    use dtdrafts_core::stale::{notification_text, reminders};
+    let mut articles = sample_articles();
+    let now = parse_date("2024-06-01").unwrap();
+    articles[0].updated_at = timestamp::parse("2024-04-22T00:00:00Z");
+    articles[2].created_at = timestamp::parse("2024-01-01T00:00:00Z");
+
+    let due = reminders(&articles, parse_age("30d").unwrap(), now);
+    let ids: Vec<u64> = due.iter().map(|r| r.id).collect();
+    assert_eq!(ids, vec![3, 1]);
+    assert_eq!(due[1].age_days, 40);
+    assert_eq!(due[0].edit_url, "https://dev.to/user/cli-tricks/edit");
+    assert!(reminders(&articles, parse_age("1y").unwrap(), now).is_empty());
+
+    let (summary, body) = notification_text(&due);
+    assert_eq!(summary, "2 draft(s) need attention");
+    assert_eq!(body, "CLI Tricks (152d)\nRust Tips (40d)");
+}
+
+#[test]
+fn test_compute_stats() {
+    let articles = sample_articles();
+    let drafts = get_draft_articles(&articles);
+    let summary = compute_stats(&drafts, &Default::default());
+    assert_eq!(summary.drafts, 2);
+    assert_eq!(summary.total_words, 12);
+    assert_eq!(summary.average_words, 6);
+    assert_eq!(summary.total_reading_time_minutes, 2);
+    assert_eq!(summary.with_cover_image, 0);
+    assert_eq!(summary.tags[0], dtdrafts_core::stats::TagCount { tag: "cli".to_string(), count: 2 });
+    assert_eq!(summary.tags.len(), 3);
+}
+
+#[test]
+fn test_reading_time_rounds_up() {
+    use dtdrafts_core::stats::reading_time_minutes;
+    assert_eq!(reading_time_minutes(0), 0);
+    assert_eq!(reading_time_minutes(1), 1);
+    assert_eq!(reading_time_minutes(275), 1);
+    assert_eq!(reading_time_minutes(276), 2);
+}
+
+#[test]
+fn test_config_word_count_options() {
+    let config: Config =
+        serde_json::from_str(r#"{ "api_key": "key", "word_count": { "include_code": true } }"#).unwrap();
+    assert!(config.word_count.include_code);
+    assert!(!config.word_count.include_liquid);
+    let saved = serde_json::to_string(&Config { api_key: "key".to_string(), ..Default::default() }).unwrap();
+    assert_eq!(saved, r#"{"api_key":"key"}"#);
+}
+
+#[test]
+fn test_config_file_sections() {
+    let file: config::ConfigFile = toml::from_str(
+        r#"
+        profile = "work"
+
+        [api]
+        key = "key"
+        base_url = "http://localhost:3000/api"
+
+        [cache]
+        format = "compact"
+        ttl_secs = 3600
+
+        [output]
+        template = "{title}"
+        word_count = { include_code = true }
+
+        [editor]
+        command = "code --wait"
+
+        [profiles.work.api]
+        key = "work-key"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(file.profile.as_deref(), Some("work"));
+    assert!(file.profiles.contains_key("work"));
+    let config = Config::from(file);
+    assert_eq!(config.api_key, "key");
+    assert_eq!(config.base_url.as_deref(), Some("http://localhost:3000/api"));
+    assert_eq!(config.cache_format, cache::CacheFormat::Compact);
+    assert_eq!(config.cache_ttl_secs, Some(3600));
+    assert_eq!(config.output_template.as_deref(), Some("{title}"));
+    assert_eq!(config.editor.as_deref(), Some("code --wait"));
+    assert!(config.word_count.include_code);
+}
+
+#[test]
+fn test_cache_older_than_ttl() {
+    let now = std::time::SystemTime::now();
+    let hour_ago = now - std::time::Duration::from_secs(3600);
+    assert!(cache::older_than(hour_ago, now, 60));
+    assert!(!cache::older_than(hour_ago, now, 7200));
+    // A clock that went backwards doesn't expire the cache
+    assert!(!cache::older_than(now, hour_ago, 0));
+}
+
+#[test]
+fn test_config_file_set_keeps_profiles() {
+    let mut file = config::ConfigFile::default();
+    file.set_api_key(Some("work"), "work-key", None).unwrap();
+    file.set(&Config { api_key: "key".to_string(), ..Default::default() });
+    let saved = toml::to_string(&file).unwrap();
+    assert_eq!(saved, "[api]\nkey = \"key\"\n\n[profiles.work.api]\nkey = \"work-key\"\n");
+    let reloaded: config::ConfigFile = toml::from_str(&saved).unwrap();
+    assert_eq!(reloaded, file);
+
+    file.set_username(Some("work"), Some("worker")).unwrap();
+    assert_eq!(file.api_key(Some("work")).unwrap().as_deref(), Some("work-key"));
+    assert_eq!(file.api_key(Some("home")).unwrap(), None);
+    assert_eq!(file.value("profiles.work.api.username").unwrap(), Some(toml::Value::String("worker".to_string())));
+}
+
+#[test]
+fn test_config_file_set_value_by_key() {
+    let mut file = config::ConfigFile::default();
+    file.set_value("http.timeout_secs", "60").unwrap();
+    file.set_value("cache.ttl_secs", "600").unwrap();
+    file.set_value("editor.command", "code --wait").unwrap();
+    file.set_value("api.key", "12345").unwrap();
+    file.set_value("output.template", "{id} {title}").unwrap();
+    file.set_value("profiles.work.api.base_url", "http://localhost:3000/api").unwrap();
+    assert_eq!(file.http.timeout_secs, Some(60));
+    assert_eq!(file.cache.ttl_secs, Some(600));
+    assert_eq!(file.editor.command.as_deref(), Some("code --wait"));
+    assert_eq!(file.api.key, "12345");
+    assert_eq!(file.output.template.as_deref(), Some("{id} {title}"));
+    assert_eq!(file.value("http.timeout_secs").unwrap(), Some(toml::Value::Integer(60)));
+    assert_eq!(
+        file.value("profiles.work.api.base_url").unwrap(),
+        Some(toml::Value::String("http://localhost:3000/api".to_string()))
+    );
+
+    assert!(file.set_value("cache.format", "bogus").is_err());
+    assert!(file.set_value("cache.ttl_secs", "soon").is_err());
+    assert!(file.set_value("http.timeout", "60").is_err());
+    assert!(file.set_value("profiles.work.profile", "home").is_err());
+    assert!(file.value("nope").is_err());
+
+    // Tables take their entries one at a time
+    assert!(file.set_value("frontmatter.rename", "foo").is_err());
+    assert!(file.set_value("frontmatter.rename.tags.x", "categories").is_err());
+    file.set_value("frontmatter.rename.tags", "categories").unwrap();
+    file.set_value("frontmatter.extra.layout", "post").unwrap();
+    assert_eq!(file.frontmatter.rename["tags"], "categories");
+    assert_eq!(file.value("frontmatter.extra.layout").unwrap(), Some(toml::Value::String("post".to_string())));
+    file.unset("frontmatter.rename.tags").unwrap();
+    file.unset("frontmatter.extra").unwrap();
+    assert!(file.frontmatter.is_default());
+
+    file.unset("cache.ttl_secs").unwrap();
+    file.unset("editor.command").unwrap();
+    file.unset("profiles.work.api.base_url").unwrap();
+    assert!(file.profiles.is_empty());
+    let keys: Vec<String> = file.settings().unwrap().into_iter().map(|(key, _)| key).collect();
+    assert_eq!(keys, ["api.key", "http.timeout_secs", "output.template"]);
+}
+
+#[test]
+fn test_config_parse_checks_profiles() {
+    assert!(config::parse("[api]\nkey = \"key\"\n[profiles.work.http]\ntimeout_secs = 5\n").is_ok());
+    let error = config::parse("[profiles.work.http]\ntimeout_secs = \"soon\"\n").unwrap_err();
+    assert!(format!("{error:#}").contains("Invalid profile 'work'"));
+}
+
+#[test]
+fn test_search_engine_from_str() {
+    assert_eq!("index".parse::<SearchEngine>(), Ok(SearchEngine::Index));
+    assert_eq!("LINEAR".parse::<SearchEngine>(), Ok(SearchEngine::Linear));
+    assert!("grep".parse::<SearchEngine>().is_err());
+}
+
+#[cfg(feature = "index")]
+#[test]
+fn test_index_search_is_ranked_and_stemmed() {
+    use dtdrafts_core::index::{build_index, search_index};
+    let dir = std::env::temp_dir().join(format!("dtdrafts-index-test-{}", std::process::id()));
+    let articles = sample_articles();
+    build_index(&articles, &dir).unwrap();
+
+    // "tip" matches "Tips" through stemming; the title hit ranks first
+    let results = search_index(&dir, "tip", 10).unwrap();
+    assert_eq!(results[0].0, 1);
+
+    let results = search_index(&dir, "cli", 10).unwrap();
+    let ids: Vec<u64> = results.iter().map(|(id, _)| *id).collect();
+    assert_eq!(ids[0], 3);
+    assert!(ids.contains(&1));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_front_matter_parse_and_render() {
+    use dtdrafts_core::frontmatter::FrontMatter;
+    let mut front_matter = FrontMatter::parse("title: \"Hello: World\"\npublished: false\ntags: [rust, 'cli']\n# comment\n");
+    assert_eq!(front_matter.get("title"), Some("Hello: World"));
+    assert_eq!(front_matter.tags(), Some(vec!["rust".to_string(), "cli".to_string()]));
+    front_matter.set("published", "true");
+    front_matter.set("series", "Basics");
+    assert_eq!(front_matter.remove("tags"), Some("[rust, 'cli']".to_string()));
+    assert_eq!(
+        front_matter.render(),
+        "---\ntitle: Hello: World\npublished: true\nseries: Basics\n---\n"
+    );
+}
+
+#[test]
+fn test_draft_document_round_trip() {
+    use dtdrafts_core::frontmatter::{document_to_input, draft_document};
+    let articles = sample_articles();
+    let document = draft_document(&articles[0]);
+    assert!(document.starts_with("---\ntitle: Rust Tips\npublished: false\ntags: rust, cli\n"));
+    assert!(document.ends_with("---\nRust is great for CLI tools."));
+
+    let edited = document.replace("Rust Tips", "Rust Tips, revised");
+    let input = document_to_input(&edited, false);
+    assert_eq!(input.title.as_deref(), Some("Rust Tips, revised"));
+    assert_eq!(input.body_markdown.as_deref(), Some("Rust is great for CLI tools."));
+    assert_eq!(input.tags, Some(vec!["rust".to_string(), "cli".to_string()]));
+    assert_eq!(input.published, Some(false));
+}
+
+#[test]
+fn test_draft_document_keeps_embedded_front_matter() {
+    use dtdrafts_core::frontmatter::{document_to_input, draft_document, has_embedded_front_matter};
+    let mut articles = sample_articles();
+    articles[0].body_markdown = Some("---\ntitle: From body\n---\nText".to_string());
+    assert!(has_embedded_front_matter(&articles[0]));
+    let document = draft_document(&articles[0]);
+    assert_eq!(document, "---\ntitle: From body\n---\nText");
+    let input = document_to_input(&document, true);
+    assert_eq!(input.body_markdown.as_deref(), Some(document.as_str()));
+    assert!(input.title.is_none());
+}
+
+#[test]
+fn test_markdown_to_html_drops_front_matter_and_boxes_liquid_tags() {
+    use dtdrafts_core::render::markdown_to_html;
+    let html = markdown_to_html("---\ntitle: Hi\n---\n# Heading\n\n{% embed https://x.dev/a<b %}\n\n| a |\n|---|\n| 1 |\n");
+    assert!(!html.contains("title: Hi"));
+    assert!(html.contains("<h1>Heading</h1>"));
+    assert!(html.contains("<div class=\"liquid-tag\"><b>embed</b> https://x.dev/a&lt;b</div>"));
+    assert!(html.contains("<table>"));
+}
+
+#[test]
+fn test_preview_page_escapes_title_and_toggles_live_reload() {
+    use dtdrafts_core::render::preview_page;
+    let page = preview_page("<Rust> & CLI", &["rust".to_string()], "Body", true);
+    assert!(page.contains("<h1 class=\"title\">&lt;Rust&gt; &amp; CLI</h1>"));
+    assert!(page.contains("<span>#rust</span>"));
+    assert!(page.contains("/__version"));
+    assert!(!preview_page("T", &[], "Body", false).contains("/__version"));
+}
+
+#[test]
+fn test_lint_liquid_tags_reports_unknown_and_malformed_tags() {
+    use dtdrafts_core::lint::lint_liquid_tags;
+    let body = "---\ntitle: T\n---\n{% embed https://dev.to/x %}\n{% youtube %}\n{% foo bar %}\n{% embed notaurl %}\n{% tweet 1";
+    let issues = lint_liquid_tags(body);
+    let found: Vec<(Option<usize>, Severity)> = issues.iter().map(|issue| (issue.line, issue.severity)).collect();
+    assert_eq!(
+        found,
+        vec![
+            (Some(5), Severity::Error),
+            (Some(6), Severity::Warning),
+            (Some(7), Severity::Error),
+            (Some(8), Severity::Error),
+        ]
+    );
+    assert!(issues[1].message.contains("`foo`"));
+}
+
+#[test]
+fn test_lint_liquid_tags_checks_blocks_and_skips_code() {
+    use dtdrafts_core::lint::lint_liquid_tags;
+    assert!(lint_liquid_tags("{% details Why %}\nbecause\n{% enddetails %}\n`{% nope %}`\n```\n{% nope %}\n```").is_empty());
+    let issues = lint_liquid_tags("{% details Why %}\ntext\n{% endkatex %}");
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].line, Some(1));
+    assert!(issues[0].message.contains("never closed"));
+    assert!(issues[1].message.contains("without a matching"));
+}
+
+#[test]
+fn test_spelling_reports_unknown_words_with_positions() {
+    use dtdrafts_core::lint::spelling_issues;
+    use dtdrafts_core::spelling::SpellChecker;
+
+    let mut checker = SpellChecker::new("SET UTF-8\n", "7\nthis\nis\na\ntest\nof\nand\nwords\n").unwrap();
+    let body = "---\ntitle: Tset\n---\nThis is a tset of `cdoe` and [wrods](https://exmaple.com)\n```\nnot checked\n```\nAPI serdeJson v2 Dtdrafts";
+    let found: Vec<(usize, usize, String)> =
+        checker.misspellings(body).into_iter().map(|word| (word.line, word.column, word.text)).collect();
+    assert_eq!(found, [(4, 11, "tset".to_string()), (4, 31, "wrods".to_string()), (8, 18, "Dtdrafts".to_string())]);
+
+    checker.add_words("# my words\n\ndtdrafts\n").unwrap();
+    let issues = spelling_issues(&checker, body);
+    assert_eq!(issues.len(), 2);
+    assert_eq!((issues[0].line, issues[0].column), (Some(4), Some(11)));
+    assert_eq!(issues[0].rule, "spelling");
+    assert!(issues[0].message.contains("did you mean test"), "{}", issues[0].message);
+}
+
+#[test]
+fn test_lint_prose_flags_repeated_and_weasel_words() {
+    use dtdrafts_core::lint::lint_prose;
+
+    let issues = lint_prose("It is is very simple. It. It works `is` is\nthe\nthe end");
+    let found: Vec<(Option<usize>, Option<usize>)> = issues.iter().map(|issue| (issue.line, issue.column)).collect();
+    assert_eq!(found, [(Some(1), Some(7)), (Some(1), Some(10))]);
+    assert!(issues[0].message.contains("repeated"));
+    assert!(issues[1].message.contains("`very`"));
+}
+
+#[test]
+fn test_extract_links_finds_links_images_and_bare_urls_outside_code() {
+    use dtdrafts_core::links::extract_links;
+    let body = "See [docs](https://docs.rs) and https://example.com/a.\n\n![logo](https://img.dev/logo.png)\n[local](/about) `https://inline.code`\n\n```\nhttps://in.code.block\n```\n";
+    let links = extract_links(body);
+    let found: Vec<(usize, &str, bool)> = links
+        .iter()
+        .map(|link| (link.line, link.url.as_str(), link.image))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (1, "https://docs.rs", false),
+            (1, "https://example.com/a", false),
+            (3, "https://img.dev/logo.png", true),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_metadata_enforces_dev_to_limits() {
+    use dtdrafts_core::lint::{validate_metadata, PublishMetadata};
+    let valid = PublishMetadata {
+        title: "Rust Tips".to_string(),
+        tags: vec!["rust".to_string(), "cli".to_string()],
+        cover_image: Some("https://img.dev/cover.png".to_string()),
+        canonical_url: None,
+    };
+    assert!(validate_metadata(&valid).is_empty());
+
+    let invalid = PublishMetadata {
+        title: "x".repeat(129),
+        tags: ["rust", "c-sharp", "a", "b", "c"].iter().map(|tag| tag.to_string()).collect(),
+        cover_image: Some("cover.png".to_string()),
+        canonical_url: None,
+    };
+    let messages: Vec<String> = validate_metadata(&invalid).into_iter().map(|issue| issue.message).collect();
+    assert_eq!(messages.len(), 4);
+    assert!(messages[0].contains("129 characters"));
+    assert!(messages[1].contains("5 tags"));
+    assert!(messages[2].contains("c-sharp"));
+    assert!(messages[3].contains("cover_image"));
+}
+
+#[test]
+fn test_publish_metadata_prefers_front_matter() {
+    use dtdrafts_core::lint::PublishMetadata;
+    let mut articles = sample_articles();
+    articles[0].body_markdown = Some("---\ntitle: From body\ntags: a, b\ncover_image: https://img.dev/c.png\n---\nText".to_string());
+    let metadata = PublishMetadata::of(&articles[0]);
+    assert_eq!(metadata.title, "From body");
+    assert_eq!(metadata.tags, vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(metadata.cover_image.as_deref(), Some("https://img.dev/c.png"));
+    assert_eq!(PublishMetadata::of(&articles[2]).title, "CLI Tricks");
+}
+
+#[test]
+fn test_extract_images_includes_html_img_tags() {
+    use dtdrafts_core::links::extract_images;
+    let body = "![a](https://img.dev/a.png)\n\n<img src=\"http://img.dev/b.gif\" alt=\"b\">\n\n[not an image](https://docs.rs)\n";
+    let urls: Vec<String> = extract_images(body).into_iter().map(|image| format!("{}:{}", image.line, image.url)).collect();
+    assert_eq!(urls, vec!["1:https://img.dev/a.png", "3:http://img.dev/b.gif"]);
+}
+
+#[test]
+fn test_backup_round_trip_restores_unpublished_drafts() {
+    use dtdrafts_core::backup::{read_backup, write_backup};
+    let mut articles = sample_articles();
+    articles[2].body_markdown = Some("---\ntitle: CLI Tricks\npublished: true\n---\nText".to_string());
+    let drafts = get_draft_articles(&articles);
+
+    let mut archive = Vec::new();
+    let written = write_backup(&drafts, &mut archive).unwrap();
+    let (manifest, restored) = read_backup(archive.as_slice()).unwrap();
+    assert_eq!(manifest, written);
+    assert_eq!(restored.len(), 2);
+    assert_eq!(restored[0].entry.file, "drafts/1-rust-tips.md");
+    assert!(restored[0].document.ends_with("---\nRust is great for CLI tools."));
+
+    let input = restored[0].to_input();
+    assert_eq!(input.title.as_deref(), Some("Rust Tips"));
+    assert_eq!(input.published, Some(false));
+    let embedded = restored[1].to_input();
+    assert!(embedded.body_markdown.unwrap().contains("published: false"));
+    assert_eq!(embedded.published, Some(false));
+}
+
+#[test]
+fn test_exporters_write_ssg_front_matter() {
+    use dtdrafts_core::export::{ExportFormat, ExportPost};
+    let mut articles = sample_articles();
+    articles[0].created_at = timestamp::parse("2024-03-05T10:00:00Z");
+    let post = ExportPost::new(&articles[0], chrono::Utc::now());
+
+    let hugo = ExportFormat::Hugo.exporter();
+    assert_eq!(hugo.file_name(&post), "rust-tips.md");
+    assert_eq!(
+        hugo.render(&post),
+        "---\ntitle: \"Rust Tips\"\ndate: 2024-03-05T10:00:00+00:00\ndraft: true\nslug: \"rust-tips\"\ntags: [\"rust\", \"cli\"]\ndescription: \"Learn Rust\"\n---\nRust is great for CLI tools."
+    );
+
+    let jekyll = ExportFormat::Jekyll.exporter();
+    assert_eq!(jekyll.file_name(&post), "2024-03-05-rust-tips.md");
+    assert!(jekyll.render(&post).contains("date: 2024-03-05 10:00:00 +0000\n"));
+
+    let zola = "zola".parse::<ExportFormat>().unwrap().exporter().render(&post);
+    assert!(zola.starts_with("+++\ntitle = \"Rust Tips\"\n"));
+    assert!(zola.contains("[taxonomies]\ntags = [\"rust\", \"cli\"]\n+++\n"));
+    assert!("gatsby".parse::<ExportFormat>().is_err());
+}
+
+#[test]
+fn test_crosspost_input_points_canonical_url_at_dev_to() {
+    use dtdrafts_core::crosspost::CrosspostInput;
+    let mut articles = sample_articles();
+    articles[1].body_markdown = Some("---\ntitle: Kotlin, revised\ntags: kotlin\n---\n\nBody".to_string());
+    let input = CrosspostInput::from_article(&articles[1]);
+    assert_eq!(input.title, "Kotlin, revised");
+    assert_eq!(input.body_markdown, "Body");
+    assert_eq!(input.tags, vec!["kotlin".to_string()]);
+    assert_eq!(input.canonical_url, "https://dev.to/user/kotlin-guide");
+}
+
+#[test]
+fn test_config_crosspost_credentials_are_optional() {
+    let config: Config = serde_json::from_str(r#"{"api_key":"k"}"#).unwrap();
+    assert!(config.crosspost.is_empty());
+    assert_eq!(serde_json::to_string(&config).unwrap(), r#"{"api_key":"k"}"#);
+
+    let config: Config =
+        serde_json::from_str(r#"{"api_key":"k","crosspost":{"medium":{"token":"t"}}}"#).unwrap();
+    assert_eq!(config.crosspost.medium.unwrap().token, "t");
+    assert!(config.crosspost.hashnode.is_none());
+}
+
+#[cfg(feature = "terminal")]
+#[test]
+fn test_render_markdown_for_the_terminal() {
+    use dtdrafts_core::terminal::render_markdown;
+    let markdown = "# Title\n\nSome **bold** text and a [link](https://example.com).\n\n\
+        - one\n- two\n\n> quoted\n\n```rust\nfn main() {}\n```\n";
+    assert_eq!(
+        without_colors(|| render_markdown(markdown)),
+        "# Title\n\nSome bold text and a link (https://example.com).\n\n\
+        • one\n• two\n\n│ quoted\n\n    fn main() {}\n"
+    );
+    // Code is highlighted when colours are on, from the syntaxes loaded once
+    for _ in 0..2 {
+        let highlighted = with_colors(true, || render_markdown("```rust\nfn main() {}\n```\n"));
+        assert!(highlighted.starts_with("    \x1b[38;2;"), "{highlighted:?}");
+        assert!(highlighted.contains("main"));
+    }
+}
+
+#[test]
+fn test_read_ghost_export_converts_html_and_tags() {
+    use dtdrafts_core::import::read_ghost_export;
+    let json = r##"{"db":[{"data":{
+        "posts":[
+            {"id":"p1","title":"Hello Ghost","slug":"hello","html":"<p>Some <strong>bold</strong> text</p>","type":"post"},
+            {"id":"p2","title":"About","slug":"about","html":"<p>Page</p>","type":"page"}
+        ],
+        "tags":[{"id":"t1","name":"Web Dev"},{"id":"t2","name":"#internal"}],
+        "posts_tags":[{"post_id":"p1","tag_id":"t1"},{"post_id":"p1","tag_id":"t2"}]
+    }}]}"##;
+    let posts = read_ghost_export(json).unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].body_markdown, "Some **bold** text");
+    assert_eq!(posts[0].tags, vec!["Web Dev".to_string()]);
+    let input = posts[0].to_input();
+    assert_eq!(input.tags, Some(vec!["webdev".to_string()]));
+    assert_eq!(input.published, Some(false));
+
+    let mut post = posts[0].clone();
+    post.tags = ["Rust", "Web Dev", "rust", "web-dev", "CLI"].map(String::from).to_vec();
+    assert_eq!(post.to_input().tags, Some(["rust", "webdev", "cli"].map(String::from).to_vec()));
+}
+
+#[test]
+fn test_read_medium_export_reads_posts_from_zip() {
+    use dtdrafts_core::import::read_medium_export;
+    use std::io::{Cursor, Write};
+    let html = r#"<html><body><article><header><h1 class="p-name">My Post</h1></header>
+<section data-field="body" class="e-content"><h3>My Post</h3><p>First <em>paragraph</em>.</p></section>
+<footer>Exported from Medium</footer></article></body></html>"#;
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default();
+    zip.start_file("posts/draft_My-Post-abc123.html", options).unwrap();
+    zip.write_all(html.as_bytes()).unwrap();
+    zip.start_file("profile/profile.html", options).unwrap();
+    zip.write_all(b"<p>me</p>").unwrap();
+    let archive = zip.finish().unwrap();
+
+    let posts = read_medium_export(Cursor::new(archive.into_inner())).unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].title, "My Post");
+    assert_eq!(posts[0].body_markdown, "First *paragraph*.");
+    assert_eq!(posts[0].source, "posts/draft_My-Post-abc123.html");
+}
+
+#[test]
+fn test_sync_classify_uses_the_last_synced_base() {
+    use dtdrafts_core::sync::{classify, SyncAction};
+    assert_eq!(classify(None, None, "remote"), SyncAction::Add);
+    assert_eq!(classify(Some("base"), Some("same"), "same"), SyncAction::Unchanged);
+    assert_eq!(classify(Some("base"), Some("base"), "remote"), SyncAction::Pull);
+    assert_eq!(classify(Some("base"), Some("local"), "base"), SyncAction::Push);
+    assert_eq!(classify(Some("base"), Some("local"), "remote"), SyncAction::Conflict);
+    assert_eq!(classify(None, Some("local"), "remote"), SyncAction::Conflict);
+}
+
+#[test]
+fn test_merge_documents_merges_or_marks_conflicts() {
+    use dtdrafts_core::sync::{has_conflict_markers, merge_documents, MergeOutcome, SyncStrategy};
+    let base = "title\n\none\ntwo\nthree\n";
+    let local = "title\n\nONE\ntwo\nthree\n";
+    let remote = "title\n\none\ntwo\nTHREE\n";
+    assert_eq!(merge_documents(base, local, remote), MergeOutcome::Clean("title\n\nONE\ntwo\nTHREE\n".to_string()));
+
+    let MergeOutcome::Conflicted(marked) = merge_documents(base, "title\n\nmine\ntwo\nthree\n", "title\n\ntheirs\ntwo\nthree\n") else {
+        panic!("expected a conflict");
+    };
+    assert!(has_conflict_markers(&marked));
+    assert!(!has_conflict_markers(local));
+    assert_eq!("merge".parse::<SyncStrategy>(), Ok(SyncStrategy::Merge));
+}
+
+#[test]
+fn test_article_id_from_file_name() {
+    use dtdrafts_core::sync::{article_id_from_file_name, draft_file_name};
+    use std::path::Path;
+    let articles = sample_articles();
+    assert_eq!(draft_file_name(&articles[0]), "1-rust-tips.md");
+    assert_eq!(article_id_from_file_name(Path::new("drafts/1-rust-tips.md")), Some(1));
+    assert_eq!(article_id_from_file_name(Path::new("42.md")), Some(42));
+    assert_eq!(article_id_from_file_name(Path::new("notes.md")), None);
+    assert_eq!(article_id_from_file_name(Path::new("1-rust-tips.md.swp")), None);
+}
+
+#[test]
+fn test_schedule_orders_replaces_and_finds_due_entries() {
+    use dtdrafts_core::schedule::{parse_schedule_time, Schedule, ScheduledPublish};
+    let at = |input: &str| parse_schedule_time(input).unwrap();
+    let entry = |id: u64, when: &str| ScheduledPublish { id, title: format!("Draft {id}"), at: at(when), last_error: None };
+
+    let mut schedule = Schedule::default();
+    schedule.add(entry(1, "2024-07-02T09:00:00Z"));
+    schedule.add(entry(2, "2024-07-01T09:00:00Z"));
+    schedule.add(entry(1, "2024-07-03T09:00:00Z"));
+    let ids: Vec<u64> = schedule.entries.iter().map(|entry| entry.id).collect();
+    assert_eq!(ids, vec![2, 1]);
+
+    let due = schedule.due(at("2024-07-02T00:00:00Z"));
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].id, 2);
+    assert_eq!(schedule.cancel(2).map(|entry| entry.id), Some(2));
+    assert!(schedule.cancel(2).is_none());
+
+    assert!(parse_schedule_time("2024-07-01 09:00").is_ok());
+    assert!(parse_schedule_time("next tuesday").is_err());
+}
+
+#[test]
+fn test_render_template_substitutes_known_variables() {
+    use dtdrafts_core::template::{render_template, DEFAULT_TEMPLATE};
+    use std::collections::BTreeMap;
+    let vars = BTreeMap::from([
+        ("title".to_string(), "Hello".to_string()),
+        ("series".to_string(), "Axum".to_string()),
+    ]);
+    assert_eq!(
+        render_template("# {{title}} ({{ series }}) {{date}} {{", &vars),
+        "# Hello (Axum) {{date}} {{"
+    );
+    assert!(render_template(DEFAULT_TEMPLATE, &vars).starts_with("---\ntitle: Hello\n"));
+}
+
+#[test]
+fn test_series_from_field_or_front_matter_grouped_in_order() {
+    use dtdrafts_core::series::group_by_series;
+    let mut articles = sample_articles();
+    articles[0].series = Some("Rust CLI".to_string());
+    articles[0].created_at = timestamp::parse("2024-02-01T00:00:00Z");
+    articles[2].created_at = timestamp::parse("2024-01-01T00:00:00Z");
+    articles[2].body_markdown = Some("---\ntitle: CLI Tricks\nseries: Rust CLI\n---\nBody".to_string());
+    assert_eq!(articles[2].series().as_deref(), Some("Rust CLI"));
+    assert!(articles[1].series().is_none());
+
+    let filter = ArticleFilter::new().series("rust cli");
+    assert_eq!(filter_articles(&articles, &filter).len(), 2);
+
+    let refs: Vec<&Article> = articles.iter().collect();
+    let groups = group_by_series(&refs);
+    assert_eq!(groups.len(), 1);
+    let ids: Vec<u64> = groups[0].articles.iter().map(|article| article.id).collect();
+    assert_eq!(ids, vec![3, 1]);
+
+    // Spelled differently, still one series
+    articles[1].series = Some("rust cli".to_string());
+    let refs: Vec<&Article> = articles.iter().collect();
+    let groups = group_by_series(&refs);
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].name, "Rust CLI");
+    assert_eq!(groups[0].articles.len(), 3);
+    let statuses = dtdrafts_core::status::StatusStore::default();
+    let groups = dtdrafts_core::group::group_articles(&refs, dtdrafts_core::group::GroupBy::Series, &statuses);
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].name.as_deref(), Some("Rust CLI"));
+}
+
+#[test]
+fn test_filter_by_organization_username() {
+    let mut articles = sample_articles();
+    articles[0].organization = serde_json::from_str(r#"{"name": "Acme Corp", "username": "acme", "slug": "acme"}"#).unwrap();
+    let filter = ArticleFilter::drafts().organization("Acme");
+    let ids: Vec<u64> = filter_articles(&articles, &filter).iter().map(|article| article.id).collect();
+    assert_eq!(ids, vec![1]);
+    assert!(filter_articles(&articles, &ArticleFilter::new().organization("other")).is_empty());
+}
+
+#[test]
+fn test_analytics_sort_and_csv_quoting() {
+    use dtdrafts_core::analytics::{sort_analytics, to_csv, AnalyticsSortKey, ArticleAnalytics};
+    let mut rows: Vec<ArticleAnalytics> = serde_json::from_str(
+        r#"[
+            {"id": 1, "title": "Quiet, but liked", "url": "https://dev.to/u/a", "page_views_count": 10, "public_reactions_count": 8},
+            {"id": 2, "title": "Popular", "url": "https://dev.to/u/b", "page_views_count": 500, "public_reactions_count": 3, "comments_count": 4}
+        ]"#,
+    )
+    .unwrap();
+    sort_analytics(&mut rows, AnalyticsSortKey::Views);
+    assert_eq!(rows[0].id, 2);
+    sort_analytics(&mut rows, "reactions".parse().unwrap());
+    assert_eq!(rows[0].id, 1);
+    assert_eq!(
+        to_csv(&rows).lines().nth(1),
+        Some("1,\"Quiet, but liked\",https://dev.to/u/a,,10,8,0")
+    );
+}
+
+#[test]
+fn test_comments_count_replies_and_convert_bodies() {
+    use dtdrafts_core::comments::{count_comments, Comment};
+    let comments: Vec<Comment> = serde_json::from_str(
+        r#"[
+            {"id_code": "a1", "created_at": "2024-01-01T00:00:00Z", "body_html": "<p>Nice <em>post</em></p>",
+             "user": {"name": "Bob", "username": "bob"},
+             "children": [{"id_code": "a2", "body_html": "<p>Thanks</p>", "user": {"username": "me"}, "children": []}]},
+            {"id_code": "a3", "body_html": "<p>[deleted]</p>", "children": []}
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(count_comments(&comments), 3);
+    assert_eq!(comments[0].body_text(), "Nice *post*");
+    assert!(comments[0].created_at_utc().is_some());
+    assert_eq!(comments[1].user.username, "");
+}
+
+#[test]
+fn test_tag_usage_issues_flag_unpopular_tags() {
+    use dtdrafts_core::lint::tag_usage_issues;
+    use dtdrafts_core::tags::{popularity_rank, Tag};
+    let popular = vec!["rust".to_string(), "webdev".to_string()];
+    let issues = tag_usage_issues(&["Rust".to_string(), "rustlang2024".to_string()], &popular);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].rule, "tags");
+    assert_eq!(issues[0].severity, Severity::Warning);
+    assert!(issues[0].message.contains("rustlang2024"));
+
+    let tags: Vec<Tag> = serde_json::from_str(r#"[{"id": 1, "name": "webdev"}, {"id": 2, "name": "rust"}]"#).unwrap();
+    assert_eq!(popularity_rank(&tags, "RUST"), Some(2));
+    assert_eq!(popularity_rank(&tags, "go"), None);
+}
+
+#[test]
+fn test_suggest_tags_votes_with_similar_articles() {
+    use dtdrafts_core::tags::suggest_tags;
+    let mut articles = sample_articles();
+    articles[0].tags = Some(vec!["rust".to_string(), "cli".to_string()]);
+    articles[1].tags = Some(vec!["kotlin".to_string()]);
+    articles[2].tags = None;
+    articles[2].body_markdown = Some("Building a CLI in Rust with clap.\n```rust\nfn main() {}\n```".to_string());
+
+    let suggestions = suggest_tags(&articles[2], &articles, 4);
+    let tags: Vec<&str> = suggestions.iter().map(|suggestion| suggestion.tag.as_str()).collect();
+    assert_eq!(tags.len(), 2);
+    assert!(tags.contains(&"rust") && tags.contains(&"cli"));
+}
+
+#[test]
+fn test_tokenize_drops_stopwords_and_numbers() {
+    use dtdrafts_core::similarity::tokenize;
+    assert_eq!(tokenize("The 3 quick Rust-CLI tips, and 2024"), vec!["quick", "rust", "cli", "tips"]);
+}
+
+#[test]
+fn test_find_duplicates_clusters_similar_drafts() {
+    use dtdrafts_core::similarity::find_duplicates;
+    let mut articles = sample_articles();
+    let mut copy = sample_articles().remove(0);
+    copy.id = 4;
+    copy.title = "Rust tips, take two".to_string();
+    articles.push(copy);
+    articles[2].body_markdown = Some("Kubernetes operators in Go, step by step.".to_string());
+    articles[2].title = "Writing operators".to_string();
+
+    let refs: Vec<&Article> = articles.iter().collect();
+    let clusters = find_duplicates(&refs, 0.5);
+    assert_eq!(clusters.len(), 1);
+    let mut ids: Vec<u64> = clusters[0].articles.iter().map(|article| article.id).collect();
+    ids.sort();
+    assert_eq!(ids, vec![1, 4]);
+    assert!(clusters[0].max_similarity >= 0.5);
+    assert!(find_duplicates(&refs, 1.0).is_empty());
+}
+
+#[test]
+fn test_similarity_index_ranks_related_articles() {
+    use dtdrafts_core::similarity::SimilarityIndex;
+    let articles = sample_articles();
+    let refs: Vec<&Article> = articles.iter().collect();
+    let related = SimilarityIndex::new(&refs).most_similar(1);
+    let ids: Vec<u64> = related.iter().map(|(article, _)| article.id).collect();
+    assert_eq!(ids, vec![3]);
+    assert!(SimilarityIndex::new(&refs).most_similar(99).is_empty());
+}
+
+#[test]
+fn test_rate_limit_delay_reads_retry_after_and_reset() {
+    use dtdrafts_core::client::rate_limit_delay;
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use std::time::Duration;
+    let headers = |pairs: &[(&'static str, &'static str)]| {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_static(value));
+        }
+        map
+    };
+    assert_eq!(rate_limit_delay(&headers(&[])), None);
+    assert_eq!(rate_limit_delay(&headers(&[("retry-after", "7")])), Some(Duration::from_secs(7)));
+    assert_eq!(rate_limit_delay(&headers(&[("ratelimit-remaining", "3"), ("ratelimit-reset", "20")])), None);
+    assert_eq!(
+        rate_limit_delay(&headers(&[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset", "20")])),
+        Some(Duration::from_secs(20))
+    );
+    // Absurd delays are capped rather than overflowing the next request time
+    assert_eq!(
+        rate_limit_delay(&headers(&[("retry-after", "18446744073709551615")])),
+        Some(Duration::from_secs(300))
+    );
+    assert_eq!(
+        rate_limit_delay(&headers(&[("ratelimit-remaining", "0"), ("ratelimit-reset", "99999999999")])),
+        Some(Duration::from_secs(300))
+    );
+}
+
+#[test]
+fn test_client_builder_applies_http_config() {
+    let config: Config = serde_json::from_str(
+        r#"{"api_key": "k", "http": {"timeout_secs": 5, "proxy": "http://proxy.local:3128"}}"#,
+    )
+    .unwrap();
+    assert_eq!(config.http.timeout_secs, Some(5));
+    assert_eq!(config.http.connect_timeout_secs, None);
+    assert!(DevToClient::from_config(&config).is_ok());
+
+    let client = DevToClient::builder()
+        .api_key("k")
+        .base_url("http://localhost:3000/api/")
+        .timeout(std::time::Duration::from_secs(1))
+        .build()
+        .unwrap();
+    assert_eq!(client.base_url(), "http://localhost:3000/api");
+    assert!(DevToClient::builder().proxy("not a url").build().is_err());
+    assert!(!serde_json::to_string(&Config::default()).unwrap().contains("http"));
+}
+
+#[test]
+fn test_client_builder_accepts_an_injected_client() {
+    let http = reqwest::Client::builder().build().unwrap();
+    let client = DevToClient::builder()
+        .api_key("k")
+        .base_url("http://localhost:3000/api")
+        .user_agent("my-tool/1.0")
+        .client(http.clone())
+        .build();
+    assert!(client.is_ok());
+    // Settings the injected client would silently ignore are refused
+    assert!(DevToClient::builder().client(http.clone()).proxy("http://proxy.local:3128").build().is_err());
+    let config: Config = serde_json::from_str(r#"{"api_key": "k", "http": {"timeout_secs": 5}}"#).unwrap();
+    assert!(DevToClient::builder().config(&config).client(http).build().is_err());
+}
+
+#[test]
+fn test_resolve_article_by_id_slug_title_and_index() {
+    let mut articles = sample_articles();
+    articles[2].title = "Rust Tricks".to_string();
+
+    assert_eq!(resolve_article(&articles, "2", &[]).unwrap().id, 2);
+    assert_eq!(resolve_article(&articles, "cli-tricks", &[]).unwrap().id, 3);
+    assert_eq!(resolve_article(&articles, "kotlin", &[]).unwrap().id, 2);
+    assert_eq!(resolve_article(&articles, "RUST TIPS", &[]).unwrap().id, 1);
+    // Ids win over result indexes; other numbers index the last listing
+    assert_eq!(resolve_article(&articles, "1", &[3, 1]).unwrap().id, 1);
+    assert_eq!(resolve_article(&articles, "4", &[3, 1, 2, 3]).unwrap().id, 3);
+    assert!(resolve_article(&articles, "5", &[3]).is_err());
+
+    let ambiguous = resolve_article(&articles, "rust t", &[]).unwrap_err().to_string();
+    assert!(ambiguous.contains("matches 2 articles"), "{ambiguous}");
+    assert!(ambiguous.contains("1  Rust Tips") && ambiguous.contains("3  Rust Tricks"), "{ambiguous}");
+    assert!(resolve_article(&articles, "python", &[]).is_err());
+}
+
+#[test]
+fn test_color_choice() {
+    use dtdrafts_core::style::ColorChoice;
+    assert_eq!("NEVER".parse::<ColorChoice>().unwrap(), ColorChoice::Never);
+    assert!("sometimes".parse::<ColorChoice>().is_err());
+    assert!(ColorChoice::Auto.enabled(true, false));
+    assert!(!ColorChoice::Auto.enabled(false, false), "pipes get no colors");
+    assert!(!ColorChoice::Auto.enabled(true, true), "NO_COLOR disables auto");
+    assert!(ColorChoice::Always.enabled(false, true));
+    assert!(!ColorChoice::Never.enabled(true, false));
+}
+
+#[test]
+fn test_should_page_only_long_output_on_terminals() {
+    use dtdrafts_core::style::should_page;
+    let output = "line\n".repeat(30);
+    assert!(should_page(&output, true, true, Some(24)));
+    assert!(!should_page(&output, false, true, Some(24)), "--no-pager prints as is");
+    assert!(!should_page(&output, true, false, Some(24)), "pipes are never paged");
+    assert!(!should_page(&output, true, true, None), "unknown heights are never paged");
+    // Paged once it no longer fits above the prompt
+    assert!(!should_page(&output, true, true, Some(31)));
+    assert!(should_page(&output, true, true, Some(30)));
+}
+
+#[test]
+fn test_output_template_renders_fields() {
+    use dtdrafts_core::output::OutputTemplate;
+    let articles = sample_articles();
+    let template: OutputTemplate = r"{id}\t{title} [{tags}] {{{word_count}}}".parse().unwrap();
+    assert!(template.needs_body());
+    let line = template.render(&articles[0], &Default::default(), chrono::Utc::now());
+    assert_eq!(line, "1\tRust Tips [rust, cli] {6}");
+
+    let template: OutputTemplate = "{edit_url}".parse().unwrap();
+    assert!(!template.needs_body());
+    assert_eq!(
+        template.render(&articles[2], &Default::default(), chrono::Utc::now()),
+        "https://dev.to/user/cli-tricks/edit"
+    );
+    assert!("{nope}".parse::<OutputTemplate>().is_err());
+    assert!("{title".parse::<OutputTemplate>().is_err());
+}
+
+#[test]
+fn test_api_cover_and_engagement_fields_are_kept() {
+    use dtdrafts_core::output::OutputTemplate;
+    let mut json = serde_json::to_value(&sample_articles()[0]).unwrap();
+    json["cover_image"] = serde_json::json!("https://example.com/cover.png");
+    json["reading_time_minutes"] = serde_json::json!(4);
+    json["comments_count"] = serde_json::json!(2);
+    json["public_reactions_count"] = serde_json::json!(7);
+    let article: Article = serde_json::from_value(json).unwrap();
+    assert!(article.extra.is_empty());
+
+    let template: OutputTemplate =
+        "{cover_image} {reading_time_minutes} {comments_count} {public_reactions_count} [{social_image}]"
+            .parse()
+            .unwrap();
+    assert!(!template.needs_body());
+    assert_eq!(
+        template.render(&article, &Default::default(), chrono::Utc::now()),
+        "https://example.com/cover.png 4 2 7 []"
+    );
+    let summary = compute_stats(&[&article], &Default::default());
+    assert_eq!((summary.with_cover_image, summary.total_comments, summary.total_reactions), (1, 2, 7));
+}
+
+#[test]
+fn test_board_groups_drafts_by_status() {
+    use dtdrafts_core::status::{board, StatusStore, WorkflowStatus};
+    let articles = sample_articles();
+    let drafts = get_draft_articles(&articles);
+    let mut store = StatusStore::default();
+    store.set(3, WorkflowStatus::Review);
+    store.set(2, WorkflowStatus::Review);
+
+    let columns = board(&drafts, &store);
+    assert_eq!(columns.len(), 5);
+    assert_eq!(columns[2].status, Some(WorkflowStatus::Review));
+    assert_eq!(columns[2].articles.iter().map(|a| a.id).collect::<Vec<_>>(), vec![3]);
+    assert_eq!(columns[4].articles.iter().map(|a| a.id).collect::<Vec<_>>(), vec![1]);
+
+    let filter = ArticleFilter::drafts().ids(store.ids_with(WorkflowStatus::Review));
+    assert_eq!(filter_articles(&articles, &filter).iter().map(|a| a.id).collect::<Vec<_>>(), vec![3]);
+    assert_eq!("Ready".parse::<WorkflowStatus>().unwrap(), WorkflowStatus::Ready);
+}
+
+#[test]
+fn test_note_store_add_remove_and_search() {
+    use dtdrafts_core::notes::NoteStore;
+    let mut store = NoteStore::default();
+    let now = chrono::Utc::now();
+    store.add(1, "Add benchmarks", now);
+    store.add(1, "Ask for review", now);
+    store.add(3, "Update the benchmark numbers", now);
+
+    assert_eq!(store.get(1).len(), 2);
+    assert!(store.get(2).is_empty());
+    assert_eq!(store.matching_ids("BENCHMARK"), vec![1, 3]);
+    assert!(store.remove(1, 3).is_none());
+    assert_eq!(store.remove(1, 1).unwrap().text, "Add benchmarks");
+    assert_eq!(store.matching_ids("benchmark"), vec![3]);
+    assert_eq!(store.clear(3).len(), 1);
+    assert!(store.matching_ids("benchmark").is_empty());
+}
+
+#[test]
+fn test_hooks_config_and_payload() {
+    use dtdrafts_core::hooks::{Hook, HookEvent, HookPayload};
+    let config: Config = serde_json::from_str(
+        r#"{"api_key": "k", "hooks": {
+            "on_publish": [{"command": "./tweet.sh"}, {"url": "https://example.com/rebuild"}]
+        }}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        config.hooks.hooks(HookEvent::Publish),
+        [
+            Hook::Command { command: "./tweet.sh".to_string() },
+            Hook::Webhook { url: "https://example.com/rebuild".to_string() },
+        ]
+    );
+    assert!(config.hooks.hooks(HookEvent::Refresh).is_empty());
+    assert!(!serde_json::to_string(&Config::default()).unwrap().contains("hooks"));
+    // Where hook output goes is decided at run time, never stored
+    let quiet = dtdrafts_core::hooks::HooksConfig { stdout_to_stderr: true, ..Default::default() };
+    assert!(quiet.is_empty());
+    assert_eq!(serde_json::to_string(&quiet).unwrap(), "{}");
+
+    let articles = sample_articles();
+    let payload = HookPayload::new(HookEvent::Publish, &[&articles[0]], chrono::Utc::now());
+    let json = serde_json::to_value(&payload).unwrap();
+    assert_eq!(json["event"], "publish");
+    assert_eq!(json["articles"][0]["edit_url"], "https://dev.to/user/rust-tips/edit");
+    assert_eq!(json["articles"][0]["tags"][1], "cli");
+}
+
+#[test]
+fn test_api_summaries_leave_out_bodies() {
+    use dtdrafts_core::api::search_summaries;
+    let articles = sample_articles();
+    let found = search_summaries(&articles, "rust");
+    let ids: Vec<u64> = found.iter().map(|draft| draft.id).collect();
+    assert_eq!(ids, vec![1, 3]);
+    assert_eq!(found[0].edit_url, "https://dev.to/user/rust-tips/edit");
+    let json = serde_json::to_value(&found[0]).unwrap();
+    assert!(json.get("body_markdown").is_none());
+    assert_eq!(json["tags"][0], "rust");
+}
+
+#[test]
+fn test_rpc_parse_request() {
+    use dtdrafts_core::rpc::{parse_request, RpcResponse, INVALID_REQUEST, PARSE_ERROR};
+    let request = parse_request(r#"{"jsonrpc":"2.0","id":7,"method":"search","params":{"query":"rust"}}"#).unwrap();
+    assert_eq!(request.id, Some(serde_json::json!(7)));
+    assert_eq!(request.method, "search");
+    assert_eq!(request.params["query"], "rust");
+
+    let notification = parse_request(r#"{"jsonrpc":"2.0","method":"search"}"#).unwrap();
+    assert_eq!(notification.id, None);
+    assert!(notification.params.is_null());
+
+    let error = |response: RpcResponse| (response.id, response.error.unwrap().code);
+    assert_eq!(error(parse_request("{not json").unwrap_err()), (serde_json::Value::Null, PARSE_ERROR));
+    assert_eq!(
+        error(parse_request(r#"{"jsonrpc":"1.0","id":"a","method":"get"}"#).unwrap_err()),
+        (serde_json::json!("a"), INVALID_REQUEST)
+    );
+    assert_eq!(error(parse_request(r#"{"jsonrpc":"2.0","id":1}"#).unwrap_err()).1, INVALID_REQUEST);
+}
+
+#[test]
+fn test_alfred_items() {
+    use dtdrafts_core::output::{alfred_items, ListFormat};
+    assert_eq!("Alfred".parse::<ListFormat>(), Ok(ListFormat::Alfred));
+    assert!("xml".parse::<ListFormat>().is_err());
+
+    let articles = sample_articles();
+    let json = serde_json::to_value(alfred_items(&[&articles[0]])).unwrap();
+    let item = &json["items"][0];
+    assert_eq!(item["uid"], "1");
+    assert_eq!(item["title"], "Rust Tips");
+    assert_eq!(item["arg"], "https://dev.to/user/rust-tips/edit");
+    assert_eq!(item["valid"], true);
+
+    let empty = serde_json::to_value(alfred_items(&[])).unwrap();
+    assert_eq!(empty["items"][0]["valid"], false);
+    assert!(empty["items"][0].get("arg").is_none());
+}
+
+#[test]
+fn test_atom_feed_orders_by_update_and_escapes() {
+    use dtdrafts_core::feed::atom_feed;
+    let mut articles = sample_articles();
+    articles[0].title = "Tips & <Tricks>".to_string();
+    articles[0].updated_at = timestamp::parse("2024-03-01T00:00:00Z");
+    articles[1].updated_at = timestamp::parse("2024-05-01T00:00:00Z");
+    let refs: Vec<&Article> = articles.iter().take(2).collect();
+    let feed = atom_feed(&refs, chrono::Utc::now());
+
+    assert!(feed.starts_with("<?xml"));
+    assert!(feed.contains("<updated>2024-05-01T00:00:00Z</updated>\n  <generator>"));
+    let first = feed.find(&format!("urn:dtdrafts:article:{}", articles[1].id)).unwrap();
+    let second = feed.find("urn:dtdrafts:article:1<").unwrap();
+    assert!(first < second);
+    assert!(feed.contains("<title>Tips &amp; &lt;Tricks&gt;</title>"));
+    assert!(feed.contains("<link rel=\"edit\" href=\"https://dev.to/user/rust-tips/edit\"/>"));
+    assert!(feed.trim_end().ends_with("</feed>"));
+}
+
+#[test]
+fn test_report_histograms_and_html() {
+    use chrono::TimeZone;
+    use dtdrafts_core::report::{html_report, staleness_histogram};
+    let now = chrono::Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+    let mut articles = sample_articles();
+    articles[0].updated_at = timestamp::parse("2024-05-30T00:00:00Z");
+    articles[1].updated_at = timestamp::parse("2024-04-01T00:00:00Z");
+    articles[2].updated_at = timestamp::parse("2022-01-01T00:00:00Z");
+    articles[2].title = "<script>".to_string();
+    let refs: Vec<&Article> = articles.iter().take(3).collect();
+
+    let counts: Vec<usize> = staleness_histogram(&refs, now).iter().map(|bar| bar.count).collect();
+    assert_eq!(counts, vec![1, 0, 1, 0, 0, 1]);
+
+    let html = html_report(&refs, &Default::default(), now);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("&lt;script&gt;"));
+    assert!(!html.contains("<script"));
+    assert!(html.contains("https://dev.to/user/rust-tips/edit"));
+}
+
+#[test]
+fn test_cache_formats_round_trip() {
+    use dtdrafts_core::cache::CacheFormat;
+    let articles = sample_articles();
+    for format in [CacheFormat::Json, CacheFormat::Compact] {
+        let decoded: Vec<Article> = format.decode(&format.encode(&articles).unwrap()).unwrap();
+        assert_eq!(decoded.len(), articles.len());
+        assert_eq!(decoded[0].title, articles[0].title);
+        assert_eq!(decoded[0].body_markdown, articles[0].body_markdown);
+        assert_eq!(decoded[0].series, articles[0].series);
+    }
+    assert!(CacheFormat::Compact.decode::<Vec<Article>>(b"[]").is_err());
+    assert_eq!("Compact".parse::<CacheFormat>(), Ok(CacheFormat::Compact));
+    assert!("bincode".parse::<CacheFormat>().is_err());
+}
+
+#[test]
+fn test_article_body_falls_back_to_cached_bodies() {
+    use dtdrafts_core::cache::CachedBodies;
+    let mut articles = sample_articles();
+    let mut bodies = CachedBodies::default();
+    bodies.insert(articles[0].id, "from the bodies file".to_string());
+    assert_eq!(articles[0].body(&bodies), articles[0].body_markdown.as_deref());
+
+    articles[0].body_markdown = None;
+    assert_eq!(articles[0].body(&bodies), Some("from the bodies file"));
+    articles[1].body_markdown = None;
+    assert_eq!(articles[1].body(&bodies), None);
+
+    let format = dtdrafts_core::cache::CacheFormat::Compact;
+    let decoded: CachedBodies = format.decode(&format.encode(&bodies).unwrap()).unwrap();
+    assert_eq!(decoded, bodies);
+}
+
+#[test]
+fn test_cache_formats_stream_elements_and_entries() {
+    use dtdrafts_core::cache::{CacheFormat, CachedBodies};
+    let dir = std::env::temp_dir().join(format!("dtdrafts-stream-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let articles = sample_articles();
+    let mut bodies = CachedBodies::default();
+    bodies.insert(7, "seven".to_string());
+    bodies.insert(8, "eight".to_string());
+
+    for format in [CacheFormat::Json, CacheFormat::Compact] {
+        let articles_file = dir.join(format.file_name());
+        std::fs::write(&articles_file, format.encode(&articles).unwrap()).unwrap();
+        let mut ids = Vec::new();
+        format.for_each_element(&articles_file, |article: Article| ids.push(article.id)).unwrap();
+        assert_eq!(ids, articles.iter().map(|article| article.id).collect::<Vec<_>>());
+
+        let bodies_file = dir.join(format.bodies_file_name());
+        std::fs::write(&bodies_file, format.encode(&bodies).unwrap()).unwrap();
+        let mut entries = Vec::new();
+        format.for_each_entry(&bodies_file, |id: u64, body: String| entries.push((id, body))).unwrap();
+        entries.sort();
+        assert_eq!(entries, vec![(7, "seven".to_string()), (8, "eight".to_string())]);
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_cache_verify_finds_damaged_records() {
+    use dtdrafts_core::cache::{checksum, verify, CacheChecksums, CacheProblem, CachedBodies};
+    let mut articles = sample_articles();
+    let mut bodies = CachedBodies::default();
+    let mut checksums = CacheChecksums::default();
+    for article in &mut articles {
+        let body = article.body_markdown.take();
+        checksums.insert(article.id, checksum(article, body.as_deref()).unwrap());
+        if let Some(body) = body {
+            bodies.insert(article.id, body);
+        }
+    }
+    checksums.insert(999, 0);
+    assert_eq!(verify(&articles, &bodies, &checksums).unwrap().len(), 1);
+
+    articles[0].title.push('!');
+    bodies.insert(articles[1].id, "tampered".to_string());
+    let damaged = verify(&articles, &bodies, &checksums).unwrap();
+    let problems: Vec<(u64, CacheProblem)> = damaged.iter().map(|record| (record.id, record.problem)).collect();
+    assert_eq!(
+        problems,
+        vec![
+            (articles[0].id, CacheProblem::Mismatch),
+            (articles[1].id, CacheProblem::Mismatch),
+            (999, CacheProblem::Missing),
+        ]
+    );
+    assert_eq!(damaged[2].title, None);
+}
+
+#[test]
+fn test_recorded_fixtures_replay_through_the_client() {
+    use dtdrafts_core::fixtures::{Fixture, FixtureMode, Recorder, REDACTED};
+    let dir = std::env::temp_dir().join(format!("dtdrafts-fixtures-test-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("content-type", "application/json".parse().unwrap());
+    headers.insert("set-cookie", "session=secret-key".parse().unwrap());
+    let body = serde_json::to_vec(&sample_articles()).unwrap();
+    let fixture = Fixture::new("GET", "/articles/me/unpublished?page=1&per_page=1000", None, 200, &headers, &body);
+    let recorder = Recorder::new(&dir, "secret-key").unwrap();
+    let file = recorder.record(&fixture).unwrap();
+    assert_eq!(file.file_name().unwrap(), "0001-get-articles_me_unpublished.json");
+    let saved = std::fs::read_to_string(&file).unwrap();
+    assert!(!saved.contains("set-cookie") && !saved.contains("secret-key"));
+    assert_eq!(dtdrafts_core::fixtures::scrub("key=secret-key", "secret-key"), format!("key={REDACTED}"));
+
+    let client = DevToClient::builder()
+        .base_url("http://127.0.0.1:9/api")
+        .fixtures(FixtureMode::Replay(dir.clone()))
+        .build()
+        .unwrap()
+        .with_progress(false);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let articles = runtime.block_on(client.get_my_articles()).unwrap();
+    let ids: Vec<u64> = articles.iter().map(|article| article.id).collect();
+    assert_eq!(ids, sample_articles().iter().map(|article| article.id).collect::<Vec<_>>());
+    let error = runtime.block_on(client.get_my_articles()).unwrap_err();
+    assert!(format!("{error:#}").contains("No recorded response left for GET /articles/me/unpublished"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_parse_articles_skips_malformed_elements() {
+    use dtdrafts_core::client::parse_articles;
+    let mut elements: Vec<serde_json::Value> =
+        sample_articles().iter().map(|article| serde_json::to_value(article).unwrap()).collect();
+    elements[0]["published"] = serde_json::json!("no");
+    elements.push(serde_json::json!({"title": "no id"}));
+    let json = serde_json::to_string(&elements).unwrap();
+
+    let (articles, failures) = parse_articles(&json, false).unwrap();
+    assert_eq!(articles.len(), sample_articles().len() - 1);
+    assert_eq!(failures.iter().map(|failure| failure.id).collect::<Vec<_>>(), [Some(1), None]);
+    let error = parse_articles(&json, true).unwrap_err();
+    assert!(format!("{error:#}").contains("article 1 is malformed"));
+    assert!(parse_articles("{", false).is_err());
+}
+
+#[test]
+fn test_unknown_article_fields_survive_cache_round_trips() {
+    use dtdrafts_core::cache::CacheFormat;
+    let mut json = serde_json::to_value(&sample_articles()[0]).unwrap();
+    json["path"] = serde_json::json!("/user/rust-tips");
+    json["flare_tag"] = serde_json::json!({"name": "discuss", "bg_color_hex": "#000000"});
+    let article: Article = serde_json::from_value(json).unwrap();
+    assert_eq!(article.extra("path"), Some(&serde_json::json!("/user/rust-tips")));
+    assert_eq!(article.extra("title"), None);
+
+    for format in [CacheFormat::Json, CacheFormat::Compact] {
+        let decoded: Vec<Article> = format.decode(&format.encode(&[&article]).unwrap()).unwrap();
+        assert_eq!(decoded[0].extra, article.extra);
+        assert_eq!(decoded[0].title, article.title);
+    }
+    let plain = serde_json::to_value(&sample_articles()[1]).unwrap();
+    assert!(plain.get("extra").is_none());
+}
+
+#[test]
+fn test_cache_versions_are_migrated() {
+    use dtdrafts_core::cache::CacheFormat;
+    use dtdrafts_core::migrate::{upgrade, CACHE_VERSION};
+    let mut legacy: Vec<serde_json::Value> =
+        sample_articles().iter().map(|article| serde_json::to_value(article).unwrap()).collect();
+    legacy[0]["created_at"] = serde_json::json!("2024-03-01T10:00:00+09:00");
+    legacy[1]["updated_at"] = serde_json::json!("yesterday");
+    let upgraded = upgrade(legacy.clone(), 1).unwrap();
+    assert_eq!(upgraded[0]["created_at"], "2024-03-01T01:00:00Z");
+    assert_eq!(upgraded[1]["updated_at"], serde_json::Value::Null);
+    assert!(upgrade(legacy.clone(), CACHE_VERSION + 1).is_err());
+
+    let dir = std::env::temp_dir().join(format!("dtdrafts-migrate-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for format in [CacheFormat::Json, CacheFormat::Compact] {
+        let path = dir.join(format.file_name());
+        std::fs::write(&path, format.encode(&legacy).unwrap()).unwrap();
+        assert_eq!(format.stored_version(&path).unwrap(), 1);
+        let (articles, version) = format.decode_articles(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!((articles.len(), version), (sample_articles().len(), 1));
+        assert_eq!(articles[0].created_at, parse_date("2024-03-01T01:00:00Z").ok());
+
+        std::fs::write(&path, format.encode_articles(&articles).unwrap()).unwrap();
+        assert_eq!(format.stored_version(&path).unwrap(), CACHE_VERSION);
+        let (reloaded, version) = format.decode_articles(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!((reloaded.len(), version), (articles.len(), CACHE_VERSION));
+        let mut ids = Vec::new();
+        format.for_each_element(&path, |article: Article| ids.push(article.id)).unwrap();
+        assert_eq!(ids, articles.iter().map(|article| article.id).collect::<Vec<_>>());
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_replacement_is_literal_unless_regex() {
+    use dtdrafts_core::bulk::BulkChange;
+    use dtdrafts_core::replace::Replacement;
+
+    let literal = Replacement::new("old-domain.com", "$price.com", false).unwrap();
+    assert_eq!(
+        literal.apply("See old-domain.com and oldXdomain.com, or old-domain.com/rss"),
+        Some(("See $price.com and oldXdomain.com, or $price.com/rss".to_string(), 2))
+    );
+    assert_eq!(literal.apply("nothing here"), None);
+
+    let regex = Replacement::new(r"https?://old\.dev/(\w+)", "https://new.dev/$1", true).unwrap();
+    assert_eq!(regex.apply("[a](http://old.dev/rust)"), Some(("[a](https://new.dev/rust)".to_string(), 1)));
+    assert!(Replacement::new("(", "x", true).is_err());
+    assert!(Replacement::new("", "x", false).is_err());
+
+    let mut article = sample_articles().remove(0);
+    article.body_markdown = Some("Visit old-domain.com".to_string());
+    match literal.plan(&article) {
+        BulkChange::Update(input, _) => assert_eq!(input.body_markdown.as_deref(), Some("Visit $price.com")),
+        other => panic!("expected an update, got {other:?}"),
+    }
+    article.body_markdown = Some("Nothing to see".to_string());
+    assert!(matches!(literal.plan(&article), BulkChange::Skip(_)));
+}
+
+#[test]
+fn test_lint_markdown_flags_structure_pitfalls() {
+    use dtdrafts_core::lint::lint_markdown;
+
+    let long = vec!["word"; 160].join(" ");
+    let body = format!(
+        "---\ntitle: My Post\n---\n# My Post\n\n## Setup\n\n#### Details\n\nSee https://example.com/docs. Or [docs](https://example.com).\n\
+         {{% embed https://example.com/embed %}}\n\n![](https://example.com/a.png) ![chart](https://example.com/b.png)\n\n\
+         <img src=\"https://example.com/c.png\">\n\n{long}\n\n```rust\nlet url = \"https://example.com\";\n"
+    );
+    let found: Vec<(Option<usize>, Severity, String)> = lint_markdown(&body, "My Post")
+        .into_iter()
+        .map(|issue| (issue.line, issue.severity, issue.message))
+        .collect();
+    let lines: Vec<(Option<usize>, Severity)> = found.iter().map(|(line, severity, _)| (*line, *severity)).collect();
+    assert_eq!(
+        lines,
+        [
+            (Some(4), Severity::Warning),
+            (Some(8), Severity::Warning),
+            (Some(10), Severity::Warning),
+            (Some(13), Severity::Warning),
+            (Some(15), Severity::Warning),
+            (Some(17), Severity::Warning),
+            (Some(19), Severity::Error),
+        ],
+        "{found:#?}"
+    );
+    assert!(found[0].2.contains("repeats the title"));
+    assert!(found[1].2.contains("h2 to h4"));
+    assert!(found[2].2.contains("bare URL https://example.com/docs;"));
+    assert!(found[3].2.contains("alt text"));
+    assert!(found[5].2.contains("160 words"));
+
+    assert!(lint_markdown("## Intro\n\nHello [there](https://example.com).\n\n### More\n", "Post").is_empty());
+    assert!(lint_markdown("# Other heading\n", "Post")[0].message.contains("use ## for sections"));
+
+    // Structure problems alone don't fail the publish checks
+    let mut article = sample_articles().remove(0);
+    article.body_markdown = Some(body);
+    assert!(lint_article(&article).iter().all(|issue| issue.rule != "markdown"));
+}
+
+#[test]
+fn test_lint_code_blocks_checks_languages_and_rust_snippets() {
+    use dtdrafts_core::lint::lint_code_blocks;
+
+    let body = "---\ntitle: Code\n---\n```rust\nfn main() {\n    println!(\"hi\");\n}\n```\n\n```rust\nlet x = 1;\nx + 1\n```\n\n\
+                ```\nplain\n```\n\n~~~brainfsck\n+\n~~~\n\n```rust\nfn broken( {\n```\n\n```rust,ignore\nfn broken( {\n```\n\n```JS {2}\nx\n```";
+    let found: Vec<(Option<usize>, String)> =
+        lint_code_blocks(body).into_iter().map(|issue| (issue.line, issue.message)).collect();
+    let lines: Vec<Option<usize>> = found.iter().map(|(line, _)| *line).collect();
+    assert_eq!(lines, [Some(15), Some(19), Some(23)], "{found:#?}");
+    assert!(found[0].1.contains("no language"));
+    assert!(found[1].1.contains("`brainfsck`"));
+    assert!(found[2].1.starts_with("rust snippet doesn't parse"));
+}
+
+#[test]
+fn test_seo_report_checks_title_description_keyword_and_readability() {
+    use dtdrafts_core::seo::{analyze, count_syllables, readability};
+
+    assert_eq!(count_syllables("cat"), 1);
+    assert_eq!(count_syllables("table"), 2);
+    assert_eq!(count_syllables("make"), 1);
+    assert_eq!(count_syllables("reading"), 2);
+
+    let easy = readability("# Intro\n\nThe cat sat. The dog ran!\n\n```\nlet ignored = code_words();\n```");
+    assert_eq!((easy.words, easy.sentences), (7, 3));
+    assert!(easy.reading_ease > 90.0, "{}", easy.reading_ease);
+
+    let mut article = sample_articles().remove(0);
+    article.title = "Building a Rust CLI: argument parsing with clap".to_string();
+    article.description = None;
+    article.body_markdown = Some(
+        "---\ndescription: Short\n---\nA Rust CLI needs arguments. This post builds a small rust cli step by step.\n"
+            .to_string(),
+    );
+    let report = analyze(&article, Some("Rust CLI"));
+    assert_eq!(report.title_chars, 47);
+    assert_eq!(report.description.as_deref(), Some("Short"));
+    let keyword = report.keyword.as_ref().unwrap();
+    assert_eq!(keyword.occurrences, 2);
+    assert!(keyword.in_title && keyword.in_intro && !keyword.in_description);
+    let passed: Vec<bool> = report.checks.iter().map(|check| check.passed).collect();
+    // title, description, cover, in title, in description, in intro, density, reading ease
+    assert_eq!(passed[..7], [true, false, false, true, false, true, false]);
+    assert!(analyze(&article, None).keyword.is_none());
+}
+
+#[test]
+fn test_toc_lists_headings_and_updates_between_markers() {
+    use dtdrafts_core::toc::{anchor, headings, insert_toc, render_toc};
+
+    assert_eq!(anchor("What's new in Rust 1.80?"), "whats-new-in-rust-180");
+    let body = "---\ntitle: T\n---\nIntro.\n\n## Setup\n\n```sh\n# not a heading\n```\n\n### Install `cargo`\n\n## Setup\n\n#### Deep\n";
+    let found = headings(body);
+    let anchors: Vec<&str> = found.iter().map(|heading| heading.anchor.as_str()).collect();
+    assert_eq!(anchors, ["setup", "install-cargo", "setup-1", "deep"]);
+    let toc = render_toc(&found, 3);
+    assert_eq!(toc, "- [Setup](#setup)\n  - [Install cargo](#install-cargo)\n- [Setup](#setup-1)\n");
+
+    let inserted = insert_toc(body, &toc);
+    assert!(inserted.starts_with("---\ntitle: T\n---\nIntro.\n\n<!-- toc -->\n- [Setup](#setup)\n"));
+    assert!(inserted.contains("<!-- tocstop -->\n\n## Setup"));
+    // Refreshing replaces the old list and keeps everything else
+    let refreshed = insert_toc(&inserted, "- [Only](#only)\n");
+    assert_eq!(refreshed, inserted.replace(&toc, "- [Only](#only)\n"));
+    assert_eq!(insert_toc("Text\n<!-- toc -->\nMore", "- [A](#a)\n"), "Text\n<!-- toc -->\n- [A](#a)\n<!-- tocstop -->\nMore");
+}
+
+#[test]
+fn test_front_matter_mapping_round_trips_hugo_and_custom_styles() {
+    use dtdrafts_core::frontmatter::{FrontMatterMapping, FrontMatterStyle};
+
+    let document = "---\ntitle: \"Rust: the good parts\"\npublished: false\ntags: rust, cli\ncanonical_url: https://example.com/a\n---\nBody\n";
+    let hugo = FrontMatterStyle::Hugo.mapping(&FrontMatterMapping::default());
+    let local = hugo.to_local(document);
+    assert_eq!(
+        local,
+        "---\ntitle: \"Rust: the good parts\"\ndraft: true\ntags: [\"rust\", \"cli\"]\ncanonical_url: https://example.com/a\n---\nBody\n"
+    );
+    assert_eq!(hugo.to_devto(&local), document);
+
+    let custom: FrontMatterMapping = toml::from_str(
+        "tag_list = true\n[rename]\ntags = \"categories\"\ncanonical_url = \"canonicalURL\"\n[extra]\nlayout = \"post\"\n",
+    )
+    .unwrap();
+    let local = FrontMatterStyle::Custom.mapping(&custom).to_local(document);
+    assert!(local.starts_with("---\nlayout: post\ntitle:"));
+    assert!(local.contains("categories: [\"rust\", \"cli\"]\ncanonicalURL: https://example.com/a\n"));
+    assert_eq!(custom.to_devto(&local), document);
+
+    let devto = FrontMatterStyle::DevTo.mapping(&custom);
+    assert_eq!(devto.to_local("---\ntitle:  odd  spacing\n---\n"), "---\ntitle:  odd  spacing\n---\n");
+    assert_eq!("jekyll".parse::<FrontMatterStyle>().unwrap(), FrontMatterStyle::Jekyll);
+    assert!("astro".parse::<FrontMatterStyle>().is_err());
+}
+
+#[test]
+fn test_canonical_url_checks_and_input() {
+    use dtdrafts_core::client::LinkCheck;
+    use dtdrafts_core::frontmatter::canonical_input;
+    use dtdrafts_core::lint::{canonical_url_issue, missing_canonical_issue, validate_metadata, PublishMetadata};
+
+    let mut articles = sample_articles();
+    articles[0].canonical_url = Some("https://blog.dev/original".to_string());
+    let metadata = PublishMetadata::of(&articles[0]);
+    assert_eq!(metadata.canonical_url.as_deref(), Some("https://blog.dev/original"));
+    assert!(missing_canonical_issue(&metadata).is_none());
+    assert!(canonical_url_issue("https://blog.dev/original", &LinkCheck::Ok).is_none());
+    let dead = canonical_url_issue("https://blog.dev/original", &LinkCheck::Broken(404)).unwrap();
+    assert_eq!(dead.severity, Severity::Error);
+
+    // Front matter wins, and an empty value means no canonical URL
+    articles[0].body_markdown = Some("---\ntitle: T\ncanonical_url:\n---\nText".to_string());
+    let metadata = PublishMetadata::of(&articles[0]);
+    assert_eq!(metadata.canonical_url, None);
+    assert_eq!(missing_canonical_issue(&metadata).unwrap().severity, Severity::Warning);
+
+    let input = canonical_input(&articles[0], "blog.dev/relative");
+    assert_eq!(input.body_markdown.as_deref(), Some("---\ntitle: T\ncanonical_url: blog.dev/relative\n---\nText"));
+    let metadata = PublishMetadata { canonical_url: input.canonical_url, ..PublishMetadata::of(&articles[1]) };
+    let messages: Vec<String> = validate_metadata(&metadata).into_iter().map(|issue| issue.message).collect();
+    assert_eq!(messages, vec!["canonical_url is not an http(s) URL"]);
+}
+
+#[test]
+fn test_title_card_renders_a_cover_sized_png() {
+    use dtdrafts_core::cover::{wrap_words, Color, TitleCard, COVER_HEIGHT, COVER_WIDTH};
+
+    assert_eq!("#0F172a".parse::<Color>().unwrap(), Color([0x0f, 0x17, 0x2a]));
+    assert_eq!(Color([255, 0, 16]).to_string(), "#ff0010");
+    assert!("#12345".parse::<Color>().is_err());
+    assert_eq!(wrap_words("Async Rust in practice", 10), vec!["Async Rust", "in", "practice"]);
+    assert_eq!(wrap_words("a supercalifragilistic b", 8), vec!["a", "supercal", "ifragili", "stic b"]);
+
+    let png = TitleCard::new("Async Rust in practice", "#rust  @me").render_png().unwrap();
+    let decoder = png::Decoder::new(png.as_slice());
+    let info = decoder.read_info().unwrap().info().clone();
+    assert_eq!((info.width, info.height), (COVER_WIDTH, COVER_HEIGHT));
+}
+
+#[test]
+fn test_history_adds_changed_versions_and_finds_them() {
+    use dtdrafts_core::frontmatter::draft_document;
+    use dtdrafts_core::history::{content_hash, Direction, History, MAX_VERSIONS};
+
+    let mut articles = sample_articles();
+    let at = chrono::Utc::now();
+    let mut history = History::default();
+    assert!(history.add(&articles[0], Direction::Pull, at).is_some());
+    assert!(history.add(&articles[0], Direction::Pull, at).is_none());
+    articles[0].body_markdown = Some("Rewritten".to_string());
+    let (version, document) = history.add(&articles[0], Direction::Push, at).unwrap();
+    assert_eq!(version.hash, content_hash(&document));
+    assert!(document.ends_with("---\nRewritten"));
+
+    let id = articles[0].id;
+    let versions = history.get(id);
+    assert_eq!(versions.len(), 2);
+    assert_eq!(history.find(id, "2").unwrap().direction, Direction::Push);
+    assert_eq!(history.find(id, &versions[0].hash[..6]).unwrap(), &versions[0]);
+    assert!(history.find(id, "3").is_err());
+    assert!(history.find(id + 1, "1").is_err());
+
+    // Only the latest versions are kept
+    let first = versions[0].hash.clone();
+    for i in 0..MAX_VERSIONS {
+        articles[0].body_markdown = Some(format!("Draft {i}"));
+        history.add(&articles[0], Direction::Push, at);
+    }
+    assert_eq!(history.get(id).len(), MAX_VERSIONS);
+    assert!(!history.hashes().contains(first.as_str()));
+    assert!(history.hashes().contains(content_hash(&draft_document(&articles[0])).as_str()));
+}
+
+#[test]
+fn test_journal_keeps_recent_entries_and_restores_drafts() {
+    use dtdrafts_core::journal::{Journal, JournalChange, JournalEntry, MAX_ENTRIES};
+
+    let articles = sample_articles();
+    let change = JournalChange::updated(articles[0].clone(), &articles[0]);
+    let input = change.restore_input();
+    assert_eq!(input.title.as_deref(), Some(articles[0].title.as_str()));
+    assert_eq!(input.published, Some(false));
+    assert_eq!(input.canonical_url.as_deref(), Some(""));
+
+    let mut embedded = articles[0].clone();
+    embedded.body_markdown = Some("---\ntitle: T\npublished: false\n---\nText".to_string());
+    let input = JournalChange::deleted(embedded).restore_input();
+    assert_eq!(input.body_markdown.as_deref(), Some("---\ntitle: T\npublished: false\n---\nText"));
+    assert_eq!((input.title, input.published), (None, Some(false)));
+
+    let mut journal = Journal::default();
+    for i in 0..MAX_ENTRIES + 2 {
+        let operation = format!("op{i}");
+        journal.push(JournalEntry { at: chrono::Utc::now(), operation, changes: vec![change.clone()] });
+    }
+    assert_eq!(journal.entries.len(), MAX_ENTRIES);
+    assert_eq!(journal.entries[0].operation, "op2");
+    assert_eq!(journal.pop().unwrap().operation, format!("op{}", MAX_ENTRIES + 1));
+}
+
+#[test]
+fn test_journal_notices_edits_made_after_the_operation() {
+    use dtdrafts_core::journal::JournalChange;
+
+    let articles = sample_articles();
+    let mut written = articles[0].clone();
+    written.updated_at = dtdrafts_core::timestamp::parse("2024-03-01T10:00:00Z");
+    let change = JournalChange::updated(articles[0].clone(), &written);
+    assert!(!change.changed_since(&written));
+
+    let mut edited = written.clone();
+    edited.body_markdown = Some("Rewritten by hand".to_string());
+    assert!(change.changed_since(&edited));
+    let mut touched = written.clone();
+    touched.updated_at = dtdrafts_core::timestamp::parse("2024-03-02T10:00:00Z");
+    assert!(change.changed_since(&touched));
+    // Listings without bodies or timestamps can't show an edit
+    let mut bare = written.clone();
+    (bare.body_markdown, bare.updated_at) = (None, None);
+    assert!(!change.changed_since(&bare));
+    // Nothing to compare with for deletions
+    assert!(!JournalChange::deleted(articles[0].clone()).changed_since(&edited));
+}
+
+#[test]
+fn test_journal_forgets_deletions_restored_from_the_trash() {
+    use dtdrafts_core::journal::{Journal, JournalChange, JournalEntry};
+
+    let articles = sample_articles();
+    let at = chrono::Utc::now();
+    let mut journal = Journal::default();
+    let deleted = |article: &Article| JournalChange::deleted(article.clone());
+    let entry = |operation: &str, changes| JournalEntry { at, operation: operation.to_string(), changes };
+    journal.push(entry("delete", vec![deleted(&articles[0])]));
+    journal.push(entry("merge", vec![deleted(&articles[1]), deleted(&articles[2])]));
+    journal.push(entry("add-tag:x", vec![JournalChange::updated(articles[1].clone(), &articles[1])]));
+
+    // Restoring one merged draft leaves the other for undo
+    assert!(journal.forget_deletion(articles[1].id));
+    assert_eq!(journal.entries.len(), 3);
+    assert_eq!(journal.entries[1].changes.len(), 1);
+    assert_eq!(journal.entries[1].changes[0].before.id, articles[2].id);
+    assert_eq!(journal.entries[2].changes.len(), 1);
+
+    assert!(journal.forget_deletion(articles[0].id));
+    assert_eq!(journal.entries.len(), 2);
+    assert_eq!(journal.last().unwrap().operation, "add-tag:x");
+    assert!(!journal.forget_deletion(articles[0].id));
+}
+
+#[test]
+#[cfg(feature = "terminal")]
+fn test_labelled_listing_mixes_drafts_and_published_articles() {
+    use dtdrafts_core::format_labelled_articles;
+
+    let mut articles = sample_articles();
+    articles[1].published = true;
+    let drafts = ArticleFilter::drafts();
+    assert!(!drafts.matches(&articles[1]));
+    assert!(drafts.include_published().matches(&articles[1]));
+
+    let refs: Vec<&Article> = articles.iter().take(2).collect();
+    let output = without_colors(|| format_labelled_articles(&refs));
+    assert!(output.starts_with("2 article(s) found (1 draft(s), 1 live):"));
+    assert!(output.contains(&format!("1. DRAFT {}\n{}\n", articles[0].title, articles[0].edit_url())));
+    assert!(output.contains(&format!("2. LIVE  {}\n{}\n", articles[1].title, articles[1].url)));
+}
+
+#[test]
+fn test_saved_search_parses_keywords_and_reports_changes() {
+    use dtdrafts_core::filter::{parse_date, parse_search};
+    use dtdrafts_core::saved::{self, ResultChange, SavedResults};
+
+    let week_ago = parse_date("-7d").unwrap();
+    assert!((chrono::Utc::now() - chrono::Duration::days(7) - week_ago).num_seconds().abs() < 5);
+
+    let (keywords, filter) = parse_search("\"error handling\" tag:rust updated-after:-7d").unwrap();
+    assert_eq!(keywords, "error handling");
+    let mut articles = sample_articles();
+    articles[0].tags = Some(vec!["rust".to_string()]);
+    articles[0].updated_at = Some(chrono::Utc::now());
+    assert!(filter.matches(&articles[0]));
+    assert!(parse_search("tag:").is_err());
+
+    let mut results = SavedResults::default();
+    let found = saved::run(&articles, "tag:rust").unwrap();
+    assert_eq!(results.update("weekly", &found), None);
+    let all: Vec<&Article> = articles.iter().collect();
+    let change = results.update("weekly", &all[1..]).unwrap();
+    assert_eq!(change.added, vec![articles[1].id, articles[2].id]);
+    assert_eq!(change.removed, vec![articles[0].id]);
+    assert_eq!(results.update("weekly", &all[1..]), Some(ResultChange::default()));
+
+    let (summary, body) = saved::notification_text("weekly", &change, &all);
+    assert_eq!(summary, "Saved search 'weekly': 2 new, 1 gone");
+    assert_eq!(body, format!("{}\n{}", articles[1].title, articles[2].title));
+    assert!(saved::validate_name("bad name").is_err());
+}
+
+#[test]
+fn test_search_history_reruns_recorded_arguments() {
+    use dtdrafts_core::search_history::{expand, rerun_trigger, SearchEntry, SearchHistory};
+
+    let args = |line: &str| line.split(' ').map(str::to_string).collect::<Vec<_>>();
+    let at = chrono::Utc::now();
+    let mut history = SearchHistory::default();
+    history.push(SearchEntry { at, args: vec!["search".into(), "error handling".into(), "--tag".into(), "rust".into()] });
+    history.push(SearchEntry { at, args: args("-q kotlin") });
+    history.push(SearchEntry { at, args: args("-q kotlin") });
+    assert_eq!(history.entries.len(), 2);
+    assert_eq!(history.get(1).unwrap().args, args("-q kotlin"));
+    assert_eq!(history.get(2).unwrap().command_line(), "search 'error handling' --tag rust");
+    assert!(history.get(3).is_err());
+
+    let takes_value = |flag: &str| matches!(flag, "-q" | "--profile");
+    assert_eq!(rerun_trigger(&args("search --again --open"), takes_value), Some(("--again", 1)));
+    assert_eq!(rerun_trigger(&args("--refresh search !2 --desc"), takes_value), Some(("!2", 2)));
+    assert_eq!(rerun_trigger(&args("--profile work search !2"), takes_value), Some(("!2", 2)));
+    assert_eq!(rerun_trigger(&args("-q !1"), takes_value), Some(("!1", 1)));
+    assert_eq!(rerun_trigger(&args("replace !2 x"), takes_value), None);
+    assert_eq!(rerun_trigger(&args("search rust"), takes_value), None);
+    // Only the subcommand counts, not a later `search` or `-q` value
+    assert_eq!(rerun_trigger(&args("replace --pattern search --with !1"), takes_value), None);
+    assert_eq!(rerun_trigger(&args("--profile search list !1"), takes_value), None);
+    assert_eq!(rerun_trigger(&args("replace -q !1"), takes_value), None);
+
+    let expanded = expand(&args("--refresh search !2 --open"), "!2", history.get(2).unwrap());
+    assert_eq!(expanded, ["search", "error handling", "--tag", "rust", "--refresh", "--open"]);
+    assert_eq!(expand(&args("-q !1"), "!1", history.get(1).unwrap()), args("-q kotlin"));
+}
+
+#[test]
+fn test_count_and_ids_list_formats() {
+    use dtdrafts_core::output::ListFormat;
+
+    let articles = sample_articles();
+    let refs: Vec<&Article> = articles.iter().collect();
+    assert_eq!("ids".parse::<ListFormat>(), Ok(ListFormat::Ids));
+    assert_eq!(ListFormat::Count.render(&refs).unwrap(), "3\n");
+    let ids: String = articles.iter().map(|article| format!("{}\n", article.id)).collect();
+    assert_eq!(ListFormat::Ids.render(&refs).unwrap(), ids);
+    assert_eq!(ListFormat::Ids.render(&[]).unwrap(), "");
+    assert!(ListFormat::Text.render(&refs).is_none());
+}
+
+#[test]
+#[cfg(feature = "terminal")]
+fn test_table_format_aligns_columns_and_truncates_titles() {
+    use dtdrafts_core::output::format_table;
+
+    let mut articles = sample_articles();
+    articles[0].title = "A very long title that cannot possibly fit in a narrow terminal".to_string();
+    let refs: Vec<&Article> = articles.iter().collect();
+    let table = without_colors(|| format_table(&refs, &Default::default(), 70));
+
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("#  ID"));
+    assert!(lines[1].contains("A very long title") && lines[1].contains('…'), "{table}");
+    let widths: Vec<usize> = lines.iter().map(|line| line.chars().count()).collect();
+    assert!(widths.iter().all(|width| *width == widths[0]), "{table}");
+    assert!(widths[0] <= 70, "{table}");
+}
+
+#[test]
+fn test_group_articles_by_tag_month_and_status() {
+    use dtdrafts_core::group::{group_articles, GroupBy};
+    use dtdrafts_core::status::{StatusStore, WorkflowStatus};
+
+    let mut articles = sample_articles();
+    articles[1].tags = Some(vec!["rust".to_string()]);
+    articles[2].tags = None;
+    articles[0].updated_at = Some("2024-05-02T10:00:00Z".parse().unwrap());
+    articles[1].created_at = Some("2024-06-10T10:00:00Z".parse().unwrap());
+    let refs: Vec<&Article> = articles.iter().collect();
+    let summary = |groups: Vec<dtdrafts_core::group::Group>| -> Vec<(Option<String>, Vec<u64>)> {
+        groups.into_iter().map(|group| (group.name, group.articles.iter().map(|a| a.id).collect())).collect()
+    };
+
+    let statuses = StatusStore::default();
+    assert_eq!(
+        summary(group_articles(&refs, GroupBy::Tag, &statuses)),
+        [(Some("rust".to_string()), vec![1, 2]), (Some("cli".to_string()), vec![1]), (None, vec![3])]
+    );
+    assert_eq!(
+        summary(group_articles(&refs, GroupBy::Month, &statuses)),
+        [(Some("2024-06".to_string()), vec![2]), (Some("2024-05".to_string()), vec![1]), (None, vec![3])]
+    );
+
+    let mut statuses = StatusStore::default();
+    statuses.set(3, WorkflowStatus::Ready);
+    statuses.set(2, WorkflowStatus::Idea);
+    assert_eq!(
+        summary(group_articles(&refs, GroupBy::Status, &statuses)),
+        [(Some("idea".to_string()), vec![2]), (Some("ready".to_string()), vec![3]), (None, vec![1])]
+    );
+    assert!("weekday".parse::<GroupBy>().is_err());
+}
+
+#[test]
+#[cfg(feature = "terminal")]
+fn test_calendar_counts_created_and_updated_days() {
+    use dtdrafts_core::calendar::{render, Activity};
+
+    let mut articles = sample_articles();
+    articles[0].created_at = Some("2024-05-06T10:00:00Z".parse().unwrap());
+    articles[0].updated_at = Some("2024-05-08T10:00:00Z".parse().unwrap());
+    articles[1].created_at = Some("2024-05-08T09:00:00Z".parse().unwrap());
+    articles[1].updated_at = Some("2024-05-08T18:00:00Z".parse().unwrap());
+    let refs: Vec<&Article> = articles.iter().collect();
+    let activity = Activity::from_articles(&refs);
+    let day = |text: &str| text.parse::<chrono::NaiveDate>().unwrap();
+    assert_eq!(activity.on(day("2024-05-06")), 1);
+    assert_eq!(activity.on(day("2024-05-08")), 2);
+    assert_eq!(activity.updated.values().sum::<usize>(), 1);
+
+    let grid = without_colors(|| render(&activity, day("2024-05-09"), 2));
+    let widest = without_colors(|| render(&activity, day("2024-05-09"), usize::MAX));
+    let monday = widest.lines().nth(1).unwrap();
+    assert_eq!(monday.chars().count(), "Mon ".len() + dtdrafts_core::calendar::MAX_WEEKS);
+    let lines: Vec<&str> = grid.lines().collect();
+    assert_eq!(lines[1], "Mon ·▒");
+    assert_eq!(lines[3], "Wed ·█");
+    assert_eq!(lines[5], "Fri ·");
+    assert!(grid.contains("2 created, 1 updated from 2024-04-29 to 2024-05-09"), "{grid}");
+}
+
+#[test]
+fn test_goal_status_tracks_months_and_streaks() {
+    use dtdrafts_core::goals::{publish_dates, status};
+
+    let mut articles = sample_articles();
+    for (article, at) in articles.iter_mut().zip(["2024-03-05T10:00:00Z", "2024-04-20T10:00:00Z", "2024-04-02T10:00:00Z"]) {
+        article.published = true;
+        article.extra.insert("published_at".to_string(), serde_json::json!(at));
+    }
+    articles[0].published = false;
+    let mut extra = sample_articles().remove(0);
+    extra.published = true;
+    extra.extra.insert("published_at".to_string(), serde_json::json!("2024-02-10T10:00:00Z"));
+    articles.push(extra);
+    let dates = publish_dates(&articles);
+    assert_eq!(dates.len(), 3);
+
+    let today = "2024-05-14".parse().unwrap();
+    let one = status(1, &dates, today, 3, 0);
+    let months: Vec<(String, u32, bool)> =
+        one.months.iter().map(|m| (m.month.format("%Y-%m").to_string(), m.published, m.met)).collect();
+    assert_eq!(months, [("2024-03".into(), 0, false), ("2024-04".into(), 2, true), ("2024-05".into(), 0, false)]);
+    assert_eq!((one.streak, one.best_streak), (1, 1));
+
+    let two = status(2, &dates, today, 1, 4);
+    assert_eq!((two.streak, two.best_streak), (1, 4));
+}
+
+#[test]
+fn test_readability_trend_orders_drafts_and_draws_sparklines() {
+    use dtdrafts_core::stats::{readability_trend, sparkline};
+
+    let mut articles = sample_articles();
+    articles[0].body_markdown = Some("This is really very simple. It reads well.".to_string());
+    articles[0].created_at = Some("2024-03-01T00:00:00Z".parse().unwrap());
+    articles[1].body_markdown = Some("Asynchronous programming necessitates comprehensive understanding.".to_string());
+    articles[1].created_at = Some("2024-01-01T00:00:00Z".parse().unwrap());
+    articles[2].body_markdown = Some("```rust\nfn main() {}\n```\n".to_string());
+    let refs: Vec<&Article> = articles.iter().collect();
+
+    let trend = readability_trend(&refs);
+    assert_eq!(trend.iter().map(|stats| stats.id).collect::<Vec<_>>(), [2, 1]);
+    assert!(trend[0].reading_ease < trend[1].reading_ease);
+    assert_eq!(trend[1].words_per_sentence, 4.0);
+    assert_eq!(trend[1].weasel_words_per_1000, 250.0);
+
+    assert_eq!(sparkline(&[1.0, 4.5, 8.0]), "▁▅█");
+    assert_eq!(sparkline(&[2.0, 2.0]), "▄▄");
+}
+
+#[test]
+fn test_vocab_counts_prose_terms_and_phrases() {
+    use dtdrafts_core::vocab::{count_terms, sort_terms, to_csv, VocabOptions, VocabSortKey};
+
+    let mut articles = sample_articles();
+    articles[0].body_markdown = Some(
+        "The borrow checker is strict. The borrow checker helps, really.\n\n```rust\nlet borrow = 1;\n```\n".to_string(),
+    );
+    articles[1].body_markdown = Some("Fighting the borrow checker, again.".to_string());
+    articles[2].body_markdown = Some("Nothing about `borrow` here.".to_string());
+    let refs: Vec<&Article> = articles.iter().collect();
+
+    let words = count_terms(&refs, &VocabOptions::default());
+    assert_eq!(words[0].term, "borrow");
+    assert_eq!((words[0].count, words[0].drafts), (3, 2));
+    assert!(words.iter().all(|term| term.term != "the"));
+
+    let options = VocabOptions { ngram: 2, ..VocabOptions::default() };
+    let mut phrases = count_terms(&refs, &options);
+    assert_eq!(phrases.iter().map(|term| term.term.as_str()).collect::<Vec<_>>(), ["borrow checker"]);
+    sort_terms(&mut phrases, VocabSortKey::Term);
+    assert!(to_csv(&phrases).starts_with("term,count,drafts,per_1000_words\nborrow checker,3,2,"));
+
+    let options = VocabOptions { ngram: 2, skip_stopwords: false, min_count: 2 };
+    let phrases = count_terms(&refs, &options);
+    assert!(phrases.iter().any(|term| term.term == "the borrow" && term.count == 3));
+}
+
+#[test]
+fn test_extract_outline_nests_sections_with_word_counts() {
+    use dtdrafts_core::toc::extract_outline;
+
+    let body = "---\ntitle: T\n---\nIntro words here.\n\n# Part one\nSome text in part one.\n## Setup\nInstall it now.\n\
+                ```sh\ncargo install a b c\n```\n#### Deep\nOne two.\n## Run\nGo.\n# Part two\nLast bit.\n";
+    let outline = extract_outline(body);
+
+    assert_eq!(outline.iter().map(|section| section.heading.text.as_str()).collect::<Vec<_>>(), ["Part one", "Part two"]);
+    let part_one = &outline[0];
+    assert_eq!(part_one.words, 5);
+    assert_eq!(part_one.subsections.iter().map(|section| section.heading.text.as_str()).collect::<Vec<_>>(), ["Setup", "Run"]);
+    let setup = &part_one.subsections[0];
+    assert_eq!(setup.words, 3);
+    assert_eq!(setup.subsections[0].heading.text, "Deep");
+    assert_eq!(setup.total_words(), 5);
+    assert_eq!(part_one.total_words(), 11);
+    assert_eq!(outline[1].words, 2);
+    assert!(extract_outline("No headings at all.").is_empty());
+
+    let options = text::WordCountOptions { include_code: true, ..Default::default() };
+    let outline = dtdrafts_core::toc::extract_outline_with(body, &options);
+    assert_eq!(outline[0].subsections[0].words, 8);
+    assert_eq!(outline[1].words, 2);
+}
+
+#[test]
+fn test_plan_split_cuts_a_draft_at_sections() {
+    use dtdrafts_core::split::{part_input, plan_split, SplitLevel};
+
+    assert_eq!("H2".parse::<SplitLevel>(), Ok(SplitLevel(2)));
+    assert!("h7".parse::<SplitLevel>().is_err());
+
+    let body = "---\ntitle: Long\n---\nWhy this matters.\n\n## Setup\nInstall it.\n### Details\nMore.\n\n## Usage\nRun it.\n";
+    let parts = plan_split(body, SplitLevel(2));
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].title, "Setup");
+    assert_eq!(parts[0].body, "Why this matters.\n\nInstall it.\n### Details\nMore.\n");
+    assert_eq!(parts[1].title, "Usage");
+    assert_eq!(parts[1].body, "Run it.\n");
+    assert_eq!(plan_split(body, SplitLevel(3)).len(), 3);
+    assert!(plan_split(body, SplitLevel(1)).is_empty());
+
+    let mut original = sample_articles().remove(0);
+    original.tags = Some(vec!["rust".to_string()]);
+    let input = part_input(&original, &parts[1], "Long");
+    assert_eq!(input.title.as_deref(), Some("Usage"));
+    assert_eq!(input.series.as_deref(), Some("Long"));
+    assert_eq!(input.published, Some(false));
+    assert_eq!(input.tags, Some(vec!["rust".to_string()]));
+}
+
+#[test]
+fn test_plan_merge_combines_bodies_and_tags() {
+    use dtdrafts_core::merge::plan_merge;
+
+    let mut articles = sample_articles();
+    articles[0].title = "Part one".to_string();
+    articles[0].body_markdown = Some("---\ntitle: Part one\n---\nIntro.\n\n## Setup\n```sh\n# not a heading\n```\n".to_string());
+    articles[0].tags = Some(vec!["rust".into(), "cli".into(), "tutorial".into()]);
+    articles[0].series = Some("Intro".to_string());
+    articles[1].title = "Part two".to_string();
+    articles[1].body_markdown = Some("Text.\n###### Deepest\n".to_string());
+    articles[1].tags = Some(vec!["Rust".into(), "beginners".into(), "async".into()]);
+    articles[1].series = Some("Intro".to_string());
+    let refs = vec![&articles[0], &articles[1]];
+
+    let plan = plan_merge(&refs, "Combined");
+    assert_eq!(plan.input.title.as_deref(), Some("Combined"));
+    assert_eq!(
+        plan.input.body_markdown.as_deref(),
+        Some("## Part one\n\nIntro.\n\n### Setup\n```sh\n# not a heading\n```\n\n## Part two\n\nText.\n###### Deepest\n")
+    );
+    assert_eq!(plan.input.tags, Some(vec!["rust".into(), "cli".into(), "tutorial".into(), "beginners".into()]));
+    assert_eq!(plan.dropped_tags, ["async"]);
+    assert_eq!(plan.input.series.as_deref(), Some("Intro"));
+    assert_eq!(plan.input.published, Some(false));
+
+    articles[1].series = None;
+    assert_eq!(plan_merge(&[&articles[0], &articles[1]], "Combined").input.series, None);
+}
+
+#[test]
+fn test_clone_strips_content_between_markers() {
+    use dtdrafts_core::clone::{clone_input, strip_content, CloneConfig};
+
+    let body = "# Roundup\n<!-- content -->\n- link one\n- link two\n<!-- /content -->\n## Sponsor\n<!-- content -->old sponsor<!-- /content -->\nThanks!\n";
+    let (stripped, count) = strip_content(body, "<!-- content -->", "<!-- /content -->");
+    assert_eq!(count, 2);
+    assert_eq!(stripped, "# Roundup\n<!-- content -->\n<!-- /content -->\n## Sponsor\n<!-- content -->\n<!-- /content -->\nThanks!\n");
+    assert_eq!(strip_content("a [[ b", "[[", "]]"), ("a [[\n".to_string(), 1));
+
+    let mut original = sample_articles().remove(0);
+    original.body_markdown = Some(body.to_string());
+    original.tags = Some(vec!["weekly".to_string()]);
+    original.series = Some("Roundups".to_string());
+    let (input, count) = clone_input(&original, "Roundup #2", &CloneConfig::default()).unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(input.title.as_deref(), Some("Roundup #2"));
+    assert_eq!(input.body_markdown.as_deref(), Some(stripped.as_str()));
+    assert_eq!(input.tags, Some(vec!["weekly".to_string()]));
+    assert_eq!(input.series.as_deref(), Some("Roundups"));
+    assert_eq!(input.published, Some(false));
+
+    original.body_markdown = Some("---\ntitle: Roundup #1\ncanonical_url: https://example.com/1\ntags: weekly\n---\n[[ links ]]\n".to_string());
+    let config = CloneConfig { content_start: Some("[[".to_string()), content_end: Some("]]".to_string()) };
+    let (input, _) = clone_input(&original, "Roundup #2", &config).unwrap();
+    assert_eq!(input.body_markdown.as_deref(), Some("---\ntitle: Roundup #2\ntags: weekly\npublished: false\n---\n[[\n]]\n"));
+}
+
+#[test]
+fn test_clone_rejects_empty_or_identical_markers() {
+    use dtdrafts_core::clone::{clone_input, strip_content, CloneConfig};
+
+    assert_eq!(strip_content("abc", "", "<!-- /content -->"), ("abc".to_string(), 0));
+    let original = sample_articles().remove(0);
+    for (start, end) in [("", "<!-- /content -->"), ("<!-- content -->", " "), ("<!-- x -->", "<!-- x -->")] {
+        let config = CloneConfig { content_start: Some(start.to_string()), content_end: Some(end.to_string()) };
+        assert!(config.markers().is_err());
+        assert!(clone_input(&original, "Copy", &config).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::analytics::{sort_analytics, to_csv, AnalyticsSortKey};
+use dtdrafts_core::*;
+
+pub async fn run(client: &DevToClient, sort: AnalyticsSortKey, limit: Option<usize>, csv: bool) -> Result<()> {
+    let mut rows = client
+        .get_my_published_analytics()
+        .await
+        .context("Failed to fetch analytics")?;
+    sort_analytics(&mut rows, sort);
+    if let Some(limit) = limit {
+        rows.truncate(limit);
+    }
+    if csv {
+        print!("{}", to_csv(&rows));
+        return Ok(());
+    }
+    if rows.is_empty() {
+        println!("{}", "No published articles found.".yellow());
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("{:>10}  {:>8}  {:>9}  {:>8}  {:<10}  {}", "ID", "Views", "Reactions", "Comments", "Published", "Title").bold()
+    );
+    for row in &rows {
+        let published = row.published_at.as_deref().and_then(|at| at.get(..10)).unwrap_or("-");
+        println!(
+            "{:>10}  {:>8}  {:>9}  {:>8}  {:<10}  {}",
+            row.id,
+            row.page_views_count,
+            row.public_reactions_count,
+            row.comments_count,
+            published,
+            row.title.cyan()
+        );
+    }
+    println!();
+    println!(
+        "{} {} views, {} reactions, {} comments",
+        "Total:".bold(),
+        rows.iter().map(|row| row.page_views_count).sum::<u64>(),
+        rows.iter().map(|row| row.public_reactions_count).sum::<u64>(),
+        rows.iter().map(|row| row.comments_count).sum::<u64>()
+    );
+    Ok(())
+}
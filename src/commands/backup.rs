@@ -0,0 +1,51 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::backup::{read_backup, write_backup};
+use dtdrafts_core::*;
+
+pub fn backup(drafts: &[&Article], out: &Path) -> Result<()> {
+    let file = File::create(out).with_context(|| format!("Failed to create {}", out.display()))?;
+    let mut writer = BufWriter::new(file);
+    let manifest = write_backup(drafts, &mut writer)?;
+    writer.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    println!("{} {} draft(s) to {}", "Backed up".green(), manifest.drafts.len(), out.display());
+    Ok(())
+}
+
+pub async fn restore(client: &DevToClient, archive: &Path, dry_run: bool) -> Result<()> {
+    let file = File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    let (manifest, drafts) = read_backup(file).with_context(|| format!("Failed to read {}", archive.display()))?;
+    println!("Backup from {} with {} draft(s)", manifest.created_at, drafts.len());
+    if dry_run {
+        for draft in &drafts {
+            println!("  would create {} {}", draft.entry.title.cyan(), format!("(was {})", draft.entry.id).dimmed());
+        }
+        println!("{}", "Dry run: nothing was created.".yellow());
+        return Ok(());
+    }
+
+    for (index, draft) in drafts.iter().enumerate() {
+        if index > 0 {
+            tokio::time::sleep(Duration::from_secs(1)).await; // rate limit mitigation
+        }
+        let created = client
+            .create_article(&draft.to_input())
+            .await
+            .with_context(|| format!("Restored {index} of {} draft(s); failed on {}", drafts.len(), draft.entry.file))?;
+        println!(
+            "  {} {} {}",
+            "created".green(),
+            created.title.cyan(),
+            format!("({} -> {})", draft.entry.id, created.id).dimmed()
+        );
+        super::history::pushed(client, &[&created]);
+        super::cache::upsert(client, created)?;
+    }
+    println!("{} {} draft(s)", "Restored".green(), drafts.len());
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use std::io::IsTerminal;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::*;
+use dialoguer::Confirm;
+use dtdrafts_core::bulk::{BulkAction, BulkChange};
+use dtdrafts_core::hooks::{self, HookEvent, HooksConfig};
+use dtdrafts_core::journal::JournalChange;
+use dtdrafts_core::trash;
+use dtdrafts_core::*;
+
+/// How `bulk` and `retag` go about applying their changes.
+pub struct BulkOptions {
+    pub dry_run: bool,
+    pub yes: bool,
+    /// Pause between writes, to stay under dev.to's rate limit
+    pub interval: Duration,
+}
+
+/// Previews `action` on the `selected` drafts, then applies it after
+/// confirmation.
+pub async fn run(
+    client: &DevToClient,
+    hooks: &HooksConfig,
+    selected: &[&Article],
+    action: &BulkAction,
+    dir: &Path,
+    options: &BulkOptions,
+) -> Result<()> {
+    let pending = preview(selected, action, dir);
+    if options.dry_run {
+        println!("{}", "Dry run: nothing was changed.".yellow());
+        return Ok(());
+    }
+    if pending.is_empty() {
+        return Ok(());
+    }
+    if !options.yes && !confirm(&format!("Apply {action} to {} draft(s)?", pending.len()))? {
+        println!("{}", "Cancelled.".yellow());
+        return Ok(());
+    }
+    if let BulkAction::Export(format) = action {
+        return super::export::run(&pending, *format, dir);
+    }
+    apply(client, hooks, &pending, options.interval, &action.to_string(), |article| action.plan(article)).await
+}
+
+/// Lists each selected draft with what `action` would do to it, and returns
+/// those it would change.
+pub fn preview<'a>(selected: &[&'a Article], action: &BulkAction, dir: &Path) -> Vec<&'a Article> {
+    if selected.is_empty() {
+        println!("{}", "No drafts match.".yellow());
+        return Vec::new();
+    }
+    let mut pending = Vec::new();
+    for article in selected {
+        let change = action.plan(article);
+        let description = match &change {
+            BulkChange::Update(_, description) => description.green(),
+            BulkChange::Delete => "delete".red(),
+            BulkChange::Export => format!("export to {}", dir.display()).green(),
+            BulkChange::Skip(reason) => format!("skip: {reason}").dimmed(),
+        };
+        println!("  {:>8}  {}  {description}", article.id.to_string().dimmed(), article.title.cyan());
+        if !matches!(change, BulkChange::Skip(_)) {
+            pending.push(*article);
+        }
+    }
+    println!("{} of {} matching draft(s) would change ({action})", pending.len(), selected.len());
+    pending
+}
+
+/// Sends the updates or deletions `plan` makes for `pending` one at a time,
+/// `interval` apart, with progress and a summary. Failures are reported per
+/// draft and don't stop the others. What changed is journaled as `operation`.
+pub async fn apply(
+    client: &DevToClient,
+    hooks: &HooksConfig,
+    pending: &[&Article],
+    interval: Duration,
+    operation: &str,
+    plan: impl Fn(&Article) -> BulkChange,
+) -> Result<()> {
+    // Changes are planned again on the current version, as the cache may be behind
+    let ids: Vec<u64> = pending.iter().map(|article| article.id).collect();
+    let latest = client.get_my_articles_by_id(&ids).await.context("Failed to fetch the drafts")?;
+    let mut updated = Vec::new();
+    let mut deleted = Vec::new();
+    let mut journal = Vec::new();
+    let mut failures = 0;
+    let mut skipped = 0;
+    for (index, article) in pending.iter().enumerate() {
+        if index > 0 && !interval.is_zero() {
+            tokio::time::sleep(interval).await;
+        }
+        let progress = format!("[{}/{}]", index + 1, pending.len()).dimmed();
+        let current = latest.iter().find(|current| current.id == article.id).unwrap_or(article);
+        let result = match plan(current) {
+            BulkChange::Update(input, _) => client.update_article(article.id, &input).await.map(|article| {
+                journal.push(JournalChange::updated(current.clone(), &article));
+                updated.push(article);
+            }),
+            BulkChange::Delete => delete(client, current).await.map(|()| {
+                deleted.push(article.id);
+                journal.push(JournalChange::deleted(current.clone()));
+            }),
+            BulkChange::Export => Ok(()),
+            BulkChange::Skip(reason) => {
+                skipped += 1;
+                println!("  {progress} {} {}: {reason}", "skipped".yellow(), article.title);
+                continue;
+            }
+        };
+        match result {
+            Ok(()) => println!("  {progress} {} {}", "done".green(), article.title),
+            Err(e) => {
+                failures += 1;
+                println!("  {progress} {} {}: {e:#}", "failed".red(), article.title);
+            }
+        }
+    }
+
+    super::undo::record(operation, journal);
+    super::cache::update(client, updated.clone(), &deleted)?;
+    let updated: Vec<&Article> = updated.iter().collect();
+    super::history::pushed(client, &updated);
+    hooks::fire(hooks, HookEvent::Push, &updated).await;
+    let changed = pending.len() - failures - skipped;
+    println!("{} {changed} draft(s), {skipped} skipped, {failures} failed", "Changed".green());
+    if failures > 0 {
+        return Err(anyhow::anyhow!("{failures} of {} draft(s) could not be changed", pending.len()));
+    }
+    Ok(())
+}
+
+// Drafts go into the trash first and are only deleted once they're safely there
+pub async fn delete(client: &DevToClient, article: &Article) -> Result<()> {
+    trash::put(article, Utc::now()).context("Not deleted, as it could not be moved to the trash")?;
+    let result = client.delete_article(article.id).await;
+    if result.is_err() {
+        trash::remove(article.id).ok();
+    }
+    result
+}
+
+pub fn confirm(prompt: &str) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow::anyhow!("Not asking for confirmation without a terminal; pass --yes to apply the changes"));
+    }
+    Ok(Confirm::new().with_prompt(prompt).default(false).interact()?)
+}
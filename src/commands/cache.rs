@@ -0,0 +1,142 @@
+use std::fs;
+
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::cache::{self, CacheChecksums, CacheFormat, CachedBodies};
+use dtdrafts_core::*;
+
+/// Switches the config to the compact cache format and converts the cache.
+pub fn compact() -> Result<()> {
+    let json_size = file_size(CacheFormat::Json)?;
+    if config::load_file()?.cache.format != CacheFormat::Compact {
+        config::update_file(|file| file.cache.format = CacheFormat::Compact)
+            .context("Failed to save the configuration")?;
+    }
+    let converted = use_cache_format(CacheFormat::Compact).context("Failed to convert the article cache")?;
+    let compact_size = file_size(CacheFormat::Compact)?;
+    match (converted, json_size, compact_size) {
+        (true, Some(before), Some(after)) => println!(
+            "{} {} → {} ({}% of the JSON cache)",
+            "Compacted the article cache:".green(),
+            format_size(before),
+            format_size(after),
+            (after * 100).checked_div(before).unwrap_or(100)
+        ),
+        (_, _, Some(size)) => println!("The article cache is already compact ({}).", format_size(size)),
+        (_, _, None) => println!("{} the next refresh will write a compact cache.", "No cache yet:".yellow()),
+    }
+    Ok(())
+}
+
+/// Checks every cached record against its checksum. With `client`, damaged
+/// records are re-fetched (or dropped when they're no longer drafts) and the
+/// cache rewritten in the given format; otherwise damage is an error, so
+/// scripts can tell.
+pub async fn verify(client: Option<(&DevToClient, CacheFormat)>) -> Result<()> {
+    if !has_articles_cache()? {
+        println!("{}", "No article cache yet.".yellow());
+        return Ok(());
+    }
+    let Some(checksums) = CacheChecksums::load()? else {
+        println!(
+            "{} it was written by an older version. Run `dtdrafts --refresh` once to add them.",
+            "The cache has no checksums:".yellow()
+        );
+        return Ok(());
+    };
+
+    let loaded = load_articles_cache_without_bodies().and_then(|metadata| Ok((metadata, CachedBodies::load()?)));
+    let (metadata, bodies) = match loaded {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            let ids = cache::checksummed_ids(&checksums);
+            println!("{} {e:#}", "The cache can't be read:".red().bold());
+            println!("All {} cached article(s) are affected.", ids.len());
+            let Some((client, format)) = client else {
+                return Err(anyhow::anyhow!("The article cache is corrupt; run `dtdrafts cache verify --fix`"));
+            };
+            // Every record has to be fetched, which a full refresh does in fewer requests
+            let articles = client.get_my_articles().await?;
+            save(client, &articles, format)?;
+            super::history::pulled(client, &articles.iter().collect::<Vec<_>>());
+            println!("{} {} article(s)", "Re-fetched the whole cache:".green(), articles.len());
+            return Ok(());
+        }
+    };
+
+    let damaged = cache::verify(&metadata, &bodies, &checksums)?;
+    if damaged.is_empty() {
+        println!("{} {} cached article(s)", "Verified".green(), metadata.len());
+        return Ok(());
+    }
+    println!("{} damaged cache record(s):", damaged.len().to_string().red().bold());
+    for record in &damaged {
+        let title = record.title.as_deref().unwrap_or("(unknown title)");
+        println!("  {}  {}  {}", record.id, title.cyan(), record.problem.describe().dimmed());
+    }
+    let Some((client, format)) = client else {
+        return Err(anyhow::anyhow!(
+            "{} damaged cache record(s); run `dtdrafts cache verify --fix` to re-fetch them",
+            damaged.len()
+        ));
+    };
+
+    let ids: Vec<u64> = damaged.iter().map(|record| record.id).collect();
+    let mut fetched = client.get_my_articles_by_id(&ids).await?;
+    super::history::pulled(client, &fetched.iter().collect::<Vec<_>>());
+    let mut articles = load_articles_cache()?;
+    articles.retain(|article| !ids.contains(&article.id));
+    let mut refetched = 0;
+    for id in &ids {
+        match fetched.iter().position(|article| article.id == *id) {
+            Some(index) if !fetched[index].published => {
+                articles.push(fetched.swap_remove(index));
+                refetched += 1;
+            }
+            Some(_) => println!("  {id} is published now; dropped it from the cache"),
+            None => println!("  {id} is no longer on dev.to; dropped it from the cache"),
+        }
+    }
+    save(client, &articles, format)?;
+    println!("{} {refetched} article(s)", "Re-fetched".green());
+    Ok(())
+}
+
+/// Replaces the cache with the drafts just fetched, in `format`.
+pub fn save(client: &DevToClient, articles: &[Article], format: CacheFormat) -> Result<()> {
+    // Replayed responses never came from dev.to, so they leave the cache alone
+    if client.is_replaying() {
+        return Ok(());
+    }
+    save_articles_cache(articles, format).context("Failed to save articles cache")
+}
+
+/// Applies drafts just uploaded or fetched, and deletions, to the cache.
+pub fn update(client: &DevToClient, updated: Vec<Article>, deleted: &[u64]) -> Result<()> {
+    if client.is_replaying() {
+        return Ok(());
+    }
+    update_cached_articles(updated, deleted).context("Failed to update the local cache")
+}
+
+pub fn upsert(client: &DevToClient, article: Article) -> Result<()> {
+    update(client, vec![article], &[])
+}
+
+// Metadata plus bodies, or None when there's no cache in this format
+fn file_size(format: CacheFormat) -> Result<Option<u64>> {
+    let dir = get_config_dir()?;
+    let Ok(metadata) = fs::metadata(dir.join(format.file_name())) else {
+        return Ok(None);
+    };
+    let bodies = fs::metadata(dir.join(format.bodies_file_name())).map_or(0, |bodies| bodies.len());
+    Ok(Some(metadata.len() + bodies))
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..1_024 => format!("{bytes} B"),
+        1_024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1_024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
@@ -0,0 +1,32 @@
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::frontmatter::canonical_input;
+use dtdrafts_core::links::is_http;
+use dtdrafts_core::lint::canonical_url_issue;
+use dtdrafts_core::*;
+
+/// Points a draft's canonical URL at the original post, or removes it with
+/// `None`. A URL that doesn't load is set anyway, with a warning.
+pub async fn set(client: &DevToClient, id: u64, url: Option<&str>) -> Result<()> {
+    if let Some(url) = url {
+        if !is_http(url) {
+            return Err(anyhow::anyhow!("'{url}' is not an http(s) URL"));
+        }
+        let check = LinkChecker::new()?.check(&[url.to_string()]).await;
+        if let Some(issue) = check.first().and_then(|check| canonical_url_issue(url, check)) {
+            println!("{} {}", "Warning:".yellow().bold(), issue.message);
+        }
+    }
+    let article = client.get_my_article(id).await?;
+    let updated = client
+        .update_article(id, &canonical_input(&article, url.unwrap_or_default()))
+        .await
+        .context("Failed to update the canonical URL")?;
+    match url {
+        Some(url) => println!("{} {} {}", "Canonical URL of".green(), updated.title.cyan().bold(), format!("set to {url}").green()),
+        None => println!("{} {}", "Removed the canonical URL of".green(), updated.title.cyan().bold()),
+    }
+    super::history::pushed(client, &[&updated]);
+    super::cache::upsert(client, updated)?;
+    Ok(())
+}
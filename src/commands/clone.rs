@@ -0,0 +1,25 @@
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::clone::{clone_input, CloneConfig};
+use dtdrafts_core::*;
+
+/// Creates a new draft titled `title` from `original`, without the content
+/// between the configured markers.
+pub async fn run(client: &DevToClient, original: &Article, title: &str, config: &CloneConfig) -> Result<()> {
+    let (input, stripped) = clone_input(original, title, config)?;
+    let created = client.create_article(&input).await.context("Failed to create the draft")?;
+    println!("{} {} {}", "Created draft".green(), title.cyan().bold(), format!("({})", created.id).dimmed());
+    if stripped == 0 {
+        let (start, end) = config.markers()?;
+        println!(
+            "{} no {start} … {end} markers found, so the whole body was copied",
+            "Note:".yellow()
+        );
+    } else {
+        println!("Emptied {stripped} content section(s) from {}", original.title.cyan());
+    }
+    println!("Edit it with: dtdrafts edit {}", created.id);
+    super::history::pushed(client, &[&created]);
+    super::cache::upsert(client, created)?;
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anyhow::Result;
+use chrono::Local;
+use colored::*;
+use dtdrafts_core::comments::{count_comments, Comment};
+use dtdrafts_core::*;
+
+/// Prints the comment tree of an article and returns the number of comments.
+pub async fn run(client: &DevToClient, id: u64, quiet: bool) -> Result<usize> {
+    let comments = client.get_comments(id).await?;
+    let total = count_comments(&comments);
+    if quiet {
+        return Ok(total);
+    }
+    if comments.is_empty() {
+        println!("{}", "No comments yet.".yellow());
+        return Ok(0);
+    }
+    println!("{} comment(s):\n", total.to_string().green().bold());
+    for comment in &comments {
+        print_comment(comment, 0);
+    }
+    Ok(total)
+}
+
+fn print_comment(comment: &Comment, depth: usize) {
+    let indent = "    ".repeat(depth);
+    let when = comment
+        .created_at_utc()
+        .map(|at| at.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+    let author = match comment.user.name.as_str() {
+        "" => format!("@{}", comment.user.username),
+        name => format!("{name} (@{})", comment.user.username),
+    };
+    println!("{indent}{}  {}", author.cyan().bold(), when.dimmed());
+    for line in comment.body_text().lines() {
+        println!("{indent}  {line}");
+    }
+    println!();
+    for reply in &comment.children {
+        print_comment(reply, depth + 1);
+    }
+}
@@ -0,0 +1,38 @@
+use std::io;
+
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::Shell;
+use dtdrafts_core::*;
+
+pub fn run(shell: Shell, command: &mut clap::Command) {
+    clap_complete::generate(shell, command, "dtdrafts", &mut io::stdout());
+}
+
+// Dynamic candidates read only the metadata sidecar so completion stays fast on
+// large caches. Errors yield no candidates rather than noise in the shell.
+
+pub fn config_keys() -> Vec<CompletionCandidate> {
+    dtdrafts_core::config::KEYS.into_iter().map(CompletionCandidate::new).collect()
+}
+
+pub fn cached_tags() -> Vec<CompletionCandidate> {
+    let meta = load_articles_meta().unwrap_or_default();
+    let mut tags: Vec<String> = meta
+        .iter()
+        .filter(|article| !article.published)
+        .flat_map(|article| article.tags.iter().flatten())
+        .map(|tag| tag.to_lowercase())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags.into_iter().map(CompletionCandidate::new).collect()
+}
+
+pub fn cached_drafts() -> Vec<CompletionCandidate> {
+    load_articles_meta()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|article| !article.published)
+        .map(|article| CompletionCandidate::new(article.id.to_string()).help(Some(article.title.into())))
+        .collect()
+}
@@ -0,0 +1,114 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::config::{self, Overrides};
+use dtdrafts_core::DevToClient;
+
+use super::edit::open_editor;
+
+/// Saves `api_key` once dev.to accepts it, with the username it belongs to.
+/// With `force` a rejected key is saved anyway, without a username.
+pub async fn set_api_key(client: &DevToClient, profile: Option<&str>, api_key: &str, force: bool, quiet: bool) -> Result<()> {
+    let username = match client.get_me().await {
+        Ok(user) => {
+            if !quiet {
+                println!("{} @{}", "Authenticated as".green(), user.username.cyan().bold());
+            }
+            Some(user.username)
+        }
+        Err(e) if force => {
+            eprintln!("{} {e:#}", "Warning: saving the API key although it could not be checked:".yellow());
+            None
+        }
+        Err(e) => return Err(e.context("The API key was not saved (use --force to save it anyway)")),
+    };
+    let mut file = config::load_file()?;
+    file.set_api_key(profile, api_key, username.as_deref())?;
+    config::update_file(|current| *current = file).context("Failed to save API key")?;
+    if !quiet {
+        println!("{}", "API key saved successfully!".green());
+    }
+    Ok(())
+}
+
+/// Prints a setting as resolved from the file, profile, environment and flags.
+pub fn get(overrides: &Overrides, key: &str) -> Result<()> {
+    match config::resolve(overrides)?.value(key)? {
+        Some(toml::Value::String(text)) => println!("{text}"),
+        Some(value) => println!("{value}"),
+        None => return Err(anyhow::anyhow!("{key} is not set")),
+    }
+    Ok(())
+}
+
+pub fn set(key: &str, value: &str) -> Result<()> {
+    let mut file = config::load_file()?;
+    file.set_value(key, value)?;
+    let path = config::update_file(|current| *current = file)?;
+    println!("{} {key} in {}", "Set".green(), path.display());
+    Ok(())
+}
+
+pub fn unset(key: &str) -> Result<()> {
+    let mut file = config::load_file()?;
+    file.unset(key)?;
+    let path = config::update_file(|current| *current = file)?;
+    println!("{} {key} in {}", "Unset".green(), path.display());
+    Ok(())
+}
+
+/// Lists the settings in effect, with API keys and tokens shortened.
+pub fn list(overrides: &Overrides) -> Result<()> {
+    let settings = config::resolve(overrides)?.settings()?;
+    if settings.is_empty() {
+        println!("{}", "Nothing configured yet. Start with: dtdrafts --set-api-key YOUR_API_KEY".yellow());
+    }
+    for (key, value) in settings {
+        let value = match value {
+            toml::Value::String(secret) if key.ends_with("key") || key.ends_with("token") => mask(&secret),
+            value => value.to_string(),
+        };
+        println!("{} = {value}", key.cyan());
+    }
+    Ok(())
+}
+
+fn mask(secret: &str) -> String {
+    let visible: String = secret.chars().skip(secret.chars().count().saturating_sub(4)).collect();
+    format!("\"****{visible}\"")
+}
+
+/// Opens a copy of the config file in the editor and saves it back only if it
+/// is still a valid config.
+pub fn edit(overrides: &Overrides) -> Result<()> {
+    // The file may be invalid already, which is what the edit is for
+    let editor = config::resolve(overrides).ok().and_then(|file| file.editor.command);
+    let path = config::get_config_file()?;
+    let original = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        // The legacy JSON file, or nothing yet
+        Err(_) => toml::to_string_pretty(&config::load_file()?)?,
+    };
+    let copy = env::temp_dir().join(format!("dtdrafts-config-{}.toml", process::id()));
+    fs::write(&copy, &original).with_context(|| format!("Failed to write {}", copy.display()))?;
+    open_editor(&copy, editor.as_deref())?;
+    let edited = fs::read_to_string(&copy).with_context(|| format!("Failed to read {}", copy.display()))?;
+    if edited == original && path.exists() {
+        fs::remove_file(&copy).ok();
+        println!("{}", "No changes.".yellow());
+        return Ok(());
+    }
+    let path = config::replace_file(&edited)
+        .with_context(|| format!("The config was not saved; your edits are kept at {}", copy.display()))?;
+    fs::remove_file(&copy).ok();
+    println!("{} {}", "Saved".green(), path.display());
+    Ok(())
+}
+
+pub fn path() -> Result<()> {
+    println!("{}", config::get_config_file()?.display());
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::cover::TitleCard;
+use dtdrafts_core::frontmatter::cover_input;
+use dtdrafts_core::links::is_http;
+use dtdrafts_core::lint::{cover_image_issue, PublishMetadata};
+use dtdrafts_core::*;
+
+/// Where the cover image comes from.
+pub enum CoverSource<'a> {
+    Url(&'a str),
+    File(&'a Path),
+    /// A title card written to `out` before uploading
+    Generate { card: TitleCard, out: &'a Path },
+}
+
+/// The title card for a draft: its title, with its tags and author below.
+pub fn title_card(article: &Article) -> TitleCard {
+    let metadata = PublishMetadata::of(article);
+    let mut subtitle: Vec<String> = metadata.tags.iter().map(|tag| format!("#{tag}")).collect();
+    if !article.user.username.is_empty() {
+        subtitle.push(format!("@{}", article.user.username));
+    }
+    TitleCard::new(metadata.title, subtitle.join("  "))
+}
+
+/// Uploads or generates the cover image as needed and sets it on the draft.
+pub async fn run(client: &DevToClient, id: u64, source: CoverSource<'_>) -> Result<()> {
+    let url = match source {
+        CoverSource::Url(url) => {
+            if !is_http(url) {
+                return Err(anyhow::anyhow!("'{url}' is not an http(s) URL"));
+            }
+            let check = LinkChecker::new()?.check(&[url.to_string()]).await;
+            if let Some(issue) = check.first().and_then(|check| cover_image_issue(url, check)) {
+                println!("{} {}", "Warning:".yellow().bold(), issue.message);
+            }
+            url.to_string()
+        }
+        CoverSource::File(path) => {
+            let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+            upload(client, path, bytes).await?
+        }
+        CoverSource::Generate { card, out } => {
+            let png = card.render_png()?;
+            fs::write(out, &png).with_context(|| format!("Failed to write {}", out.display()))?;
+            println!("{} {}", "Generated".green(), out.display());
+            upload(client, out, png).await?
+        }
+    };
+
+    let article = client.get_my_article(id).await?;
+    let updated = client
+        .update_article(id, &cover_input(&article, &url))
+        .await
+        .context("Failed to set the cover image")?;
+    println!("{} {} {}", "Cover image of".green(), updated.title.cyan().bold(), format!("set to {url}").green());
+    super::history::pushed(client, &[&updated]);
+    super::cache::upsert(client, updated)?;
+    Ok(())
+}
+
+async fn upload(client: &DevToClient, path: &Path, bytes: Vec<u8>) -> Result<String> {
+    let file_name = path.file_name().map_or_else(|| "cover.png".into(), |name| name.to_string_lossy());
+    println!("{}", format!("Uploading {file_name}...").blue());
+    client.upload_image(&file_name, bytes).await.with_context(|| {
+        format!(
+            "dev.to did not take the upload; host {} elsewhere and run `dtdrafts cover <id> --url <url>`",
+            path.display()
+        )
+    })
+}
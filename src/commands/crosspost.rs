@@ -0,0 +1,43 @@
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::crosspost::{CrosspostInput, HashnodeClient, MediumClient, Platform};
+use dtdrafts_core::*;
+
+pub async fn run(config: &Config, id: u64, platform: Platform, dry_run: bool) -> Result<()> {
+    let client = DevToClient::from_config(config)?;
+    println!("{}", format!("Downloading article {id}...").blue());
+    let article = client.get_my_article(id).await?;
+    if !article.published {
+        return Err(anyhow::anyhow!(
+            "Article {id} is not published on dev.to yet. Publish it first (dtdrafts publish {id}) \
+             so the canonical URL points at a live post."
+        ));
+    }
+    let input = CrosspostInput::from_article(&article);
+    println!("Cross-posting {} to {platform}", input.title.cyan().bold());
+    println!("  canonical_url: {}", input.canonical_url);
+    if dry_run {
+        println!("{}", "Dry run: nothing was posted.".yellow());
+        return Ok(());
+    }
+
+    let missing = || {
+        anyhow::anyhow!(
+            "No {platform} credentials configured. Add a [crosspost.{platform}] section to {} (see the README)",
+            get_config_file().map(|path| path.display().to_string()).unwrap_or_default()
+        )
+    };
+    let url = match platform {
+        Platform::Hashnode => {
+            let credentials = config.crosspost.hashnode.clone().ok_or_else(missing)?;
+            HashnodeClient::new(credentials)?.publish(&input).await
+        }
+        Platform::Medium => {
+            let credentials = config.crosspost.medium.clone().ok_or_else(missing)?;
+            MediumClient::new(credentials)?.publish(&input).await
+        }
+    }
+    .with_context(|| format!("Failed to cross-post to {platform}"))?;
+    println!("{} {}", "Posted:".green(), url.cyan());
+    Ok(())
+}
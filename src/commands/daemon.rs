@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::Local;
+use colored::*;
+use dtdrafts_core::hooks::{self, HookEvent};
+use dtdrafts_core::*;
+
+/// Re-fetches the article cache every `interval` until interrupted, so
+/// interactive commands never have to. Failed refreshes are reported and
+/// retried on the next tick. The cache is written in the configured format
+/// and the configured hooks fire. After each refresh the `saved` searches, if
+/// any, are run and changed results reported (and notified, when built with
+/// the `notifications` feature).
+pub async fn run(
+    client: &DevToClient,
+    config: &Config,
+    interval: Duration,
+    saved: Option<&BTreeMap<String, String>>,
+    quiet: bool,
+) -> Result<()> {
+    if !quiet {
+        println!(
+            "Refreshing the article cache every {}. Press Ctrl-C to stop.",
+            format_interval(interval)
+        );
+    }
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        let time = Local::now().format("%Y-%m-%d %H:%M:%S").to_string().dimmed();
+        match refresh(client, config).await {
+            Ok(articles) => {
+                if !quiet {
+                    println!("{time} {} {} article(s)", "refreshed".green(), articles.len());
+                }
+                if let Some(searches) = saved {
+                    let notify = cfg!(feature = "notifications");
+                    if let Err(e) = super::saved::check_all(searches, &articles, notify, quiet) {
+                        tracing::warn!("saved searches failed: {e:#}");
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!("cache refresh failed: {e:#}");
+                if !quiet {
+                    println!("{time} {} {e:#}", "failed".red().bold());
+                }
+            }
+        }
+    }
+}
+
+#[tracing::instrument(skip_all)]
+async fn refresh(client: &DevToClient, config: &Config) -> Result<Vec<Article>> {
+    let articles = client.get_my_articles().await?;
+    super::cache::save(client, &articles, config.cache_format)?;
+    let fetched: Vec<&Article> = articles.iter().collect();
+    super::history::pulled(client, &fetched);
+    hooks::fire(&config.hooks, HookEvent::Refresh, &fetched).await;
+    Ok(articles)
+}
+
+fn format_interval(interval: Duration) -> String {
+    let seconds = interval.as_secs();
+    match seconds {
+        s if s % 86_400 == 0 => format!("{}d", s / 86_400),
+        s if s % 3_600 == 0 => format!("{}h", s / 3_600),
+        s if s % 60 == 0 => format!("{}m", s / 60),
+        s => format!("{s}s"),
+    }
+}
@@ -0,0 +1,35 @@
+use colored::*;
+use dtdrafts_core::similarity::find_duplicates;
+use dtdrafts_core::*;
+
+/// Prints clusters of similar drafts unless `quiet` and returns how many there are.
+pub fn run(drafts: &[&Article], threshold: f64, quiet: bool) -> usize {
+    let clusters = find_duplicates(drafts, threshold);
+    if quiet {
+        return clusters.len();
+    }
+    if clusters.is_empty() {
+        println!(
+            "{}",
+            format!("No drafts are {:.0}% or more similar.", threshold * 100.0).green()
+        );
+        return 0;
+    }
+    for (i, cluster) in clusters.iter().enumerate() {
+        println!(
+            "{} {}",
+            format!("Cluster {}", i + 1).magenta().bold(),
+            format!("({} drafts, up to {:.0}% similar)", cluster.articles.len(), cluster.max_similarity * 100.0).dimmed()
+        );
+        for article in &cluster.articles {
+            let updated = article
+                .updated_at_utc()
+                .or_else(|| article.created_at_utc())
+                .map(|at| at.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            println!("  {:>10}  {:<10}  {}", article.id, updated.dimmed(), article.title.cyan());
+        }
+        println!();
+    }
+    clusters.len()
+}
@@ -0,0 +1,79 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::frontmatter::{document_to_input, draft_document, has_embedded_front_matter};
+use dtdrafts_core::hooks::{self, HookEvent};
+use dtdrafts_core::*;
+
+pub async fn run(client: &DevToClient, config: &Config, id: u64, force: bool) -> Result<()> {
+    println!("{}", format!("Downloading article {id}...").blue());
+    let original = client.get_my_article(id).await?;
+    let embedded = has_embedded_front_matter(&original);
+    let document = draft_document(&original);
+
+    let path = env::temp_dir().join(format!("dtdrafts-{id}-{}.md", process::id()));
+    fs::write(&path, &document).with_context(|| format!("Failed to write {}", path.display()))?;
+    open_editor(&path, config.editor.as_deref())?;
+    let edited = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    if edited == document {
+        fs::remove_file(&path).ok();
+        println!("{}", "No changes, nothing to upload.".yellow());
+        return Ok(());
+    }
+
+    // Someone (or the web editor) may have changed the draft while we were editing
+    if !force {
+        let current = client.get_my_article(id).await?;
+        if draft_document(&current) != document {
+            return Err(anyhow::anyhow!(
+                "Article {id} changed on dev.to while you were editing. Your version is kept at {}. \
+                 Re-run with --force to overwrite the remote draft.",
+                path.display()
+            ));
+        }
+    }
+
+    let updated = client
+        .update_article(id, &document_to_input(&edited, embedded))
+        .await
+        .with_context(|| format!("Upload failed; your edits are kept at {}", path.display()))?;
+    fs::remove_file(&path).ok();
+    println!("{} {}", "Updated:".green(), updated.title.cyan().bold());
+    hooks::fire(&config.hooks, HookEvent::Push, &[&updated]).await;
+    super::history::pushed(client, &[&updated]);
+    super::cache::upsert(client, updated)?;
+    Ok(())
+}
+
+/// The `editor.command` setting, else `$VISUAL`, else `$EDITOR`, else `vi`.
+pub fn editor_command(configured: Option<&str>) -> String {
+    configured
+        .map(str::to_string)
+        .filter(|editor| !editor.trim().is_empty())
+        .or_else(|| env::var("VISUAL").ok())
+        .or_else(|| env::var("EDITOR").ok())
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Runs the editor (which may carry arguments, e.g. `code --wait`) on `path`
+/// and waits for it to exit, see [`editor_command`].
+pub fn open_editor(path: &Path, configured: Option<&str>) -> Result<()> {
+    let editor = editor_command(configured);
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = process::Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to start editor '{editor}'"))?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("Editor '{editor}' exited with {status}"));
+    }
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::*;
+use dtdrafts_core::export::{ExportFormat, ExportPost};
+use dtdrafts_core::*;
+
+pub fn run(drafts: &[&Article], format: ExportFormat, dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let exporter = format.exporter();
+    let now = Utc::now();
+    for article in drafts {
+        let post = ExportPost::new(article, now);
+        let path = dir.join(exporter.file_name(&post));
+        fs::write(&path, exporter.render(&post)).with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("  {} {}", "wrote".green(), path.display());
+    }
+    println!("Exported {} draft(s) for {format} to {}", drafts.len(), dir.display());
+    Ok(())
+}
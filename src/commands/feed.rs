@@ -0,0 +1,21 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::*;
+use dtdrafts_core::feed::atom_feed;
+use dtdrafts_core::*;
+
+/// Writes the Atom feed to `out`, or to stdout when no file is given.
+pub fn run(drafts: &[&Article], out: Option<&Path>) -> Result<()> {
+    let feed = atom_feed(drafts, Utc::now());
+    match out {
+        Some(out) => {
+            fs::write(out, feed).with_context(|| format!("Failed to write {}", out.display()))?;
+            println!("{} {} draft(s) to {}", "Wrote a feed of".green(), drafts.len(), out.display());
+        }
+        None => print!("{feed}"),
+    }
+    Ok(())
+}
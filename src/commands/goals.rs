@@ -0,0 +1,60 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::*;
+use dtdrafts_core::goals::{self, GoalStatus, Goals};
+use dtdrafts_core::*;
+
+/// Sets the target. A best streak reached under another target is dropped.
+pub fn set(publish_per_month: u32) -> Result<()> {
+    let mut goals = Goals::load()?;
+    if goals.publish_per_month != Some(publish_per_month) {
+        goals.best_streak = 0;
+    }
+    goals.publish_per_month = Some(publish_per_month);
+    goals.save().context("Failed to save goals")?;
+    println!("{} publish {publish_per_month} article(s) per month", "Goal set:".green());
+    Ok(())
+}
+
+/// Prints the progress towards the goal over the last `months` months and
+/// returns how many articles were published this month. The best streak is
+/// saved when it grows.
+pub fn status(published: &[Article], months: usize, quiet: bool) -> Result<usize> {
+    let mut goals = Goals::load()?;
+    let Some(target) = goals.publish_per_month else {
+        if !quiet {
+            println!("{}", "No goal set yet; set one with `dtdrafts goals set --publish-per-month 2`.".yellow());
+        }
+        return Ok(0);
+    };
+    let today = Utc::now().date_naive();
+    let status = goals::status(target, &goals::publish_dates(published), today, months, goals.best_streak);
+    if status.best_streak > goals.best_streak {
+        goals.best_streak = status.best_streak;
+        goals.save().context("Failed to save goals")?;
+    }
+    if !quiet {
+        print_status(&status);
+    }
+    Ok(status.current().map_or(0, |month| month.published as usize))
+}
+
+fn print_status(status: &GoalStatus) {
+    println!("{} publish {} article(s) per month\n", "Goal:".bold(), status.target);
+    for (index, month) in status.months.iter().enumerate() {
+        let current = index + 1 == status.months.len();
+        let bar: String = (0..status.target.max(month.published))
+            .map(|n| if n < month.published { '█' } else { '·' })
+            .collect();
+        let mark = match (month.met, current) {
+            (true, _) => "✓".green().to_string(),
+            (false, true) => format!("{} to go", status.target - month.published).yellow().to_string(),
+            (false, false) => "✗".red().to_string(),
+        };
+        let bar = if month.met { bar.green() } else { bar.normal() };
+        println!("  {}  {bar}  {}/{}  {mark}", month.month.format("%Y-%m"), month.published, status.target);
+    }
+    let streak = format!("{} month(s)", status.streak);
+    let streak = if status.streak > 0 { streak.green().bold() } else { streak.normal() };
+    println!("\n{} {streak} (best: {})", "Streak:".bold(), status.best_streak);
+}
@@ -0,0 +1,105 @@
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::frontmatter::{draft_document, restore_input};
+use dtdrafts_core::history::{self, load_document, Direction, History};
+use dtdrafts_core::hooks::{self, HookEvent, HooksConfig};
+use dtdrafts_core::search_history::SearchHistory;
+use dtdrafts_core::*;
+
+use super::bulk::confirm;
+
+/// Records the drafts just fetched from dev.to in the history.
+pub fn pulled(client: &DevToClient, articles: &[&Article]) {
+    record(client, articles, Direction::Pull);
+}
+
+/// Records the drafts just uploaded to dev.to in the history.
+pub fn pushed(client: &DevToClient, articles: &[&Article]) {
+    record(client, articles, Direction::Push);
+}
+
+// Replayed responses never came from dev.to, and the history is only a
+// convenience, so failing to write it is only worth a warning
+fn record(client: &DevToClient, articles: &[&Article], direction: Direction) {
+    if client.is_replaying() {
+        return;
+    }
+    if let Err(e) = history::record(articles, direction) {
+        tracing::warn!("Failed to record draft history: {e:#}");
+    }
+}
+
+/// Lists the recorded versions of a draft, newest first, and returns how many
+/// there are. With `show`, prints that version's document instead.
+pub fn run(id: u64, show: Option<&str>, quiet: bool) -> Result<usize> {
+    let history = History::load()?;
+    if let Some(version) = show {
+        print!("{}", load_document(history.find(id, version)?)?);
+        return Ok(1);
+    }
+    let versions = history.get(id);
+    if quiet {
+        return Ok(versions.len());
+    }
+    if versions.is_empty() {
+        println!("{}", format!("No history for article {id} yet; versions are recorded when it is pulled or pushed.").yellow());
+    }
+    for (number, version) in versions.iter().enumerate().rev() {
+        let at = version.at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+        let direction = match version.direction {
+            Direction::Push => version.direction.to_string().green(),
+            Direction::Pull => version.direction.to_string().blue(),
+        };
+        println!(
+            "{:>4}  {}  {at}  {direction:<6}  {}",
+            number + 1,
+            version.short_hash().dimmed(),
+            version.title.cyan()
+        );
+    }
+    Ok(versions.len())
+}
+
+/// Uploads a recorded version of a draft over the current one.
+pub async fn revert(client: &DevToClient, hooks: &HooksConfig, id: u64, to: &str, yes: bool) -> Result<()> {
+    let history = History::load()?;
+    let version = history.find(id, to)?;
+    let document = load_document(version)?;
+    let current = client.get_my_article(id).await?;
+    if draft_document(&current) == document {
+        println!("{}", format!("{} already matches version {}.", current.title, version.short_hash()).yellow());
+        return Ok(());
+    }
+    let at = version.at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+    println!("Reverting {} to version {} ({at}, {}).", current.title.cyan().bold(), version.short_hash(), version.direction);
+    if !yes && !confirm("Overwrite the draft on dev.to?")? {
+        println!("{}", "Nothing was changed.".yellow());
+        return Ok(());
+    }
+    let updated = client
+        .update_article(id, &restore_input(&document, version.embedded))
+        .await
+        .context("Failed to revert the draft")?;
+    println!("{} {}", "Reverted:".green(), updated.title.cyan().bold());
+    hooks::fire(hooks, HookEvent::Push, &[&updated]).await;
+    pushed(client, &[&updated]);
+    super::cache::upsert(client, updated)?;
+    Ok(())
+}
+
+/// Lists the recent searches, newest first, numbered as `search '!N'` takes
+/// them, and returns how many there are.
+pub fn searches(quiet: bool) -> Result<usize> {
+    let history = SearchHistory::load()?;
+    if quiet {
+        return Ok(history.entries.len());
+    }
+    if history.entries.is_empty() {
+        println!("{}", "No searches recorded yet.".yellow());
+    }
+    for (number, entry) in history.recent() {
+        let at = entry.at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+        println!("{:>4}  {}  {}", format!("!{number}").cyan(), at.to_string().dimmed(), entry.command_line());
+    }
+    Ok(history.entries.len())
+}
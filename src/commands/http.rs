@@ -0,0 +1,129 @@
+//! A tiny HTTP/1.1 server for the local, single-user endpoints (preview and
+//! friends). One request per connection, no keep-alive, no TLS.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: Vec<(String, String)>,
+}
+
+impl Request {
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn new(status: u16, content_type: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Self { status, content_type, body: body.into() }
+    }
+
+    pub fn html(body: impl Into<Vec<u8>>) -> Self {
+        Self::new(200, "text/html; charset=utf-8", body)
+    }
+
+    pub fn text(body: impl Into<Vec<u8>>) -> Self {
+        Self::new(200, "text/plain; charset=utf-8", body)
+    }
+
+    pub fn json(value: &impl serde::Serialize) -> Self {
+        match serde_json::to_vec_pretty(value) {
+            Ok(body) => Self::new(200, "application/json", body),
+            Err(e) => Self::error(&e.to_string()),
+        }
+    }
+
+    pub fn not_found() -> Self {
+        Self::new(404, "text/plain; charset=utf-8", "Not found\n")
+    }
+
+    pub fn error(message: &str) -> Self {
+        Self::new(500, "text/plain; charset=utf-8", format!("{message}\n"))
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Accepts connections forever, answering each request with `handler`.
+pub async fn serve<F, Fut>(listener: TcpListener, handler: F) -> Result<()>
+where
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Response> + Send,
+{
+    let handler = Arc::new(handler);
+    loop {
+        let (stream, _) = listener.accept().await.context("Failed to accept connection")?;
+        let handler = Arc::clone(&handler);
+        tokio::spawn(async move {
+            let _ = handle(stream, &*handler).await;
+        });
+    }
+}
+
+async fn handle<F, Fut>(stream: TcpStream, handler: &F) -> Result<()>
+where
+    F: Fn(Request) -> Fut,
+    Fut: Future<Output = Response>,
+{
+    let mut reader = BufReader::new(stream);
+    let response = match read_request(&mut reader).await {
+        Ok(request) => handler(request).await,
+        Err(e) => Response::new(400, "text/plain; charset=utf-8", format!("{e}\n")),
+    };
+    let mut stream = reader.into_inner();
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        response.content_type,
+        response.body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&response.body).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+async fn read_request(reader: &mut BufReader<TcpStream>) -> Result<Request> {
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().context("Empty request")?.to_string();
+    let target = parts.next().context("Missing request target")?;
+    let url = reqwest::Url::parse(&format!("http://localhost{target}")).context("Invalid request target")?;
+
+    // Headers are not used; skip to the blank line
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let query = url.query_pairs().map(|(key, value)| (key.into_owned(), value.into_owned())).collect();
+    Ok(Request { method, path: url.path().to_string(), query })
+}
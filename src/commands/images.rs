@@ -0,0 +1,89 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::links::extract_images;
+use dtdrafts_core::lint::PublishMetadata;
+use dtdrafts_core::*;
+
+/// Lists the images in a draft, flagging plain http and dead ones, and
+/// optionally downloads them into `download_dir`. Returns the image count.
+pub async fn run(article: &Article, download_dir: Option<&Path>) -> Result<usize> {
+    let mut images: Vec<(String, String)> = extract_images(article.body_markdown.as_deref().unwrap_or_default())
+        .into_iter()
+        .map(|image| (image.line.to_string(), image.url))
+        .collect();
+    if let Some(cover) = PublishMetadata::of(article).cover_image {
+        images.insert(0, ("cover".to_string(), cover));
+    }
+    if images.is_empty() {
+        println!("{}", "No images found.".yellow());
+        return Ok(0);
+    }
+
+    let urls: Vec<String> = images.iter().map(|(_, url)| url.clone()).collect();
+    let checks = LinkChecker::new()?.check(&urls).await;
+    println!("{} {}", article.title.cyan().bold(), format!("({})", article.id).dimmed());
+    for ((location, url), check) in images.iter().zip(&checks) {
+        let mut notes = Vec::new();
+        if !url.starts_with("https://") {
+            notes.push("not https".yellow().to_string());
+        }
+        match check {
+            LinkCheck::Ok => {}
+            LinkCheck::Redirect { status, .. } => notes.push(format!("redirect {status}").yellow().to_string()),
+            LinkCheck::Broken(status) => notes.push(format!("dead {status}").red().to_string()),
+            LinkCheck::Failed(reason) => notes.push(format!("unreachable: {reason}").red().to_string()),
+        }
+        let status = if notes.is_empty() { "ok".green().to_string() } else { notes.join(", ") };
+        println!("  {:>5}  {url}  {status}", location.dimmed());
+    }
+
+    if let Some(dir) = download_dir {
+        download_all(&urls, dir).await?;
+    }
+    Ok(images.len())
+}
+
+async fn download_all(urls: &[String], dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let client = reqwest::Client::builder()
+        .user_agent(dtdrafts_core::client::DEFAULT_USER_AGENT)
+        .build()
+        .context("Failed to build HTTP client")?;
+    println!();
+    let mut saved = 0;
+    for (index, url) in urls.iter().enumerate() {
+        let path = dir.join(asset_file_name(index, url));
+        match download(&client, url).await {
+            Ok(bytes) => {
+                fs::write(&path, bytes).with_context(|| format!("Failed to write {}", path.display()))?;
+                println!("{} {}", "Saved".green(), path.display());
+                saved += 1;
+            }
+            Err(e) => println!("{} {url}: {e:#}", "Skipped".yellow()),
+        }
+    }
+    println!("{saved} of {} image(s) saved to {}", urls.len(), dir.display());
+    Ok(())
+}
+
+async fn download(client: &reqwest::Client, url: &str) -> Result<Vec<u8>> {
+    let response = client.get(url).send().await?.error_for_status()?;
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// `03-diagram.png`: numbered so images with the same name don't collide.
+fn asset_file_name(index: usize, url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let name: String = path
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+        .collect();
+    let name = if name.trim_matches(['.', '_']).is_empty() { "image".to_string() } else { name };
+    format!("{:02}-{name}", index + 1)
+}
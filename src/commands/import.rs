@@ -0,0 +1,41 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::import::read_export;
+use dtdrafts_core::*;
+
+pub async fn run(client: &DevToClient, from: &Path, dry_run: bool) -> Result<()> {
+    let posts = read_export(from)?;
+    println!("Found {} post(s) in {}", posts.len(), from.display());
+    if dry_run {
+        for post in &posts {
+            let input = post.to_input();
+            let tags = input.tags.unwrap_or_default().join(", ");
+            let words = text::word_count(&post.body_markdown);
+            println!(
+                "  would create {} {}",
+                post.title.cyan(),
+                format!("({words} words; tags: {tags}; from {})", post.source).dimmed()
+            );
+        }
+        println!("{}", "Dry run: nothing was created.".yellow());
+        return Ok(());
+    }
+
+    for (index, post) in posts.iter().enumerate() {
+        if index > 0 {
+            tokio::time::sleep(Duration::from_secs(1)).await; // rate limit mitigation
+        }
+        let created = client
+            .create_article(&post.to_input())
+            .await
+            .with_context(|| format!("Imported {index} of {} post(s); failed on {}", posts.len(), post.source))?;
+        println!("  {} {} {}", "created".green(), created.title.cyan(), format!("({})", created.id).dimmed());
+        super::history::pushed(client, &[&created]);
+        super::cache::upsert(client, created)?;
+    }
+    println!("{} {} post(s) as drafts", "Imported".green(), posts.len());
+    Ok(())
+}
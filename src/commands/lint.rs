@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use colored::*;
+use dtdrafts_core::links::{extract_links, is_http, Link};
+use dtdrafts_core::lint::{
+    canonical_url_issue, link_issue, lint_code_blocks, lint_markdown, lint_prose, missing_canonical_issue, spelling_issues,
+    tag_usage_issues, PublishMetadata,
+};
+use dtdrafts_core::spelling::SpellChecker;
+use dtdrafts_core::*;
+
+/// The optional checks of `lint`, on top of the offline ones.
+#[derive(Default)]
+pub struct LintOptions<'a> {
+    /// Request every http(s) URL in the drafts
+    pub links: bool,
+    /// Flag tags outside these lowercase names
+    pub popular_tags: Option<&'a [String]>,
+    /// Flag words this checker doesn't know
+    pub spelling: Option<&'a SpellChecker>,
+    /// Apply the prose style rules
+    pub prose: bool,
+    /// Check code block languages and parse Rust snippets
+    pub code: bool,
+    /// Treat the drafts as cross-posts: require a canonical_url that loads
+    pub canonical: bool,
+}
+
+/// Prints issues grouped per draft. Returns `(errors, warnings)`.
+pub async fn run(articles: &[&Article], options: &LintOptions<'_>, quiet: bool) -> Result<(usize, usize)> {
+    let check_links = options.links;
+    let mut urls: Vec<String> = Vec::new();
+    if check_links {
+        urls.extend(articles.iter().flat_map(|article| article_links(article)).map(|link| link.url));
+    }
+    if options.canonical {
+        urls.extend(
+            articles.iter().filter_map(|article| PublishMetadata::of(article).canonical_url).filter(|url| is_http(url)),
+        );
+    }
+    let link_checks = if urls.is_empty() { HashMap::new() } else { check_all_links(urls, quiet).await? };
+
+    let mut errors = 0;
+    let mut warnings = 0;
+    for article in articles {
+        let body = article.body_markdown.as_deref().unwrap_or_default();
+        let mut issues = lint_article(article);
+        issues.extend(lint_markdown(body, &PublishMetadata::of(article).title));
+        if let Some(popular) = options.popular_tags {
+            issues.extend(tag_usage_issues(&PublishMetadata::of(article).tags, popular));
+        }
+        if let Some(checker) = options.spelling {
+            issues.extend(spelling_issues(checker, body));
+        }
+        if options.prose {
+            issues.extend(lint_prose(body));
+        }
+        if options.code {
+            issues.extend(lint_code_blocks(body));
+        }
+        if options.canonical {
+            let metadata = PublishMetadata::of(article);
+            issues.extend(missing_canonical_issue(&metadata));
+            if let Some(url) = &metadata.canonical_url {
+                issues.extend(link_checks.get(url).and_then(|check| canonical_url_issue(url, check)));
+            }
+        }
+        if check_links {
+            issues.extend(
+                article_links(article)
+                    .iter()
+                    .filter_map(|link| link_issue(link, link_checks.get(&link.url)?)),
+            );
+        }
+        issues.sort_by_key(|issue| (issue.line, issue.column));
+        if issues.is_empty() {
+            continue;
+        }
+        errors += issues.iter().filter(|issue| issue.severity == Severity::Error).count();
+        warnings += issues.iter().filter(|issue| issue.severity == Severity::Warning).count();
+        if quiet {
+            continue;
+        }
+        println!("{} {}", article.title.cyan().bold(), format!("({})", article.id).dimmed());
+        for issue in issues {
+            print_issue(&issue);
+        }
+        println!();
+    }
+    if !quiet {
+        let summary = format!("{errors} error(s), {warnings} warning(s) in {} draft(s)", articles.len());
+        if errors > 0 {
+            println!("{}", summary.red().bold());
+        } else if warnings > 0 {
+            println!("{}", summary.yellow());
+        } else {
+            println!("{}", summary.green());
+        }
+    }
+    Ok((errors, warnings))
+}
+
+fn article_links(article: &Article) -> Vec<Link> {
+    extract_links(article.body_markdown.as_deref().unwrap_or_default())
+}
+
+// Each distinct URL is requested once, however many drafts link to it
+async fn check_all_links(mut urls: Vec<String>, quiet: bool) -> Result<HashMap<String, LinkCheck>> {
+    urls.sort();
+    urls.dedup();
+    if !quiet {
+        println!("{}", format!("Checking {} link(s)...", urls.len()).blue());
+    }
+    let checks = LinkChecker::new()?.check(&urls).await;
+    Ok(urls.into_iter().zip(checks).collect())
+}
+
+pub fn print_issue(issue: &LintIssue) {
+    let location = match (issue.line, issue.column) {
+        (Some(line), Some(column)) => format!("{line}:{column}"),
+        (Some(line), None) => line.to_string(),
+        (None, _) => "-".to_string(),
+    };
+    let severity = match issue.severity {
+        Severity::Error => issue.severity.to_string().red().bold(),
+        Severity::Warning => issue.severity.to_string().yellow().bold(),
+    };
+    println!(
+        "  {:>6}  {severity} {} {}",
+        location.dimmed(),
+        format!("[{}]", issue.rule).dimmed(),
+        issue.message
+    );
+}
@@ -0,0 +1,58 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use clap_mangen::Man;
+
+/// Writes `dtdrafts.1` plus one `dtdrafts-<subcommand>.1` page per visible
+/// subcommand. `after_long_help` text is rendered as an EXAMPLES section.
+pub fn run(command: clap::Command, out_dir: &Path) -> Result<()> {
+    fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create {}", out_dir.display()))?;
+    let mut command = command.disable_help_subcommand(true);
+    command.build();
+
+    let mut written = vec![write_page(&command, out_dir)?];
+    for subcommand in command.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        written.push(write_page(subcommand, out_dir)?);
+    }
+    for path in written {
+        println!("{}", path);
+    }
+    Ok(())
+}
+
+fn write_page(command: &clap::Command, out_dir: &Path) -> Result<String> {
+    // Examples get their own section instead of clap_mangen's generic EXTRA one
+    let examples = command.get_after_long_help().map(|help| help.to_string());
+    let man = Man::new(command.clone().after_long_help(None::<&str>));
+    let path = out_dir.join(man.get_filename());
+
+    let mut page = Vec::new();
+    man.render(&mut page)?;
+    if let Some(examples) = examples {
+        render_examples(&mut page, &examples)?;
+    }
+    fs::write(&path, page).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path.display().to_string())
+}
+
+fn render_examples(page: &mut Vec<u8>, examples: &str) -> Result<()> {
+    writeln!(page, ".SH EXAMPLES")?;
+    writeln!(page, ".nf")?;
+    for line in examples.lines().filter(|line| line.trim() != "Examples:") {
+        writeln!(page, "{}", escape_roff(line.trim_start()))?;
+    }
+    writeln!(page, ".fi")?;
+    Ok(())
+}
+
+fn escape_roff(line: &str) -> String {
+    let escaped = line.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{escaped}")
+    } else {
+        escaped
+    }
+}
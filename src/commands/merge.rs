@@ -0,0 +1,55 @@
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::journal::JournalChange;
+use dtdrafts_core::merge::plan_merge;
+use dtdrafts_core::*;
+
+/// Creates a draft titled `title` from the drafts `ids`, in that order. With
+/// `trash`, the originals are then moved to the trash (and can be brought
+/// back with `undo` or `trash restore`).
+pub async fn run(client: &DevToClient, ids: &[u64], title: &str, trash: bool) -> Result<()> {
+    let latest = client.get_my_articles_by_id(ids).await.context("Failed to fetch the drafts")?;
+    let mut originals = Vec::new();
+    for id in ids {
+        let article = latest.iter().find(|article| article.id == *id).with_context(|| format!("No draft with id {id}"))?;
+        originals.push(article);
+    }
+
+    let plan = plan_merge(&originals, title);
+    println!("{} {} draft(s) into {}", "Merging".bold(), originals.len(), title.cyan().bold());
+    for article in &originals {
+        println!("  {:>8}  {}", article.id.to_string().dimmed(), article.title);
+    }
+    if !plan.dropped_tags.is_empty() {
+        println!(
+            "{} dev.to allows {} tags, so these were left out: {}",
+            "Note:".yellow(),
+            dtdrafts_core::lint::MAX_TAGS,
+            plan.dropped_tags.join(", ")
+        );
+    }
+    let created = client.create_article(&plan.input).await.context("Failed to create the merged draft")?;
+    println!("{} {} {}", "Created draft".green(), created.title.cyan().bold(), format!("({})", created.id).dimmed());
+
+    let mut deleted = Vec::new();
+    if trash {
+        let mut journal = Vec::new();
+        for article in &originals {
+            match super::bulk::delete(client, article).await {
+                Ok(()) => {
+                    println!("  {} {}", "trashed".green(), article.title);
+                    deleted.push(article.id);
+                    journal.push(JournalChange::deleted((*article).clone()));
+                }
+                Err(e) => println!("  {} {}: {e:#}", "not trashed".red(), article.title),
+            }
+        }
+        super::undo::record("merge", journal);
+    }
+    super::history::pushed(client, &[&created]);
+    super::cache::update(client, vec![created], &deleted)?;
+    if deleted.len() < originals.len() && trash {
+        return Err(anyhow::anyhow!("{} of {} original(s) could not be trashed", originals.len() - deleted.len(), originals.len()));
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+pub mod analytics;
+pub mod backup;
+pub mod bulk;
+pub mod cache;
+pub mod canonical;
+pub mod clone;
+pub mod comments;
+pub mod completions;
+pub mod config;
+pub mod cover;
+pub mod crosspost;
+pub mod daemon;
+pub mod dupes;
+pub mod edit;
+pub mod export;
+pub mod feed;
+pub mod goals;
+pub mod history;
+pub mod http;
+pub mod images;
+pub mod import;
+pub mod lint;
+pub mod man;
+pub mod merge;
+pub mod note;
+pub mod pager;
+pub mod pick;
+pub mod preview;
+pub mod publish;
+pub mod related;
+pub mod remind;
+pub mod replace;
+pub mod report;
+pub mod retag;
+pub mod rpc;
+pub mod saved;
+pub mod schedule;
+pub mod selftest;
+pub mod seo;
+pub mod series;
+pub mod serve;
+pub mod show;
+pub mod split;
+pub mod stale;
+pub mod stats;
+pub mod status;
+pub mod sync;
+pub mod tags;
+pub mod template;
+pub mod toc;
+pub mod trash;
+pub mod undo;
+pub mod vocab;
+pub mod watch;
+pub mod whoami;
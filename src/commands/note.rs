@@ -0,0 +1,58 @@
+use anyhow::{Context, Result};
+use chrono::{Local, Utc};
+use colored::*;
+use dtdrafts_core::notes::{Note, NoteStore};
+use dtdrafts_core::*;
+
+pub fn add(article: &Article, text: &str) -> Result<()> {
+    let mut store = NoteStore::load()?;
+    store.add(article.id, text, Utc::now());
+    store.save().context("Failed to save notes")?;
+    println!("{} {}", "Added a note to".green(), article.title.cyan().bold());
+    Ok(())
+}
+
+/// Prints an article's notes unless `quiet` and returns how many there are.
+pub fn list(article: &Article, quiet: bool) -> Result<usize> {
+    let store = NoteStore::load()?;
+    let notes = store.get(article.id);
+    if !quiet {
+        if notes.is_empty() {
+            println!("{}", format!("No notes for {}", article.title).yellow());
+        } else {
+            println!("{}", article.title.cyan().bold());
+            print!("{}", format_notes(notes));
+        }
+    }
+    Ok(notes.len())
+}
+
+pub fn remove(article: &Article, number: usize) -> Result<()> {
+    let mut store = NoteStore::load()?;
+    let note = store
+        .remove(article.id, number)
+        .with_context(|| format!("{} has no note #{number}", article.title))?;
+    store.save().context("Failed to save notes")?;
+    println!("{} {}", "Removed note:".green(), note.text);
+    Ok(())
+}
+
+pub fn clear(article: &Article) -> Result<()> {
+    let mut store = NoteStore::load()?;
+    let removed = store.clear(article.id);
+    store.save().context("Failed to save notes")?;
+    println!("{}", format!("Removed {} note(s) from {}", removed.len(), article.title).green());
+    Ok(())
+}
+
+/// Numbered lines, as used by `note` and `show`.
+pub fn format_notes(notes: &[Note]) -> String {
+    notes
+        .iter()
+        .enumerate()
+        .map(|(i, note)| {
+            let date = note.created_at.with_timezone(&Local).format("%Y-%m-%d");
+            format!("  {}. {}  {}\n", i + 1, note.text, date.to_string().dimmed())
+        })
+        .collect()
+}
@@ -0,0 +1,88 @@
+use std::env;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process::{self, Stdio};
+
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::style::should_page;
+
+/// Prints `output`, paging it like git does when stdout is a terminal and the
+/// text doesn't fit on one screen. `$PAGER` is used when set (empty or `cat`
+/// disables paging), then `less`, then a minimal built-in pager.
+pub fn page(output: &str, enabled: bool) -> Result<()> {
+    let height = terminal_height();
+    if !should_page(output, enabled, io::stdout().is_terminal(), height) {
+        print!("{output}");
+        return Ok(());
+    }
+
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+    let pager = pager.trim();
+    if pager.is_empty() || pager == "cat" {
+        print!("{output}");
+        return Ok(());
+    }
+    match spawn_pager(pager, output) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            tracing::debug!(pager, error = %e, "falling back to the built-in pager");
+            builtin_pager(output, height.unwrap_or(24))
+        }
+    }
+}
+
+/// Columns of the terminal (or `$COLUMNS`), 100 when neither is known.
+pub fn terminal_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(columns), _)| usize::from(columns))
+        .or_else(|| env::var("COLUMNS").ok()?.parse().ok())
+        .unwrap_or(100)
+}
+
+fn terminal_height() -> Option<usize> {
+    terminal_size::terminal_size()
+        .map(|(_, terminal_size::Height(rows))| usize::from(rows))
+        .or_else(|| env::var("LINES").ok()?.parse().ok())
+}
+
+fn spawn_pager(pager: &str, output: &str) -> Result<()> {
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().unwrap_or("less");
+    let mut command = process::Command::new(program);
+    command.args(parts).stdin(Stdio::piped());
+    // Same defaults as git: quit if one screen, keep colors, don't clear
+    if env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+    let mut child = command.spawn().with_context(|| format!("Failed to start pager '{pager}'"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The user quitting the pager early closes the pipe; that's not an error
+        match stdin.write_all(output.as_bytes()) {
+            Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e.into()),
+            _ => {}
+        }
+    }
+    child.wait()?;
+    Ok(())
+}
+
+fn builtin_pager(output: &str, height: usize) -> Result<()> {
+    let page_size = height.saturating_sub(1).max(1);
+    let lines: Vec<&str> = output.lines().collect();
+    let mut stdout = io::stdout().lock();
+    let mut input = io::stdin().lock();
+    for (page, chunk) in lines.chunks(page_size).enumerate() {
+        if page > 0 {
+            write!(stdout, "{}", "-- More -- (Enter for the next page, q to quit) ".reversed())?;
+            stdout.flush()?;
+            let mut answer = String::new();
+            if input.read_line(&mut answer)? == 0 || answer.trim().eq_ignore_ascii_case("q") {
+                break;
+            }
+        }
+        for line in chunk {
+            writeln!(stdout, "{line}")?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use std::io::IsTerminal;
+use std::process;
+
+use anyhow::{Context, Result};
+use colored::*;
+use dialoguer::FuzzySelect;
+use dtdrafts_core::*;
+
+/// Narrows search results down to the one article an action applies to,
+/// asking interactively when there is more than one.
+pub fn pick<'a>(articles: &[&'a Article]) -> Result<&'a Article> {
+    match articles {
+        [] => Err(anyhow::anyhow!("No draft articles found.")),
+        [article] => Ok(article),
+        _ if std::io::stdin().is_terminal() && std::io::stderr().is_terminal() => {
+            // Fuzzy matching runs over the whole item, so it holds only the title
+            let items: Vec<&str> = articles.iter().map(|article| article.title.as_str()).collect();
+            let selection = FuzzySelect::new()
+                .with_prompt(format!("{} drafts match, pick one", articles.len()))
+                .items(&items)
+                .default(0)
+                .interact_opt()?;
+            selection
+                .map(|index| articles[index])
+                .context("No draft selected")
+        }
+        _ => {
+            let mut message = format!("{} drafts match; narrow the query or pass an id:", articles.len());
+            for article in articles {
+                message.push_str(&format!("\n  {}  {}", article.id, article.title));
+            }
+            Err(anyhow::anyhow!(message))
+        }
+    }
+}
+
+pub fn open_in_browser(article: &Article) -> Result<()> {
+    let url = article.edit_url();
+    let (program, args): (&str, &[&str]) = if cfg!(target_os = "macos") {
+        ("open", &[])
+    } else if cfg!(windows) {
+        ("cmd", &["/C", "start", ""])
+    } else {
+        ("xdg-open", &[])
+    };
+    let status = process::Command::new(program)
+        .args(args)
+        .arg(&url)
+        .status()
+        .with_context(|| format!("Failed to open {url}"))?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("'{program}' exited with {status} while opening {url}"));
+    }
+    println!("{}", format!("Opened {}", article.title).green());
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::frontmatter::parse_document;
+use dtdrafts_core::render::preview_page;
+use dtdrafts_core::*;
+use tokio::net::TcpListener;
+
+use super::http::{self, Request, Response};
+
+/// Where the previewed markdown comes from. A cached draft is re-read from the
+/// cache on every request, so `dtdrafts edit` uploads show up on reload.
+pub enum Source {
+    Cached(u64),
+    File(PathBuf),
+}
+
+impl Source {
+    fn watched_path(&self) -> Result<PathBuf> {
+        match self {
+            Source::Cached(_) => get_cache_file(),
+            Source::File(path) => Ok(path.clone()),
+        }
+    }
+
+    fn load(&self) -> Result<(String, Vec<String>, String)> {
+        match self {
+            Source::Cached(id) => {
+                let article = load_articles_cache()?
+                    .into_iter()
+                    .find(|article| article.id == *id)
+                    .with_context(|| format!("Article {id} is not in the cache"))?;
+                Ok((
+                    article.title,
+                    article.tags.unwrap_or_default(),
+                    article.body_markdown.unwrap_or_default(),
+                ))
+            }
+            Source::File(path) => {
+                let markdown = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+                let (front_matter, _) = parse_document(&markdown);
+                let front_matter = front_matter.unwrap_or_default();
+                let title = front_matter
+                    .get("title")
+                    .map(str::to_string)
+                    .unwrap_or_else(|| path.display().to_string());
+                Ok((title, front_matter.tags().unwrap_or_default(), markdown))
+            }
+        }
+    }
+}
+
+pub async fn run(client: &DevToClient, source: Source, port: u16) -> Result<()> {
+    if let Source::Cached(id) = source {
+        ensure_cached(client, id).await?;
+    }
+    // Fail early rather than serving an error page
+    source.load()?;
+
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .await
+        .with_context(|| format!("Failed to listen on port {port}"))?;
+    let watched = source.watched_path()?;
+    println!("{} http://127.0.0.1:{port}/", "Previewing at".green());
+    println!("Reloads when {} changes. Press Ctrl-C to stop.", watched.display());
+
+    let source = Arc::new(source);
+    http::serve(listener, move |request| {
+        let source = Arc::clone(&source);
+        async move { respond(&source, request) }
+    })
+    .await
+}
+
+async fn ensure_cached(client: &DevToClient, id: u64) -> Result<()> {
+    let cached = load_articles_cache()?
+        .iter()
+        .any(|article| article.id == id && article.body_markdown.is_some());
+    if !cached {
+        println!("{}", format!("Downloading article {id}...").blue());
+        let article = client.get_my_article(id).await?;
+        super::history::pulled(client, &[&article]);
+        super::cache::upsert(client, article)?;
+    }
+    Ok(())
+}
+
+fn respond(source: &Source, request: Request) -> Response {
+    if request.method != "GET" {
+        return Response::new(405, "text/plain; charset=utf-8", "Method not allowed\n");
+    }
+    match request.path.as_str() {
+        "/" => match source.load() {
+            Ok((title, tags, markdown)) => Response::html(preview_page(&title, &tags, &markdown, true)),
+            Err(e) => Response::error(&format!("{e:#}")),
+        },
+        "/__version" => match source.watched_path() {
+            Ok(path) => Response::text(modified_nanos(&path).to_string()),
+            Err(e) => Response::error(&format!("{e:#}")),
+        },
+        _ => Response::not_found(),
+    }
+}
+
+fn modified_nanos(path: &Path) -> u128 {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |since| since.as_nanos())
+}
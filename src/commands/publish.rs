@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::frontmatter::{has_embedded_front_matter, parse_document};
+use dtdrafts_core::hooks::{self, HookEvent, HooksConfig};
+use dtdrafts_core::journal::JournalChange;
+use dtdrafts_core::lint::{cover_image_issue, lint_markdown, PublishMetadata};
+use dtdrafts_core::*;
+
+use super::lint::print_issue;
+
+/// How `publish` checks a draft before publishing it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PublishOptions {
+    /// Only run the checks
+    pub dry_run: bool,
+    /// Publish despite errors
+    pub force: bool,
+    /// Also run the markdown structure checks, whose errors stop the publish too
+    pub markdown: bool,
+}
+
+pub async fn run(client: &DevToClient, hooks: &HooksConfig, id: u64, options: PublishOptions) -> Result<()> {
+    let PublishOptions { dry_run, force, markdown } = options;
+    println!("{}", format!("Downloading article {id}...").blue());
+    let article = client.get_my_article(id).await?;
+    if article.published {
+        println!("{}", format!("{} is already published: {}", article.title, article.url).yellow());
+        return Ok(());
+    }
+
+    let issues = checks(&article, markdown).await?;
+    let errors = issues.iter().filter(|issue| issue.severity == Severity::Error).count();
+    if !issues.is_empty() {
+        println!("{} {}", article.title.cyan().bold(), format!("({id})").dimmed());
+        for issue in &issues {
+            print_issue(issue);
+        }
+        println!();
+    }
+    if errors > 0 && !force {
+        return Err(anyhow::anyhow!(
+            "{errors} problem(s) must be fixed before publishing (or re-run with --force)"
+        ));
+    }
+    if dry_run {
+        println!("{}", "Dry run: checks passed, nothing was published.".green());
+        return Ok(());
+    }
+
+    let published = client
+        .update_article(id, &publish_input(&article))
+        .await
+        .context("Failed to publish")?;
+    super::undo::record("publish", vec![JournalChange::updated(article, &published)]);
+    println!("{} {}", "Published:".green(), published.url.cyan());
+    hooks::fire(hooks, HookEvent::Publish, &[&published]).await;
+    super::history::pushed(client, &[&published]);
+    super::cache::upsert(client, published)?;
+    Ok(())
+}
+
+/// The pre-publish checks: the lint rules plus whether the cover image loads,
+/// and with `markdown` the markdown structure checks of `lint`.
+pub async fn checks(article: &Article, markdown: bool) -> Result<Vec<LintIssue>> {
+    let mut issues = lint_article(article);
+    if markdown {
+        let body = article.body_markdown.as_deref().unwrap_or_default();
+        issues.extend(lint_markdown(body, &PublishMetadata::of(article).title));
+        issues.sort_by_key(|issue| issue.line);
+    }
+    if let Some(url) = PublishMetadata::of(article).cover_image {
+        let check = LinkChecker::new()?.check(std::slice::from_ref(&url)).await;
+        issues.extend(check.first().and_then(|check| cover_image_issue(&url, check)));
+        issues.sort_by_key(|issue| issue.line);
+    }
+    Ok(issues)
+}
+
+// dev.to reads `published` from embedded front matter, which would otherwise
+// win over the field in the request
+pub fn publish_input(article: &Article) -> ArticleInput {
+    let body = article.body_markdown.as_deref().unwrap_or_default();
+    if !has_embedded_front_matter(article) {
+        return ArticleInput { published: Some(true), ..Default::default() };
+    }
+    let (front_matter, rest) = parse_document(body);
+    let mut front_matter = front_matter.unwrap_or_default();
+    front_matter.set("published", "true");
+    ArticleInput {
+        body_markdown: Some(format!("{}{rest}", front_matter.render())),
+        published: Some(true),
+        ..Default::default()
+    }
+}
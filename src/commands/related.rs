@@ -0,0 +1,34 @@
+use std::collections::HashSet;
+
+use colored::*;
+use dtdrafts_core::similarity::SimilarityIndex;
+use dtdrafts_core::*;
+
+/// Prints the articles most similar to `article` unless `quiet` and returns
+/// how many were found. Published posts show their public URL for linking.
+pub fn run(article: &Article, others: &[&Article], limit: usize, quiet: bool) -> usize {
+    let mut seen = HashSet::from([article.id]);
+    let mut articles = vec![article];
+    articles.extend(others.iter().filter(|other| seen.insert(other.id)));
+    let index = SimilarityIndex::new(&articles);
+    let mut related = index.most_similar(article.id);
+    related.truncate(limit);
+    if quiet {
+        return related.len();
+    }
+    if related.is_empty() {
+        println!("{}", "No related articles found.".yellow());
+        return 0;
+    }
+    println!("Related to {}:\n", article.title.cyan().bold());
+    for (other, similarity) in &related {
+        let state = if other.published { "published".green() } else { "draft".yellow() };
+        println!("  {:>4}  {}  {}", format!("{:.0}%", similarity * 100.0).dimmed(), other.title.cyan(), state);
+        if other.published {
+            println!("        {}", other.url.blue().underline());
+        } else {
+            println!("        {}", format!("({})", other.id).dimmed());
+        }
+    }
+    related.len()
+}
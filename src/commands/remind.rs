@@ -0,0 +1,53 @@
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use colored::*;
+use dtdrafts_core::stale::{reminders, Reminder};
+use dtdrafts_core::*;
+
+/// Reports drafts untouched for `older_than` and returns how many there are.
+/// A notification is only sent when there is something to remind about.
+pub fn run(articles: &[Article], older_than: Duration, notify: bool, json: bool, quiet: bool) -> Result<usize> {
+    let reminders = reminders(articles, older_than, Utc::now());
+    if json && !quiet {
+        println!("{}", serde_json::to_string_pretty(&reminders)?);
+    } else if !quiet {
+        print_reminders(&reminders, older_than);
+    }
+    if notify && !reminders.is_empty() {
+        let (summary, body) = dtdrafts_core::stale::notification_text(&reminders);
+        send_notification(&summary, &body)?;
+    }
+    Ok(reminders.len())
+}
+
+fn print_reminders(reminders: &[Reminder], older_than: Duration) {
+    if reminders.is_empty() {
+        println!("{}", format!("No drafts untouched for {} days or more.", older_than.num_days()).green());
+    }
+    for reminder in reminders {
+        println!(
+            "{}  {}  {}",
+            format!("{:>4}d", reminder.age_days).yellow(),
+            reminder.title.cyan(),
+            reminder.edit_url.blue().underline()
+        );
+    }
+}
+
+#[cfg(feature = "notifications")]
+pub fn send_notification(summary: &str, body: &str) -> Result<()> {
+    notify_rust::Notification::new()
+        .appname("dtdrafts")
+        .summary(summary)
+        .body(body)
+        .show()
+        .map_err(|e| anyhow::anyhow!("Failed to send the notification: {e}"))?;
+    Ok(())
+}
+
+#[cfg(not(feature = "notifications"))]
+pub fn send_notification(_: &str, _: &str) -> Result<()> {
+    Err(anyhow::anyhow!(
+        "dtdrafts was built without the `notifications` feature. Reinstall with: cargo install --path . --features notifications"
+    ))
+}
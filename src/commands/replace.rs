@@ -0,0 +1,76 @@
+use anyhow::Result;
+use colored::*;
+use diffy::Line;
+use dtdrafts_core::bulk::BulkChange;
+use dtdrafts_core::hooks::HooksConfig;
+use dtdrafts_core::replace::Replacement;
+use dtdrafts_core::*;
+
+use super::bulk::{self, BulkOptions};
+
+/// Shows a diff for every selected draft `replacement` changes, then uploads
+/// the changes after confirmation.
+pub async fn run(
+    client: &DevToClient,
+    hooks: &HooksConfig,
+    selected: &[&Article],
+    replacement: &Replacement,
+    options: &BulkOptions,
+) -> Result<()> {
+    let mut pending = Vec::new();
+    let mut total = 0;
+    for article in selected {
+        let body = article.body_markdown.as_deref().unwrap_or_default();
+        let Some((replaced, count)) = replacement.apply(body) else {
+            continue;
+        };
+        let matches = format!("{count} replacement(s)");
+        println!("{} {} {}", article.title.cyan().bold(), format!("({})", article.id).dimmed(), matches.green());
+        print_diff(body, &replaced);
+        pending.push(*article);
+        total += count;
+    }
+    if pending.is_empty() {
+        println!("{}", "No drafts contain the pattern.".yellow());
+        return Ok(());
+    }
+    println!("{total} replacement(s) in {} of {} draft(s)", pending.len(), selected.len());
+    if options.dry_run {
+        println!("{}", "Dry run: nothing was changed.".yellow());
+        return Ok(());
+    }
+    if !options.yes && !bulk::confirm(&format!("Upload the changes to {} draft(s)?", pending.len()))? {
+        println!("{}", "Cancelled.".yellow());
+        return Ok(());
+    }
+    bulk::apply(client, hooks, &pending, options.interval, "replace", |article| match replacement.plan(article) {
+        // Another edit may have removed the matches since the preview
+        BulkChange::Skip(_) => BulkChange::Skip("no match any more".to_string()),
+        change => change,
+    })
+    .await
+}
+
+fn print_diff(old: &str, new: &str) {
+    for hunk in diffy::create_patch(old, new).hunks() {
+        let (before, after) = (hunk.old_range(), hunk.new_range());
+        println!("{}", format!("@@ -{},{} +{},{} @@", before.start(), before.len(), after.start(), after.len()).cyan());
+        for line in hunk.lines() {
+            match line {
+                Line::Context(text) => print!(" {text}"),
+                Line::Delete(text) => print!("{}", format!("-{text}").red()),
+                Line::Insert(text) => print!("{}", format!("+{text}").green()),
+            }
+            if !line_text(line).ends_with('\n') {
+                println!();
+            }
+        }
+    }
+    println!();
+}
+
+fn line_text<'a>(line: &Line<'a, str>) -> &'a str {
+    match line {
+        Line::Context(text) | Line::Delete(text) | Line::Insert(text) => text,
+    }
+}
@@ -0,0 +1,19 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::*;
+use dtdrafts_core::report::html_report;
+use dtdrafts_core::text::WordCountOptions;
+use dtdrafts_core::*;
+
+/// Writes the dashboard to `dir/index.html`, creating `dir` if needed.
+pub fn html(drafts: &[&Article], options: &WordCountOptions, dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join("index.html");
+    fs::write(&path, html_report(drafts, options, Utc::now()))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    println!("{} for {} draft(s) to {}", "Wrote a report".green(), drafts.len(), path.display());
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+use colored::*;
+use dtdrafts_core::bulk::BulkAction;
+use dtdrafts_core::hooks::HooksConfig;
+use dtdrafts_core::*;
+
+use super::bulk;
+
+/// Replaces tag `from` with `to` on the `selected` drafts that carry it.
+pub async fn run(
+    client: &DevToClient,
+    hooks: &HooksConfig,
+    selected: &[&Article],
+    from: &str,
+    to: &str,
+    dry_run: bool,
+    interval: Duration,
+) -> Result<()> {
+    let action = BulkAction::rename_tag(from, to);
+    let pending = bulk::preview(selected, &action, Path::new("."));
+    if dry_run {
+        println!("{}", "Dry run: nothing was changed.".yellow());
+        return Ok(());
+    }
+    if pending.is_empty() {
+        return Ok(());
+    }
+    bulk::apply(client, hooks, &pending, interval, "retag", |article| action.plan(article)).await
+}
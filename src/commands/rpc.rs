@@ -0,0 +1,175 @@
+use anyhow::{Context, Result};
+use dtdrafts_core::api::{search_summaries, DraftSummary};
+use dtdrafts_core::frontmatter::{document_to_input, has_embedded_front_matter};
+use dtdrafts_core::hooks::{self, HookEvent, HooksConfig};
+use dtdrafts_core::journal::JournalChange;
+use dtdrafts_core::rpc::{parse_request, RpcRequest, RpcResponse, INVALID_PARAMS, METHOD_NOT_FOUND, SERVER_ERROR};
+use dtdrafts_core::*;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+/// Answers JSON-RPC requests from stdin until it is closed. Nothing else may
+/// be printed to stdout while this runs; logs, and the output of command
+/// hooks, go to stderr.
+pub async fn run(client: &DevToClient, hooks: &HooksConfig) -> Result<()> {
+    let hooks = &HooksConfig { stdout_to_stderr: true, ..hooks.clone() };
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match parse_request(&line) {
+            Ok(request) => {
+                let id = request.id.clone();
+                let response = dispatch(client, hooks, request).await;
+                // Notifications don't get a response
+                match id {
+                    Some(_) => response,
+                    None => continue,
+                }
+            }
+            Err(response) => response,
+        };
+        let mut out = serde_json::to_vec(&response)?;
+        out.push(b'\n');
+        stdout.write_all(&out).await?;
+        stdout.flush().await?;
+    }
+    Ok(())
+}
+
+async fn dispatch(client: &DevToClient, hooks: &HooksConfig, request: RpcRequest) -> RpcResponse {
+    let id = request.id.clone().unwrap_or(Value::Null);
+    tracing::debug!(method = request.method, "rpc request");
+    let result = match request.method.as_str() {
+        "search" => search(request.params),
+        "get" => get(request.params),
+        "push" => push(client, hooks, request.params).await,
+        "publish" => publish(client, hooks, request.params).await,
+        method => Err(RpcFailure(METHOD_NOT_FOUND, format!("Method not found: {method}"))),
+    };
+    match result {
+        Ok(value) => RpcResponse::success(id, value),
+        Err(RpcFailure(code, message)) => RpcResponse::failure(id, code, message),
+    }
+}
+
+struct RpcFailure(i64, String);
+
+impl From<anyhow::Error> for RpcFailure {
+    fn from(e: anyhow::Error) -> Self {
+        RpcFailure(SERVER_ERROR, format!("{e:#}"))
+    }
+}
+
+fn parse_params<T: for<'de> Deserialize<'de>>(params: Value) -> Result<T, RpcFailure> {
+    serde_json::from_value(params).map_err(|e| RpcFailure(INVALID_PARAMS, format!("Invalid params: {e}")))
+}
+
+/// An article id, or any reference `resolve_article` understands.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ArticleRef {
+    Id(u64),
+    Reference(String),
+}
+
+impl ArticleRef {
+    fn resolve(&self) -> Result<u64> {
+        match self {
+            ArticleRef::Id(id) => Ok(*id),
+            ArticleRef::Reference(reference) => {
+                let articles = load_articles_cache()?;
+                Ok(resolve_article(&articles, reference, &[])?.id)
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SearchParams {
+    query: String,
+}
+
+#[derive(Deserialize)]
+struct GetParams {
+    id: ArticleRef,
+}
+
+#[derive(Deserialize)]
+struct PushParams {
+    id: ArticleRef,
+    markdown: String,
+}
+
+#[derive(Deserialize)]
+struct PublishParams {
+    id: ArticleRef,
+    #[serde(default)]
+    force: bool,
+    #[serde(default)]
+    markdown: bool,
+}
+
+fn search(params: Value) -> Result<Value, RpcFailure> {
+    let params: SearchParams = parse_params(params)?;
+    let articles = load_articles_cache()?;
+    Ok(json!(search_summaries(&articles, &params.query)))
+}
+
+fn get(params: Value) -> Result<Value, RpcFailure> {
+    let params: GetParams = parse_params(params)?;
+    let id = params.id.resolve()?;
+    let articles = load_articles_cache()?;
+    let article = articles
+        .iter()
+        .find(|article| article.id == id)
+        .with_context(|| format!("Article {id} not found in the cache"))?;
+    Ok(json!(article))
+}
+
+async fn push(client: &DevToClient, hooks: &HooksConfig, params: Value) -> Result<Value, RpcFailure> {
+    let params: PushParams = parse_params(params)?;
+    let id = params.id.resolve()?;
+    let original = client.get_my_article(id).await?;
+    let input = document_to_input(&params.markdown, has_embedded_front_matter(&original));
+    let updated = client.update_article(id, &input).await.context("Upload failed")?;
+    hooks::fire(hooks, HookEvent::Push, &[&updated]).await;
+    let summary = DraftSummary::from(&updated);
+    super::history::pushed(client, &[&updated]);
+    super::cache::upsert(client, updated)?;
+    Ok(json!(summary))
+}
+
+async fn publish(client: &DevToClient, hooks: &HooksConfig, params: Value) -> Result<Value, RpcFailure> {
+    let params: PublishParams = parse_params(params)?;
+    let id = params.id.resolve()?;
+    let article = client.get_my_article(id).await?;
+    if article.published {
+        return Err(RpcFailure(SERVER_ERROR, format!("{} is already published: {}", article.title, article.url)));
+    }
+    let issues = super::publish::checks(&article, params.markdown).await?;
+    let errors: Vec<String> = issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .map(|issue| format!("[{}] {}", issue.rule, issue.message))
+        .collect();
+    if !errors.is_empty() && !params.force {
+        return Err(RpcFailure(
+            SERVER_ERROR,
+            format!("{} problem(s) must be fixed before publishing: {}", errors.len(), errors.join("; ")),
+        ));
+    }
+    let published = client
+        .update_article(id, &super::publish::publish_input(&article))
+        .await
+        .context("Failed to publish")?;
+    super::undo::record("publish", vec![JournalChange::updated(article, &published)]);
+    hooks::fire(hooks, HookEvent::Publish, &[&published]).await;
+    let summary = DraftSummary::from(&published);
+    super::history::pushed(client, &[&published]);
+    super::cache::upsert(client, published)?;
+    Ok(json!(summary))
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::config;
+use dtdrafts_core::filter::parse_search;
+use dtdrafts_core::saved::{self, ResultChange, SavedResults};
+use dtdrafts_core::*;
+
+use super::remind::send_notification;
+
+/// Stores `expression` under `name` in the config file, replacing any search
+/// saved under that name.
+pub fn add(name: &str, expression: &str, quiet: bool) -> Result<()> {
+    saved::validate_name(name)?;
+    parse_search(expression)?;
+    let mut replaced = false;
+    config::update_file(|file| replaced = file.saved.insert(name.to_string(), expression.to_string()).is_some())
+        .context("Failed to save the search")?;
+    if !quiet {
+        let verb = if replaced { "Updated" } else { "Saved" };
+        println!("{} {} {}", format!("{verb} search").green(), name.cyan().bold(), expression.dimmed());
+    }
+    Ok(())
+}
+
+pub fn remove(searches: &BTreeMap<String, String>, name: &str) -> Result<()> {
+    if !searches.contains_key(name) {
+        return Err(unknown(searches, name));
+    }
+    config::update_file(|file| {
+        file.saved.remove(name);
+    })
+    .context("Failed to remove the search")?;
+    let mut results = SavedResults::load()?;
+    results.remove(name);
+    results.save()?;
+    println!("{} {}", "Removed search".green(), name.cyan().bold());
+    Ok(())
+}
+
+/// Prints the saved searches unless `quiet` and returns how many there are.
+pub fn list(searches: &BTreeMap<String, String>, quiet: bool) -> Result<usize> {
+    if quiet {
+        return Ok(searches.len());
+    }
+    if searches.is_empty() {
+        println!("{}", "No saved searches yet; add one with `dtdrafts saved add NAME QUERY`.".yellow());
+    }
+    let width = searches.keys().map(String::len).max().unwrap_or_default();
+    for (name, expression) in searches {
+        println!("  {}  {expression}", format!("{name:<width$}").cyan());
+    }
+    Ok(searches.len())
+}
+
+/// The expression saved under `name`.
+pub fn expression<'a>(searches: &'a BTreeMap<String, String>, name: &str) -> Result<&'a str> {
+    searches.get(name).map(String::as_str).ok_or_else(|| unknown(searches, name))
+}
+
+fn unknown(searches: &BTreeMap<String, String>, name: &str) -> anyhow::Error {
+    let names: Vec<&str> = searches.keys().map(String::as_str).collect();
+    if names.is_empty() {
+        anyhow::anyhow!("No saved search named '{name}'. There are no saved searches yet")
+    } else {
+        anyhow::anyhow!("No saved search named '{name}'. Saved searches: {}", names.join(", "))
+    }
+}
+
+/// Records what a saved search found and reports the change since its
+/// previous run, with a desktop notification when `notify` is set and
+/// something changed.
+pub fn report(name: &str, found: &[&Article], notify: bool, quiet: bool) -> Result<Option<ResultChange>> {
+    let mut results = SavedResults::load()?;
+    let change = results.update(name, found);
+    results.save()?;
+    let Some(change) = change.filter(|change| !change.is_empty()) else {
+        return Ok(None);
+    };
+    if !quiet {
+        println!(
+            "{} {} new, {} gone since the last run",
+            format!("{name}:").cyan().bold(),
+            change.added.len().to_string().green(),
+            change.removed.len().to_string().red()
+        );
+    }
+    if notify {
+        let (summary, body) = saved::notification_text(name, &change, found);
+        send_notification(&summary, &body)?;
+    }
+    Ok(Some(change))
+}
+
+/// Runs every saved search against `articles`, reporting the ones whose
+/// results changed; used by `daemon --saved` after each refresh.
+pub fn check_all(searches: &BTreeMap<String, String>, articles: &[Article], notify: bool, quiet: bool) -> Result<usize> {
+    let mut changed = 0;
+    for (name, expression) in searches {
+        let found = saved::run(articles, expression).with_context(|| format!("Saved search '{name}' is invalid"))?;
+        if report(name, &found, notify, quiet)?.is_some() {
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use colored::*;
+use dtdrafts_core::hooks::HooksConfig;
+use dtdrafts_core::schedule::{Schedule, ScheduledPublish};
+use dtdrafts_core::*;
+
+pub async fn add(client: &DevToClient, id: u64, at: DateTime<Utc>) -> Result<()> {
+    if at <= Utc::now() {
+        return Err(anyhow::anyhow!("{} is in the past", local(at)));
+    }
+    let article = client.get_my_article(id).await?;
+    if article.published {
+        return Err(anyhow::anyhow!("Article {id} is already published"));
+    }
+    let mut schedule = Schedule::load()?;
+    schedule.add(ScheduledPublish { id, title: article.title.clone(), at, last_error: None });
+    schedule.save().context("Failed to save the schedule")?;
+    println!("{} {} for {}", "Scheduled".green(), article.title.cyan().bold(), local(at));
+    println!("Run `dtdrafts scheduler run` (or `--once` from cron) to publish it when it is due.");
+    Ok(())
+}
+
+pub fn list(quiet: bool) -> Result<usize> {
+    let schedule = Schedule::load()?;
+    if quiet {
+        return Ok(schedule.entries.len());
+    }
+    if schedule.entries.is_empty() {
+        println!("{}", "Nothing scheduled.".yellow());
+        return Ok(0);
+    }
+    let now = Utc::now();
+    for entry in &schedule.entries {
+        let due = if entry.at <= now { " (due)".yellow().to_string() } else { String::new() };
+        println!("{}  {} {}{due}", local(entry.at), entry.title.cyan(), format!("({})", entry.id).dimmed());
+        if let Some(error) = &entry.last_error {
+            println!("      {} {error}", "last attempt failed:".red());
+        }
+    }
+    Ok(schedule.entries.len())
+}
+
+pub fn cancel(id: u64) -> Result<()> {
+    let mut schedule = Schedule::load()?;
+    let entry = schedule
+        .cancel(id)
+        .with_context(|| format!("Article {id} is not scheduled"))?;
+    schedule.save().context("Failed to save the schedule")?;
+    println!("{} {} (was due {})", "Cancelled".green(), entry.title.cyan(), local(entry.at));
+    Ok(())
+}
+
+/// Publishes due drafts. Runs forever, checking every `interval`, unless
+/// `once` is set (for cron).
+pub async fn run(client: &DevToClient, hooks: &HooksConfig, once: bool, interval: Duration) -> Result<()> {
+    if !once {
+        println!("Scheduler running, checking every {}s. Press Ctrl-C to stop.", interval.as_secs());
+    }
+    loop {
+        publish_due(client, hooks).await?;
+        if once {
+            return Ok(());
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+async fn publish_due(client: &DevToClient, hooks: &HooksConfig) -> Result<()> {
+    let mut schedule = Schedule::load()?;
+    let due = schedule.due(Utc::now());
+    if due.is_empty() {
+        return Ok(());
+    }
+    for entry in due {
+        println!("{} {} (due {})", "Publishing".blue(), entry.title.cyan(), local(entry.at));
+        match super::publish::run(client, hooks, entry.id, Default::default()).await {
+            Ok(()) => {
+                schedule.cancel(entry.id);
+            }
+            Err(e) => {
+                println!("{} {e:#}", "Failed:".red().bold());
+                if let Some(pending) = schedule.entries.iter_mut().find(|pending| pending.id == entry.id) {
+                    pending.last_error = Some(format!("{e:#}"));
+                }
+            }
+        }
+    }
+    schedule.save().context("Failed to save the schedule")
+}
+
+fn local(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local).format("%Y-%m-%d %H:%M %Z").to_string()
+}
@@ -0,0 +1,87 @@
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::*;
+
+const STEPS: usize = 4;
+
+pub async fn run(config: &Config, live: bool) -> Result<()> {
+    let client = DevToClient::from_config(config)?;
+    if !live {
+        println!("{}", "selftest is a live check and only runs with --live.".yellow());
+        println!("It will, against {}:", client.base_url());
+        println!("  1. create an unpublished throwaway draft");
+        println!("  2. update its body");
+        println!("  3. fetch your drafts and search for it");
+        println!("  4. delete it again");
+        println!();
+        println!("Run: dtdrafts selftest --live");
+        return Ok(());
+    }
+
+    let marker = format!("dtdrafts-selftest-{}", chrono::Utc::now().timestamp());
+    println!("Running live selftest against {}", client.base_url().cyan());
+
+    step(1, "Create draft");
+    let created = client
+        .create_article(&ArticleInput {
+            title: Some(format!("[dtdrafts selftest] {marker} (safe to delete)")),
+            body_markdown: Some("Temporary draft created by `dtdrafts selftest`.".to_string()),
+            published: Some(false),
+            ..Default::default()
+        })
+        .await
+        .context("selftest failed while creating a draft")?;
+    ok(&format!("id {}", created.id));
+
+    let result = verify(&client, created.id, &marker).await;
+
+    step(4, "Delete draft");
+    match client.delete_article(created.id).await {
+        Ok(()) => ok("deleted"),
+        Err(e) => {
+            println!("{}", "not supported".yellow());
+            println!("  {e}");
+            println!(
+                "  Remove it manually: https://dev.to/{}/{}/edit",
+                created.user.username, created.slug
+            );
+        }
+    }
+
+    result?;
+    println!("{}", "Selftest passed: the write path works with your API key.".green().bold());
+    Ok(())
+}
+
+async fn verify(client: &DevToClient, id: u64, marker: &str) -> Result<()> {
+    step(2, "Update draft");
+    let body = format!("Updated by dtdrafts selftest: {marker}-updated");
+    client
+        .update_article(id, &ArticleInput {
+            body_markdown: Some(body),
+            ..Default::default()
+        })
+        .await
+        .context("selftest failed while updating the draft")?;
+    ok("updated");
+
+    step(3, "Search for draft");
+    let articles = client.get_my_articles().await?;
+    let found = search_articles(&articles, &format!("{marker}-updated"));
+    if !found.iter().any(|article| article.id == id) {
+        println!("{}", "failed".red());
+        return Err(anyhow::anyhow!(
+            "selftest draft {id} was not found with its updated body in your drafts"
+        ));
+    }
+    ok("found");
+    Ok(())
+}
+
+fn step(n: usize, label: &str) {
+    println!("[{n}/{STEPS}] {label}...");
+}
+
+fn ok(detail: &str) {
+    println!("      {} {detail}", "ok".green());
+}
@@ -0,0 +1,49 @@
+use anyhow::Result;
+use colored::*;
+use dtdrafts_core::seo::{self, SeoReport};
+use dtdrafts_core::*;
+
+pub fn run(article: &Article, keyword: Option<&str>, json: bool) -> Result<()> {
+    let report = seo::analyze(article, keyword);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    print_report(&report);
+    Ok(())
+}
+
+fn print_report(report: &SeoReport) {
+    println!("{} {}", report.title.cyan().bold(), format!("({})", report.id).dimmed());
+    println!("  Title:        {} characters", report.title_chars);
+    match &report.description {
+        Some(description) => println!("  Description:  {description}"),
+        None => println!("  Description:  {}", "none".dimmed()),
+    }
+    if let Some(keyword) = &report.keyword {
+        println!("  Keyword:      \"{}\" {}× ({:.1}% of words)", keyword.phrase, keyword.occurrences, keyword.density);
+    }
+    let readability = &report.readability;
+    println!(
+        "  Reading ease: {:.0} ({}), {} words, {:.1} words per sentence",
+        readability.reading_ease,
+        readability.level(),
+        readability.words,
+        readability.words_per_sentence()
+    );
+
+    println!();
+    println!("{}", "Checklist:".bold());
+    for check in &report.checks {
+        let mark = if check.passed { "ok".green() } else { "!!".red().bold() };
+        println!("  {mark}  {}", check.message);
+    }
+    let passed = report.checks.iter().filter(|check| check.passed).count();
+    let summary = format!("{passed} of {} checks passed", report.checks.len());
+    println!();
+    if passed == report.checks.len() {
+        println!("{}", summary.green());
+    } else {
+        println!("{}", summary.yellow());
+    }
+}
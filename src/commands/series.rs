@@ -0,0 +1,49 @@
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::frontmatter::series_input;
+use dtdrafts_core::series::group_by_series;
+use dtdrafts_core::*;
+
+/// Prints each series with its parts unless `quiet` and returns the number of series.
+pub fn list(articles: &[&Article], quiet: bool, pager: bool) -> Result<usize> {
+    let groups = group_by_series(articles);
+    if quiet {
+        return Ok(groups.len());
+    }
+    if groups.is_empty() {
+        println!("{}", "No drafts belong to a series.".yellow());
+    }
+    let mut out = String::new();
+    for series in &groups {
+        out.push_str(&format!("{} ({})\n", series.name.magenta().bold(), series.articles.len()));
+        for (i, article) in series.articles.iter().enumerate() {
+            let state = if article.published { "published".green() } else { "draft".yellow() };
+            out.push_str(&format!(
+                "  {}. {}  {} {}\n",
+                i + 1,
+                article.title.cyan(),
+                state,
+                format!("({})", article.id).dimmed()
+            ));
+        }
+        out.push('\n');
+    }
+    super::pager::page(&out, pager)?;
+    Ok(groups.len())
+}
+
+/// Adds a draft to `series`, or removes it from its series with `None`.
+pub async fn set(client: &DevToClient, id: u64, series: Option<&str>) -> Result<()> {
+    let article = client.get_my_article(id).await?;
+    let updated = client
+        .update_article(id, &series_input(&article, series.unwrap_or_default()))
+        .await
+        .context("Failed to update the series")?;
+    match series {
+        Some(series) => println!("{} {} {}", "Added".green(), updated.title.cyan().bold(), format!("to {series}").green()),
+        None => println!("{} {} {}", "Removed".green(), updated.title.cyan().bold(), "from its series".green()),
+    }
+    super::history::pushed(client, &[&updated]);
+    super::cache::upsert(client, updated)?;
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::*;
+use dtdrafts_core::api::{search_summaries, summaries};
+use dtdrafts_core::feed::atom_feed;
+use dtdrafts_core::*;
+use serde_json::json;
+use tokio::net::TcpListener;
+
+use super::http::{self, Request, Response};
+
+pub async fn bind(port: u16) -> Result<TcpListener> {
+    TcpListener::bind(("127.0.0.1", port))
+        .await
+        .with_context(|| format!("Failed to listen on port {port}"))
+}
+
+pub async fn run(port: u16) -> Result<()> {
+    let listener = bind(port).await?;
+    println!("{} http://127.0.0.1:{port}/drafts", "Serving drafts at".green());
+    println!("Press Ctrl-C to stop.");
+    serve(listener).await
+}
+
+/// Answers the JSON endpoints from the article cache, re-read on every request
+/// so a running `daemon` (or any refresh) shows up immediately.
+pub async fn serve(listener: TcpListener) -> Result<()> {
+    http::serve(listener, |request| async move { respond(request) }).await
+}
+
+fn respond(request: Request) -> Response {
+    if request.method != "GET" {
+        return json_error(405, "Method not allowed");
+    }
+    let articles = match load_articles_cache() {
+        Ok(articles) => articles,
+        Err(e) => return json_error(500, &format!("{e:#}")),
+    };
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    match segments.as_slice() {
+        ["drafts"] => {
+            let mut filter = ArticleFilter::drafts();
+            for (_, tag) in request.query.iter().filter(|(key, _)| key == "tag") {
+                filter = filter.tag(tag);
+            }
+            Response::json(&summaries(&filter_articles(&articles, &filter)))
+        }
+        ["drafts.atom"] => {
+            let drafts = filter_articles(&articles, &ArticleFilter::drafts());
+            Response::new(200, "application/atom+xml; charset=utf-8", atom_feed(&drafts, Utc::now()))
+        }
+        ["drafts", "search"] => match request.query_param("q") {
+            Some(query) if !query.trim().is_empty() => Response::json(&search_summaries(&articles, query)),
+            _ => json_error(400, "Missing query parameter q"),
+        },
+        ["drafts", reference] => match resolve_article(&articles, reference, &[]) {
+            Ok(article) => Response::json(article),
+            Err(e) => json_error(404, &format!("{e:#}")),
+        },
+        _ => json_error(404, "Not found"),
+    }
+}
+
+fn json_error(status: u16, message: &str) -> Response {
+    Response::new(status, "application/json", json!({ "error": message }).to_string())
+}
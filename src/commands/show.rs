@@ -0,0 +1,44 @@
+use colored::*;
+use dtdrafts_core::frontmatter::{draft_document, parse_document};
+use dtdrafts_core::notes::Note;
+use dtdrafts_core::terminal::render_markdown;
+use dtdrafts_core::*;
+
+// Raw output is meant to be piped or saved, so notes go to stderr there
+pub fn run(article: &Article, render: bool, notes: &[Note]) {
+    if !render {
+        print!("{}", draft_document(article));
+        if !notes.is_empty() {
+            eprint!("\n{}\n{}", "Notes:".yellow().bold(), super::note::format_notes(notes));
+        }
+        return;
+    }
+    println!("{}", article.title.bold().underline());
+    let tags = article.tags.as_deref().unwrap_or_default();
+    if !tags.is_empty() {
+        let tags: Vec<String> = tags.iter().map(|tag| format!("#{tag}")).collect();
+        println!("{}", tags.join(" ").dimmed());
+    }
+    let mut details = Vec::new();
+    if let Some(minutes) = article.reading_time_minutes {
+        details.push(format!("{minutes} min read"));
+    }
+    if article.comments_count > 0 {
+        details.push(format!("{} comments", article.comments_count));
+    }
+    if article.public_reactions_count > 0 {
+        details.push(format!("{} reactions", article.public_reactions_count));
+    }
+    if !details.is_empty() {
+        println!("{}", details.join(" · ").dimmed());
+    }
+    if let Some(cover_image) = &article.cover_image {
+        println!("{} {}", "Cover image:".dimmed(), cover_image);
+    }
+    println!();
+    let (_, body) = parse_document(article.body_markdown.as_deref().unwrap_or_default());
+    print!("{}", render_markdown(body));
+    if !notes.is_empty() {
+        print!("\n{}\n{}", "Notes:".yellow().bold(), super::note::format_notes(notes));
+    }
+}
@@ -0,0 +1,94 @@
+use std::io::IsTerminal;
+
+use anyhow::{Context, Result};
+use colored::*;
+use dialoguer::{Input, Select};
+use dtdrafts_core::split::{part_input, plan_split, SplitLevel, SplitPart};
+use dtdrafts_core::*;
+
+pub struct SplitOptions {
+    pub level: SplitLevel,
+    /// Defaults to the draft's series, or else its title
+    pub series: Option<String>,
+    pub dry_run: bool,
+    pub yes: bool,
+}
+
+/// Proposes new drafts, one per section of `article`, lets the parts be
+/// renamed, and creates them in a series once confirmed. The original
+/// draft is left as it is.
+pub async fn run(client: &DevToClient, article: &Article, options: &SplitOptions) -> Result<()> {
+    let mut parts = plan_split(article.body_markdown.as_deref().unwrap_or_default(), options.level);
+    if parts.len() < 2 {
+        return Err(anyhow::anyhow!(
+            "{} has fewer than two sections at h{}; try a deeper level with --by",
+            article.title,
+            options.level.0
+        ));
+    }
+    let series = options.series.clone().or_else(|| article.series()).unwrap_or_else(|| article.title.clone());
+
+    preview(&parts, &series);
+    if options.dry_run {
+        println!("{}", "Dry run: nothing was created.".yellow());
+        return Ok(());
+    }
+    if !options.yes && !review(&mut parts)? {
+        println!("{}", "Cancelled.".yellow());
+        return Ok(());
+    }
+
+    let mut created = Vec::new();
+    for (index, part) in parts.iter().enumerate() {
+        let progress = format!("[{}/{}]", index + 1, parts.len()).dimmed();
+        let draft = client
+            .create_article(&part_input(article, part, &series))
+            .await
+            .with_context(|| format!("Failed to create {}; {} part(s) were created before it", part.title, created.len()))?;
+        println!("  {progress} {} {} {}", "created".green(), draft.title, format!("({})", draft.id).dimmed());
+        created.push(draft);
+    }
+    super::history::pushed(client, &created.iter().collect::<Vec<_>>());
+    super::cache::update(client, created, &[])?;
+    println!(
+        "{} {} is unchanged; delete it with `dtdrafts bulk --filter id:{} --action delete` once the parts look right.",
+        "Done.".green(),
+        article.title.cyan(),
+        article.id
+    );
+    Ok(())
+}
+
+fn preview(parts: &[SplitPart], series: &str) {
+    println!("{} {} draft(s) in the series {}", "Split into".bold(), parts.len(), series.cyan());
+    for (index, part) in parts.iter().enumerate() {
+        println!("  {:>2}. {}  {}", index + 1, part.title, format!("{} words", part.words()).dimmed());
+    }
+}
+
+// Asks until the parts are confirmed (true) or the split is cancelled
+fn review(parts: &mut [SplitPart]) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow::anyhow!("Not asking for confirmation without a terminal; pass --yes to create the drafts"));
+    }
+    loop {
+        let choices = [format!("Create {} drafts", parts.len()), "Rename a part".to_string(), "Cancel".to_string()];
+        match Select::new().with_prompt("Split the draft?").items(&choices).default(0).interact_opt()? {
+            Some(0) => return Ok(true),
+            Some(1) => {
+                let titles: Vec<&str> = parts.iter().map(|part| part.title.as_str()).collect();
+                let Some(index) = Select::new().with_prompt("Which part?").items(&titles).default(0).interact_opt()? else {
+                    continue;
+                };
+                let title: String = Input::new().with_prompt("Title").with_initial_text(&parts[index].title).interact_text()?;
+                if !title.trim().is_empty() {
+                    parts[index].title = title.trim().to_string();
+                }
+                for (number, part) in parts.iter().enumerate() {
+                    println!("  {:>2}. {}", number + 1, part.title);
+                }
+            }
+            _ => return Ok(false),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use colored::*;
+use dtdrafts_core::*;
+
+/// Prints the report (paged, see [`super::pager::page`]) unless `quiet` and
+/// returns the number of stale drafts.
+pub fn run(articles: &[Article], older_than: Duration, quiet: bool, pager: bool) -> Result<usize> {
+    let report = stale_report(articles, older_than, Utc::now());
+    let total = report.total();
+    if quiet {
+        return Ok(total);
+    }
+    let mut out = String::new();
+    if total == 0 {
+        out.push_str(&format!(
+            "{}\n",
+            format!("No drafts untouched for {} days or more.", report.older_than_days).green()
+        ));
+    } else {
+        out.push_str(&format!(
+            "{} draft(s) not updated in {} days or more:\n\n",
+            total.to_string().yellow().bold(),
+            report.older_than_days
+        ));
+    }
+
+    for bucket in report.buckets.iter().filter(|bucket| !bucket.articles.is_empty()) {
+        out.push_str(&format!("{} ({})\n", bucket.label().magenta().bold(), bucket.articles.len()));
+        for article in &bucket.articles {
+            let updated = stale::last_touched(article)
+                .map(|at| at.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            out.push_str(&format!("  {}  {}\n", updated.dimmed(), article.title.cyan()));
+            out.push_str(&format!("              {}\n", article.edit_url().blue().underline()));
+        }
+        out.push('\n');
+    }
+
+    if total > 0 {
+        out.push_str(&format!("{}\n", "Totals:".bold()));
+        for bucket in &report.buckets {
+            out.push_str(&format!("  {:>10}  {}\n", bucket.label(), bucket.articles.len()));
+        }
+    }
+    if !report.undated.is_empty() {
+        out.push_str(&format!(
+            "{}\n",
+            format!("{} draft(s) have no timestamps and were skipped.", report.undated.len()).dimmed()
+        ));
+    }
+    super::pager::page(&out, pager)?;
+    Ok(total)
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use colored::*;
+use dtdrafts_core::stats::{readability_trend, sparkline, ReadabilityStats};
+use dtdrafts_core::text::WordCountOptions;
+use dtdrafts_core::*;
+
+const TOP_TAGS: usize = 10;
+
+pub fn run(articles: &[&Article], options: &WordCountOptions, json: bool, readability: bool) -> Result<()> {
+    let mut summary = compute_stats(articles, options);
+    if readability {
+        summary.readability = readability_trend(articles);
+    }
+    if json {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(());
+    }
+    if summary.drafts == 0 {
+        println!("{}", "No draft articles found.".yellow());
+        return Ok(());
+    }
+
+    let title_width = summary
+        .articles
+        .iter()
+        .map(|stats| stats.title.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(5, 60);
+    println!(
+        "{}",
+        format!("{:>10}  {:>7}  {:>5}  {:<title_width$}", "ID", "Words", "Read", "Title").bold()
+    );
+    for stats in &summary.articles {
+        println!(
+            "{:>10}  {:>7}  {:>4}m  {}",
+            stats.id,
+            stats.words,
+            stats.reading_time_minutes,
+            truncate(&stats.title, title_width).cyan()
+        );
+    }
+
+    println!();
+    println!("{}", "Totals:".bold());
+    println!("  Drafts:        {}", summary.drafts.to_string().green().bold());
+    println!("  Words:         {}", summary.total_words);
+    println!("  Average words: {}", summary.average_words);
+    println!("  Reading time:  {} min", summary.total_reading_time_minutes);
+    println!("  Cover images:  {} of {}", summary.with_cover_image, summary.drafts);
+    if summary.total_comments > 0 || summary.total_reactions > 0 {
+        println!("  Comments:      {}", summary.total_comments);
+        println!("  Reactions:     {}", summary.total_reactions);
+    }
+
+    if !summary.tags.is_empty() {
+        println!();
+        println!("{}", "Top tags:".bold());
+        for tag in summary.tags.iter().take(TOP_TAGS) {
+            println!("  {:>4}  #{}", tag.count, tag.tag);
+        }
+    }
+
+    if readability {
+        print_readability(&summary.readability, title_width);
+    }
+    Ok(())
+}
+
+fn print_readability(stats: &[ReadabilityStats], title_width: usize) {
+    println!();
+    println!("{}", "Readability (oldest first):".bold());
+    if stats.is_empty() {
+        println!("  {}", "No drafts with prose yet.".yellow());
+        return;
+    }
+    println!(
+        "{}",
+        format!("{:>10}  {:>5}  {:<16}  {:>9}  {:>9}  {:<title_width$}", "ID", "Ease", "Level", "Words/snt", "Weasel/1k", "Title")
+            .bold()
+    );
+    for draft in stats {
+        println!(
+            "{:>10}  {:>5.1}  {:<16}  {:>9.1}  {:>9.1}  {}",
+            draft.id,
+            draft.reading_ease,
+            draft.level,
+            draft.words_per_sentence,
+            draft.weasel_words_per_1000,
+            truncate(&draft.title, title_width).cyan()
+        );
+    }
+    if stats.len() < 2 {
+        return;
+    }
+    println!();
+    println!("{}", "Trend:".bold());
+    let trend = |label: &str, values: Vec<f64>| {
+        let (first, last) = (values[0], values[values.len() - 1]);
+        println!("  {label:<16} {}  {first:.1} → {last:.1}", sparkline(&values).green());
+    };
+    trend("Reading ease", stats.iter().map(|draft| draft.reading_ease).collect());
+    trend("Words/sentence", stats.iter().map(|draft| draft.words_per_sentence).collect());
+    trend("Weasel words/1k", stats.iter().map(|draft| draft.weasel_words_per_1000).collect());
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
@@ -0,0 +1,57 @@
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::status::{board, StatusStore, WorkflowStatus};
+use dtdrafts_core::*;
+
+pub fn set(article: &Article, status: WorkflowStatus) -> Result<()> {
+    let mut store = StatusStore::load()?;
+    store.set(article.id, status);
+    store.save().context("Failed to save statuses")?;
+    println!("{} {} {}", "Marked".green(), article.title.cyan().bold(), format!("as {status}").green());
+    Ok(())
+}
+
+pub fn clear(article: &Article) -> Result<()> {
+    let mut store = StatusStore::load()?;
+    match store.clear(article.id) {
+        Some(status) => {
+            store.save().context("Failed to save statuses")?;
+            println!("{} {} {}", "Cleared".green(), article.title.cyan().bold(), format!("(was {status})").dimmed());
+        }
+        None => println!("{}", format!("{} has no status", article.title).yellow()),
+    }
+    Ok(())
+}
+
+/// Prints the drafts grouped by status and returns how many have one.
+pub fn print_board(drafts: &[&Article], quiet: bool, pager: bool) -> Result<usize> {
+    let store = StatusStore::load()?;
+    let columns = board(drafts, &store);
+    let with_status = columns
+        .iter()
+        .filter(|column| column.status.is_some())
+        .map(|column| column.articles.len())
+        .sum();
+    if quiet {
+        return Ok(with_status);
+    }
+    let mut out = String::new();
+    for column in &columns {
+        let name = column.status.map(|status| status.to_string()).unwrap_or_else(|| "no status".to_string());
+        let heading = format!("{} ({})", name.to_uppercase(), column.articles.len());
+        let heading = match column.status {
+            Some(WorkflowStatus::Idea) => heading.blue(),
+            Some(WorkflowStatus::Writing) => heading.yellow(),
+            Some(WorkflowStatus::Review) => heading.magenta(),
+            Some(WorkflowStatus::Ready) => heading.green(),
+            None => heading.dimmed(),
+        };
+        out.push_str(&format!("{}\n", heading.bold()));
+        for article in &column.articles {
+            out.push_str(&format!("  {} {}\n", article.title.cyan(), format!("({})", article.id).dimmed()));
+        }
+        out.push('\n');
+    }
+    super::pager::page(&out, pager)?;
+    Ok(with_status)
+}
@@ -0,0 +1,239 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::frontmatter::{document_to_input, draft_document, has_embedded_front_matter, FrontMatterMapping, FrontMatterStyle};
+use dtdrafts_core::cache::CacheFormat;
+use dtdrafts_core::hooks::{self, HookEvent, HooksConfig};
+use dtdrafts_core::sync::{
+    classify, draft_file_name, has_conflict_markers, merge_documents, MergeOutcome, SyncAction, SyncState,
+    SyncStrategy, SyncedDraft,
+};
+use dtdrafts_core::*;
+
+const STATE_FILE: &str = "dtdrafts-sync.json";
+
+/// How `sync` writes and reads the files in the repository.
+#[derive(Debug)]
+pub struct SyncOptions<'a> {
+    pub push: bool,
+    pub strategy: Option<SyncStrategy>,
+    /// Front matter style, or the one used last time
+    pub frontmatter: Option<FrontMatterStyle>,
+    /// The mapping for [`FrontMatterStyle::Custom`]
+    pub custom: &'a FrontMatterMapping,
+    /// Format the fetched drafts are cached in
+    pub cache_format: CacheFormat,
+}
+
+/// Mirrors drafts into a git working tree, one commit per changed draft.
+/// With `push`, drafts edited locally are uploaded. Drafts edited on both
+/// sides since the last sync are reported and left alone unless a
+/// `strategy` says how to resolve them.
+#[tracing::instrument(skip(client, hooks))]
+pub async fn run(client: &DevToClient, hooks: &HooksConfig, repo: &Path, options: &SyncOptions<'_>) -> Result<()> {
+    let (push, strategy) = (options.push, options.strategy);
+    ensure_repo(repo)?;
+    // Kept inside .git so it never shows up as a change in the working tree
+    let state_path = git_dir(repo)?.join(STATE_FILE);
+    let mut state = SyncState::load(&state_path)?;
+    // Files written in the previous style are unchanged locally, so they are pulled again in the new one
+    let style = options.frontmatter.or(state.frontmatter).unwrap_or_default();
+    state.frontmatter = (style != FrontMatterStyle::DevTo).then_some(style);
+    let mapping = style.mapping(options.custom);
+
+    println!("{}", "Fetching drafts from dev.to...".blue());
+    let articles = client.get_my_articles().await?;
+    super::cache::save(client, &articles, options.cache_format)?;
+    super::history::pulled(client, &articles.iter().collect::<Vec<_>>());
+    let drafts = get_draft_articles(&articles);
+
+    let (mut changed, mut pending, mut conflicts) = (0, 0, 0);
+    for article in &drafts {
+        let remote = mapping.to_local(&draft_document(article));
+        let synced = state.drafts.get(&article.id);
+        let file = synced.map_or_else(|| draft_file_name(article), |synced| synced.file.clone());
+        let path = repo.join(&file);
+        let local = fs::read_to_string(&path).ok();
+        let base = synced.map(|synced| synced.base.clone());
+        let title = &article.title;
+
+        let mut action = classify(base.as_deref(), local.as_deref(), &remote);
+        tracing::debug!(id = article.id, file, ?action, "classified draft");
+        if action == SyncAction::Conflict {
+            match strategy {
+                None => {
+                    println!("  {} {file} changed locally and on dev.to since the last sync", "conflict".red().bold());
+                    conflicts += 1;
+                    continue;
+                }
+                Some(SyncStrategy::Theirs) => action = SyncAction::Pull,
+                Some(SyncStrategy::Ours) if !push => {
+                    // Remember dev.to's version as the base so the next sync pushes ours
+                    println!("  {} {file} kept local version (run with --push to upload)", "ours  ".yellow());
+                    pending += 1;
+                    state.drafts.insert(article.id, SyncedDraft { file, base: remote });
+                    continue;
+                }
+                Some(SyncStrategy::Ours) => action = SyncAction::Push,
+                Some(SyncStrategy::Merge) => {
+                    let local = local.clone().unwrap_or_default();
+                    let base = base.clone().unwrap_or_default();
+                    match merge_documents(&base, &local, &remote) {
+                        MergeOutcome::Clean(merged) => {
+                            write(&path, &merged)?;
+                            commit(repo, &file, &format!("Merge dev.to changes into \"{title}\""))?;
+                            println!("  {} {file}", "merged".green());
+                            changed += 1;
+                            let new_base = if push {
+                                upload(client, hooks, article, repo, &file, &mapping.to_devto(&merged)).await?;
+                                merged
+                            } else {
+                                pending += 1;
+                                remote
+                            };
+                            state.drafts.insert(article.id, SyncedDraft { file, base: new_base });
+                        }
+                        MergeOutcome::Conflicted(marked) => {
+                            write(&path, &marked)?;
+                            println!(
+                                "  {} {file} has overlapping edits; conflict markers written, resolve them and sync --push",
+                                "conflict".red().bold()
+                            );
+                            conflicts += 1;
+                            state.drafts.insert(article.id, SyncedDraft { file, base: remote });
+                        }
+                    }
+                    continue;
+                }
+            }
+        }
+
+        let new_base = match action {
+            SyncAction::Unchanged => remote,
+            SyncAction::Conflict => unreachable!("conflicts are resolved or skipped above"),
+            SyncAction::Add | SyncAction::Pull => {
+                let added = local.is_none();
+                write(&path, &remote)?;
+                let message = if added {
+                    format!("Add draft \"{title}\" ({})", article.id)
+                } else {
+                    format!("Update \"{title}\" from dev.to")
+                };
+                commit(repo, &file, &message)?;
+                println!("  {} {file}", if added { "added ".green() } else { "pulled".green() });
+                changed += 1;
+                remote
+            }
+            SyncAction::Push if has_conflict_markers(local.as_deref().unwrap_or_default()) => {
+                println!("  {} {file} still contains conflict markers, not pushing", "skip  ".yellow());
+                pending += 1;
+                continue;
+            }
+            SyncAction::Push if push => {
+                let local = local.unwrap_or_default();
+                upload(client, hooks, article, repo, &file, &mapping.to_devto(&local)).await?;
+                changed += 1;
+                local
+            }
+            SyncAction::Push => {
+                println!("  {} {file} (run with --push to upload)", "local ".yellow());
+                pending += 1;
+                continue;
+            }
+        };
+        state.drafts.insert(article.id, SyncedDraft { file, base: new_base });
+    }
+
+    for (id, synced) in &state.drafts {
+        if !drafts.iter().any(|article| article.id == *id) {
+            println!("  {} {} is no longer a draft on dev.to", "gone  ".dimmed(), synced.file);
+        }
+    }
+    state.drafts.retain(|id, _| drafts.iter().any(|article| article.id == *id));
+    state.save(&state_path)?;
+
+    println!(
+        "Synced {} draft(s): {changed} changed, {pending} with unpushed local edits, {conflicts} conflict(s)",
+        drafts.len()
+    );
+    if conflicts > 0 && strategy.is_some() {
+        return Err(anyhow::anyhow!("{conflicts} draft(s) need their conflict markers resolved before --push"));
+    }
+    if conflicts > 0 {
+        return Err(anyhow::anyhow!(
+            "{conflicts} draft(s) changed on both sides; resolve them or re-run with --strategy ours|theirs|merge"
+        ));
+    }
+    Ok(())
+}
+
+/// The front matter style `sync` last used for `repo`, if it is a sync repository.
+pub fn saved_style(repo: &Path) -> Option<FrontMatterStyle> {
+    let state = SyncState::load(&git_dir(repo).ok()?.join(STATE_FILE)).ok()?;
+    state.frontmatter
+}
+
+async fn upload(
+    client: &DevToClient,
+    hooks: &HooksConfig,
+    article: &Article, repo: &Path, file: &str, document: &str) -> Result<()> {
+    let updated = client
+        .update_article(article.id, &document_to_input(document, has_embedded_front_matter(article)))
+        .await
+        .with_context(|| format!("Failed to push {file}"))?;
+    commit(repo, file, &format!("Edit \"{}\"", article.title))?;
+    hooks::fire(hooks, HookEvent::Push, &[&updated]).await;
+    super::history::pushed(client, &[&updated]);
+    super::cache::upsert(client, updated)?;
+    println!("  {} {file}", "pushed".green());
+    Ok(())
+}
+
+fn write(path: &Path, document: &str) -> Result<()> {
+    fs::write(path, document).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn git(repo: &Path, args: &[&str]) -> Result<String> {
+    tracing::debug!(repo = %repo.display(), args = args.join(" "), "running git");
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()
+        .context("Failed to run git (is it installed?)")?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn ensure_repo(repo: &Path) -> Result<()> {
+    fs::create_dir_all(repo).with_context(|| format!("Failed to create {}", repo.display()))?;
+    if git(repo, &["rev-parse", "--git-dir"]).is_err() {
+        git(repo, &["init", "-q"])?;
+        println!("Initialized a git repository in {}", repo.display());
+    }
+    Ok(())
+}
+
+fn git_dir(repo: &Path) -> Result<PathBuf> {
+    let dir = PathBuf::from(git(repo, &["rev-parse", "--git-dir"])?);
+    Ok(if dir.is_absolute() { dir } else { repo.join(dir) })
+}
+
+/// Commits `file` on its own, leaving anything else staged untouched.
+fn commit(repo: &Path, file: &str, message: &str) -> Result<()> {
+    if git(repo, &["status", "--porcelain", "--", file])?.is_empty() {
+        return Ok(());
+    }
+    git(repo, &["add", "--", file])?;
+    git(repo, &["commit", "-q", "-m", message, "--", file])?;
+    Ok(())
+}
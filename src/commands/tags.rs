@@ -0,0 +1,85 @@
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::frontmatter::tags_input;
+use dtdrafts_core::lint::{PublishMetadata, MAX_TAGS};
+use dtdrafts_core::tags::{popularity_rank, suggest_tags, POPULAR_TAG_LIMIT};
+use dtdrafts_core::*;
+
+pub async fn trending(client: &DevToClient, limit: usize) -> Result<()> {
+    let tags = client.get_popular_tags(limit).await?;
+    for (i, tag) in tags.iter().enumerate() {
+        println!("{:>4}. {}", i + 1, format!("#{}", tag.name).cyan());
+    }
+    Ok(())
+}
+
+pub async fn followed(client: &DevToClient) -> Result<()> {
+    let mut tags = client.get_followed_tags().await?;
+    if tags.is_empty() {
+        println!("{}", "You don't follow any tags.".yellow());
+        return Ok(());
+    }
+    tags.sort_by(|a, b| b.points.total_cmp(&a.points).then_with(|| a.name.cmp(&b.name)));
+    for tag in &tags {
+        println!("{:>6}  {}", format!("{:.1}", tag.points).dimmed(), format!("#{}", tag.name).cyan());
+    }
+    Ok(())
+}
+
+/// Popularity rank, whether it's followed and how often `articles` use it.
+pub async fn info(client: &DevToClient, articles: &[Article], tag: &str) -> Result<()> {
+    let tag = tag.trim_start_matches('#').to_lowercase();
+    let popular = client.get_popular_tags(POPULAR_TAG_LIMIT).await?;
+    let followed = client.get_followed_tags().await?;
+
+    println!("{}", format!("#{tag}").cyan().bold());
+    println!("  https://dev.to/t/{tag}");
+    match popularity_rank(&popular, &tag) {
+        Some(rank) => println!("  Popularity: #{rank} of the {} most popular tags", popular.len()),
+        None => println!(
+            "  Popularity: {}",
+            format!("not among the {} most popular tags (low usage)", popular.len()).yellow()
+        ),
+    }
+    match followed.iter().find(|followed| followed.name.eq_ignore_ascii_case(&tag)) {
+        Some(followed) => println!("  Followed: yes ({:.1} points)", followed.points),
+        None => println!("  Followed: no"),
+    }
+    let using: Vec<&Article> = articles
+        .iter()
+        .filter(|article| PublishMetadata::of(article).tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)))
+        .collect();
+    let drafts = using.iter().filter(|article| !article.published).count();
+    println!("  Your articles: {} draft(s), {} published", drafts, using.len() - drafts);
+    Ok(())
+}
+
+/// Prints up to four suggested tags for a draft and, with `apply`, replaces
+/// its tags with them.
+pub async fn suggest(client: &DevToClient, articles: &[Article], article: &Article, apply: bool) -> Result<()> {
+    let suggestions = suggest_tags(article, articles, MAX_TAGS);
+    if suggestions.is_empty() {
+        println!("{}", "No suggestions: none of your other articles are similar and tagged.".yellow());
+        return Ok(());
+    }
+    let current = PublishMetadata::of(article).tags;
+    println!("Suggested tags for {}:", article.title.cyan().bold());
+    for suggestion in &suggestions {
+        let note = if current.iter().any(|tag| tag.eq_ignore_ascii_case(&suggestion.tag)) { " (already set)" } else { "" };
+        println!("  {:<20} {}{}", format!("#{}", suggestion.tag), format!("{:.2}", suggestion.score).dimmed(), note.dimmed());
+    }
+    if !apply {
+        return Ok(());
+    }
+
+    let tags: Vec<String> = suggestions.into_iter().map(|suggestion| suggestion.tag).collect();
+    let latest = client.get_my_article(article.id).await?;
+    let updated = client
+        .update_article(article.id, &tags_input(&latest, &tags))
+        .await
+        .context("Failed to update the tags")?;
+    println!("{} {}", "Tagged".green(), tags.join(", "));
+    super::history::pushed(client, &[&updated]);
+    super::cache::upsert(client, updated)?;
+    Ok(())
+}
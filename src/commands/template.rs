@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use colored::*;
+use dtdrafts_core::frontmatter::parse_document;
+use dtdrafts_core::template::{
+    list_templates, load_template, render_template, save_template, template_exists, template_path,
+    DEFAULT_TEMPLATE,
+};
+use dtdrafts_core::*;
+
+use super::edit::open_editor;
+
+/// Creates a draft from a template. `{{title}}`, `{{date}}` and `{{series}}`
+/// are always defined; `vars` adds or overrides others.
+pub async fn new_draft(
+    client: &DevToClient,
+    title: &str,
+    template: Option<&str>,
+    series: Option<&str>,
+    vars: Vec<(String, String)>,
+    org: Option<&str>,
+) -> Result<()> {
+    let organization_id = match org {
+        Some(org) => Some(client.resolve_organization_id(org).await?),
+        None => None,
+    };
+    let template = match template {
+        Some(name) => load_template(name)?,
+        None => DEFAULT_TEMPLATE.to_string(),
+    };
+    let mut values = BTreeMap::from([
+        ("title".to_string(), title.to_string()),
+        ("date".to_string(), Local::now().format("%Y-%m-%d").to_string()),
+        ("series".to_string(), series.unwrap_or_default().to_string()),
+    ]);
+    values.extend(vars);
+    let document = render_template(&template, &values);
+
+    // Templates with front matter are sent whole so dev.to reads every key
+    let input = match parse_document(&document) {
+        (Some(mut front_matter), body) => {
+            front_matter.set("published", "false");
+            if front_matter.get("title").is_none_or(str::is_empty) {
+                front_matter.set("title", title);
+            }
+            if let Some(series) = series.filter(|_| front_matter.get("series").is_none_or(str::is_empty)) {
+                front_matter.set("series", series);
+            }
+            ArticleInput {
+                body_markdown: Some(format!("{}{body}", front_matter.render())),
+                published: Some(false),
+                organization_id,
+                ..Default::default()
+            }
+        }
+        (None, body) => ArticleInput {
+            title: Some(title.to_string()),
+            body_markdown: Some(body.to_string()),
+            published: Some(false),
+            series: series.map(str::to_string),
+            organization_id,
+            ..Default::default()
+        },
+    };
+    let created = client.create_article(&input).await.context("Failed to create the draft")?;
+    println!("{} {} {}", "Created draft".green(), title.cyan().bold(), format!("({})", created.id).dimmed());
+    println!("Edit it with: dtdrafts edit {}", created.id);
+    super::history::pushed(client, &[&created]);
+    super::cache::upsert(client, created)?;
+    Ok(())
+}
+
+pub fn list() -> Result<()> {
+    let names = list_templates()?;
+    if names.is_empty() {
+        println!("{}", "No templates yet. Create one with: dtdrafts template add <name>".yellow());
+    }
+    for name in &names {
+        println!("{name}");
+    }
+    Ok(())
+}
+
+/// Adds a template from `from`, or from the default template.
+pub fn add(name: &str, from: Option<&Path>, force: bool) -> Result<()> {
+    if template_exists(name)? && !force {
+        return Err(anyhow::anyhow!("Template '{name}' already exists (use --force to replace it)"));
+    }
+    let content = match from {
+        Some(path) => fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?,
+        None => DEFAULT_TEMPLATE.to_string(),
+    };
+    let path = save_template(name, &content)?;
+    println!("{} {}", "Saved template".green(), path.display());
+    Ok(())
+}
+
+/// Opens a template in the editor, creating it from the default first if needed.
+pub fn edit(name: &str, editor: Option<&str>) -> Result<()> {
+    let path = match template_path(name)? {
+        path if path.exists() => path,
+        _ => save_template(name, DEFAULT_TEMPLATE)?,
+    };
+    open_editor(&path, editor)
+}
@@ -0,0 +1,110 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::hooks::{self, HookEvent, HooksConfig};
+use dtdrafts_core::sync::article_id_from_file_name;
+use dtdrafts_core::text::{word_count_with, WordCountOptions};
+use dtdrafts_core::toc::{extract_outline_with, headings, insert_toc, render_toc, Section};
+use dtdrafts_core::*;
+
+/// Prints the table of contents of a draft.
+pub fn print(article: &Article, max_level: u8) -> Result<()> {
+    let toc = render_toc(&headings(article.body_markdown.as_deref().unwrap_or_default()), max_level);
+    if toc.is_empty() {
+        return Err(anyhow::anyhow!("{} has no headings", article.title));
+    }
+    print!("{toc}");
+    Ok(())
+}
+
+/// Prints the heading tree of a draft with the words in each section.
+/// Sections below `max_level` aren't shown; their words count towards the
+/// section above them, or the intro.
+pub fn outline(article: &Article, max_level: u8, options: &WordCountOptions) -> Result<()> {
+    let body = article.body_markdown.as_deref().unwrap_or_default();
+    let mut sections: Vec<Section> = Vec::new();
+    for section in extract_outline_with(body, options) {
+        match sections.last_mut() {
+            _ if section.heading.level <= max_level => sections.push(section),
+            Some(above) => above.subsections.push(section),
+            None => {}
+        }
+    }
+    if sections.is_empty() {
+        return Err(anyhow::anyhow!("{} has no headings up to level {max_level}", article.title));
+    }
+    let total = word_count_with(body, options);
+    println!("{} {}", article.title.cyan().bold(), format!("({total} words)").dimmed());
+    let intro = total.saturating_sub(sections.iter().map(Section::total_words).sum());
+    if intro > 0 {
+        println!("{}", format!("   intro: {intro} words").dimmed());
+    }
+    print_sections(&sections, "", max_level);
+    Ok(())
+}
+
+fn print_sections(sections: &[Section], prefix: &str, max_level: u8) {
+    for (index, section) in sections.iter().enumerate() {
+        let last = index + 1 == sections.len();
+        let shown: Vec<Section> =
+            section.subsections.iter().filter(|sub| sub.heading.level <= max_level).cloned().collect();
+        let words = if shown.is_empty() {
+            format!("{} words", section.total_words())
+        } else {
+            format!("{} words, {} with subsections", section.words, section.total_words())
+        };
+        println!("{prefix}{} {}  {}", if last { "└─" } else { "├─" }, section.heading.text, words.dimmed());
+        print_sections(&shown, &format!("{prefix}{}", if last { "   " } else { "│  " }), max_level);
+    }
+}
+
+/// Inserts or refreshes the table of contents in the draft on dev.to.
+pub async fn insert(client: &DevToClient, hooks: &HooksConfig, id: u64, max_level: u8) -> Result<()> {
+    let article = client.get_my_article(id).await?;
+    let body = article.body_markdown.as_deref().unwrap_or_default();
+    let Some(updated_body) = with_toc(body, max_level)? else {
+        println!("{}", "The table of contents is up to date.".yellow());
+        return Ok(());
+    };
+    let input = ArticleInput { body_markdown: Some(updated_body), ..Default::default() };
+    let updated = client.update_article(id, &input).await.context("Failed to update the draft")?;
+    println!("{} {}", "Updated the table of contents of".green(), updated.title.cyan().bold());
+    hooks::fire(hooks, HookEvent::Push, &[&updated]).await;
+    super::history::pushed(client, &[&updated]);
+    super::cache::upsert(client, updated)?;
+    Ok(())
+}
+
+/// Inserts or refreshes the table of contents in the draft's `<id>-<slug>.md`
+/// file in a `sync` repository, to be pushed with the next `sync --push`.
+pub fn insert_local(repo: &Path, id: u64, max_level: u8) -> Result<()> {
+    let path = find_draft_file(repo, id)?;
+    let body = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let Some(updated_body) = with_toc(&body, max_level)? else {
+        println!("{}", "The table of contents is up to date.".yellow());
+        return Ok(());
+    };
+    fs::write(&path, updated_body).with_context(|| format!("Failed to write {}", path.display()))?;
+    println!("{} {}", "Updated the table of contents in".green(), path.display());
+    Ok(())
+}
+
+// `None` when the body already has this table of contents
+fn with_toc(body: &str, max_level: u8) -> Result<Option<String>> {
+    let toc = render_toc(&headings(body), max_level);
+    if toc.is_empty() {
+        return Err(anyhow::anyhow!("The draft has no headings to list"));
+    }
+    let updated = insert_toc(body, &toc);
+    Ok((updated != body).then_some(updated))
+}
+
+fn find_draft_file(repo: &Path, id: u64) -> Result<PathBuf> {
+    let entries = fs::read_dir(repo).with_context(|| format!("Failed to read {}", repo.display()))?;
+    entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .find(|path| article_id_from_file_name(path) == Some(id))
+        .ok_or_else(|| anyhow::anyhow!("No {id}-*.md file in {}; run `dtdrafts sync` first", repo.display()))
+}
@@ -0,0 +1,67 @@
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::frontmatter::article_input;
+use dtdrafts_core::journal::Journal;
+use dtdrafts_core::trash;
+use dtdrafts_core::*;
+
+use super::bulk::confirm;
+
+/// Lists the trashed drafts unless `quiet` and returns how many there are.
+pub fn list(quiet: bool) -> Result<usize> {
+    let trashed = trash::list()?;
+    if quiet {
+        return Ok(trashed.len());
+    }
+    if trashed.is_empty() {
+        println!("{}", "The trash is empty.".yellow());
+    }
+    for draft in &trashed {
+        let at = draft.deleted_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+        let words = text::word_count(draft.article.body_markdown.as_deref().unwrap_or_default());
+        println!(
+            "  {:>8}  {}  {}  {}",
+            draft.article.id.to_string().dimmed(),
+            at,
+            draft.article.title.cyan(),
+            format!("({words} words)").dimmed()
+        );
+    }
+    Ok(trashed.len())
+}
+
+/// Re-creates a trashed draft on dev.to, under a new id, and takes it out
+/// of the trash and of the undo journal.
+pub async fn restore(client: &DevToClient, id: u64) -> Result<()> {
+    let trashed = trash::get(id)?;
+    let mut input = article_input(&trashed.article);
+    input.published = Some(false);
+    let created = client.create_article(&input).await.context("Failed to re-create the draft")?;
+    trash::remove(id)?;
+    let mut journal = Journal::load()?;
+    if journal.forget_deletion(id) {
+        journal.save()?;
+    }
+    println!("{} {} as {}", "Restored".green(), created.title.cyan().bold(), created.id);
+    super::history::pushed(client, &[&created]);
+    super::cache::upsert(client, created)?;
+    Ok(())
+}
+
+/// Deletes every trashed draft for good.
+pub fn empty(yes: bool) -> Result<()> {
+    let trashed = trash::list()?;
+    if trashed.is_empty() {
+        println!("{}", "The trash is empty.".yellow());
+        return Ok(());
+    }
+    if !yes && !confirm(&format!("Permanently delete {} trashed draft(s)?", trashed.len()))? {
+        println!("{}", "Nothing was deleted.".yellow());
+        return Ok(());
+    }
+    for draft in &trashed {
+        trash::remove(draft.article.id)?;
+    }
+    println!("{} {} draft(s)", "Emptied the trash:".green(), trashed.len());
+    Ok(())
+}
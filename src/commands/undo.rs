@@ -0,0 +1,103 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::*;
+use dtdrafts_core::hooks::{self, HookEvent, HooksConfig};
+use dtdrafts_core::journal::{self, Journal, JournalChange};
+use dtdrafts_core::trash;
+use dtdrafts_core::*;
+
+use super::bulk::confirm;
+
+/// Journals an operation that already went through; a journal that can't be
+/// written is only worth a warning at that point.
+pub fn record(operation: &str, changes: Vec<JournalChange>) {
+    if let Err(e) = journal::record(operation, changes, Utc::now()) {
+        tracing::warn!("Failed to record {operation} for undo: {e:#}");
+    }
+}
+
+/// Restores the drafts the last journaled operation changed. Deleted drafts
+/// are re-created, under a new id. Drafts edited since the operation are not
+/// overwritten unless `force` is given. Changes that fail stay in the journal.
+pub async fn run(client: &DevToClient, hooks: &HooksConfig, yes: bool, force: bool) -> Result<()> {
+    let mut journal = Journal::load()?;
+    let Some(entry) = journal.last() else {
+        println!("{}", "Nothing to undo.".yellow());
+        return Ok(());
+    };
+    let at = entry.at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+    println!("Last operation: {} on {} draft(s) at {at}", entry.operation.cyan().bold(), entry.changes.len());
+    for change in &entry.changes {
+        let action = if change.deleted { "re-create".red() } else { "restore".green() };
+        println!("  {:>8}  {}  {action}", change.before.id.to_string().dimmed(), change.before.title.cyan());
+    }
+
+    let ids: Vec<u64> = entry.changes.iter().filter(|change| !change.deleted).map(|change| change.before.id).collect();
+    let current = if ids.is_empty() { Vec::new() } else { client.get_my_articles_by_id(&ids).await.context("Failed to fetch the drafts")? };
+    let edited: Vec<&Article> = entry
+        .changes
+        .iter()
+        .filter_map(|change| current.iter().find(|article| article.id == change.before.id && change.changed_since(article)))
+        .collect();
+    if !edited.is_empty() {
+        for article in &edited {
+            println!("  {} {} was edited after the operation", "Changed:".yellow(), article.title.cyan());
+        }
+        if !force {
+            return Err(anyhow::anyhow!(
+                "{} draft(s) changed since {}; undoing would discard those edits (re-run with --force)",
+                edited.len(),
+                entry.operation
+            ));
+        }
+    }
+    if !yes && !confirm("Undo it?")? {
+        println!("{}", "Nothing was changed.".yellow());
+        return Ok(());
+    }
+
+    let Some(mut entry) = journal.pop() else {
+        return Ok(());
+    };
+    let mut restored = Vec::new();
+    let mut failed = Vec::new();
+    for change in entry.changes {
+        let input = change.restore_input();
+        let id = change.before.id;
+        let result = if change.deleted {
+            client.create_article(&input).await
+        } else {
+            client.update_article(id, &input).await
+        };
+        match result {
+            Ok(article) if change.deleted => {
+                println!("  {} {} as {}", "re-created".green(), article.title, article.id);
+                trash::remove(id).ok();
+                restored.push(article);
+            }
+            Ok(article) => {
+                println!("  {} {}", "restored".green(), article.title);
+                restored.push(article);
+            }
+            Err(e) => {
+                println!("  {} {}: {e:#}", "failed".red(), change.before.title);
+                failed.push(change);
+            }
+        }
+    }
+
+    let failures = failed.len();
+    if !failed.is_empty() {
+        entry.changes = failed;
+        journal.push(entry);
+    }
+    journal.save()?;
+    let pushed: Vec<&Article> = restored.iter().collect();
+    super::history::pushed(client, &pushed);
+    hooks::fire(hooks, HookEvent::Push, &pushed).await;
+    super::cache::update(client, restored, &[])?;
+    if failures > 0 {
+        return Err(anyhow::anyhow!("{failures} draft(s) could not be restored; run `dtdrafts undo` again to retry"));
+    }
+    Ok(())
+}
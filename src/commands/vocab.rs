@@ -0,0 +1,38 @@
+use colored::*;
+use dtdrafts_core::vocab::{count_terms, sort_terms, to_csv, VocabOptions, VocabSortKey};
+use dtdrafts_core::*;
+
+/// Prints the most used terms across `drafts` (all of them as CSV) and
+/// returns how many were listed.
+pub fn run(drafts: &[&Article], options: &VocabOptions, sort: VocabSortKey, limit: Option<usize>, csv: bool, quiet: bool) -> usize {
+    let mut terms = count_terms(drafts, options);
+    sort_terms(&mut terms, sort);
+    if let Some(limit) = limit {
+        terms.truncate(limit);
+    }
+    if csv {
+        print!("{}", to_csv(&terms));
+        return terms.len();
+    }
+    if quiet {
+        return terms.len();
+    }
+    if terms.is_empty() {
+        println!("{}", format!("No term is used {} or more times.", options.min_count).yellow());
+        return 0;
+    }
+    let width = terms.iter().map(|term| term.term.chars().count()).max().unwrap_or(0).max(4);
+    println!("{}", format!("{:>4}  {:<width$}  {:>6}  {:>6}  {:>7}", "#", "Term", "Count", "Drafts", "Per 1k").bold());
+    for (rank, term) in terms.iter().enumerate() {
+        println!(
+            "{:>4}  {:<width$}  {:>6}  {:>6}  {:>7.2}",
+            rank + 1,
+            term.term.cyan(),
+            term.count,
+            term.drafts,
+            term.per_1000
+        );
+    }
+    println!("\n{}", format!("Across {} draft(s)", drafts.len()).dimmed());
+    terms.len()
+}
@@ -0,0 +1,112 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use colored::*;
+use dtdrafts_core::frontmatter::{document_to_input, draft_document, has_embedded_front_matter, FrontMatterMapping};
+use dtdrafts_core::hooks::{self, HookEvent, HooksConfig};
+use dtdrafts_core::sync::{article_id_from_file_name, has_conflict_markers};
+use dtdrafts_core::*;
+use notify::{RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+
+/// Watches `dir` for saved `<id>-<slug>.md` files (as written by `sync`) and
+/// uploads each one once it has been quiet for `debounce`. Front matter is
+/// converted back to dev.to's with `mapping`.
+pub async fn run(
+    client: &DevToClient,
+    hooks: &HooksConfig,
+    dir: &Path,
+    debounce: Duration,
+    mapping: &FrontMatterMapping,
+) -> Result<()> {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            if event.kind.is_modify() || event.kind.is_create() {
+                for path in event.paths {
+                    let _ = tx.send(path);
+                }
+            }
+        }
+    })
+    .context("Failed to start the file watcher")?;
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {}", dir.display()))?;
+    println!(
+        "Watching {} for changes to <id>-<slug>.md files (debounce {}s). Press Ctrl-C to stop.",
+        dir.display(),
+        debounce.as_secs_f32()
+    );
+
+    // What dev.to last saw for each file, so saves without changes aren't uploaded
+    let mut uploaded: HashMap<PathBuf, String> = HashMap::new();
+    let mut changed: BTreeSet<PathBuf> = BTreeSet::new();
+    loop {
+        match tokio::time::timeout(debounce, rx.recv()).await {
+            Ok(Some(path)) => {
+                changed.insert(path);
+            }
+            Ok(None) => return Ok(()),
+            Err(_) => {
+                for path in std::mem::take(&mut changed) {
+                    push_file(client, hooks, &path, mapping, &mut uploaded).await;
+                }
+            }
+        }
+    }
+}
+
+async fn push_file(
+    client: &DevToClient,
+    hooks: &HooksConfig,
+    path: &Path,
+    mapping: &FrontMatterMapping,
+    uploaded: &mut HashMap<PathBuf, String>,
+) {
+    let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    let Some(id) = article_id_from_file_name(path) else {
+        return;
+    };
+    let time = chrono::Local::now().format("%H:%M:%S").to_string().dimmed();
+    match upload(client, hooks, id, path, mapping, uploaded).await {
+        Ok(true) => println!("{time} {} {name}", "pushed".green()),
+        Ok(false) => println!("{time} {} {name} (unchanged)", "skipped".dimmed()),
+        Err(e) => println!("{time} {} {name}: {e:#}", "failed".red().bold()),
+    }
+}
+
+async fn upload(
+    client: &DevToClient,
+    hooks: &HooksConfig,
+    id: u64,
+    path: &Path,
+    mapping: &FrontMatterMapping,
+    uploaded: &mut HashMap<PathBuf, String>,
+) -> Result<bool> {
+    let document = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if has_conflict_markers(&document) {
+        return Err(anyhow::anyhow!("file contains conflict markers"));
+    }
+    let original = match load_articles_cache()?.into_iter().find(|article| article.id == id) {
+        Some(article) if article.body_markdown.is_some() => article,
+        _ => client.get_my_article(id).await?,
+    };
+    let last = uploaded
+        .entry(path.to_path_buf())
+        .or_insert_with(|| mapping.to_local(&draft_document(&original)));
+    if *last == document {
+        return Ok(false);
+    }
+    let updated = client
+        .update_article(id, &document_to_input(&mapping.to_devto(&document), has_embedded_front_matter(&original)))
+        .await?;
+    *last = document;
+    hooks::fire(hooks, HookEvent::Push, &[&updated]).await;
+    super::history::pushed(client, &[&updated]);
+    super::cache::upsert(client, updated)?;
+    Ok(true)
+}
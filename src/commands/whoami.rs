@@ -0,0 +1,41 @@
+use anyhow::Result;
+use colored::*;
+use dtdrafts_core::config::{self, Overrides};
+use dtdrafts_core::*;
+
+/// Prints the account behind the API key and remembers its username for
+/// edit URLs, when the key is the one in the config file.
+pub async fn run(client: &DevToClient, config: &Config, overrides: &Overrides, json: bool) -> Result<()> {
+    let user = client.get_me().await?;
+    if config.username.as_deref() != Some(user.username.as_str()) {
+        if let Err(e) = remember_username(config, overrides, &user.username) {
+            tracing::warn!("Could not save the username: {e:#}");
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&user)?);
+        return Ok(());
+    }
+    println!("{} {}", format!("@{}", user.username).cyan().bold(), user.name);
+    println!("{} {}", "id:".dimmed(), user.id);
+    if let Some(joined_at) = &user.joined_at {
+        println!("{} {joined_at}", "joined:".dimmed());
+    }
+    println!("{} {}", "api:".dimmed(), client.base_url());
+    Ok(())
+}
+
+// Keys from the environment aren't tied to the file, so neither is their user
+fn remember_username(config: &Config, overrides: &Overrides, username: &str) -> Result<()> {
+    let mut file = config::load_file()?;
+    // A profile without a key of its own uses the top-level one
+    let profile = config::active_profile(overrides)?
+        .filter(|profile| file.api_key(Some(profile)).is_ok_and(|key| key.is_some()));
+    if file.api_key(profile.as_deref())?.as_deref() != Some(config.api_key.as_str()) {
+        return Ok(());
+    }
+    file.set_username(profile.as_deref(), Some(username))?;
+    config::update_file(|current| *current = file)?;
+    Ok(())
+}
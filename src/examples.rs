@@ -0,0 +1,415 @@
+// Per-command examples shown by `--help` and rendered into the man pages'
+// EXAMPLES sections by `gen-man`. Each block starts with an "Examples:" line.
+
+pub const ROOT: &str = "\
+Examples:
+  dtdrafts --set-api-key YOUR_API_KEY
+  dtdrafts -q rust
+  dtdrafts --all
+  dtdrafts --refresh --all
+  dtdrafts list --tag rust --sort updated
+  dtdrafts -q kubernetes --quiet && echo \"already drafted\"";
+
+pub const SEARCH: &str = "\
+Examples:
+  dtdrafts search rust
+  dtdrafts search \"error handling\" --tag rust --sort updated --desc
+  dtdrafts search async --created-after 2024-01-01
+  dtdrafts search kotlin --open
+  dtdrafts search rust --include-published
+  dtdrafts search --again --open
+  dtdrafts search '!3'
+  dtdrafts search rust --tag cli --edit
+  dtdrafts search \"{query}\" --format alfred";
+
+pub const SEARCHES: &str = "\
+Examples:
+  dtdrafts searches
+  dtdrafts search '!3'
+  dtdrafts searches --quiet && dtdrafts search --again";
+
+pub const SAVED: &str = "\
+Examples:
+  dtdrafts saved add weekly 'tag:rust updated-after:-7d'
+  dtdrafts saved add async-wip '\"async runtime\" series:\"Async Rust\"'
+  dtdrafts saved run weekly --sort updated --desc
+  dtdrafts saved run weekly --notify
+  dtdrafts saved list
+  dtdrafts saved remove weekly";
+
+pub const LIST: &str = "\
+Examples:
+  dtdrafts list
+  dtdrafts list --tag rust --tag cli
+  dtdrafts list --updated-before 2023-06-30 --sort updated
+  dtdrafts list --output-template '{id}\\t{title}\\t{edit_url}'
+  dtdrafts list --output-template '{age_days}d {word_count}w {title}' --sort words
+  dtdrafts list --format table --sort updated --desc
+  dtdrafts list --group-by tag
+  dtdrafts list --group-by status --tag rust
+  dtdrafts list --tag wip --count
+  dtdrafts list --updated-before 2023-01-01 --ids";
+
+pub const STALE: &str = "\
+Examples:
+  dtdrafts stale
+  dtdrafts stale --older-than 6m";
+
+pub const REMIND: &str = "\
+Examples:
+  dtdrafts remind
+  dtdrafts remind --older-than 2w --json
+  0 9 * * 1 dtdrafts remind --older-than 30d --notify --quiet   # crontab entry";
+
+pub const STATS: &str = "\
+Examples:
+  dtdrafts stats
+  dtdrafts stats --tag rust --json
+  dtdrafts stats --readability --series \"Advent of Rust\"";
+
+pub const CALENDAR: &str = "\
+Examples:
+  dtdrafts calendar
+  dtdrafts calendar --weeks 12
+  dtdrafts calendar --tag rust";
+
+pub const VOCAB: &str = "\
+Examples:
+  dtdrafts vocab
+  dtdrafts vocab --ngram 2 --limit 20
+  dtdrafts vocab --sort drafts --tag rust
+  dtdrafts vocab --ngram 3 --csv > phrases.csv";
+
+pub const GOALS: &str = "\
+Examples:
+  dtdrafts goals set --publish-per-month 2
+  dtdrafts goals status
+  dtdrafts goals status --months 12 --refresh";
+
+pub const ANALYTICS: &str = "\
+Examples:
+  dtdrafts analytics
+  dtdrafts analytics --sort reactions --limit 10
+  dtdrafts analytics --csv > analytics-$(date +%F).csv";
+
+pub const COMMENTS: &str = "\
+Examples:
+  dtdrafts comments 1234567
+  dtdrafts comments 1234567 --quiet || echo \"no comments yet\"";
+
+pub const TAGS: &str = "\
+Examples:
+  dtdrafts tags trending --limit 50
+  dtdrafts tags followed
+  dtdrafts tags info rust";
+
+pub const DUPES: &str = "\
+Examples:
+  dtdrafts dupes
+  dtdrafts dupes --threshold 0.3 --tag rust
+  dtdrafts dupes --quiet || echo \"no duplicates\"";
+
+pub const RELATED: &str = "\
+Examples:
+  dtdrafts related 1234567
+  dtdrafts related 1234567 --limit 3
+  dtdrafts related 1234567 --drafts-only";
+
+pub const SUGGEST_TAGS: &str = "\
+Examples:
+  dtdrafts suggest-tags 1234567
+  dtdrafts suggest-tags 1234567 --apply";
+
+pub const NEW: &str = "\
+Examples:
+  dtdrafts new --title \"Getting started with Axum\"
+  dtdrafts new --title \"Axum part 2\" --template tutorial --series \"Axum from scratch\"
+  dtdrafts new --title \"Release notes\" --template release --var version=1.4.0";
+
+pub const SERIES: &str = "\
+Examples:
+  dtdrafts series list
+  dtdrafts series list --published
+  dtdrafts series set 1234567 \"Axum from scratch\"
+  dtdrafts series remove 1234567
+  dtdrafts list --series \"Axum from scratch\"";
+
+pub const CANONICAL: &str = "\
+Examples:
+  dtdrafts canonical set 1234567 https://blog.example.com/axum-from-scratch
+  dtdrafts canonical remove 1234567
+  dtdrafts lint --canonical 1234567";
+
+pub const STATUS: &str = "\
+Examples:
+  dtdrafts status set 1234567 writing
+  dtdrafts status set \"async rust\" review
+  dtdrafts status clear 1234567
+  dtdrafts list --status ready";
+
+pub const NOTE: &str = "\
+Examples:
+  dtdrafts note 1234567 \"remember to add benchmarks\"
+  dtdrafts note 1234567
+  dtdrafts note 1234567 --remove 1
+  dtdrafts search benchmarks        # notes are searched too";
+
+pub const BOARD: &str = "\
+Examples:
+  dtdrafts board
+  dtdrafts list --status review";
+
+pub const TEMPLATE: &str = "\
+Examples:
+  dtdrafts template list
+  dtdrafts template add tutorial
+  dtdrafts template add release --from ~/notes/release-template.md
+  dtdrafts template edit tutorial
+
+Templates are markdown files in ~/.dtdrafts/templates. {{title}}, {{date}}
+and {{series}} are always available; `new --var key=value` defines more.";
+
+pub const EDIT: &str = "\
+Examples:
+  dtdrafts edit 1234567
+  EDITOR=\"code --wait\" dtdrafts edit 1234567
+  dtdrafts edit 1234567 --force";
+
+pub const HISTORY: &str = "\
+Examples:
+  dtdrafts history 1234567
+  dtdrafts history 1234567 --show 3
+  dtdrafts history 1234567 --show 3 | diff - <(dtdrafts history 1234567 --show 4)";
+
+pub const REVERT: &str = "\
+Examples:
+  dtdrafts revert 1234567 --to 3
+  dtdrafts revert 1234567 --to 9f2c41d0 --yes";
+
+pub const TRASH: &str = "\
+Examples:
+  dtdrafts bulk --filter 'tag:wip' --action delete
+  dtdrafts trash list
+  dtdrafts trash restore 1234567
+  dtdrafts trash empty --yes";
+
+pub const UNDO: &str = "\
+Examples:
+  dtdrafts retag --from js --to javascript --all-drafts && dtdrafts undo
+  dtdrafts undo --yes
+  dtdrafts undo --force             # even if a draft was edited since";
+
+pub const LINT: &str = "\
+Examples:
+  dtdrafts lint 1234567
+  dtdrafts lint --all
+  dtdrafts lint --links 1234567
+  dtdrafts lint --tags --all
+  dtdrafts lint --spelling 1234567
+  dtdrafts lint --spelling --language en_GB --prose --all
+  dtdrafts lint --code --all
+  dtdrafts lint --canonical --all
+  dtdrafts lint --all --quiet && echo clean";
+
+pub const TOC: &str = "\
+Examples:
+  dtdrafts toc 1234567
+  dtdrafts toc 1234567 --insert
+  dtdrafts toc 1234567 --insert --max-level 2
+  dtdrafts toc 1234567 --insert --repo ~/drafts";
+
+pub const OUTLINE: &str = "\
+Examples:
+  dtdrafts outline 1234567
+  dtdrafts outline 1234567 --max-level 2";
+
+pub const SPLIT: &str = "\
+Examples:
+  dtdrafts split 1234567 --dry-run
+  dtdrafts split 1234567 --by h2
+  dtdrafts split 1234567 --by h1 --series \"Rust from scratch\"";
+
+pub const CLONE: &str = "\
+Examples:
+  dtdrafts clone 1234567 --title \"Weekly roundup #42\"
+  dtdrafts config set clone.content_start '<!-- links -->'
+  dtdrafts config set clone.content_end '<!-- /links -->'";
+
+pub const MERGE: &str = "\
+Examples:
+  dtdrafts merge 1234567 1234568 --into \"Async Rust in depth\"
+  dtdrafts merge 1 2 3 --into \"Week notes\" --trash";
+
+pub const SEO: &str = "\
+Examples:
+  dtdrafts seo 1234567
+  dtdrafts seo rust-cli-tips --keyword \"rust cli\"
+  dtdrafts seo 1234567 --keyword clap --json";
+
+pub const IMAGES: &str = "\
+Examples:
+  dtdrafts images 1234567
+  dtdrafts images 1234567 --download
+  dtdrafts images 1234567 --download --out ~/archive/1234567";
+
+pub const COVER: &str = "\
+Examples:
+  dtdrafts cover 1234567 --from-file banner.png
+  dtdrafts cover 1234567 --url https://blog.example.com/banner.png
+  dtdrafts cover 1234567 --generate
+  dtdrafts cover 1234567 --generate --background '#1e1b4b' --out covers/1234567.png";
+
+pub const PUBLISH: &str = "\
+Examples:
+  dtdrafts publish 1234567 --dry-run
+  dtdrafts publish 1234567 --check-markdown
+  dtdrafts publish 1234567";
+
+pub const CROSSPOST: &str = "\
+Examples:
+  dtdrafts crosspost 1234567 --to hashnode --dry-run
+  dtdrafts crosspost 1234567 --to medium";
+
+pub const SCHEDULE: &str = "\
+Examples:
+  dtdrafts schedule 1234567 --at \"2024-07-01 09:00\"
+  dtdrafts schedule list
+  dtdrafts schedule cancel 1234567";
+
+pub const SCHEDULER: &str = "\
+Examples:
+  dtdrafts scheduler run
+  */5 * * * * dtdrafts scheduler run --once   # crontab entry";
+
+pub const DAEMON: &str = "\
+Examples:
+  dtdrafts daemon
+  dtdrafts daemon --interval 15m
+  nohup dtdrafts daemon --quiet >/dev/null 2>&1 &
+  dtdrafts daemon --interval 30m --port 7878
+  dtdrafts daemon --saved --interval 15m";
+
+pub const SERVE: &str = "\
+Examples:
+  dtdrafts serve
+  curl 'http://127.0.0.1:7878/drafts?tag=rust'
+  curl 'http://127.0.0.1:7878/drafts/search?q=async'
+  curl http://127.0.0.1:7878/drafts/1234567
+  curl http://127.0.0.1:7878/drafts.atom";
+
+pub const SHOW: &str = "\
+Examples:
+  dtdrafts show 1234567
+  dtdrafts show 1234567 --render
+  dtdrafts show my-first-post-4k2j
+  dtdrafts show \"getting started\"
+  dtdrafts show 2                  # second article of the last list or search";
+
+pub const PREVIEW: &str = "\
+Examples:
+  dtdrafts preview 1234567
+  dtdrafts preview 1234567 --port 8080
+  dtdrafts preview --file ./post.md";
+
+pub const CACHE: &str = "\
+Examples:
+  dtdrafts cache compact
+  dtdrafts cache verify
+  dtdrafts cache verify --fix";
+
+pub const CONFIG: &str = "\
+Examples:
+  dtdrafts config list
+  dtdrafts config get api.base_url
+  dtdrafts config set cache.format compact
+  dtdrafts config set http.timeout_secs 60
+  dtdrafts config set profiles.work.api.key WORK_API_KEY
+  dtdrafts config unset output.template
+  dtdrafts config edit
+  dtdrafts --profile work config list";
+
+pub const BACKUP: &str = "\
+Examples:
+  dtdrafts backup
+  dtdrafts backup --out drafts-2024.tar.gz";
+
+pub const EXPORT: &str = "\
+Examples:
+  dtdrafts export --format hugo --dir ./content/posts
+  dtdrafts export --format jekyll --dir ./_drafts --tag rust
+  dtdrafts export --format zola --dir ./content/blog";
+
+pub const FEED: &str = "\
+Examples:
+  dtdrafts feed --out drafts.xml
+  dtdrafts feed --tag rust > rust-drafts.xml
+  curl http://127.0.0.1:7878/drafts.atom   # the same feed from `serve`";
+
+pub const REPORT: &str = "\
+Examples:
+  dtdrafts report --html out/
+  dtdrafts report --html ./rust-report --tag rust";
+
+pub const IMPORT: &str = "\
+Examples:
+  dtdrafts import --from medium-export.zip --dry-run
+  dtdrafts import --from ghost-export.json";
+
+pub const RESTORE: &str = "\
+Examples:
+  dtdrafts restore drafts-2024.tar.gz --dry-run
+  dtdrafts restore drafts-2024.tar.gz";
+
+pub const SYNC: &str = "\
+Examples:
+  dtdrafts sync --repo ./my-drafts
+  dtdrafts sync --repo ./my-drafts --push
+  dtdrafts sync --repo ./my-drafts --push --strategy merge
+  dtdrafts sync --repo ./blog/content/posts --frontmatter hugo";
+
+pub const WATCH: &str = "\
+Examples:
+  dtdrafts sync --repo ./drafts && dtdrafts watch ./drafts
+  dtdrafts watch ./drafts --debounce 5";
+
+pub const COMPLETIONS: &str = "\
+Examples:
+  dtdrafts completions zsh > ~/.zfunc/_dtdrafts
+  source <(COMPLETE=bash dtdrafts)";
+
+pub const GEN_MAN: &str = "\
+Examples:
+  dtdrafts gen-man --out /usr/local/share/man/man1";
+
+pub const BULK: &str = "\
+Examples:
+  dtdrafts bulk --filter 'tag:old-series updated-before:2022-01-01' --action add-tag:archive --dry-run
+  dtdrafts bulk --filter 'tag:old-series updated-before:2022-01-01' --action add-tag:archive
+  dtdrafts bulk --filter 'series:\"Advent of Rust\"' --action remove-series --yes
+  dtdrafts bulk --filter 'tag:wip created-before:2021-01-01' --action delete
+  dtdrafts bulk --filter 'tag:rust' --action export:hugo --dir ./content/posts";
+
+pub const RETAG: &str = "\
+Examples:
+  dtdrafts retag --from beginners --to beginner --all-drafts --dry-run
+  dtdrafts retag --from beginners --to beginner --all-drafts
+  dtdrafts retag --from js --to javascript 1234567 rust-tips
+  dtdrafts retag --from beginners --to beginner --all-drafts --interval 3s";
+
+pub const REPLACE: &str = "\
+Examples:
+  dtdrafts replace --pattern old-domain.com --with new-domain.com --dry-run
+  dtdrafts replace --pattern old-domain.com --with new-domain.com --tag rust
+  dtdrafts replace --pattern 'https?://old-domain\\.com/(\\w+)' --with 'https://new-domain.com/$1' --regex
+  dtdrafts replace --pattern TODO --with FIXME --series \"Advent of Rust\" --yes";
+
+pub const WHOAMI: &str = "\
+Examples:
+  dtdrafts whoami
+  dtdrafts whoami --json
+  dtdrafts --profile work whoami";
+
+pub const SELFTEST: &str = "\
+Examples:
+  dtdrafts selftest
+  dtdrafts selftest --live";
@@ -1,85 +1,2357 @@
-use clap::Parser;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap_complete::engine::ArgValueCandidates;
 use colored::*;
 use anyhow::{Result, Context};
-use dtdrafts::*;
+use chrono::{DateTime, Utc};
+use dtdrafts_core::cache::CachedBodies;
+use dtdrafts_core::fixtures::FixtureMode;
+use dtdrafts_core::group::{format_groups, group_articles, GroupBy};
+use dtdrafts_core::hooks::{self, HookEvent};
+use dtdrafts_core::notes::NoteStore;
+use dtdrafts_core::output::{format_table, ListFormat, OutputTemplate};
+use dtdrafts_core::resolve::{load_last_results, save_last_results};
+use dtdrafts_core::search_history::{self, SearchHistory};
+use dtdrafts_core::status::{StatusStore, WorkflowStatus};
+use dtdrafts_core::spelling::SpellChecker;
+use dtdrafts_core::*;
+use std::process::ExitCode;
+
+mod commands;
+mod examples;
 
 #[derive(Parser)]
 #[command(name = "dtdrafts")]
 #[command(about = "Search your dev.to draft articles")]
 #[command(version = "0.1.3")]
+#[command(after_long_help = examples::ROOT)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Search query
     #[arg(short, long)]
     query: Option<String>,
 
-    /// Set dev.to API key
+    /// Set dev.to API key (checked against dev.to first)
     #[arg(long)]
     set_api_key: Option<String>,
 
+    /// Save the key given to --set-api-key even if dev.to doesn't accept it
+    #[arg(long, requires = "set_api_key")]
+    force: bool,
+
     /// Force refresh cached articles
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     refresh: bool,
 
     /// Show all drafts without filtering
     #[arg(short, long)]
     all: bool,
+
+    #[command(flatten)]
+    output: OutputArgs,
+
+    /// Print nothing; exit 0 if drafts matched, 1 if none, 2 on errors
+    #[arg(long, global = true)]
+    quiet: bool,
+
+    /// Log to stderr: -v info, -vv debug, -vvv trace (RUST_LOG overrides)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Log format: text or json
+    #[arg(long, global = true, value_name = "FORMAT", default_value = "text")]
+    log_format: LogFormat,
+
+    /// Color output: auto, always or never (auto respects NO_COLOR)
+    #[arg(long, global = true, value_name = "WHEN", default_value = "auto")]
+    color: dtdrafts_core::style::ColorChoice,
+
+    /// Print long listings directly instead of through $PAGER
+    #[arg(long, global = true)]
+    no_pager: bool,
+
+    /// Speak line-delimited JSON-RPC on stdin/stdout (for editor plugins)
+    #[arg(long)]
+    serve_stdio: bool,
+
+    /// Save every dev.to API response into DIR, with the API key scrubbed
+    #[arg(long, global = true, value_name = "DIR", conflicts_with = "replay")]
+    record: Option<std::path::PathBuf>,
+
+    /// Answer dev.to API requests from responses saved with --record
+    #[arg(long, global = true, value_name = "DIR")]
+    replay: Option<std::path::PathBuf>,
+
+    /// Fail when an article from the API can't be parsed instead of skipping it
+    #[arg(long, global = true)]
+    strict: bool,
+
+    /// Use the [profiles.NAME] settings of the config file
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// dev.to API base URL, overriding the config file and environment
+    #[arg(long, global = true, value_name = "URL")]
+    base_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogFormat {
+    Text,
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!("unknown log format '{s}' (expected text or json)")),
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Search draft articles by title, body, and tags
+    #[command(after_long_help = examples::SEARCH)]
+    Search {
+        /// Search query, or !N to run the Nth most recent search again (see `history`)
+        #[arg(required_unless_present = "again")]
+        query: Option<String>,
+
+        /// Run the last search again; other options given are added to it
+        #[arg(long, conflicts_with = "query")]
+        again: bool,
+
+        /// Search engine: linear (substring scan), index (ranked, needs the `index` feature)
+        /// or streamed (substring scan reading the cache incrementally, for low memory)
+        #[arg(long, value_name = "ENGINE", default_value = "linear")]
+        engine: SearchEngine,
+
+        /// Open the matching draft in the browser (asks which one if several match)
+        #[arg(long, conflicts_with = "edit")]
+        open: bool,
+
+        /// Edit the matching draft in $EDITOR (asks which one if several match)
+        #[arg(long)]
+        edit: bool,
+
+        /// Also search your published articles (cached on first use, refreshed with --refresh)
+        #[arg(long)]
+        include_published: bool,
+
+        #[command(flatten)]
+        filter: FilterArgs,
+
+        #[command(flatten)]
+        sort: SortArgs,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// List the recent searches, numbered as `search '!N'` runs them again
+    #[command(after_long_help = examples::SEARCHES)]
+    Searches,
+    /// Save named searches and run them again, reporting what changed
+    #[command(after_long_help = examples::SAVED)]
+    Saved {
+        #[command(subcommand)]
+        action: SavedAction,
+    },
+    /// List draft articles
+    #[command(after_long_help = examples::LIST)]
+    List {
+        /// Show the drafts in sections per tag, series, month (of the last update) or status
+        #[arg(long, value_name = "KEY", conflicts_with_all = ["output_template", "format", "count", "ids"])]
+        group_by: Option<dtdrafts_core::group::GroupBy>,
+
+        #[command(flatten)]
+        filter: FilterArgs,
+
+        #[command(flatten)]
+        sort: SortArgs,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Report drafts not updated for a while, grouped by age
+    #[command(after_long_help = examples::STALE)]
+    Stale {
+        /// Minimum age, e.g. 90d, 12w, 6m or 1y
+        #[arg(long, value_name = "AGE", default_value = "90d", value_parser = parse_age_arg)]
+        older_than: chrono::Duration,
+    },
+    /// List neglected drafts, optionally as a desktop notification (for cron)
+    #[command(after_long_help = examples::REMIND)]
+    Remind {
+        /// Minimum age, e.g. 30d, 12w, 6m or 1y
+        #[arg(long, value_name = "AGE", default_value = "30d", value_parser = parse_age_arg)]
+        older_than: chrono::Duration,
+
+        /// Send a desktop notification (needs the `notifications` feature)
+        #[arg(long)]
+        notify: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Word counts, reading time and tag frequency for drafts
+    #[command(after_long_help = examples::STATS)]
+    Stats {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Also show reading ease, sentence length and weasel words per draft, with their trend
+        #[arg(long)]
+        readability: bool,
+
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// Grid of the days drafts were created and updated, one column per week
+    #[command(after_long_help = examples::CALENDAR)]
+    Calendar {
+        /// Number of weeks to show, ending with the current one
+        #[arg(long, value_name = "N", default_value_t = 53, value_parser = clap::value_parser!(u16).range(1..=dtdrafts_core::calendar::MAX_WEEKS as i64))]
+        weeks: u16,
+
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// Most used words and phrases across drafts, without code
+    #[command(after_long_help = examples::VOCAB)]
+    Vocab {
+        /// Count phrases of N words instead of single words
+        #[arg(long, value_name = "N", default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=dtdrafts_core::vocab::MAX_NGRAM as i64))]
+        ngram: u8,
+
+        /// Sort by count, drafts or term
+        #[arg(long, value_name = "KEY", default_value = "count")]
+        sort: dtdrafts_core::vocab::VocabSortKey,
+
+        /// Only show the first N terms
+        #[arg(long, value_name = "N", default_value_t = 30)]
+        limit: usize,
+
+        /// Leave out terms used fewer than N times
+        #[arg(long, value_name = "N", default_value_t = 2)]
+        min_count: usize,
+
+        /// Count stop words such as "the" and "with" too
+        #[arg(long)]
+        keep_stopwords: bool,
+
+        /// Output every term as CSV, ignoring --limit
+        #[arg(long)]
+        csv: bool,
+
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// Set a publishing goal and track progress and streaks against it
+    #[command(after_long_help = examples::GOALS)]
+    Goals {
+        #[command(subcommand)]
+        action: GoalsAction,
+    },
+    /// Views, reactions and comments of published articles
+    #[command(after_long_help = examples::ANALYTICS)]
+    Analytics {
+        /// Sort by views, reactions, comments, published or title
+        #[arg(long, value_name = "KEY", default_value = "views")]
+        sort: dtdrafts_core::analytics::AnalyticsSortKey,
+
+        /// Only show the first N articles
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+
+        /// Output as CSV
+        #[arg(long)]
+        csv: bool,
+    },
+    /// Look up dev.to tags before using them
+    #[command(after_long_help = examples::TAGS)]
+    Tags {
+        #[command(subcommand)]
+        action: TagsAction,
+    },
+    /// Find drafts that cover the same topic
+    #[command(after_long_help = examples::DUPES)]
+    Dupes {
+        /// Minimum similarity (0.0 to 1.0) for two drafts to count as duplicates
+        #[arg(long, value_name = "SCORE", default_value_t = 0.5, value_parser = parse_threshold_arg)]
+        threshold: f64,
+
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// Rank your other articles by similarity to a draft, for internal links
+    #[command(after_long_help = examples::RELATED)]
+    Related {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Number of articles to show
+        #[arg(long, value_name = "N", default_value_t = 10)]
+        limit: usize,
+
+        /// Only compare with other drafts (skips fetching published posts)
+        #[arg(long)]
+        drafts_only: bool,
+    },
+    /// Suggest tags for a draft based on your similar, already tagged articles
+    #[command(after_long_help = examples::SUGGEST_TAGS)]
+    SuggestTags {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Replace the draft's tags with the suggestions
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Create a draft, optionally from a template
+    #[command(after_long_help = examples::NEW)]
+    New {
+        /// Draft title
+        #[arg(long)]
+        title: String,
+
+        /// Template name from ~/.dtdrafts/templates
+        #[arg(long, value_name = "NAME")]
+        template: Option<String>,
+
+        /// Value for {{series}}
+        #[arg(long)]
+        series: Option<String>,
+
+        /// Extra template variable (repeatable)
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var_arg)]
+        vars: Vec<(String, String)>,
+
+        /// Create the draft under an organization (username or id)
+        #[arg(long, value_name = "ORG")]
+        org: Option<String>,
+    },
+    /// List series or move drafts between them
+    #[command(after_long_help = examples::SERIES)]
+    Series {
+        #[command(subcommand)]
+        action: SeriesAction,
+    },
+    /// Point a draft's canonical URL at the original of a cross-post
+    #[command(after_long_help = examples::CANONICAL)]
+    Canonical {
+        #[command(subcommand)]
+        action: CanonicalAction,
+    },
+    /// Track a draft's local workflow status (idea, writing, review, ready)
+    #[command(after_long_help = examples::STATUS)]
+    Status {
+        #[command(subcommand)]
+        action: StatusAction,
+    },
+    /// Attach a local note to a draft, or list its notes
+    #[command(after_long_help = examples::NOTE)]
+    Note {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Note text; without it the draft's notes are listed
+        text: Option<String>,
+
+        /// Remove the note with this number
+        #[arg(long, value_name = "N", conflicts_with_all = ["text", "clear"])]
+        remove: Option<usize>,
+
+        /// Remove all of the draft's notes
+        #[arg(long, conflicts_with = "text")]
+        clear: bool,
+    },
+    /// Show drafts grouped by workflow status
+    #[command(after_long_help = examples::BOARD)]
+    Board,
+    /// Manage draft templates
+    #[command(after_long_help = examples::TEMPLATE)]
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+    /// Edit a draft in $EDITOR and upload the changes
+    #[command(after_long_help = examples::EDIT)]
+    Edit {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Upload even if the draft changed on dev.to while editing
+        #[arg(long)]
+        force: bool,
+    },
+    /// List the versions of a draft recorded each time it was pulled or pushed
+    #[command(after_long_help = examples::HISTORY)]
+    History {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Print a version (number or hash prefix) instead of listing them
+        #[arg(long, value_name = "VERSION")]
+        show: Option<String>,
+    },
+    /// List, restore or empty the drafts deleted through dtdrafts
+    #[command(after_long_help = examples::TRASH)]
+    Trash {
+        #[command(subcommand)]
+        action: TrashAction,
+    },
+    /// Reverse the last publish, bulk change, retag or replace
+    #[command(after_long_help = examples::UNDO)]
+    Undo {
+        /// Undo without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Undo even if a draft was edited after the operation, discarding those edits
+        #[arg(long)]
+        force: bool,
+    },
+    /// Upload a recorded version of a draft over the current one
+    #[command(after_long_help = examples::REVERT)]
+    Revert {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Version number or hash prefix, as listed by `history`
+        #[arg(long, value_name = "VERSION")]
+        to: String,
+
+        /// Revert without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Check drafts for liquid tag and markdown structure problems, broken links, spelling and style
+    #[command(after_long_help = examples::LINT)]
+    Lint {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", required_unless_present = "all", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: Option<String>,
+
+        /// Lint every draft
+        #[arg(long, conflicts_with = "article")]
+        all: bool,
+
+        /// Also request every link and image, reporting broken links and redirects
+        #[arg(long)]
+        links: bool,
+
+        /// Also warn about tags outside dev.to's most popular tags
+        #[arg(long)]
+        tags: bool,
+
+        /// Also check spelling against a Hunspell dictionary and ~/.dtdrafts/dictionary.txt
+        #[arg(long)]
+        spelling: bool,
+
+        /// Dictionary for --spelling [default: lint.language setting, or en_US]
+        #[arg(long, value_name = "LANG", requires = "spelling")]
+        language: Option<String>,
+
+        /// Also apply prose style rules (repeated words, weasel words)
+        #[arg(long)]
+        prose: bool,
+
+        /// Also check that code blocks name a language dev.to highlights, and that Rust snippets parse
+        #[arg(long)]
+        code: bool,
+
+        /// Treat the drafts as cross-posts: warn without a canonical_url, and request it
+        #[arg(long)]
+        canonical: bool,
+    },
+    /// List the images in a draft, flag insecure or dead ones, and optionally download them
+    #[command(after_long_help = examples::IMAGES)]
+    Images {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Download the images into a local folder
+        #[arg(long)]
+        download: bool,
+
+        /// Folder for --download [default: assets/<id>]
+        #[arg(long, value_name = "DIR", requires = "download")]
+        out: Option<std::path::PathBuf>,
+    },
+    /// Set a draft's cover image from a URL, an uploaded file or a generated title card
+    #[command(after_long_help = examples::COVER)]
+    #[command(group = clap::ArgGroup::new("source").required(true).args(["from_file", "url", "generate"]))]
+    Cover {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Upload an image file through dev.to's image uploads
+        #[arg(long, value_name = "FILE")]
+        from_file: Option<std::path::PathBuf>,
+
+        /// Use an image that is already hosted
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Render a title card with the draft's title and tags, then upload it
+        #[arg(long)]
+        generate: bool,
+
+        /// Where --generate writes the PNG [default: cover-<id>.png]
+        #[arg(long, value_name = "FILE", requires = "generate")]
+        out: Option<std::path::PathBuf>,
+
+        /// Title card background
+        #[arg(long, value_name = "#RRGGBB", requires = "generate")]
+        background: Option<dtdrafts_core::cover::Color>,
+
+        /// Title card text color
+        #[arg(long, value_name = "#RRGGBB", requires = "generate")]
+        foreground: Option<dtdrafts_core::cover::Color>,
+    },
+    /// Print a table of contents built from a draft's headings, or insert it into the draft
+    #[command(after_long_help = examples::TOC)]
+    Toc {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Insert or refresh the table of contents between <!-- toc --> and <!-- tocstop --> markers
+        #[arg(long)]
+        insert: bool,
+
+        /// Deepest heading level to list
+        #[arg(long, value_name = "LEVEL", default_value_t = 3, value_parser = clap::value_parser!(u8).range(1..=6))]
+        max_level: u8,
+
+        /// Update the draft's file in this sync repository instead of dev.to
+        #[arg(long, value_name = "DIR", requires = "insert")]
+        repo: Option<std::path::PathBuf>,
+    },
+    /// Print a draft's heading tree with word counts per section
+    #[command(after_long_help = examples::OUTLINE)]
+    Outline {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Deepest heading level to show; deeper sections count towards their parent
+        #[arg(long, value_name = "LEVEL", default_value_t = 6, value_parser = clap::value_parser!(u8).range(1..=6))]
+        max_level: u8,
+    },
+    /// Split a long draft into a series of new drafts, one per section
+    #[command(after_long_help = examples::SPLIT)]
+    Split {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Heading level to split at, h1 to h6; higher-level headings split too
+        #[arg(long, value_name = "LEVEL", default_value = "h2")]
+        by: dtdrafts_core::split::SplitLevel,
+
+        /// Series for the new drafts (defaults to the draft's series, or its title)
+        #[arg(long)]
+        series: Option<String>,
+
+        /// Only show the drafts that would be created
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Create the drafts without reviewing them
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Start a new draft from an existing one, keeping its structure, tags and series
+    #[command(after_long_help = examples::CLONE)]
+    Clone {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Title of the new draft
+        #[arg(long)]
+        title: String,
+    },
+    /// Merge drafts into a new one, each under a heading with its title
+    #[command(after_long_help = examples::MERGE)]
+    Merge {
+        /// Article ids, slugs, title prefixes or numbers from the last listing, in the order to merge them
+        #[arg(value_name = "ARTICLE", num_args = 2.., required = true, add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        articles: Vec<String>,
+
+        /// Title of the merged draft
+        #[arg(long, value_name = "TITLE")]
+        into: String,
+
+        /// Move the merged drafts to the trash afterwards
+        #[arg(long)]
+        trash: bool,
+    },
+    /// Check a draft's title, description, keyword use and reading ease for search and sharing
+    #[command(after_long_help = examples::SEO)]
+    Seo {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Phrase readers would search for
+        #[arg(long, value_name = "PHRASE")]
+        keyword: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Validate a draft's metadata and content, then publish it
+    #[command(after_long_help = examples::PUBLISH)]
+    Publish {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Only run the checks
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Publish even if the checks report errors
+        #[arg(long, conflicts_with = "dry_run")]
+        force: bool,
+
+        /// Also run the markdown structure checks of `lint`; an unclosed code fence stops the publish
+        #[arg(long)]
+        check_markdown: bool,
+    },
+    /// Post a published article to Hashnode or Medium with dev.to as the canonical URL
+    #[command(after_long_help = examples::CROSSPOST)]
+    Crosspost {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE")]
+        article: String,
+
+        /// hashnode or medium
+        #[arg(long)]
+        to: dtdrafts_core::crosspost::Platform,
+
+        /// Only show what would be posted
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Schedule a draft to be published later, or list and cancel schedules
+    #[command(after_long_help = examples::SCHEDULE)]
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    Schedule {
+        #[command(subcommand)]
+        action: Option<ScheduleAction>,
+
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", required = true, add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: Option<String>,
+
+        /// When to publish: "YYYY-MM-DD HH:MM" (local time) or RFC 3339
+        #[arg(long, required = true, value_parser = parse_schedule_time_arg)]
+        at: Option<DateTime<Utc>>,
+    },
+    /// Publish scheduled drafts when they are due
+    #[command(after_long_help = examples::SCHEDULER)]
+    Scheduler {
+        #[command(subcommand)]
+        action: SchedulerAction,
+    },
+    /// Keep the article cache fresh in the background
+    #[command(after_long_help = examples::DAEMON)]
+    Daemon {
+        /// Time between refreshes, e.g. 30m, 1h or 1d
+        #[arg(long, value_name = "INTERVAL", default_value = "1h", value_parser = parse_interval_arg)]
+        interval: std::time::Duration,
+
+        /// Also serve the local HTTP API (as `serve` does) on this port
+        #[arg(long)]
+        port: Option<u16>,
+
+        /// Run the saved searches after each refresh and report (or notify) changed results
+        #[arg(long)]
+        saved: bool,
+    },
+    /// Serve the cached drafts as a JSON API on localhost for editor plugins
+    #[command(after_long_help = examples::SERVE)]
+    Serve {
+        /// Port to listen on (127.0.0.1 only)
+        #[arg(long, default_value_t = 7878)]
+        port: u16,
+    },
+    /// Print a draft with its front matter, or rendered for the terminal
+    #[command(after_long_help = examples::SHOW)]
+    Show {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Render the markdown (headings, emphasis, highlighted code) instead of printing it raw
+        #[arg(long)]
+        render: bool,
+    },
+    /// Show the comment threads on one of your articles
+    #[command(after_long_help = examples::COMMENTS)]
+    Comments {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE")]
+        article: String,
+    },
+    /// Serve a draft rendered as HTML on localhost, reloading when it changes
+    #[command(after_long_help = examples::PREVIEW)]
+    Preview {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(
+            value_name = "ARTICLE",
+            required_unless_present = "file",
+            add = ArgValueCandidates::new(commands::completions::cached_drafts)
+        )]
+        article: Option<String>,
+
+        /// Preview a local markdown file instead, reloading whenever it is saved
+        #[arg(long, value_name = "PATH", conflicts_with = "article")]
+        file: Option<std::path::PathBuf>,
+
+        /// Port to listen on (127.0.0.1 only)
+        #[arg(long, default_value_t = 4000)]
+        port: u16,
+    },
+    /// Manage the local article cache
+    #[command(after_long_help = examples::CACHE)]
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// View and change settings in ~/.dtdrafts/config.toml
+    #[command(after_long_help = examples::CONFIG)]
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Write every cached draft plus a manifest to a .tar.gz archive
+    #[command(after_long_help = examples::BACKUP)]
+    Backup {
+        /// Archive to write [default: dtdrafts-backup-<date>.tar.gz]
+        #[arg(long, value_name = "FILE")]
+        out: Option<std::path::PathBuf>,
+    },
+    /// Convert drafts into posts for a static site generator
+    #[command(after_long_help = examples::EXPORT)]
+    Export {
+        /// hugo, jekyll or zola
+        #[arg(long)]
+        format: dtdrafts_core::export::ExportFormat,
+
+        /// Output directory
+        #[arg(long, value_name = "DIR", default_value = ".")]
+        dir: std::path::PathBuf,
+
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// Write an Atom feed of drafts, most recently updated first
+    #[command(after_long_help = examples::FEED)]
+    Feed {
+        /// File to write [default: stdout]
+        #[arg(long, value_name = "FILE")]
+        out: Option<std::path::PathBuf>,
+
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// Generate a self-contained HTML dashboard of the drafts
+    #[command(after_long_help = examples::REPORT)]
+    Report {
+        /// Directory to write index.html to
+        #[arg(long, value_name = "DIR")]
+        html: std::path::PathBuf,
+
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// Create dev.to drafts from a Medium (.zip) or Ghost (.json) export
+    #[command(after_long_help = examples::IMPORT)]
+    Import {
+        /// Export file
+        #[arg(long, value_name = "FILE")]
+        from: std::path::PathBuf,
+
+        /// Only list the drafts that would be created
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Re-create the drafts in a backup archive as new dev.to drafts
+    #[command(after_long_help = examples::RESTORE)]
+    Restore {
+        /// Archive written by `dtdrafts backup`
+        archive: std::path::PathBuf,
+
+        /// Only list the drafts that would be created
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Mirror drafts into a git repository, one commit per changed draft
+    #[command(after_long_help = examples::SYNC)]
+    Sync {
+        /// Git working tree to sync into (created if missing)
+        #[arg(long, value_name = "DIR")]
+        repo: std::path::PathBuf,
+
+        /// Upload drafts that were edited in the repository
+        #[arg(long)]
+        push: bool,
+
+        /// Resolve drafts changed on both sides: ours, theirs or merge (three-way)
+        #[arg(long)]
+        strategy: Option<dtdrafts_core::sync::SyncStrategy>,
+
+        /// Front matter style of the files: devto, hugo, jekyll or custom (the [frontmatter] config
+        /// mapping) [default: the style of the last sync, or devto]
+        #[arg(long, value_name = "STYLE")]
+        frontmatter: Option<dtdrafts_core::frontmatter::FrontMatterStyle>,
+    },
+    /// Upload <id>-<slug>.md files in a directory whenever they are saved
+    #[command(after_long_help = examples::WATCH)]
+    Watch {
+        /// Directory with draft files, e.g. a `sync` repository
+        dir: std::path::PathBuf,
+
+        /// Seconds to wait after the last change before uploading
+        #[arg(long, value_name = "SECONDS", default_value_t = 2)]
+        debounce: u64,
+
+        /// Front matter style of the files [default: the style `sync` used for DIR, or devto]
+        #[arg(long, value_name = "STYLE")]
+        frontmatter: Option<dtdrafts_core::frontmatter::FrontMatterStyle>,
+    },
+    /// Print a shell completion script
+    #[command(after_long_help = examples::COMPLETIONS)]
+    Completions {
+        /// Target shell
+        shell: clap_complete::Shell,
+    },
+    /// Write man pages for dtdrafts and every subcommand
+    #[command(after_long_help = examples::GEN_MAN)]
+    GenMan {
+        /// Output directory
+        #[arg(long, value_name = "DIR", default_value = "man")]
+        out: std::path::PathBuf,
+    },
+    /// Apply one change to every draft matching a filter expression
+    #[command(after_long_help = examples::BULK)]
+    Bulk {
+        /// Space-separated key:value terms, all of which must match: tag, series, org, id,
+        /// created-after, created-before, updated-after, updated-before
+        #[arg(long, value_name = "EXPR")]
+        filter: String,
+
+        /// add-tag:TAG, remove-tag:TAG, rename-tag:FROM:TO, set-series:SERIES, remove-series, delete
+        /// or export:FORMAT
+        #[arg(long)]
+        action: dtdrafts_core::bulk::BulkAction,
+
+        /// Output directory for export:FORMAT
+        #[arg(long, value_name = "DIR", default_value = ".")]
+        dir: std::path::PathBuf,
+
+        /// Show what would change without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Apply without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Pause between updates, to stay under dev.to's rate limit
+        #[arg(long, value_name = "INTERVAL", default_value = "1s", value_parser = parse_interval_arg)]
+        interval: std::time::Duration,
+    },
+    /// Replace a tag with another on drafts, e.g. after changing a tag convention
+    #[command(after_long_help = examples::RETAG)]
+    Retag {
+        /// Tag to replace
+        #[arg(long, value_name = "TAG")]
+        from: String,
+
+        /// Tag to use instead
+        #[arg(long, value_name = "TAG")]
+        to: String,
+
+        /// Retag every draft that carries --from
+        #[arg(long, required_unless_present = "articles")]
+        all_drafts: bool,
+
+        /// Article ids, slugs, title prefixes or numbers from the last listing
+        #[arg(value_name = "ARTICLE", conflicts_with = "all_drafts")]
+        articles: Vec<String>,
+
+        /// Show what would change without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Pause between updates, to stay under dev.to's rate limit
+        #[arg(long, value_name = "INTERVAL", default_value = "1s", value_parser = parse_interval_arg)]
+        interval: std::time::Duration,
+    },
+    /// Find and replace text in draft bodies, previewing a diff per draft
+    #[command(after_long_help = examples::REPLACE)]
+    Replace {
+        /// Text to find
+        #[arg(long, value_name = "TEXT")]
+        pattern: String,
+
+        /// Text to put instead (with --regex, may use $1 or ${name})
+        #[arg(long = "with", value_name = "TEXT")]
+        replacement: String,
+
+        /// Treat --pattern as a regular expression
+        #[arg(long)]
+        regex: bool,
+
+        #[command(flatten)]
+        filter: FilterArgs,
+
+        /// Show the diffs without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Apply without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Pause between updates, to stay under dev.to's rate limit
+        #[arg(long, value_name = "INTERVAL", default_value = "1s", value_parser = parse_interval_arg)]
+        interval: std::time::Duration,
+    },
+    /// Show the dev.to account the API key belongs to
+    #[command(after_long_help = examples::WHOAMI)]
+    Whoami {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Verify the API write path (create, update, search, delete a throwaway draft)
+    #[command(after_long_help = examples::SELFTEST)]
+    Selftest {
+        /// Actually run against the configured dev.to instance
+        #[arg(long)]
+        live: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ScheduleAction {
+    /// List scheduled publishes
+    List,
+    /// Cancel a scheduled publish
+    Cancel {
+        /// Article id
+        id: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum SchedulerAction {
+    /// Publish due drafts, checking periodically
+    Run {
+        /// Publish whatever is due and exit (for cron)
+        #[arg(long)]
+        once: bool,
+
+        /// Seconds between checks
+        #[arg(long, value_name = "SECONDS", default_value_t = 60)]
+        interval: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum StatusAction {
+    /// Set a draft's status
+    Set {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// idea, writing, review or ready
+        status: WorkflowStatus,
+    },
+    /// Remove a draft's status
+    Clear {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum SeriesAction {
+    /// List series with their parts in order
+    List {
+        /// Include published parts
+        #[arg(long)]
+        published: bool,
+    },
+    /// Add a draft to a series (created if new)
+    Set {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// Series name
+        name: String,
+    },
+    /// Remove a draft from its series
+    Remove {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum GoalsAction {
+    /// Set the target
+    Set {
+        /// Articles to publish each month
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        publish_per_month: u32,
+    },
+    /// Compare the articles published in recent months with the target
+    Status {
+        /// Number of months to show, ending with the current one
+        #[arg(long, default_value_t = 6)]
+        months: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum SavedAction {
+    /// Save a search under a name, replacing any search saved under it
+    Add {
+        name: String,
+
+        /// Keywords and key:value filter terms, e.g. 'tag:rust updated-after:-7d'
+        query: String,
+    },
+    /// Run a saved search and report the drafts added or gone since its last run
+    Run {
+        name: String,
+
+        /// Send a desktop notification when the results changed (needs the `notifications` feature)
+        #[arg(long)]
+        notify: bool,
+
+        #[command(flatten)]
+        sort: SortArgs,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// List the saved searches
+    List,
+    /// Forget a saved search
+    Remove {
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TrashAction {
+    /// List trashed drafts, most recently deleted first
+    List,
+    /// Re-create a trashed draft on dev.to (it gets a new id)
+    Restore {
+        /// Id the draft had when it was deleted
+        id: u64,
+    },
+    /// Delete every trashed draft for good
+    Empty {
+        /// Empty without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CanonicalAction {
+    /// Set the canonical URL, warning if it doesn't load
+    Set {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+
+        /// URL of the original post
+        url: String,
+    },
+    /// Remove the canonical URL
+    Remove {
+        /// Article id, slug, title prefix or number from the last listing
+        #[arg(value_name = "ARTICLE", add = ArgValueCandidates::new(commands::completions::cached_drafts))]
+        article: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TagsAction {
+    /// List the most popular tags
+    Trending {
+        /// Number of tags to show
+        #[arg(long, value_name = "N", default_value_t = 20)]
+        limit: usize,
+    },
+    /// List the tags you follow, by feed weight
+    Followed,
+    /// Show a tag's popularity, whether you follow it and how often you use it
+    Info {
+        /// Tag name
+        tag: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print a setting as resolved from the file, environment and flags
+    Get {
+        /// Dotted key, e.g. api.base_url
+        #[arg(add = ArgValueCandidates::new(commands::completions::config_keys))]
+        key: String,
+    },
+    /// Change a setting in the config file
+    Set {
+        /// Dotted key, e.g. cache.format or profiles.work.api.key
+        #[arg(add = ArgValueCandidates::new(commands::completions::config_keys))]
+        key: String,
+
+        /// New value: a number, true/false, a TOML array, or text
+        value: String,
+    },
+    /// Remove a setting from the config file so its default applies
+    Unset {
+        /// Dotted key
+        #[arg(add = ArgValueCandidates::new(commands::completions::config_keys))]
+        key: String,
+    },
+    /// List the settings in effect (API keys and tokens are shortened)
+    List,
+    /// Open the config file in $EDITOR; it is saved only if still valid
+    Edit,
+    /// Print the path of the config file
+    Path,
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    /// Store the cache as compressed MessagePack (sets cache.format in the config)
+    Compact,
+    /// Check cached articles against their checksums
+    Verify {
+        /// Re-fetch damaged articles from dev.to
+        #[arg(long)]
+        fix: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum TemplateAction {
+    /// List templates
+    List,
+    /// Add a template, from a file or the default template
+    Add {
+        /// Template name
+        name: String,
+
+        /// Markdown file to copy
+        #[arg(long, value_name = "FILE")]
+        from: Option<std::path::PathBuf>,
+
+        /// Replace an existing template
+        #[arg(long)]
+        force: bool,
+    },
+    /// Open a template in $EDITOR (created from the default if missing)
+    Edit {
+        /// Template name
+        name: String,
+    },
+}
+
+#[derive(Args)]
+struct FilterArgs {
+    /// Only include drafts with this tag (repeatable; all must match)
+    #[arg(long = "tag", value_name = "TAG", add = ArgValueCandidates::new(commands::completions::cached_tags))]
+    tags: Vec<String>,
+
+    /// Only include drafts in this series
+    #[arg(long, value_name = "NAME")]
+    series: Option<String>,
+
+    /// Only include drafts belonging to this organization (username)
+    #[arg(long, value_name = "ORG")]
+    org: Option<String>,
+
+    /// Only include drafts with this local status (idea, writing, review or ready)
+    #[arg(long, value_name = "STATUS")]
+    status: Option<WorkflowStatus>,
+
+    /// Only include drafts created after this date (YYYY-MM-DD, or an age such as -7d)
+    #[arg(long, value_name = "DATE", value_parser = parse_date_arg, allow_hyphen_values = true)]
+    created_after: Option<DateTime<Utc>>,
+
+    /// Only include drafts created before this date (YYYY-MM-DD, or an age such as -7d)
+    #[arg(long, value_name = "DATE", value_parser = parse_date_end_arg, allow_hyphen_values = true)]
+    created_before: Option<DateTime<Utc>>,
+
+    /// Only include drafts updated after this date (YYYY-MM-DD, or an age such as -7d)
+    #[arg(long, value_name = "DATE", value_parser = parse_date_arg, allow_hyphen_values = true)]
+    updated_after: Option<DateTime<Utc>>,
+
+    /// Only include drafts updated before this date (YYYY-MM-DD, or an age such as -7d)
+    #[arg(long, value_name = "DATE", value_parser = parse_date_end_arg, allow_hyphen_values = true)]
+    updated_before: Option<DateTime<Utc>>,
+}
+
+impl FilterArgs {
+    fn to_filter(&self) -> Result<ArticleFilter> {
+        let mut filter = ArticleFilter::drafts().tags(self.tags.iter().cloned());
+        if let Some(series) = &self.series {
+            filter = filter.series(series);
+        }
+        if let Some(org) = &self.org {
+            filter = filter.organization(org);
+        }
+        if let Some(status) = self.status {
+            filter = filter.ids(StatusStore::load()?.ids_with(status));
+        }
+        if let Some(at) = self.created_after {
+            filter = filter.created_after(at);
+        }
+        if let Some(at) = self.created_before {
+            filter = filter.created_before(at);
+        }
+        if let Some(at) = self.updated_after {
+            filter = filter.updated_after(at);
+        }
+        if let Some(at) = self.updated_before {
+            filter = filter.updated_before(at);
+        }
+        Ok(filter)
+    }
+}
+
+#[derive(Args)]
+struct SortArgs {
+    /// Sort by title, created, updated or words
+    #[arg(long, value_name = "KEY")]
+    sort: Option<SortKey>,
+
+    /// Reverse the sort order
+    #[arg(long, requires = "sort")]
+    desc: bool,
+}
+
+impl SortArgs {
+    fn needs_body(&self) -> bool {
+        self.sort.is_some_and(SortKey::needs_body)
+    }
+
+    fn apply(&self, articles: &mut [&Article]) {
+        if let Some(key) = self.sort {
+            sort_articles(articles, key, self.desc);
+        }
+    }
+}
+
+#[derive(Args)]
+struct OutputArgs {
+    /// Print each article as this template, e.g. '{id}\t{title}\t{edit_url}'
+    #[arg(long, value_name = "TEMPLATE")]
+    output_template: Option<OutputTemplate>,
+
+    /// Output format: text, table (aligned columns), alfred for Alfred/Raycast script filters,
+    /// count or ids
+    #[arg(long, value_name = "FORMAT", default_value = "text", conflicts_with = "output_template")]
+    format: ListFormat,
+
+    /// Print only the number of matches (same as --format count)
+    #[arg(long, conflicts_with_all = ["output_template", "format", "ids"])]
+    count: bool,
+
+    /// Print only the ids of the matches, one per line (same as --format ids)
+    #[arg(long, conflicts_with_all = ["output_template", "format"])]
+    ids: bool,
+}
+
+/// How a listing command prints its results.
+struct Listing {
+    template: Option<OutputTemplate>,
+    format: ListFormat,
+    /// Label drafts and published articles
+    labels: bool,
+}
+
+impl Listing {
+    fn needs_body(&self) -> bool {
+        // The table shows word counts
+        self.format == ListFormat::Table || self.template.as_ref().is_some_and(OutputTemplate::needs_body)
+    }
+}
+
+fn find_article<'a>(articles: &'a [Article], reference: &str) -> Result<&'a Article> {
+    resolve_article(articles, reference, &load_last_results().unwrap_or_default())
+}
+
+fn parse_interval_arg(input: &str) -> Result<std::time::Duration, String> {
+    parse_interval(input).map_err(|e| e.to_string())
+}
+
+fn parse_age_arg(input: &str) -> Result<chrono::Duration, String> {
+    parse_age(input).map_err(|e| e.to_string())
+}
+
+fn parse_schedule_time_arg(input: &str) -> Result<DateTime<Utc>, String> {
+    dtdrafts_core::schedule::parse_schedule_time(input).map_err(|e| e.to_string())
+}
+
+fn parse_threshold_arg(input: &str) -> Result<f64, String> {
+    match input.parse::<f64>() {
+        Ok(value) if (0.0..=1.0).contains(&value) => Ok(value),
+        _ => Err(format!("expected a number between 0.0 and 1.0, got '{input}'")),
+    }
+}
+
+fn parse_var_arg(input: &str) -> Result<(String, String), String> {
+    input
+        .split_once('=')
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{input}'"))
+}
+
+fn parse_date_arg(input: &str) -> Result<DateTime<Utc>, String> {
+    parse_date(input).map_err(|e| e.to_string())
+}
+
+fn parse_date_end_arg(input: &str) -> Result<DateTime<Utc>, String> {
+    parse_date_end(input).map_err(|e| e.to_string())
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
+async fn main() -> ExitCode {
+    // Answers dynamic completion requests (COMPLETE=<shell> dtdrafts ...) and exits
+    clap_complete::CompleteEnv::with_factory(Cli::command).complete();
+
+    let args: Vec<String> = std::env::args_os().skip(1).map(|arg| arg.to_string_lossy().into_owned()).collect();
+    let args = match rerun_search(args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            return ExitCode::from(2);
+        }
+    };
+    let cli = Cli::parse_from(std::iter::once("dtdrafts").chain(args.iter().map(String::as_str)));
+    cli.color.apply();
+    init_logging(cli.verbose, cli.quiet, cli.log_format, cli.color.stderr_enabled());
+    let quiet = cli.quiet;
+    match run(cli, &args).await {
+        Ok(Outcome::Matches(0)) if quiet => ExitCode::from(1),
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Replaces `search --again`, `search '!N'` and `-q '!N'` with the arguments
+/// of the recorded search they ask for.
+fn rerun_search(args: Vec<String>) -> Result<Vec<String>> {
+    let command = Cli::command();
+    let takes_value = |flag: &str| {
+        command.get_arguments().any(|arg| {
+            let named = match flag.strip_prefix("--") {
+                Some(long) => arg.get_long() == Some(long),
+                None => flag.strip_prefix('-').and_then(|short| short.parse::<char>().ok()).is_some_and(|short| arg.get_short() == Some(short)),
+            };
+            named && arg.get_action().takes_values()
+        })
+    };
+    let Some((trigger, number)) = search_history::rerun_trigger(&args, takes_value) else {
+        return Ok(args);
+    };
+    let history = SearchHistory::load()?;
+    Ok(search_history::expand(&args, trigger, history.get(number)?))
+}
+
+// Warnings and fetch progress only by default, so logs never mix with normal
+// output unless asked for; `--quiet` drops the progress and rate limit notices
+fn init_logging(verbose: u8, quiet: bool, format: LogFormat, ansi: bool) {
+    use tracing_subscriber::EnvFilter;
+    let default = match verbose {
+        0 => "warn",
+        1 => "warn,dtdrafts=info,dtdrafts_core=info",
+        2 => "warn,dtdrafts=debug,dtdrafts_core=debug",
+        _ => "trace",
+    };
+    let progress = if quiet { "off" } else { "info" };
+    let default = format!("{default},{}={progress}", dtdrafts_core::client::PROGRESS_TARGET);
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default));
+    let subscriber = tracing_subscriber::fmt().with_env_filter(filter).with_writer(std::io::stderr);
+    match format {
+        LogFormat::Text => subscriber.with_ansi(ansi).init(),
+        LogFormat::Json => subscriber.json().init(),
+    }
+}
+
+/// What a command produced, used to pick the exit code in `--quiet` mode.
+enum Outcome {
+    Done,
+    Matches(usize),
+}
+
+/// Shared state for command handlers.
+struct App {
+    config: Config,
+    refresh: bool,
+    quiet: bool,
+    pager: bool,
+    fixtures: Option<FixtureMode>,
+    strict: bool,
+}
+
+async fn run(cli: Cli, args: &[String]) -> Result<Outcome> {
+    // Commands that work without an API key
+    match &cli.command {
+        Some(Command::Completions { shell }) => {
+            commands::completions::run(*shell, &mut Cli::command());
+            return Ok(Outcome::Done);
+        }
+        Some(Command::GenMan { out }) => {
+            commands::man::run(Cli::command(), out)?;
+            return Ok(Outcome::Done);
+        }
+        Some(Command::Config { action }) => {
+            let overrides = config::Overrides { profile: cli.profile.clone(), base_url: cli.base_url.clone() };
+            match action {
+                ConfigAction::Get { key } => commands::config::get(&overrides, key)?,
+                ConfigAction::Set { key, value } => commands::config::set(key, value)?,
+                ConfigAction::Unset { key } => commands::config::unset(key)?,
+                ConfigAction::List => commands::config::list(&overrides)?,
+                ConfigAction::Edit => commands::config::edit(&overrides)?,
+                ConfigAction::Path => commands::config::path()?,
+            }
+            return Ok(Outcome::Done);
+        }
+        _ => {}
+    }
+
+    let fixtures = match (cli.record, cli.replay) {
+        (Some(dir), _) => Some(FixtureMode::Record(dir)),
+        (_, Some(dir)) => Some(FixtureMode::Replay(dir)),
+        _ => None,
+    };
+    let overrides = config::Overrides { profile: cli.profile, base_url: cli.base_url };
 
     // Set API key
     if let Some(api_key) = cli.set_api_key {
-        let config = Config { api_key };
-        save_config(&config).context("Failed to save API key")?;
-        println!("{}", "API key saved successfully!".green());
-        return Ok(());
+        // The key is checked against the base URL and proxy it will be used with
+        let config = config::load(&overrides).context("Failed to load configuration")?;
+        let mut builder = DevToClient::builder().config(&config).api_key(api_key.as_str());
+        if let Some(mode) = fixtures {
+            builder = builder.fixtures(mode);
+        }
+        let client = builder.build()?.with_progress(false);
+        commands::config::set_api_key(&client, overrides.profile.as_deref(), &api_key, cli.force, cli.quiet).await?;
+        return Ok(Outcome::Done);
     }
 
     // Load config
-    let config = load_config().context("Failed to load configuration")?;
-
-    // Get articles (from cache or API)
-    let prev_cache_count = load_articles_cache().map(|a| a.len()).unwrap_or(0);
-    if cli.refresh && prev_cache_count > 0 {
-        let est_pages = (prev_cache_count as f64 / 1000.0).ceil() as u64;
-        let est_time = est_pages;
-        println!(
-            "Current cache: {prev_cache_count} articles. Estimated time to refresh: about {est_time} seconds ({est_pages} pages)."
-        );
-    }
-    let articles = if cli.refresh || load_articles_cache().unwrap_or_default().is_empty() {
-        println!("{}", "Fetching articles from dev.to...".blue());
-        let client = DevToClient::new(config.api_key);
-        let articles = client.get_my_articles().await?;
-        save_articles_cache(&articles).context("Failed to save articles cache")?;
-        println!("{}", "Articles cached successfully!".green());
-        articles
-    } else {
-        load_articles_cache().context("Failed to load articles cache")?
+    let mut config = match load_config_with(&overrides) {
+        Ok(config) => config,
+        // A replayed session needs no API key
+        Err(_) if matches!(fixtures, Some(FixtureMode::Replay(_))) => Config { base_url: overrides.base_url.clone(), ..Default::default() },
+        Err(e) => return Err(e.context("Failed to load configuration")),
     };
+    if matches!(fixtures, Some(FixtureMode::Replay(_))) {
+        // A replayed session has no side effects: hooks don't fire, and the
+        // cache is neither converted nor written (see `commands::cache::save`)
+        config.hooks = hooks::HooksConfig::default();
+    } else {
+        use_cache_format(config.cache_format).context("Failed to convert the article cache")?;
+        match upgrade_articles_cache() {
+            Ok(Some(from)) => tracing::info!(from, to = migrate::CACHE_VERSION, "upgraded the article cache"),
+            Ok(None) => {}
+            // Loading reports the same problem where the cache is needed, and --refresh replaces it
+            Err(e) => tracing::warn!("Could not upgrade the article cache: {e:#}"),
+        }
+    }
+    // A cache older than `cache.ttl_secs` is refetched as if --refresh were given
+    let refresh = cli.refresh || cache::is_expired(config.cache_ttl_secs)?;
+    let app = App { config, refresh, quiet: cli.quiet, pager: !cli.no_pager, fixtures, strict: cli.strict };
+
+    if cli.serve_stdio {
+        commands::rpc::run(&app.client()?.with_progress(false), &app.config.hooks).await?;
+        return Ok(Outcome::Done);
+    }
+
+    // Searches are kept for `search --again`, whether run at a terminal or piped
+    let searching = matches!(cli.command, Some(Command::Search { .. })) || (cli.command.is_none() && cli.query.is_some());
+    if searching && app.config.search_history != Some(false) {
+        if let Err(e) = search_history::record(args, Utc::now()) {
+            tracing::warn!("Failed to record the search: {e:#}");
+        }
+    }
 
     // Filter and display articles
-    if cli.all {
-        let drafts = get_draft_articles(&articles);
-        display_articles(&drafts);
-    } else if let Some(query) = cli.query {
-        let filtered_articles = search_articles(&articles, &query);
-        display_articles(&filtered_articles);
-    } else {
-        println!("{}", "Usage:".yellow().bold());
-        println!("  dtdrafts -q <query>    Search draft articles");
-        println!("  dtdrafts --all         Show all draft articles");
-        println!("  dtdrafts --refresh     Refresh article cache");
-        println!("  dtdrafts --set-api-key <key>  Set dev.to API key");
-        println!();
-        println!("{}", "Examples:".yellow().bold());
-        println!("  dtdrafts -q aws");
-        println!("  dtdrafts -q rust");
-        println!("  dtdrafts --all");
+    let outcome = match cli.command {
+        Some(Command::Search { query, again: _, engine, open, edit, include_published, filter, sort, output }) => {
+            let query = query.unwrap_or_default();
+            let mut listing = app.listing(output)?;
+            listing.labels = include_published;
+            let filter = filter.to_filter()?;
+            let published = if include_published { app.load_published_articles().await? } else { Vec::new() };
+            let articles = match engine {
+                SearchEngine::Streamed => {
+                    let with_bodies = sort.needs_body() || listing.needs_body();
+                    app.search_streamed(&query, &filter, with_bodies).await?
+                }
+                SearchEngine::Linear | SearchEngine::Index => app.load_articles().await?,
+            };
+            let found = match engine {
+                SearchEngine::Linear => search_articles_with(&articles, &query, &filter),
+                SearchEngine::Index => search_with_index(&articles, &query, &filter)?,
+                SearchEngine::Streamed => articles.iter().collect(),
+            };
+            let mut filtered_articles = with_note_matches(found, &articles, &query, &filter)?;
+            // Published articles are always scanned, whatever the engine
+            filtered_articles.extend(search_articles_with(&published, &query, &filter.clone().include_published()));
+            sort.apply(&mut filtered_articles);
+            if open || edit {
+                let article = commands::pick::pick(&filtered_articles)?;
+                if open {
+                    commands::pick::open_in_browser(article)?;
+                } else {
+                    commands::edit::run(&app.client()?, &app.config, article.id, false).await?;
+                }
+                Outcome::Done
+            } else {
+                app.display(&filtered_articles, &listing)?
+            }
+        }
+        Some(Command::Searches) => Outcome::Matches(commands::history::searches(app.quiet)?),
+        Some(Command::Saved { action }) => match action {
+            SavedAction::Add { name, query } => {
+                commands::saved::add(&name, &query, app.quiet)?;
+                Outcome::Done
+            }
+            SavedAction::Run { name, notify, sort, output } => {
+                let listing = app.listing(output)?;
+                let expression = commands::saved::expression(&app.config.saved, &name)?;
+                let articles = app.load_articles().await?;
+                let mut found = dtdrafts_core::saved::run(&articles, expression)?;
+                sort.apply(&mut found);
+                let outcome = app.display(&found, &listing)?;
+                commands::saved::report(&name, &found, notify, app.quiet)?;
+                outcome
+            }
+            SavedAction::List => Outcome::Matches(commands::saved::list(&app.config.saved, app.quiet)?),
+            SavedAction::Remove { name } => {
+                commands::saved::remove(&app.config.saved, &name)?;
+                Outcome::Done
+            }
+        },
+        Some(Command::List { group_by, filter, sort, output }) => {
+            let listing = app.listing(output)?;
+            let articles = if sort.needs_body() || listing.needs_body() {
+                app.load_articles().await?
+            } else {
+                app.load_articles_without_bodies().await?
+            };
+            let mut drafts = filter_articles(&articles, &filter.to_filter()?);
+            sort.apply(&mut drafts);
+            match group_by {
+                Some(by) => {
+                    let statuses = if by == GroupBy::Status { StatusStore::load()? } else { StatusStore::default() };
+                    let groups = group_articles(&drafts, by, &statuses);
+                    if !app.quiet {
+                        commands::pager::page(&format_groups(&groups, by), app.pager)?;
+                    }
+                    Outcome::Matches(drafts.len())
+                }
+                None => app.display(&drafts, &listing)?,
+            }
+        }
+        Some(Command::Stale { older_than }) => {
+            let articles = app.load_articles_without_bodies().await?;
+            Outcome::Matches(commands::stale::run(&articles, older_than, app.quiet, app.pager)?)
+        }
+        Some(Command::Remind { older_than, notify, json }) => {
+            let articles = app.load_articles_without_bodies().await?;
+            Outcome::Matches(commands::remind::run(&articles, older_than, notify, json, app.quiet)?)
+        }
+        Some(Command::Stats { json, readability, filter }) => {
+            let articles = app.load_articles().await?;
+            let drafts = filter_articles(&articles, &filter.to_filter()?);
+            if !app.quiet {
+                commands::stats::run(&drafts, &app.config.word_count, json, readability)?;
+            }
+            Outcome::Matches(drafts.len())
+        }
+        Some(Command::Calendar { weeks, filter }) => {
+            let articles = app.load_articles_without_bodies().await?;
+            let drafts = filter_articles(&articles, &filter.to_filter()?);
+            if !app.quiet {
+                let activity = dtdrafts_core::calendar::Activity::from_articles(&drafts);
+                print!("{}", dtdrafts_core::calendar::render(&activity, Utc::now().date_naive(), weeks as usize));
+            }
+            Outcome::Matches(drafts.len())
+        }
+        Some(Command::Vocab { ngram, sort, limit, min_count, keep_stopwords, csv, filter }) => {
+            let articles = app.load_articles().await?;
+            let drafts = filter_articles(&articles, &filter.to_filter()?);
+            let options = dtdrafts_core::vocab::VocabOptions { ngram: ngram.into(), skip_stopwords: !keep_stopwords, min_count };
+            let limit = (!csv).then_some(limit);
+            Outcome::Matches(commands::vocab::run(&drafts, &options, sort, limit, csv, app.quiet))
+        }
+        Some(Command::Goals { action }) => match action {
+            GoalsAction::Set { publish_per_month } => {
+                commands::goals::set(publish_per_month)?;
+                Outcome::Done
+            }
+            GoalsAction::Status { months } => {
+                let published = app.fetch_published_articles().await?;
+                Outcome::Matches(commands::goals::status(&published, months, app.quiet)?)
+            }
+        },
+        Some(Command::Analytics { sort, limit, csv }) => {
+            commands::analytics::run(&app.client()?, sort, limit, csv).await?;
+            Outcome::Done
+        }
+        Some(Command::Tags { action }) => {
+            let client = app.client()?;
+            match action {
+                TagsAction::Trending { limit } => commands::tags::trending(&client, limit).await?,
+                TagsAction::Followed => commands::tags::followed(&client).await?,
+                TagsAction::Info { tag } => {
+                    let articles = app.load_articles().await?;
+                    commands::tags::info(&client, &articles, &tag).await?;
+                }
+            }
+            Outcome::Done
+        }
+        Some(Command::Dupes { threshold, filter }) => {
+            let articles = app.load_articles().await?;
+            let drafts = filter_articles(&articles, &filter.to_filter()?);
+            Outcome::Matches(commands::dupes::run(&drafts, threshold, app.quiet))
+        }
+        Some(Command::Related { article, limit, drafts_only }) => {
+            let articles = app.load_articles().await?;
+            let article = find_article(&articles, &article)?;
+            let published = if drafts_only {
+                Vec::new()
+            } else {
+                app.client()?.with_progress(false).get_my_published_articles().await?
+            };
+            let others: Vec<&Article> = articles.iter().chain(&published).collect();
+            Outcome::Matches(commands::related::run(article, &others, limit, app.quiet))
+        }
+        Some(Command::SuggestTags { article, apply }) => {
+            let articles = app.load_articles().await?;
+            let article = find_article(&articles, &article)?;
+            commands::tags::suggest(&app.client()?, &articles, article, apply).await?;
+            Outcome::Done
+        }
+        Some(Command::New { title, template, series, vars, org }) => {
+            let client = app.client()?;
+            commands::template::new_draft(&client, &title, template.as_deref(), series.as_deref(), vars, org.as_deref())
+                .await?;
+            Outcome::Done
+        }
+        Some(Command::Series { action }) => match action {
+            SeriesAction::List { published } => {
+                let articles = app.load_articles_without_bodies().await?;
+                let articles: Vec<&Article> = if published {
+                    articles.iter().collect()
+                } else {
+                    get_draft_articles(&articles)
+                };
+                Outcome::Matches(commands::series::list(&articles, app.quiet, app.pager)?)
+            }
+            SeriesAction::Set { article, name } => {
+                let id = app.resolve_id(&article).await?;
+                commands::series::set(&app.client()?, id, Some(&name)).await?;
+                Outcome::Done
+            }
+            SeriesAction::Remove { article } => {
+                let id = app.resolve_id(&article).await?;
+                commands::series::set(&app.client()?, id, None).await?;
+                Outcome::Done
+            }
+        },
+        Some(Command::Canonical { action }) => {
+            let (article, url) = match action {
+                CanonicalAction::Set { article, url } => (article, Some(url)),
+                CanonicalAction::Remove { article } => (article, None),
+            };
+            let id = app.resolve_id(&article).await?;
+            commands::canonical::set(&app.client()?, id, url.as_deref()).await?;
+            Outcome::Done
+        }
+        Some(Command::Status { action }) => {
+            let articles = app.load_articles_without_bodies().await?;
+            match action {
+                StatusAction::Set { article, status } => commands::status::set(find_article(&articles, &article)?, status)?,
+                StatusAction::Clear { article } => commands::status::clear(find_article(&articles, &article)?)?,
+            }
+            Outcome::Done
+        }
+        Some(Command::Note { article, text, remove, clear }) => {
+            let articles = app.load_articles_without_bodies().await?;
+            let article = find_article(&articles, &article)?;
+            match (text, remove) {
+                (Some(text), _) => commands::note::add(article, &text)?,
+                (None, Some(number)) => commands::note::remove(article, number)?,
+                (None, None) if clear => commands::note::clear(article)?,
+                (None, None) => return Ok(Outcome::Matches(commands::note::list(article, app.quiet)?)),
+            }
+            Outcome::Done
+        }
+        Some(Command::Board) => {
+            let articles = app.load_articles_without_bodies().await?;
+            let drafts = get_draft_articles(&articles);
+            Outcome::Matches(commands::status::print_board(&drafts, app.quiet, app.pager)?)
+        }
+        Some(Command::Template { action }) => match action {
+            TemplateAction::List => {
+                commands::template::list()?;
+                Outcome::Done
+            }
+            TemplateAction::Add { name, from, force } => {
+                commands::template::add(&name, from.as_deref(), force)?;
+                Outcome::Done
+            }
+            TemplateAction::Edit { name } => {
+                commands::template::edit(&name, app.config.editor.as_deref())?;
+                Outcome::Done
+            }
+        },
+        Some(Command::Edit { article, force }) => {
+            let id = app.resolve_id(&article).await?;
+            commands::edit::run(&app.client()?, &app.config, id, force).await?;
+            Outcome::Done
+        }
+        Some(Command::History { article, show }) => {
+            let id = app.resolve_id(&article).await?;
+            Outcome::Matches(commands::history::run(id, show.as_deref(), app.quiet)?)
+        }
+        Some(Command::Trash { action }) => match action {
+            TrashAction::List => Outcome::Matches(commands::trash::list(app.quiet)?),
+            TrashAction::Restore { id } => {
+                commands::trash::restore(&app.client()?, id).await?;
+                Outcome::Done
+            }
+            TrashAction::Empty { yes } => {
+                commands::trash::empty(yes)?;
+                Outcome::Done
+            }
+        },
+        Some(Command::Undo { yes, force }) => {
+            commands::undo::run(&app.client()?, &app.config.hooks, yes, force).await?;
+            Outcome::Done
+        }
+        Some(Command::Revert { article, to, yes }) => {
+            let id = app.resolve_id(&article).await?;
+            commands::history::revert(&app.client()?, &app.config.hooks, id, &to, yes).await?;
+            Outcome::Done
+        }
+        Some(Command::Lint { article, all, links, tags, spelling, language, prose, code, canonical }) => {
+            let articles = app.load_articles().await?;
+            let drafts = match article {
+                Some(article) if !all => vec![find_article(&articles, &article)?],
+                _ => get_draft_articles(&articles),
+            };
+            let popular_tags = if tags {
+                let popular = app.client()?.get_popular_tags(dtdrafts_core::tags::POPULAR_TAG_LIMIT).await?;
+                Some(popular.into_iter().map(|tag| tag.name.to_lowercase()).collect::<Vec<_>>())
+            } else {
+                None
+            };
+            let checker = if spelling {
+                let language = language.or_else(|| app.config.spelling_language.clone());
+                Some(SpellChecker::load(language.as_deref().unwrap_or(dtdrafts_core::spelling::DEFAULT_LANGUAGE))?)
+            } else {
+                None
+            };
+            let options = commands::lint::LintOptions {
+                links,
+                popular_tags: popular_tags.as_deref(),
+                spelling: checker.as_ref(),
+                prose,
+                code,
+                canonical,
+            };
+            let (errors, _) = commands::lint::run(&drafts, &options, app.quiet).await?;
+            if errors > 0 {
+                return Err(anyhow::anyhow!("lint found {errors} error(s)"));
+            }
+            Outcome::Done
+        }
+        Some(Command::Images { article, download, out }) => {
+            let articles = app.load_articles().await?;
+            let article = find_article(&articles, &article)?;
+            let dir = download.then(|| out.unwrap_or_else(|| std::path::Path::new("assets").join(article.id.to_string())));
+            Outcome::Matches(commands::images::run(article, dir.as_deref()).await?)
+        }
+        Some(Command::Cover { article, from_file, url, out, background, foreground, .. }) => {
+            let articles = app.load_articles().await?;
+            let article = find_article(&articles, &article)?;
+            let out = out.unwrap_or_else(|| format!("cover-{}.png", article.id).into());
+            let source = match (url.as_deref(), from_file.as_deref()) {
+                (Some(url), _) => commands::cover::CoverSource::Url(url),
+                (None, Some(path)) => commands::cover::CoverSource::File(path),
+                (None, None) => {
+                    let mut card = commands::cover::title_card(article);
+                    card.background = background.unwrap_or(card.background);
+                    card.foreground = foreground.unwrap_or(card.foreground);
+                    commands::cover::CoverSource::Generate { card, out: &out }
+                }
+            };
+            commands::cover::run(&app.client()?, article.id, source).await?;
+            Outcome::Done
+        }
+        Some(Command::Toc { article, insert, max_level, repo }) => {
+            if insert {
+                let id = app.resolve_id(&article).await?;
+                match repo {
+                    Some(repo) => commands::toc::insert_local(&repo, id, max_level)?,
+                    None => commands::toc::insert(&app.client()?, &app.config.hooks, id, max_level).await?,
+                }
+            } else {
+                let articles = app.load_articles().await?;
+                commands::toc::print(find_article(&articles, &article)?, max_level)?;
+            }
+            Outcome::Done
+        }
+        Some(Command::Outline { article, max_level }) => {
+            let articles = app.load_articles().await?;
+            commands::toc::outline(find_article(&articles, &article)?, max_level, &app.config.word_count)?;
+            Outcome::Done
+        }
+        Some(Command::Split { article, by, series, dry_run, yes }) => {
+            let id = app.resolve_id(&article).await?;
+            let client = app.client()?;
+            let article = client.get_my_article(id).await?;
+            commands::split::run(&client, &article, &commands::split::SplitOptions { level: by, series, dry_run, yes }).await?;
+            Outcome::Done
+        }
+        Some(Command::Clone { article, title }) => {
+            let id = app.resolve_id(&article).await?;
+            let client = app.client()?;
+            let original = client.get_my_article(id).await?;
+            commands::clone::run(&client, &original, &title, &app.config.clone).await?;
+            Outcome::Done
+        }
+        Some(Command::Merge { articles: references, into, trash }) => {
+            let mut ids = Vec::new();
+            for reference in &references {
+                let id = app.resolve_id(reference).await?;
+                if ids.contains(&id) {
+                    return Err(anyhow::anyhow!("{reference} is given more than once"));
+                }
+                ids.push(id);
+            }
+            commands::merge::run(&app.client()?, &ids, &into, trash).await?;
+            Outcome::Done
+        }
+        Some(Command::Seo { article, keyword, json }) => {
+            let articles = app.load_articles().await?;
+            commands::seo::run(find_article(&articles, &article)?, keyword.as_deref(), json)?;
+            Outcome::Done
+        }
+        Some(Command::Publish { article, dry_run, force, check_markdown }) => {
+            let id = app.resolve_id(&article).await?;
+            let options = commands::publish::PublishOptions { dry_run, force, markdown: check_markdown };
+            commands::publish::run(&app.client()?, &app.config.hooks, id, options).await?;
+            Outcome::Done
+        }
+        Some(Command::Crosspost { article, to, dry_run }) => {
+            let id = app.resolve_id(&article).await?;
+            commands::crosspost::run(&app.config, id, to, dry_run).await?;
+            Outcome::Done
+        }
+        Some(Command::Schedule { action, article, at }) => match (action, article, at) {
+            (Some(ScheduleAction::List), _, _) => Outcome::Matches(commands::schedule::list(app.quiet)?),
+            (Some(ScheduleAction::Cancel { id }), _, _) => {
+                commands::schedule::cancel(id)?;
+                Outcome::Done
+            }
+            (None, Some(article), Some(at)) => {
+                let id = app.resolve_id(&article).await?;
+                commands::schedule::add(&app.client()?, id, at).await?;
+                Outcome::Done
+            }
+            (None, _, _) => unreachable!("clap requires <ARTICLE> and --at without a subcommand"),
+        },
+        Some(Command::Scheduler { action: SchedulerAction::Run { once, interval } }) => {
+            commands::schedule::run(&app.client()?, &app.config.hooks, once, std::time::Duration::from_secs(interval)).await?;
+            Outcome::Done
+        }
+        Some(Command::Daemon { interval, port, saved }) => {
+            let client = app.client()?.with_progress(false);
+            let saved = saved.then_some(&app.config.saved);
+            let refresh = commands::daemon::run(&client, &app.config, interval, saved, app.quiet);
+            match port {
+                Some(port) => {
+                    let listener = commands::serve::bind(port).await?;
+                    if !app.quiet {
+                        println!("{} http://127.0.0.1:{port}/drafts", "Serving drafts at".green());
+                    }
+                    tokio::select! {
+                        result = refresh => result?,
+                        result = commands::serve::serve(listener) => result?,
+                    }
+                }
+                None => refresh.await?,
+            }
+            Outcome::Done
+        }
+        Some(Command::Serve { port }) => {
+            commands::serve::run(port).await?;
+            Outcome::Done
+        }
+        Some(Command::Show { article, render }) => {
+            let articles = app.load_articles_without_bodies().await?;
+            let found = find_article(&articles, &article)?;
+            let article = Article { body_markdown: found.body(&CachedBodies::load()?).map(str::to_string), ..found.clone() };
+            commands::show::run(&article, render, NoteStore::load()?.get(article.id));
+            Outcome::Done
+        }
+        Some(Command::Comments { article }) => {
+            let id = app.resolve_id(&article).await?;
+            Outcome::Matches(commands::comments::run(&app.client()?, id, app.quiet).await?)
+        }
+        Some(Command::Preview { article, file, port }) => {
+            let source = match (article, file) {
+                (_, Some(path)) => commands::preview::Source::File(path),
+                (Some(article), None) => commands::preview::Source::Cached(app.resolve_id(&article).await?),
+                (None, None) => unreachable!("clap requires an article or --file"),
+            };
+            commands::preview::run(&app.client()?, source, port).await?;
+            Outcome::Done
+        }
+        Some(Command::Cache { action }) => {
+            match action {
+                CacheAction::Compact => commands::cache::compact()?,
+                CacheAction::Verify { fix: false } => commands::cache::verify(None).await?,
+                CacheAction::Verify { fix: true } => {
+                    commands::cache::verify(Some((&app.client()?, app.config.cache_format))).await?
+                }
+            }
+            Outcome::Done
+        }
+        Some(Command::Backup { out }) => {
+            let articles = app.load_articles().await?;
+            let drafts = get_draft_articles(&articles);
+            let out = out.unwrap_or_else(|| format!("dtdrafts-backup-{}.tar.gz", Utc::now().format("%Y-%m-%d")).into());
+            commands::backup::backup(&drafts, &out)?;
+            Outcome::Done
+        }
+        Some(Command::Export { format, dir, filter }) => {
+            let articles = app.load_articles().await?;
+            let drafts = filter_articles(&articles, &filter.to_filter()?);
+            commands::export::run(&drafts, format, &dir)?;
+            Outcome::Matches(drafts.len())
+        }
+        Some(Command::Feed { out, filter }) => {
+            let articles = app.load_articles().await?;
+            let drafts = filter_articles(&articles, &filter.to_filter()?);
+            commands::feed::run(&drafts, out.as_deref())?;
+            Outcome::Matches(drafts.len())
+        }
+        Some(Command::Report { html, filter }) => {
+            let articles = app.load_articles().await?;
+            let drafts = filter_articles(&articles, &filter.to_filter()?);
+            commands::report::html(&drafts, &app.config.word_count, &html)?;
+            Outcome::Matches(drafts.len())
+        }
+        Some(Command::Import { from, dry_run }) => {
+            commands::import::run(&app.client()?, &from, dry_run).await?;
+            Outcome::Done
+        }
+        Some(Command::Restore { archive, dry_run }) => {
+            commands::backup::restore(&app.client()?, &archive, dry_run).await?;
+            Outcome::Done
+        }
+        Some(Command::Sync { repo, push, strategy, frontmatter }) => {
+            let options = commands::sync::SyncOptions {
+                push,
+                strategy,
+                frontmatter,
+                custom: &app.config.frontmatter,
+                cache_format: app.config.cache_format,
+            };
+            commands::sync::run(&app.client()?, &app.config.hooks, &repo, &options).await?;
+            Outcome::Done
+        }
+        Some(Command::Watch { dir, debounce, frontmatter }) => {
+            let style = frontmatter.or_else(|| commands::sync::saved_style(&dir)).unwrap_or_default();
+            let mapping = style.mapping(&app.config.frontmatter);
+            let debounce = std::time::Duration::from_secs(debounce);
+            commands::watch::run(&app.client()?, &app.config.hooks, &dir, debounce, &mapping).await?;
+            Outcome::Done
+        }
+        Some(Command::Completions { .. } | Command::GenMan { .. } | Command::Config { .. }) => {
+            unreachable!("handled before loading the config")
+        }
+        Some(Command::Bulk { filter, action, dir, dry_run, yes, interval }) => {
+            let filter = filter::parse_filter(&filter)?;
+            let articles = app.load_articles().await?;
+            let selected = filter_articles(&articles, &filter);
+            let options = commands::bulk::BulkOptions { dry_run, yes, interval };
+            commands::bulk::run(&app.client()?, &app.config.hooks, &selected, &action, &dir, &options).await?;
+            Outcome::Done
+        }
+        Some(Command::Retag { from, to, all_drafts, articles: references, dry_run, interval }) => {
+            let filter = if all_drafts {
+                ArticleFilter::drafts().tag(from.trim_start_matches('#'))
+            } else {
+                let mut ids = Vec::new();
+                for reference in &references {
+                    ids.push(app.resolve_id(reference).await?);
+                }
+                ArticleFilter::new().ids(ids)
+            };
+            let articles = app.load_articles().await?;
+            let selected = filter_articles(&articles, &filter);
+            commands::retag::run(&app.client()?, &app.config.hooks, &selected, &from, &to, dry_run, interval).await?;
+            Outcome::Done
+        }
+        Some(Command::Replace { pattern, replacement, regex, filter, dry_run, yes, interval }) => {
+            let replacement = dtdrafts_core::replace::Replacement::new(&pattern, &replacement, regex)?;
+            let filter = filter.to_filter()?;
+            let articles = app.load_articles().await?;
+            let selected = filter_articles(&articles, &filter);
+            let options = commands::bulk::BulkOptions { dry_run, yes, interval };
+            commands::replace::run(&app.client()?, &app.config.hooks, &selected, &replacement, &options).await?;
+            Outcome::Done
+        }
+        Some(Command::Whoami { json }) => {
+            commands::whoami::run(&app.client()?, &app.config, &overrides, json).await?;
+            Outcome::Done
+        }
+        Some(Command::Selftest { live }) => {
+            commands::selftest::run(&app.config, live).await?;
+            Outcome::Done
+        }
+        None if cli.all => {
+            let listing = app.listing(cli.output)?;
+            let articles = if listing.needs_body() {
+                app.load_articles().await?
+            } else {
+                app.load_articles_without_bodies().await?
+            };
+            let drafts = get_draft_articles(&articles);
+            app.display(&drafts, &listing)?
+        }
+        None => {
+            let articles = app.load_articles().await?;
+            if let Some(query) = cli.query {
+                let listing = app.listing(cli.output)?;
+                let filtered_articles =
+                    with_note_matches(search_articles(&articles, &query), &articles, &query, &ArticleFilter::drafts())?;
+                app.display(&filtered_articles, &listing)?
+            } else {
+                if !app.quiet {
+                    print_usage();
+                }
+                Outcome::Done
+            }
+        }
+    };
+
+    Ok(outcome)
+}
+
+// Local notes are searched too; their matches follow the content matches
+fn with_note_matches<'a>(
+    mut found: Vec<&'a Article>,
+    articles: &'a [Article],
+    query: &str,
+    filter: &ArticleFilter,
+) -> Result<Vec<&'a Article>> {
+    let ids = NoteStore::load()?.matching_ids(query);
+    for article in articles {
+        if ids.contains(&article.id) && filter.matches(article) && !found.iter().any(|f| f.id == article.id) {
+            found.push(article);
+        }
+    }
+    Ok(found)
+}
+
+#[cfg(feature = "index")]
+fn search_with_index<'a>(articles: &'a [Article], query: &str, filter: &ArticleFilter) -> Result<Vec<&'a Article>> {
+    dtdrafts_core::index::search_articles_ranked(articles, query, filter)
+}
+
+#[cfg(not(feature = "index"))]
+fn search_with_index<'a>(_: &'a [Article], _: &str, _: &ArticleFilter) -> Result<Vec<&'a Article>> {
+    Err(anyhow::anyhow!(
+        "dtdrafts was built without the `index` feature. Reinstall with: cargo install --path . --features index"
+    ))
+}
+
+impl App {
+    fn client(&self) -> Result<DevToClient> {
+        let mut builder = DevToClient::builder().config(&self.config).strict(self.strict);
+        if let Some(mode) = &self.fixtures {
+            builder = builder.fixtures(mode.clone());
+        }
+        Ok(builder.build()?.with_progress(!self.quiet))
     }
 
-    Ok(())
+    fn display(&self, articles: &[&Article], listing: &Listing) -> Result<Outcome> {
+        if let Some(output) = listing.format.render(articles).filter(|_| !self.quiet) {
+            print!("{output}");
+        } else if !self.quiet {
+            let output = match &listing.template {
+                Some(template) => {
+                    let now = Utc::now();
+                    articles
+                        .iter()
+                        .map(|article| template.render(article, &self.config.word_count, now) + "\n")
+                        .collect()
+                }
+                None if listing.format == ListFormat::Table => {
+                    format_table(articles, &self.config.word_count, commands::pager::terminal_width())
+                }
+                None if listing.labels => format_labelled_articles(articles),
+                None => format_articles(articles),
+            };
+            commands::pager::page(&output, self.pager)?;
+        }
+        if let Err(e) = save_last_results(articles) {
+            tracing::debug!(error = %e, "could not save last results");
+        }
+        Ok(Outcome::Matches(articles.len()))
+    }
+
+    // --output-template wins over the config default, which formats other than text ignore
+    fn listing(&self, output: OutputArgs) -> Result<Listing> {
+        let format = match (output.count, output.ids) {
+            (true, _) => ListFormat::Count,
+            (_, true) => ListFormat::Ids,
+            _ => output.format,
+        };
+        let template = match (output.output_template, &self.config.output_template) {
+            (Some(template), _) => Some(template),
+            (None, Some(_)) if format != ListFormat::Text => None,
+            (None, Some(template)) => Some(
+                template
+                    .parse()
+                    .map_err(|e| anyhow::anyhow!("Invalid output_template in config: {e}"))?,
+            ),
+            (None, None) => None,
+        };
+        Ok(Listing { template, format, labels: false })
+    }
+
+    // Commands that fetch the article themselves only need its id. References
+    // are resolved against the cache; numbers that aren't cached (e.g.
+    // published articles) are passed through as ids.
+    async fn resolve_id(&self, reference: &str) -> Result<u64> {
+        let articles = self.load_articles_without_bodies().await?;
+        let last_results = load_last_results().unwrap_or_default();
+        match resolve_article(&articles, reference, &last_results) {
+            Ok(article) => Ok(article.id),
+            Err(e) => {
+                let number: u64 = reference.trim().parse().map_err(|_| e)?;
+                let index = usize::try_from(number).ok().and_then(|n| n.checked_sub(1));
+                Ok(index.and_then(|i| last_results.get(i)).copied().unwrap_or(number))
+            }
+        }
+    }
+
+    // Listing never needs bodies, so it skips the file they're cached in
+    async fn load_articles_without_bodies(&self) -> Result<Vec<Article>> {
+        if !self.refresh {
+            let articles = load_articles_cache_without_bodies().context("Failed to load articles cache")?;
+            if !articles.is_empty() {
+                return Ok(articles);
+            }
+        }
+        self.load_articles().await
+    }
+
+    // Only the matches are kept in memory. Fetching (with --refresh or on
+    // first use) needs all articles at once anyway.
+    async fn search_streamed(&self, query: &str, filter: &ArticleFilter, with_bodies: bool) -> Result<Vec<Article>> {
+        if self.refresh || !has_articles_cache()? {
+            self.load_articles().await?;
+        }
+        let also = NoteStore::load()?.matching_ids(query);
+        search_cache_streamed(query, filter, &also, with_bodies).context("Failed to search the articles cache")
+    }
+
+    // Published articles are fetched once and then read from their own cache
+    async fn load_published_articles(&self) -> Result<Vec<Article>> {
+        if !self.refresh {
+            if let Some(articles) = load_published_cache()? {
+                return Ok(articles);
+            }
+        }
+        self.fetch_published_articles().await
+    }
+
+    // For views of what was published lately, which a cache of any age would get wrong
+    async fn fetch_published_articles(&self) -> Result<Vec<Article>> {
+        if !self.quiet {
+            println!("{}", "Fetching published articles from dev.to...".blue());
+        }
+        let client = self.client()?;
+        let articles = client.get_my_published_articles().await?;
+        if !client.is_replaying() {
+            save_published_cache(&articles, self.config.cache_format).context("Failed to save the published articles cache")?;
+        }
+        Ok(articles)
+    }
+
+    // Get articles (from cache or API)
+    async fn load_articles(&self) -> Result<Vec<Article>> {
+        let prev_cache_count = load_articles_cache().map(|a| a.len()).unwrap_or(0);
+        // How long a refresh takes is up to dev.to's rate limiting, so only the pages are estimated
+        if self.refresh && prev_cache_count > 0 && !self.quiet {
+            let est_pages = prev_cache_count.div_ceil(1000);
+            println!("Current cache: {prev_cache_count} articles. Refreshing about {est_pages} page(s).");
+        }
+        let articles = if self.refresh || load_articles_cache().unwrap_or_default().is_empty() {
+            if !self.quiet {
+                println!("{}", "Fetching articles from dev.to...".blue());
+            }
+            let client = self.client()?;
+            let articles = client.get_my_articles().await?;
+            commands::cache::save(&client, &articles, self.config.cache_format)?;
+            if !self.quiet && !client.is_replaying() {
+                println!("{}", "Articles cached successfully!".green());
+            }
+            let fetched: Vec<&Article> = articles.iter().collect();
+            commands::history::pulled(&client, &fetched);
+            hooks::fire(&self.config.hooks, HookEvent::Refresh, &fetched).await;
+            articles
+        } else {
+            load_articles_cache().context("Failed to load articles cache")?
+        };
+        Ok(articles)
+    }
 }
 
+fn print_usage() {
+    println!("{}", "Usage:".yellow().bold());
+    println!("  dtdrafts -q <query>    Search draft articles");
+    println!("  dtdrafts --all         Show all draft articles");
+    println!("  dtdrafts --serve-stdio Answer JSON-RPC on stdin/stdout for editor plugins");
+    println!("  dtdrafts search <query> [--tag <tag>]...  Search drafts with filters");
+    println!("      --open|--edit      Act on the match, picking interactively if several match");
+    println!("      --include-published  Also search published articles, labelled LIVE");
+    println!("      --again|'!N'       Run the last (or Nth most recent) search again; `dtdrafts searches` lists them");
+    println!("  dtdrafts searches                         List recent searches, numbered for '!N'");
+    println!("  dtdrafts saved add <name> <query>|run <name>|list|remove  Name searches and see what changed");
+    println!("  dtdrafts list [--tag <tag>]... [--series <name>] [--org <org>]  List drafts with filters");
+    println!("      --created-after/--created-before/--updated-after/--updated-before <YYYY-MM-DD>");
+    println!("      --sort title|created|updated|words [--desc]");
+    println!("      --group-by tag|series|month|status  Sections with counts (list only)");
+    println!("      --output-template '{{id}}\\t{{title}}\\t{{edit_url}}'  One line per article from a template");
+    println!("      --format table     Aligned columns: title, tags, updated, words");
+    println!("      --count|--ids      Print only the number of matches, or their ids");
+    println!("  dtdrafts stale [--older-than 90d]         Report drafts not updated for a while");
+    println!("  dtdrafts remind [--older-than 30d] [--notify] [--json]  Remind about neglected drafts");
+    println!("  dtdrafts stats [--json]                   Word counts, reading time and tags");
+    println!("  dtdrafts stats --readability              Reading level per draft, with trends");
+    println!("  dtdrafts calendar [--weeks 53]            Grid of the days drafts were created and updated");
+    println!("  dtdrafts vocab [--ngram 2] [--csv]        Most used words and phrases across drafts");
+    println!("  dtdrafts goals set --publish-per-month <n>|status  Track a publishing goal and streak");
+    println!("  dtdrafts tags trending|followed|info <tag>  Check tag popularity before tagging a draft");
+    println!("  dtdrafts dupes [--threshold 0.5]          Find drafts on the same topic");
+    println!("  dtdrafts related <id> [--drafts-only]     Similar articles to link to from a draft");
+    println!("  dtdrafts suggest-tags <id> [--apply]      Suggest up to 4 tags from similar articles");
+    println!("  dtdrafts analytics [--sort views] [--csv] Views, reactions and comments of published posts");
+    println!("  dtdrafts new --title <title> [--template <name>] [--org <org>]  Create a draft");
+    println!("  dtdrafts template list|add|edit           Manage draft templates");
+    println!("  dtdrafts status set <id> idea|writing|review|ready  Track a draft's workflow status");
+    println!("  dtdrafts note <id> [\"text\"]               Attach a local note to a draft (or list its notes)");
+    println!("  dtdrafts board                            Drafts grouped by local status");
+    println!("  dtdrafts series list|set|remove           Group drafts into dev.to series");
+    println!("  dtdrafts canonical set|remove <id> [url]  Manage the canonical URL of a cross-post");
+    println!("  dtdrafts edit <id>                        Edit a draft in $EDITOR and upload it");
+    println!("  dtdrafts history <id>                     List the recorded versions of a draft");
+    println!("  dtdrafts revert <id> --to <version>       Upload an earlier version of a draft");
+    println!("  dtdrafts undo [--force]                   Reverse the last publish, bulk change, retag or replace");
+    println!("  dtdrafts trash list|restore <id>|empty    Recover drafts deleted through dtdrafts");
+    println!("  dtdrafts lint <id>|--all [--links] [--tags] [--spelling] [--prose] [--code]  Check liquid tags and structure (and links, tags, spelling, style, code) in drafts");
+    println!("  dtdrafts toc <id> [--insert] [--repo <dir>]  Print or insert a table of contents");
+    println!("  dtdrafts outline <id> [--max-level 2]     Heading tree with words per section");
+    println!("  dtdrafts split <id> [--by h2]             Split a long draft into a series");
+    println!("  dtdrafts merge <id>... --into <title>     Merge drafts into a new one");
+    println!("  dtdrafts clone <id> --title <title>       New draft from an existing one's structure");
+    println!("  dtdrafts seo <id> [--keyword <phrase>]  Check title, description, keyword use and reading ease");
+    println!("  dtdrafts images <id> [--download]         Audit (and download) the images in a draft");
+    println!("  dtdrafts cover <id> --from-file|--url|--generate  Set a draft's cover image");
+    println!("  dtdrafts publish <id> [--dry-run]         Check title, tags and cover image, then publish");
+    println!("  dtdrafts crosspost <id> --to hashnode|medium  Cross-post with dev.to as canonical URL");
+    println!("  dtdrafts schedule <id> --at \"YYYY-MM-DD HH:MM\"  Publish a draft later (schedule list/cancel)");
+    println!("  dtdrafts scheduler run [--once]           Publish scheduled drafts when due");
+    println!("  dtdrafts daemon [--interval 1h] [--port <port>] [--saved]  Keep the cache fresh (and serve the API)");
+    println!("  dtdrafts serve [--port 7878]              JSON API over the cache for editor plugins");
+    println!("  dtdrafts show <id> [--render]             Print a draft, optionally rendered for the terminal");
+    println!("  dtdrafts preview <id> | --file <path>     Render a draft on localhost with live reload");
+    println!("  dtdrafts comments <id>                    Show the comment threads on an article");
+    println!("  dtdrafts backup [--out <file>]            Archive all drafts to a .tar.gz");
+    println!("  dtdrafts cache compact                    Store the cache compressed");
+    println!("  dtdrafts cache verify [--fix]             Find damaged cache records (and re-fetch them)");
+    println!("  dtdrafts export --format hugo|jekyll|zola --dir <dir>  Write drafts as static site posts");
+    println!("  dtdrafts feed [--out drafts.xml]          Atom feed of drafts for a feed reader");
+    println!("  dtdrafts report --html <dir>              HTML dashboard to share progress");
+    println!("  dtdrafts import --from <file> [--dry-run] Create drafts from a Medium/Ghost export");
+    println!("  dtdrafts restore <file> [--dry-run]       Re-create drafts from a backup archive");
+    println!("  dtdrafts sync --repo <dir> [--push] [--strategy ours|theirs|merge]  Mirror drafts into git");
+    println!("  dtdrafts watch <dir> [--debounce <secs>]  Upload draft files whenever they are saved");
+    println!("  dtdrafts completions <shell>              Print a shell completion script");
+    println!("  dtdrafts gen-man [--out <dir>]            Write man pages");
+    println!("  dtdrafts --refresh     Refresh article cache");
+    println!("  dtdrafts --record <dir> | --replay <dir>  Save API responses, or answer requests from them");
+    println!("  dtdrafts --refresh --strict               Fail on malformed articles instead of skipping them");
+    println!("  dtdrafts list --no-pager                  Don't page long listings through $PAGER");
+    println!("  dtdrafts list --color auto|always|never   Control colors (auto respects NO_COLOR)");
+    println!("  dtdrafts -q <query> --quiet               Exit 0 if drafts match, 1 if none (for scripts)");
+    println!("  dtdrafts selftest --live  Check the API write path with a throwaway draft");
+    println!("  dtdrafts --set-api-key <key> [--force]  Check and save a dev.to API key");
+    println!("  dtdrafts bulk --filter <expr> --action <action> [--dry-run]  Change many drafts at once");
+    println!("  dtdrafts retag --from <tag> --to <tag> --all-drafts  Rename a tag on every draft");
+    println!("  dtdrafts replace --pattern <text> --with <text> [--regex]  Find and replace across drafts");
+    println!("  dtdrafts whoami [--json]                  Show the account the API key belongs to");
+    println!("  dtdrafts config list|get|set|unset|edit|path  View and change settings");
+    println!("  dtdrafts --profile <name> ...  Use the [profiles.<name>] settings of ~/.dtdrafts/config.toml");
+    println!("  <id> also accepts a slug, a unique title prefix or the number from the last listing");
+    println!();
+    println!("{}", "Examples:".yellow().bold());
+    println!("  dtdrafts -q aws");
+    println!("  dtdrafts -q rust");
+    println!("  dtdrafts --all");
+    println!("  dtdrafts list --tag rust --tag cli");
+    println!("  dtdrafts list --updated-before 2023-06-30");
+    println!("  dtdrafts list --sort updated");
+}